@@ -0,0 +1,171 @@
+use arrow_buffer::{OffsetBuffer, ScalarBuffer};
+use geo_traits::CoordTrait;
+use geoarrow_schema::Dimension;
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+
+use crate::array::CoordBuffer;
+use crate::builder::CoordBufferBuilder;
+
+/// The values used to fill a Z or M dimension that is added when changing the dimension of an
+/// array with `into_dimension`.
+///
+/// Dimensions that exist in both the source and target dimension keep their values, and
+/// dimensions missing from the target dimension are dropped.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DimensionFill {
+    /// Use the same value for every coordinate.
+    Constant(f64),
+
+    /// Use one value per geometry, applied to every coordinate of that geometry.
+    ///
+    /// The buffer must have the same length as the array being converted.
+    PerGeometry(ScalarBuffer<f64>),
+
+    /// Use the values of the other optional dimension of the source coordinates: M values when
+    /// adding a Z dimension, or Z values when adding an M dimension.
+    ///
+    /// This converts `XYM` coordinates to `XYZ` and vice versa. It is an error if the source
+    /// coordinates do not have the other dimension.
+    OtherDimension,
+}
+
+impl Default for DimensionFill {
+    fn default() -> Self {
+        Self::Constant(0.0)
+    }
+}
+
+impl DimensionFill {
+    /// Check that per-geometry values match an array of length `len`.
+    pub(crate) fn check_len(&self, len: usize) -> GeoArrowResult<()> {
+        if let Self::PerGeometry(values) = self
+            && values.len() != len
+        {
+            return Err(GeoArrowError::InvalidGeoArrow(format!(
+                "Expected {len} per-geometry fill values, got {}",
+                values.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Map per-geometry values onto the children of each geometry, as delimited by `offsets`.
+    ///
+    /// Children not referenced by any geometry (e.g. because the array has been sliced) are
+    /// filled with NaN.
+    pub(crate) fn expand(&self, offsets: &OffsetBuffer<i32>, num_children: usize) -> Self {
+        match self {
+            Self::PerGeometry(values) => {
+                let mut out = vec![f64::NAN; num_children];
+                for (value, window) in values.iter().zip(offsets.windows(2)) {
+                    out[window[0] as usize..window[1] as usize].fill(*value);
+                }
+                Self::PerGeometry(out.into())
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Map per-geometry values of a union array onto the union child with the given type id.
+    ///
+    /// Children not referenced by any geometry are filled with NaN.
+    pub(crate) fn union_child(
+        &self,
+        type_ids: &[i8],
+        offsets: &[i32],
+        type_id: i8,
+        child_len: usize,
+    ) -> Self {
+        match self {
+            Self::PerGeometry(values) => {
+                let mut out = vec![f64::NAN; child_len];
+                for ((value, id), offset) in values.iter().zip(type_ids).zip(offsets) {
+                    if *id == type_id {
+                        out[*offset as usize] = *value;
+                    }
+                }
+                Self::PerGeometry(out.into())
+            }
+            _ => self.clone(),
+        }
+    }
+
+    fn value(&self, index: usize, other: Option<f64>) -> f64 {
+        match self {
+            Self::Constant(value) => *value,
+            Self::PerGeometry(values) => values[index],
+            Self::OtherDimension => other.unwrap_or(f64::NAN),
+        }
+    }
+}
+
+fn z_index(dim: Dimension) -> Option<usize> {
+    match dim {
+        Dimension::XYZ | Dimension::XYZM => Some(2),
+        Dimension::XY | Dimension::XYM => None,
+    }
+}
+
+fn m_index(dim: Dimension) -> Option<usize> {
+    match dim {
+        Dimension::XYM => Some(2),
+        Dimension::XYZM => Some(3),
+        Dimension::XY | Dimension::XYZ => None,
+    }
+}
+
+impl CoordBuffer {
+    /// Convert this coordinate buffer to the given [`Dimension`].
+    ///
+    /// Z and M values are kept where both dimensions have them, dropped where the target
+    /// dimension does not, and filled from `z` or `m` where only the target dimension has them.
+    /// Per-geometry fill values are interpreted as one value per coordinate.
+    ///
+    /// This is a no-op if `dim` matches the existing dimension.
+    pub fn into_dimension(
+        self,
+        dim: Dimension,
+        z: &DimensionFill,
+        m: &DimensionFill,
+    ) -> GeoArrowResult<Self> {
+        let source_dim = self.dim();
+        if source_dim == dim {
+            return Ok(self);
+        }
+
+        let len = self.len();
+        let source_z = z_index(source_dim);
+        let source_m = m_index(source_dim);
+        let fill_z = z_index(dim).is_some() && source_z.is_none();
+        let fill_m = m_index(dim).is_some() && source_m.is_none();
+        if fill_z {
+            z.check_len(len)?;
+        }
+        if fill_m {
+            m.check_len(len)?;
+        }
+        if len > 0
+            && ((fill_z && *z == DimensionFill::OtherDimension && source_m.is_none())
+                || (fill_m && *m == DimensionFill::OtherDimension && source_z.is_none()))
+        {
+            return Err(GeoArrowError::InvalidGeoArrow(format!(
+                "Cannot fill {dim:?} coordinates from the other dimension of {source_dim:?} coordinates"
+            )));
+        }
+
+        let mut builder = CoordBufferBuilder::with_capacity(len, self.coord_type(), dim);
+        for i in 0..len {
+            let coord = self.value(i);
+            let source_z = source_z.map(|n| coord.nth_or_panic(n));
+            let source_m = source_m.map(|n| coord.nth_or_panic(n));
+            let out = wkt::types::Coord {
+                x: coord.x(),
+                y: coord.y(),
+                z: z_index(dim).map(|_| source_z.unwrap_or_else(|| z.value(i, source_m))),
+                m: m_index(dim).map(|_| source_m.unwrap_or_else(|| m.value(i, source_z))),
+            };
+            builder.push_coord(&out);
+        }
+        Ok(builder.finish())
+    }
+}
@@ -62,6 +62,65 @@ macro_rules! impl_child_accessor {
     }};
 }
 
+/// Convert the children of every dimension for one geometry type to `dim`, merging them into the
+/// child for `dim`.
+///
+/// Evaluates to the new children, where all children other than the one for `dim` are empty, and
+/// the amount by which offsets into each of the old children must be shifted to point into the
+/// merged child.
+macro_rules! merge_children {
+    ($children:expr, $builder:ident, $type_offset:expr, $dim:expr, $z:expr, $m:expr, $type_ids:expr, $offsets:expr) => {{
+        let source_types = $children
+            .each_ref()
+            .map(|child| child.extension_type().clone());
+
+        let mut converted = Vec::with_capacity(4);
+        for (order, child) in $children.into_iter().enumerate() {
+            let type_id = order as i8 * 10 + $type_offset;
+            let z = $z.union_child($type_ids, $offsets, type_id, child.len());
+            let m = $m.union_child($type_ids, $offsets, type_id, child.len());
+            converted.push(child.into_dimension($dim, &z, &m)?);
+        }
+
+        let mut shifts = [0i32; 4];
+        let mut total = 0;
+        for (shift, child) in shifts.iter_mut().zip(&converted) {
+            *shift = total;
+            total += child.len() as i32;
+        }
+
+        let target_order = $dim.order();
+        let non_empty = converted.iter().filter(|child| !child.is_empty()).count();
+        let merged = if non_empty <= 1 {
+            // Offsets into the only non-empty child don't need to be shifted, since all children
+            // before it are empty
+            converted
+                .iter()
+                .find(|child| !child.is_empty())
+                .unwrap_or(&converted[target_order])
+                .clone()
+        } else {
+            let mut builder = $builder::new(converted[target_order].extension_type().clone());
+            for child in &converted {
+                for geom in child.iter() {
+                    builder.push_geometry(geom.transpose()?.as_ref())?;
+                }
+            }
+            builder.finish()
+        };
+
+        let mut merged = Some(merged);
+        let children = core::array::from_fn(|order| {
+            if order == target_order {
+                merged.take().unwrap()
+            } else {
+                $builder::new(source_types[order].clone()).finish()
+            }
+        });
+        (children, shifts)
+    }};
+}
+
 /// An immutable array of geometries of unknown geometry type and dimension.
 ///
 // # Invariants
@@ -387,6 +446,127 @@ impl GeometryArray {
         }
     }
 
+    /// Change the [`Dimension`] of every geometry in this array.
+    ///
+    /// The children of every dimension are converted to `dim` and merged into the children for
+    /// `dim`. Z and M values are kept where both dimensions have them, dropped where `dim` does
+    /// not, and filled from `z` or `m` where only `dim` has them. See [`DimensionFill`].
+    pub fn into_dimension(
+        self,
+        dim: Dimension,
+        z: &DimensionFill,
+        m: &DimensionFill,
+    ) -> GeoArrowResult<Self> {
+        z.check_len(self.len())?;
+        m.check_len(self.len())?;
+
+        let type_ids = &self.type_ids;
+        let offsets = &self.offsets;
+        let (points, point_shifts) = merge_children!(
+            self.points,
+            PointBuilder,
+            PointType::GEOMETRY_TYPE_OFFSET,
+            dim,
+            z,
+            m,
+            type_ids,
+            offsets
+        );
+        let (line_strings, line_string_shifts) = merge_children!(
+            self.line_strings,
+            LineStringBuilder,
+            LineStringType::GEOMETRY_TYPE_OFFSET,
+            dim,
+            z,
+            m,
+            type_ids,
+            offsets
+        );
+        let (polygons, polygon_shifts) = merge_children!(
+            self.polygons,
+            PolygonBuilder,
+            PolygonType::GEOMETRY_TYPE_OFFSET,
+            dim,
+            z,
+            m,
+            type_ids,
+            offsets
+        );
+        let (mpoints, mpoint_shifts) = merge_children!(
+            self.mpoints,
+            MultiPointBuilder,
+            MultiPointType::GEOMETRY_TYPE_OFFSET,
+            dim,
+            z,
+            m,
+            type_ids,
+            offsets
+        );
+        let (mline_strings, mline_string_shifts) = merge_children!(
+            self.mline_strings,
+            MultiLineStringBuilder,
+            MultiLineStringType::GEOMETRY_TYPE_OFFSET,
+            dim,
+            z,
+            m,
+            type_ids,
+            offsets
+        );
+        let (mpolygons, mpolygon_shifts) = merge_children!(
+            self.mpolygons,
+            MultiPolygonBuilder,
+            MultiPolygonType::GEOMETRY_TYPE_OFFSET,
+            dim,
+            z,
+            m,
+            type_ids,
+            offsets
+        );
+        let (gcs, gc_shifts) = merge_children!(
+            self.gcs,
+            GeometryCollectionBuilder,
+            GeometryCollectionType::GEOMETRY_TYPE_OFFSET,
+            dim,
+            z,
+            m,
+            type_ids,
+            offsets
+        );
+
+        // Point every geometry at its position in the merged child for the new dimension
+        let dim_offset = dim.order() as i8 * 10;
+        let mut new_type_ids = Vec::with_capacity(type_ids.len());
+        let mut new_offsets = Vec::with_capacity(offsets.len());
+        for (type_id, offset) in type_ids.iter().zip(offsets.iter()) {
+            let source_order = (type_id / 10) as usize;
+            let shifts = match type_id % 10 {
+                PointType::GEOMETRY_TYPE_OFFSET => &point_shifts,
+                LineStringType::GEOMETRY_TYPE_OFFSET => &line_string_shifts,
+                PolygonType::GEOMETRY_TYPE_OFFSET => &polygon_shifts,
+                MultiPointType::GEOMETRY_TYPE_OFFSET => &mpoint_shifts,
+                MultiLineStringType::GEOMETRY_TYPE_OFFSET => &mline_string_shifts,
+                MultiPolygonType::GEOMETRY_TYPE_OFFSET => &mpolygon_shifts,
+                GeometryCollectionType::GEOMETRY_TYPE_OFFSET => &gc_shifts,
+                _ => unreachable!("unknown type_id {}", type_id),
+            };
+            new_type_ids.push(type_id % 10 + dim_offset);
+            new_offsets.push(offset + shifts[source_order]);
+        }
+
+        Ok(Self {
+            data_type: self.data_type,
+            type_ids: new_type_ids.into(),
+            offsets: new_offsets.into(),
+            points,
+            line_strings,
+            polygons,
+            mpoints,
+            mline_strings,
+            mpolygons,
+            gcs,
+        })
+    }
+
     /// Change the [`Metadata`] of this array.
     pub fn with_metadata(self, metadata: Arc<Metadata>) -> Self {
         Self {
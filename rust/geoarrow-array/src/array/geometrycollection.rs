@@ -8,7 +8,7 @@ use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
 use geoarrow_schema::type_id::GeometryTypeId;
 use geoarrow_schema::{CoordType, Dimension, GeoArrowType, GeometryCollectionType, Metadata};
 
-use crate::array::{GenericWkbArray, MixedGeometryArray};
+use crate::array::{DimensionFill, GenericWkbArray, MixedGeometryArray};
 use crate::builder::GeometryCollectionBuilder;
 use crate::capacity::GeometryCollectionCapacity;
 use crate::eq::offset_buffer_eq;
@@ -104,6 +104,28 @@ impl GeometryCollectionArray {
         }
     }
 
+    /// Change the [`Dimension`] of this array.
+    ///
+    /// Z and M values are kept where both dimensions have them, dropped where `dim` does not, and
+    /// filled from `z` or `m` where only `dim` has them. See [`DimensionFill`].
+    pub fn into_dimension(
+        self,
+        dim: Dimension,
+        z: &DimensionFill,
+        m: &DimensionFill,
+    ) -> GeoArrowResult<Self> {
+        z.check_len(self.len())?;
+        m.check_len(self.len())?;
+        let num_children = self.array.type_ids.len();
+        let z = z.expand(&self.geom_offsets, num_children);
+        let m = m.expand(&self.geom_offsets, num_children);
+        Ok(Self {
+            data_type: self.data_type.with_dimension(dim),
+            array: self.array.into_dimension(dim, &z, &m)?,
+            ..self
+        })
+    }
+
     /// Change the [`Metadata`] of this array.
     pub fn with_metadata(self, metadata: Arc<Metadata>) -> Self {
         Self {
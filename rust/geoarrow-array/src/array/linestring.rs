@@ -8,7 +8,7 @@ use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
 use geoarrow_schema::type_id::GeometryTypeId;
 use geoarrow_schema::{CoordType, Dimension, GeoArrowType, LineStringType, Metadata};
 
-use crate::array::{CoordBuffer, GenericWkbArray};
+use crate::array::{CoordBuffer, DimensionFill, GenericWkbArray};
 use crate::builder::LineStringBuilder;
 use crate::capacity::LineStringCapacity;
 use crate::eq::offset_buffer_eq;
@@ -155,6 +155,28 @@ impl LineStringArray {
         }
     }
 
+    /// Change the [`Dimension`] of this array.
+    ///
+    /// Z and M values are kept where both dimensions have them, dropped where `dim` does not, and
+    /// filled from `z` or `m` where only `dim` has them. See [`DimensionFill`].
+    pub fn into_dimension(
+        self,
+        dim: Dimension,
+        z: &DimensionFill,
+        m: &DimensionFill,
+    ) -> GeoArrowResult<Self> {
+        z.check_len(self.len())?;
+        m.check_len(self.len())?;
+        let num_coords = self.coords.len();
+        let z = z.expand(&self.geom_offsets, num_coords);
+        let m = m.expand(&self.geom_offsets, num_coords);
+        Ok(Self {
+            data_type: self.data_type.with_dimension(dim),
+            coords: self.coords.into_dimension(dim, &z, &m)?,
+            ..self
+        })
+    }
+
     /// Change the [`Metadata`] of this array.
     pub fn with_metadata(self, metadata: Arc<Metadata>) -> Self {
         Self {
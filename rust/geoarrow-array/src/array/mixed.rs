@@ -14,8 +14,8 @@ use geoarrow_schema::{
 
 use crate::GeoArrowArrayAccessor;
 use crate::array::{
-    DimensionIndex, LineStringArray, MultiLineStringArray, MultiPointArray, MultiPolygonArray,
-    PointArray, PolygonArray,
+    DimensionFill, DimensionIndex, LineStringArray, MultiLineStringArray, MultiPointArray,
+    MultiPolygonArray, PointArray, PolygonArray,
 };
 use crate::builder::{
     LineStringBuilder, MultiLineStringBuilder, MultiPointBuilder, MultiPolygonBuilder,
@@ -441,6 +441,52 @@ impl MixedGeometryArray {
         }
     }
 
+    /// Change the [`Dimension`] of this array.
+    ///
+    /// Z and M values are kept where both dimensions have them, dropped where `dim` does not, and
+    /// filled from `z` or `m` where only `dim` has them. Per-geometry fill values are interpreted
+    /// as one value per geometry of this array. See [`DimensionFill`].
+    pub fn into_dimension(
+        self,
+        dim: Dimension,
+        z: &DimensionFill,
+        m: &DimensionFill,
+    ) -> GeoArrowResult<Self> {
+        macro_rules! convert_child {
+            ($child:expr, $type_offset:expr) => {{
+                let type_id = self.dim.order() as i8 * 10 + $type_offset;
+                let child_len = $child.len();
+                let z = z.union_child(&self.type_ids, &self.offsets, type_id, child_len);
+                let m = m.union_child(&self.type_ids, &self.offsets, type_id, child_len);
+                $child.into_dimension(dim, &z, &m)?
+            }};
+        }
+
+        let dim_offset = dim.order() as i8 * 10;
+        let type_ids = self
+            .type_ids
+            .iter()
+            .map(|id| id % 10 + dim_offset)
+            .collect();
+        Ok(Self {
+            dim,
+            points: convert_child!(self.points, PointType::GEOMETRY_TYPE_OFFSET),
+            line_strings: convert_child!(self.line_strings, LineStringType::GEOMETRY_TYPE_OFFSET),
+            polygons: convert_child!(self.polygons, PolygonType::GEOMETRY_TYPE_OFFSET),
+            multi_points: convert_child!(self.multi_points, MultiPointType::GEOMETRY_TYPE_OFFSET),
+            multi_line_strings: convert_child!(
+                self.multi_line_strings,
+                MultiLineStringType::GEOMETRY_TYPE_OFFSET
+            ),
+            multi_polygons: convert_child!(
+                self.multi_polygons,
+                MultiPolygonType::GEOMETRY_TYPE_OFFSET
+            ),
+            type_ids,
+            ..self
+        })
+    }
+
     pub fn contained_types(&self) -> HashSet<GeoArrowType> {
         let mut types = HashSet::new();
         if self.has_points() {
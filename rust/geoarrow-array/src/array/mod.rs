@@ -3,6 +3,7 @@
 //! All arrays implement the core [GeoArrowArray] trait.
 
 mod coord;
+mod dimension;
 mod geometry;
 mod geometrycollection;
 mod linestring;
@@ -23,6 +24,7 @@ use std::sync::Arc;
 use arrow_array::Array;
 use arrow_schema::Field;
 pub use coord::{CoordBuffer, InterleavedCoordBuffer, SeparatedCoordBuffer};
+pub use dimension::DimensionFill;
 use geoarrow_schema::GeoArrowType;
 use geoarrow_schema::error::GeoArrowResult;
 pub(crate) use geometry::DimensionIndex;
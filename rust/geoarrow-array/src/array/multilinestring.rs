@@ -8,7 +8,7 @@ use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
 use geoarrow_schema::type_id::GeometryTypeId;
 use geoarrow_schema::{CoordType, Dimension, GeoArrowType, Metadata, MultiLineStringType};
 
-use crate::array::{CoordBuffer, GenericWkbArray, LineStringArray};
+use crate::array::{CoordBuffer, DimensionFill, GenericWkbArray, LineStringArray};
 use crate::builder::MultiLineStringBuilder;
 use crate::capacity::MultiLineStringCapacity;
 use crate::eq::offset_buffer_eq;
@@ -189,6 +189,33 @@ impl MultiLineStringArray {
         }
     }
 
+    /// Change the [`Dimension`] of this array.
+    ///
+    /// Z and M values are kept where both dimensions have them, dropped where `dim` does not, and
+    /// filled from `z` or `m` where only `dim` has them. See [`DimensionFill`].
+    pub fn into_dimension(
+        self,
+        dim: Dimension,
+        z: &DimensionFill,
+        m: &DimensionFill,
+    ) -> GeoArrowResult<Self> {
+        z.check_len(self.len())?;
+        m.check_len(self.len())?;
+        let num_lines = self.ring_offsets.len_proxy();
+        let num_coords = self.coords.len();
+        let z = z
+            .expand(&self.geom_offsets, num_lines)
+            .expand(&self.ring_offsets, num_coords);
+        let m = m
+            .expand(&self.geom_offsets, num_lines)
+            .expand(&self.ring_offsets, num_coords);
+        Ok(Self {
+            data_type: self.data_type.with_dimension(dim),
+            coords: self.coords.into_dimension(dim, &z, &m)?,
+            ..self
+        })
+    }
+
     /// Change the [`Metadata`] of this array.
     pub fn with_metadata(self, metadata: Arc<Metadata>) -> Self {
         Self {
@@ -372,8 +399,8 @@ impl From<LineStringArray> for MultiLineStringArray {
         let (coord_type, dimension, metadata) = value.data_type.into_inner();
         let new_type = MultiLineStringType::new(dimension, metadata).with_coord_type(coord_type);
 
+        let geom_offsets = OffsetBuffer::from_lengths(vec![1; value.geom_offsets.len() - 1]);
         let coords = value.coords;
-        let geom_offsets = OffsetBuffer::from_lengths(vec![1; coords.len()]);
         let ring_offsets = value.geom_offsets;
         let nulls = value.nulls;
         Self {
@@ -405,11 +432,34 @@ impl GeometryTypeId for MultiLineStringArray {
 
 #[cfg(test)]
 mod test {
-    use geo_traits::to_geo::ToGeoMultiLineString;
+    use geo_traits::to_geo::{ToGeoLineString, ToGeoMultiLineString};
     use geoarrow_schema::{CoordType, Dimension};
 
     use super::*;
-    use crate::test::multilinestring;
+    use crate::test::{linestring, multilinestring};
+
+    #[test]
+    fn from_line_string_array() {
+        let line_strings = linestring::array(CoordType::Interleaved, Dimension::XY);
+        let multi_line_strings = MultiLineStringArray::from(line_strings.clone());
+        assert_eq!(multi_line_strings.len(), line_strings.len());
+        assert_eq!(
+            multi_line_strings.logical_nulls(),
+            line_strings.logical_nulls()
+        );
+        for (line_string, multi_line_string) in line_strings.iter().zip(multi_line_strings.iter()) {
+            assert_eq!(
+                line_string
+                    .transpose()
+                    .unwrap()
+                    .map(|g| geo_types::MultiLineString::new(vec![g.to_line_string()])),
+                multi_line_string
+                    .transpose()
+                    .unwrap()
+                    .map(|g| g.to_multi_line_string())
+            );
+        }
+    }
 
     #[test]
     fn geo_round_trip() {
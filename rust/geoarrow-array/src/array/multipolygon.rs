@@ -8,7 +8,7 @@ use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
 use geoarrow_schema::type_id::GeometryTypeId;
 use geoarrow_schema::{CoordType, Dimension, GeoArrowType, Metadata, MultiPolygonType};
 
-use crate::array::{CoordBuffer, GenericWkbArray, PolygonArray};
+use crate::array::{CoordBuffer, DimensionFill, GenericWkbArray, PolygonArray};
 use crate::builder::MultiPolygonBuilder;
 use crate::capacity::MultiPolygonCapacity;
 use crate::eq::offset_buffer_eq;
@@ -225,6 +225,36 @@ impl MultiPolygonArray {
         }
     }
 
+    /// Change the [`Dimension`] of this array.
+    ///
+    /// Z and M values are kept where both dimensions have them, dropped where `dim` does not, and
+    /// filled from `z` or `m` where only `dim` has them. See [`DimensionFill`].
+    pub fn into_dimension(
+        self,
+        dim: Dimension,
+        z: &DimensionFill,
+        m: &DimensionFill,
+    ) -> GeoArrowResult<Self> {
+        z.check_len(self.len())?;
+        m.check_len(self.len())?;
+        let num_polygons = self.polygon_offsets.len_proxy();
+        let num_rings = self.ring_offsets.len_proxy();
+        let num_coords = self.coords.len();
+        let z = z
+            .expand(&self.geom_offsets, num_polygons)
+            .expand(&self.polygon_offsets, num_rings)
+            .expand(&self.ring_offsets, num_coords);
+        let m = m
+            .expand(&self.geom_offsets, num_polygons)
+            .expand(&self.polygon_offsets, num_rings)
+            .expand(&self.ring_offsets, num_coords);
+        Ok(Self {
+            data_type: self.data_type.with_dimension(dim),
+            coords: self.coords.into_dimension(dim, &z, &m)?,
+            ..self
+        })
+    }
+
     /// Change the [`Metadata`] of this array.
     pub fn with_metadata(self, metadata: Arc<Metadata>) -> Self {
         Self {
@@ -425,8 +455,8 @@ impl From<PolygonArray> for MultiPolygonArray {
         let (coord_type, dimension, metadata) = value.data_type.into_inner();
         let new_type = MultiPolygonType::new(dimension, metadata).with_coord_type(coord_type);
 
+        let geom_offsets = OffsetBuffer::from_lengths(vec![1; value.geom_offsets.len() - 1]);
         let coords = value.coords;
-        let geom_offsets = OffsetBuffer::from_lengths(vec![1; coords.len()]);
         let ring_offsets = value.ring_offsets;
         let polygon_offsets = value.geom_offsets;
         let nulls = value.nulls;
@@ -461,11 +491,11 @@ impl GeometryTypeId for MultiPolygonArray {
 
 #[cfg(test)]
 mod test {
-    use geo_traits::to_geo::ToGeoMultiPolygon;
+    use geo_traits::to_geo::{ToGeoMultiPolygon, ToGeoPolygon};
     use geoarrow_schema::{CoordType, Dimension};
 
     use super::*;
-    use crate::test::multipolygon;
+    use crate::test::{multipolygon, polygon};
 
     #[test]
     fn geo_round_trip() {
@@ -514,6 +544,26 @@ mod test {
         }
     }
 
+    #[test]
+    fn from_polygon_array() {
+        let polygons = polygon::array(CoordType::Interleaved, Dimension::XY);
+        let multi_polygons = MultiPolygonArray::from(polygons.clone());
+        assert_eq!(multi_polygons.len(), polygons.len());
+        assert_eq!(multi_polygons.logical_nulls(), polygons.logical_nulls());
+        for (polygon, multi_polygon) in polygons.iter().zip(multi_polygons.iter()) {
+            assert_eq!(
+                polygon
+                    .transpose()
+                    .unwrap()
+                    .map(|g| geo_types::MultiPolygon::new(vec![g.to_polygon()])),
+                multi_polygon
+                    .transpose()
+                    .unwrap()
+                    .map(|g| g.to_multi_polygon())
+            );
+        }
+    }
+
     #[test]
     fn try_from_arrow() {
         for coord_type in [CoordType::Interleaved, CoordType::Separated] {
@@ -8,7 +8,7 @@ use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
 use geoarrow_schema::type_id::GeometryTypeId;
 use geoarrow_schema::{CoordType, Dimension, GeoArrowType, Metadata, PointType};
 
-use crate::array::{CoordBuffer, InterleavedCoordBuffer, SeparatedCoordBuffer};
+use crate::array::{CoordBuffer, DimensionFill, InterleavedCoordBuffer, SeparatedCoordBuffer};
 use crate::eq::point_eq;
 use crate::scalar::Point;
 use crate::trait_::{GeoArrowArray, GeoArrowArrayAccessor, IntoArrow};
@@ -119,6 +119,25 @@ impl PointArray {
         }
     }
 
+    /// Change the [`Dimension`] of this array.
+    ///
+    /// Z and M values are kept where both dimensions have them, dropped where `dim` does not, and
+    /// filled from `z` or `m` where only `dim` has them. See [`DimensionFill`].
+    pub fn into_dimension(
+        self,
+        dim: Dimension,
+        z: &DimensionFill,
+        m: &DimensionFill,
+    ) -> GeoArrowResult<Self> {
+        z.check_len(self.len())?;
+        m.check_len(self.len())?;
+        Ok(Self {
+            data_type: self.data_type.with_dimension(dim),
+            coords: self.coords.into_dimension(dim, z, m)?,
+            ..self
+        })
+    }
+
     /// Change the [`Metadata`] of this array.
     pub fn with_metadata(self, metadata: Arc<Metadata>) -> Self {
         Self {
@@ -8,7 +8,7 @@ use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
 use geoarrow_schema::type_id::GeometryTypeId;
 use geoarrow_schema::{CoordType, Dimension, GeoArrowType, Metadata, PolygonType};
 
-use crate::array::{CoordBuffer, GenericWkbArray, RectArray};
+use crate::array::{CoordBuffer, DimensionFill, GenericWkbArray, RectArray};
 use crate::builder::PolygonBuilder;
 use crate::capacity::PolygonCapacity;
 use crate::eq::offset_buffer_eq;
@@ -191,6 +191,33 @@ impl PolygonArray {
         }
     }
 
+    /// Change the [`Dimension`] of this array.
+    ///
+    /// Z and M values are kept where both dimensions have them, dropped where `dim` does not, and
+    /// filled from `z` or `m` where only `dim` has them. See [`DimensionFill`].
+    pub fn into_dimension(
+        self,
+        dim: Dimension,
+        z: &DimensionFill,
+        m: &DimensionFill,
+    ) -> GeoArrowResult<Self> {
+        z.check_len(self.len())?;
+        m.check_len(self.len())?;
+        let num_rings = self.ring_offsets.len_proxy();
+        let num_coords = self.coords.len();
+        let z = z
+            .expand(&self.geom_offsets, num_rings)
+            .expand(&self.ring_offsets, num_coords);
+        let m = m
+            .expand(&self.geom_offsets, num_rings)
+            .expand(&self.ring_offsets, num_coords);
+        Ok(Self {
+            data_type: self.data_type.with_dimension(dim),
+            coords: self.coords.into_dimension(dim, &z, &m)?,
+            ..self
+        })
+    }
+
     /// Change the [`Metadata`] of this array.
     pub fn with_metadata(self, metadata: Arc<Metadata>) -> Self {
         Self {
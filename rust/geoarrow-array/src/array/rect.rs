@@ -6,9 +6,9 @@ use arrow_array::{Array, ArrayRef, StructArray};
 use arrow_buffer::NullBuffer;
 use arrow_schema::{DataType, Field};
 use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
-use geoarrow_schema::{BoxType, GeoArrowType, Metadata};
+use geoarrow_schema::{BoxType, Dimension, GeoArrowType, Metadata};
 
-use crate::array::SeparatedCoordBuffer;
+use crate::array::{CoordBuffer, DimensionFill, SeparatedCoordBuffer};
 use crate::scalar::Rect;
 use crate::trait_::{GeoArrowArray, GeoArrowArrayAccessor, IntoArrow};
 
@@ -89,6 +89,31 @@ impl RectArray {
         }
     }
 
+    /// Change the [`Dimension`] of this array.
+    ///
+    /// Z and M values are kept where both dimensions have them, dropped where `dim` does not, and
+    /// filled from `z` or `m` where only `dim` has them. Both corners of each rect receive the
+    /// same fill values. See [`DimensionFill`].
+    pub fn into_dimension(
+        self,
+        dim: Dimension,
+        z: &DimensionFill,
+        m: &DimensionFill,
+    ) -> GeoArrowResult<Self> {
+        let into_dimension = |coords: SeparatedCoordBuffer| match CoordBuffer::Separated(coords)
+            .into_dimension(dim, z, m)?
+        {
+            CoordBuffer::Separated(coords) => Ok::<_, GeoArrowError>(coords),
+            CoordBuffer::Interleaved(_) => unreachable!(),
+        };
+        Ok(Self {
+            data_type: self.data_type.with_dimension(dim),
+            lower: into_dimension(self.lower)?,
+            upper: into_dimension(self.upper)?,
+            nulls: self.nulls,
+        })
+    }
+
     /// Change the [`Metadata`] of this array.
     pub fn with_metadata(self, metadata: Arc<Metadata>) -> Self {
         Self {
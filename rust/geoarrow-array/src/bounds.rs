@@ -0,0 +1,594 @@
+//! Compute the bounding boxes of GeoArrow arrays.
+//!
+//! [`bounding_rect`] computes the envelope of each row, returning a [`RectArray`], while
+//! [`total_bounds`] reduces an entire array to a single [`BoundingRect`].
+//!
+//! Note that these functions **do not** currently handle the antimeridian.
+
+use std::ops::Add;
+
+use geo_traits::{
+    CoordTrait, GeometryCollectionTrait, GeometryTrait, GeometryType, LineStringTrait, LineTrait,
+    MultiLineStringTrait, MultiPointTrait, MultiPolygonTrait, PointTrait, PolygonTrait, RectTrait,
+    TriangleTrait, UnimplementedGeometryCollection, UnimplementedLine, UnimplementedLineString,
+    UnimplementedMultiLineString, UnimplementedMultiPoint, UnimplementedMultiPolygon,
+    UnimplementedPoint, UnimplementedPolygon, UnimplementedTriangle,
+};
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+use geoarrow_schema::{BoxType, Dimension};
+use wkt::types::Coord;
+
+use crate::array::RectArray;
+use crate::builder::RectBuilder;
+use crate::{GeoArrowArray, GeoArrowArrayAccessor, downcast_geoarrow_array};
+
+/// A mutable, axis-aligned bounding box with optional Z values.
+///
+/// A newly-created `BoundingRect` is _empty_: its minimum values are `+∞` and its maximum values
+/// are `-∞`, so that adding any coordinate will set the bounds to that coordinate. Use
+/// [`is_empty`][Self::is_empty] to check whether any coordinates have been added.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingRect {
+    minx: f64,
+    miny: f64,
+    minz: f64,
+    maxx: f64,
+    maxy: f64,
+    maxz: f64,
+}
+
+impl BoundingRect {
+    /// Create a new, empty bounding rect.
+    pub fn new() -> Self {
+        BoundingRect {
+            minx: f64::INFINITY,
+            miny: f64::INFINITY,
+            minz: f64::INFINITY,
+            maxx: -f64::INFINITY,
+            maxy: -f64::INFINITY,
+            maxz: -f64::INFINITY,
+        }
+    }
+
+    /// The minimum x value.
+    pub fn minx(&self) -> f64 {
+        self.minx
+    }
+
+    /// The minimum y value.
+    pub fn miny(&self) -> f64 {
+        self.miny
+    }
+
+    /// The minimum z value, or `None` if no coordinates with a Z value have been added.
+    pub fn minz(&self) -> Option<f64> {
+        if self.minz == f64::INFINITY {
+            None
+        } else {
+            Some(self.minz)
+        }
+    }
+
+    /// The maximum x value.
+    pub fn maxx(&self) -> f64 {
+        self.maxx
+    }
+
+    /// The maximum y value.
+    pub fn maxy(&self) -> f64 {
+        self.maxy
+    }
+
+    /// The maximum z value, or `None` if no coordinates with a Z value have been added.
+    pub fn maxz(&self) -> Option<f64> {
+        if self.maxz == -f64::INFINITY {
+            None
+        } else {
+            Some(self.maxz)
+        }
+    }
+
+    /// Returns `true` if no coordinates have been added to this bounding rect.
+    ///
+    /// This is the case for null and empty geometries.
+    pub fn is_empty(&self) -> bool {
+        self.minx > self.maxx || self.miny > self.maxy
+    }
+
+    /// Returns `true` if any coordinates with a Z value have been added to this bounding rect.
+    pub fn has_z(&self) -> bool {
+        self.minz <= self.maxz
+    }
+
+    /// Expand this bounding rect to include a coordinate.
+    ///
+    /// Coordinates with `NaN` x or y values (as used to represent empty points) are ignored.
+    pub fn add_coord(&mut self, coord: &impl CoordTrait<T = f64>) {
+        let x = coord.x();
+        let y = coord.y();
+        if x.is_nan() || y.is_nan() {
+            return;
+        }
+
+        if x < self.minx {
+            self.minx = x;
+        }
+        if y < self.miny {
+            self.miny = y;
+        }
+        if x > self.maxx {
+            self.maxx = x;
+        }
+        if y > self.maxy {
+            self.maxy = y;
+        }
+
+        if has_z(coord.dim())
+            && let Some(z) = coord.nth(2)
+        {
+            if z < self.minz {
+                self.minz = z;
+            }
+            if z > self.maxz {
+                self.maxz = z;
+            }
+        }
+    }
+
+    /// Expand this bounding rect to include a point.
+    pub fn add_point(&mut self, point: &impl PointTrait<T = f64>) {
+        if let Some(coord) = point.coord() {
+            self.add_coord(&coord);
+        }
+    }
+
+    /// Expand this bounding rect to include a line string.
+    pub fn add_line_string(&mut self, line_string: &impl LineStringTrait<T = f64>) {
+        for coord in line_string.coords() {
+            self.add_coord(&coord);
+        }
+    }
+
+    /// Expand this bounding rect to include a polygon.
+    pub fn add_polygon(&mut self, polygon: &impl PolygonTrait<T = f64>) {
+        if let Some(exterior_ring) = polygon.exterior() {
+            self.add_line_string(&exterior_ring);
+        }
+
+        for interior in polygon.interiors() {
+            self.add_line_string(&interior)
+        }
+    }
+
+    /// Expand this bounding rect to include a multi point.
+    pub fn add_multi_point(&mut self, multi_point: &impl MultiPointTrait<T = f64>) {
+        for point in multi_point.points() {
+            self.add_point(&point);
+        }
+    }
+
+    /// Expand this bounding rect to include a multi line string.
+    pub fn add_multi_line_string(
+        &mut self,
+        multi_line_string: &impl MultiLineStringTrait<T = f64>,
+    ) {
+        for linestring in multi_line_string.line_strings() {
+            self.add_line_string(&linestring);
+        }
+    }
+
+    /// Expand this bounding rect to include a multi polygon.
+    pub fn add_multi_polygon(&mut self, multi_polygon: &impl MultiPolygonTrait<T = f64>) {
+        for polygon in multi_polygon.polygons() {
+            self.add_polygon(&polygon);
+        }
+    }
+
+    /// Expand this bounding rect to include a geometry collection.
+    pub fn add_geometry_collection(
+        &mut self,
+        geometry_collection: &impl GeometryCollectionTrait<T = f64>,
+    ) {
+        for geometry in geometry_collection.geometries() {
+            self.add_geometry(&geometry);
+        }
+    }
+
+    /// Expand this bounding rect to include a rect.
+    pub fn add_rect(&mut self, rect: &impl RectTrait<T = f64>) {
+        self.add_coord(&rect.min());
+        self.add_coord(&rect.max());
+    }
+
+    /// Expand this bounding rect to include a triangle.
+    pub fn add_triangle(&mut self, triangle: &impl TriangleTrait<T = f64>) {
+        for coord in triangle.coords() {
+            self.add_coord(&coord);
+        }
+    }
+
+    /// Expand this bounding rect to include a line.
+    pub fn add_line(&mut self, line: &impl LineTrait<T = f64>) {
+        for coord in line.coords() {
+            self.add_coord(&coord);
+        }
+    }
+
+    /// Expand this bounding rect to include any geometry.
+    pub fn add_geometry(&mut self, geometry: &impl GeometryTrait<T = f64>) {
+        use GeometryType::*;
+
+        match geometry.as_type() {
+            Point(g) => self.add_point(g),
+            LineString(g) => self.add_line_string(g),
+            Polygon(g) => self.add_polygon(g),
+            MultiPoint(g) => self.add_multi_point(g),
+            MultiLineString(g) => self.add_multi_line_string(g),
+            MultiPolygon(g) => self.add_multi_polygon(g),
+            GeometryCollection(g) => self.add_geometry_collection(g),
+            Rect(g) => self.add_rect(g),
+            Triangle(g) => self.add_triangle(g),
+            Line(g) => self.add_line(g),
+        }
+    }
+
+    /// Expand this bounding rect to include another bounding rect.
+    ///
+    /// Empty bounding rects are ignored.
+    pub fn update(&mut self, other: &BoundingRect) {
+        *self = *self + *other;
+    }
+
+    /// The minimum coordinate of this bounding rect in the given dimension.
+    ///
+    /// Missing Z values are filled with `NaN`.
+    fn min_coord(&self, dim: Dimension) -> Coord<f64> {
+        Coord {
+            x: self.minx,
+            y: self.miny,
+            z: fill_z(dim, self.minz()),
+            m: None,
+        }
+    }
+
+    /// The maximum coordinate of this bounding rect in the given dimension.
+    ///
+    /// Missing Z values are filled with `NaN`.
+    fn max_coord(&self, dim: Dimension) -> Coord<f64> {
+        Coord {
+            x: self.maxx,
+            y: self.maxy,
+            z: fill_z(dim, self.maxz()),
+            m: None,
+        }
+    }
+}
+
+impl Default for BoundingRect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Add for BoundingRect {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        BoundingRect {
+            minx: self.minx.min(rhs.minx),
+            miny: self.miny.min(rhs.miny),
+            minz: self.minz.min(rhs.minz),
+            maxx: self.maxx.max(rhs.maxx),
+            maxy: self.maxy.max(rhs.maxy),
+            maxz: self.maxz.max(rhs.maxz),
+        }
+    }
+}
+
+impl RectTrait for BoundingRect {
+    type CoordType<'a> = Coord<f64>;
+
+    fn min(&self) -> Self::CoordType<'_> {
+        Coord {
+            x: self.minx,
+            y: self.miny,
+            z: self.minz(),
+            m: None,
+        }
+    }
+
+    fn max(&self) -> Self::CoordType<'_> {
+        Coord {
+            x: self.maxx,
+            y: self.maxy,
+            z: self.maxz(),
+            m: None,
+        }
+    }
+}
+
+impl GeometryTrait for BoundingRect {
+    type T = f64;
+    type PointType<'a>
+        = UnimplementedPoint<f64>
+    where
+        Self: 'a;
+    type LineStringType<'a>
+        = UnimplementedLineString<f64>
+    where
+        Self: 'a;
+    type PolygonType<'a>
+        = UnimplementedPolygon<f64>
+    where
+        Self: 'a;
+    type MultiPointType<'a>
+        = UnimplementedMultiPoint<f64>
+    where
+        Self: 'a;
+    type MultiLineStringType<'a>
+        = UnimplementedMultiLineString<f64>
+    where
+        Self: 'a;
+    type MultiPolygonType<'a>
+        = UnimplementedMultiPolygon<f64>
+    where
+        Self: 'a;
+    type GeometryCollectionType<'a>
+        = UnimplementedGeometryCollection<f64>
+    where
+        Self: 'a;
+    type RectType<'a>
+        = Self
+    where
+        Self: 'a;
+    type TriangleType<'a>
+        = UnimplementedTriangle<f64>
+    where
+        Self: 'a;
+    type LineType<'a>
+        = UnimplementedLine<f64>
+    where
+        Self: 'a;
+
+    fn dim(&self) -> geo_traits::Dimensions {
+        if self.minz().is_some() {
+            geo_traits::Dimensions::Xyz
+        } else {
+            geo_traits::Dimensions::Xy
+        }
+    }
+
+    fn as_type(
+        &self,
+    ) -> GeometryType<
+        '_,
+        Self::PointType<'_>,
+        Self::LineStringType<'_>,
+        Self::PolygonType<'_>,
+        Self::MultiPointType<'_>,
+        Self::MultiLineStringType<'_>,
+        Self::MultiPolygonType<'_>,
+        Self::GeometryCollectionType<'_>,
+        Self::RectType<'_>,
+        Self::TriangleType<'_>,
+        Self::LineType<'_>,
+    > {
+        GeometryType::Rect(self)
+    }
+}
+
+fn has_z(dim: geo_traits::Dimensions) -> bool {
+    matches!(
+        dim,
+        geo_traits::Dimensions::Xyz
+            | geo_traits::Dimensions::Xyzm
+            | geo_traits::Dimensions::Unknown(3)
+            | geo_traits::Dimensions::Unknown(4)
+    )
+}
+
+fn fill_z(dim: Dimension, z: Option<f64>) -> Option<f64> {
+    match dim {
+        Dimension::XYZ => Some(z.unwrap_or(f64::NAN)),
+        _ => None,
+    }
+}
+
+/// Create a new [`RectArray`] using the bounding box of each geometry.
+///
+/// `dim` must be either [`Dimension::XY`] or [`Dimension::XYZ`], and determines the
+/// [`BoxType`] of the output. When computing XYZ boxes, rows without any Z values will have `NaN`
+/// Z bounds.
+///
+/// Null geometries and empty geometries both produce null rows in the output array.
+///
+/// This supports every GeoArrow array type, including WKB and WKT arrays, which will be parsed.
+/// The metadata of the input array is propagated to the output.
+///
+/// ```
+/// # use geoarrow_array::GeoArrowArray;
+/// # use geoarrow_array::bounds::bounding_rect;
+/// # use geoarrow_array::builder::PointBuilder;
+/// # use geoarrow_schema::{Dimension, PointType};
+/// #
+/// let point = geo_types::point!(x: 1., y: 2.);
+/// let point_type = PointType::new(Dimension::XY, Default::default());
+/// let point_array =
+///     PointBuilder::from_nullable_points([Some(&point), None].into_iter(), point_type).finish();
+///
+/// let rect_array = bounding_rect(&point_array, Dimension::XY).unwrap();
+/// assert_eq!(rect_array.len(), 2);
+/// assert!(rect_array.is_null(1));
+/// ```
+pub fn bounding_rect(array: &dyn GeoArrowArray, dim: Dimension) -> GeoArrowResult<RectArray> {
+    match dim {
+        Dimension::XY | Dimension::XYZ => {}
+        _ => {
+            return Err(GeoArrowError::InvalidGeoArrow(format!(
+                "Bounding rects must have XY or XYZ dimension, got {dim}"
+            )));
+        }
+    }
+    downcast_geoarrow_array!(array, impl_bounding_rect, dim)
+}
+
+fn impl_bounding_rect<'a>(
+    array: &'a impl GeoArrowArrayAccessor<'a>,
+    dim: Dimension,
+) -> GeoArrowResult<RectArray> {
+    let typ = BoxType::new(dim, array.data_type().metadata().clone());
+    let mut builder = RectBuilder::with_capacity(typ, array.len());
+
+    for item in array.iter() {
+        if let Some(geom) = item {
+            let mut rect = BoundingRect::new();
+            rect.add_geometry(&geom?);
+            if rect.is_empty() {
+                builder.push_null();
+            } else {
+                builder.push_min_max(&rect.min_coord(dim), &rect.max_coord(dim));
+            }
+        } else {
+            builder.push_null();
+        }
+    }
+
+    Ok(builder.finish())
+}
+
+/// Get the total bounds of the entire array.
+///
+/// Null and empty geometries are skipped. If the array contains no non-empty geometries, the
+/// returned [`BoundingRect`] will be [empty][BoundingRect::is_empty].
+///
+/// Z bounds are included if any coordinate in the array has a Z value.
+pub fn total_bounds(array: &dyn GeoArrowArray) -> GeoArrowResult<BoundingRect> {
+    downcast_geoarrow_array!(array, impl_total_bounds)
+}
+
+fn impl_total_bounds<'a>(
+    array: &'a impl GeoArrowArrayAccessor<'a>,
+) -> GeoArrowResult<BoundingRect> {
+    let mut rect = BoundingRect::new();
+
+    for item in array.iter().flatten() {
+        rect.add_geometry(&item?);
+    }
+
+    Ok(rect)
+}
+
+#[cfg(test)]
+mod test {
+    use geoarrow_schema::CoordType;
+
+    use super::*;
+    use crate::cast::{to_wkb, to_wkt};
+    use crate::test;
+
+    #[test]
+    fn bounding_rect_point_nulls_and_empties() {
+        let arr = test::point::array(CoordType::Separated, Dimension::XY);
+        let rects = bounding_rect(&arr, Dimension::XY).unwrap();
+        assert_eq!(rects.len(), 4);
+
+        let r0 = rects.value(0).unwrap();
+        assert_eq!((r0.min().x(), r0.min().y()), (30., 10.));
+        assert_eq!((r0.max().x(), r0.max().y()), (30., 10.));
+
+        // Null and empty points
+        assert!(rects.is_null(2));
+        assert!(rects.is_null(3));
+    }
+
+    #[test]
+    fn bounding_rect_polygon_xyz() {
+        let arr = test::polygon::array(CoordType::Interleaved, Dimension::XYZ);
+        let rects = bounding_rect(&arr, Dimension::XYZ).unwrap();
+        assert_eq!(rects.data_type().dimension(), Some(Dimension::XYZ));
+
+        for (i, geom) in arr.iter().enumerate() {
+            let rect = rects.get(i).unwrap();
+            let Some(geom) = geom else {
+                assert!(rect.is_none());
+                continue;
+            };
+
+            let mut expected = BoundingRect::new();
+            expected.add_geometry(&geom.unwrap());
+            if expected.is_empty() {
+                assert!(rect.is_none());
+            } else {
+                let rect = rect.unwrap();
+                assert_eq!(rect.min().nth(2), expected.minz());
+                assert_eq!(rect.max().nth(2), expected.maxz());
+            }
+        }
+    }
+
+    #[test]
+    fn bounding_rect_xyz_from_xy_is_nan() {
+        let arr = test::point::array(CoordType::Separated, Dimension::XY);
+        let rects = bounding_rect(&arr, Dimension::XYZ).unwrap();
+        assert!(rects.value(0).unwrap().min().nth(2).unwrap().is_nan());
+    }
+
+    #[test]
+    fn bounding_rect_serialized_matches_native() {
+        let arr = test::multipolygon::array(CoordType::Separated, Dimension::XYZ);
+        let expected = bounding_rect(&arr, Dimension::XYZ).unwrap();
+
+        let wkb_arr = to_wkb::<i32>(&arr).unwrap();
+        assert_eq!(bounding_rect(&wkb_arr, Dimension::XYZ).unwrap(), expected);
+
+        let wkt_arr = to_wkt::<i32>(&arr).unwrap();
+        assert_eq!(bounding_rect(&wkt_arr, Dimension::XYZ).unwrap(), expected);
+    }
+
+    #[test]
+    fn bounding_rect_invalid_dimension() {
+        let arr = test::point::array(CoordType::Separated, Dimension::XYM);
+        assert!(bounding_rect(&arr, Dimension::XYM).is_err());
+    }
+
+    #[test]
+    fn total_bounds_point() {
+        let arr = test::point::array(CoordType::Separated, Dimension::XYZ);
+        let bounds = total_bounds(&arr).unwrap();
+        assert_eq!(bounds.minx(), 30.);
+        assert_eq!(bounds.miny(), 10.);
+        assert_eq!(bounds.minz(), Some(40.));
+        assert_eq!(bounds.maxx(), 40.);
+        assert_eq!(bounds.maxy(), 20.);
+        assert_eq!(bounds.maxz(), Some(60.));
+    }
+
+    #[test]
+    fn total_bounds_xym_has_no_z() {
+        let arr = test::point::array(CoordType::Separated, Dimension::XYM);
+        let bounds = total_bounds(&arr).unwrap();
+        assert!(!bounds.has_z());
+        assert_eq!(bounds.minz(), None);
+    }
+
+    #[test]
+    fn total_bounds_all_null_or_empty() {
+        let arr = test::point::array(CoordType::Separated, Dimension::XY).slice(2, 2);
+        let bounds = total_bounds(&arr).unwrap();
+        assert!(bounds.is_empty());
+    }
+
+    #[test]
+    fn update_with_empty() {
+        let mut rect = BoundingRect::new();
+        rect.add_coord(&Coord {
+            x: 1.,
+            y: 2.,
+            z: None,
+            m: None,
+        });
+        let before = rect;
+        rect.update(&BoundingRect::new());
+        assert_eq!(rect, before);
+    }
+}
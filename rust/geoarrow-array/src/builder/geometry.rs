@@ -657,11 +657,14 @@ impl<'a> GeometryBuilder {
 
     /// Add a new GeometryCollection to the end of this array.
     ///
+    /// Unlike [`push_geometry`][Self::push_geometry], a collection with a single member is kept
+    /// as a collection.
+    ///
     /// # Errors
     ///
     /// This function errors iff the new last item is larger than what O supports.
     #[inline]
-    fn push_geometry_collection(
+    pub fn push_geometry_collection(
         &mut self,
         value: Option<&impl GeometryCollectionTrait<T = f64>>,
     ) -> GeoArrowResult<()> {
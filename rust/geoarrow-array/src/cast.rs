@@ -21,6 +21,12 @@ use crate::builder::{
 use crate::trait_::GeoArrowArray;
 use crate::{GeoArrowArrayAccessor, IntoArrow};
 
+mod polyline;
+mod twkb;
+
+pub use polyline::{from_polyline, to_polyline};
+pub use twkb::{TwkbOptions, from_twkb, to_twkb};
+
 /// Helpers for downcasting a [`GeoArrowArray`] to a concrete implementation.
 ///
 /// ```
@@ -745,6 +751,150 @@ pub fn from_wkt<A: GenericWktArrayType>(
     Ok(result)
 }
 
+/// The minimum number of rows parsed by each task in [`from_wkb_parallel`] and
+/// [`from_wkt_parallel`].
+#[cfg(feature = "rayon")]
+const MIN_PARALLEL_CHUNK_LEN: usize = 1024;
+
+/// Parse a [`GenericWkbArray`] or [`WkbViewArray`] to a [`GeoArrowArray`] with the designated
+/// [`GeoArrowType`], using multiple threads.
+///
+/// The input is split into contiguous chunks which are parsed concurrently on the rayon thread
+/// pool, each into a builder pre-sized from that chunk's capacity. The resulting arrays are then
+/// concatenated. The output is identical to [`from_wkb`].
+///
+/// A [`GeoArrowType::Geometry`] output is only parsed in parallel: the parsed chunks are then
+/// copied, geometry by geometry, into a single builder on the calling thread so that nulls are
+/// laid out as in the sequential path. This extra pass means `Geometry` gains less from
+/// parallelism than the other types.
+#[cfg(feature = "rayon")]
+pub fn from_wkb_parallel<'a, A: GenericWkbArrayType<'a>>(
+    arr: &'a A,
+    to_type: GeoArrowType,
+) -> GeoArrowResult<Arc<dyn GeoArrowArray>> {
+    from_wkb_chunked(arr, to_type, parallel_chunk_len(arr.len()))
+}
+
+#[cfg(feature = "rayon")]
+fn from_wkb_chunked<'a, A: GenericWkbArrayType<'a>>(
+    arr: &'a A,
+    to_type: GeoArrowType,
+    chunk_len: usize,
+) -> GeoArrowResult<Arc<dyn GeoArrowArray>> {
+    use rayon::prelude::*;
+
+    if arr.len() <= chunk_len {
+        return from_wkb(arr, to_type);
+    }
+
+    let chunks = slice_chunks(arr, chunk_len);
+    let arrays = chunks
+        .par_iter()
+        .map(|chunk| match chunk.data_type() {
+            GeoArrowType::Wkb(_) => from_wkb(chunk.as_wkb::<i32>(), to_type.clone()),
+            GeoArrowType::LargeWkb(_) => from_wkb(chunk.as_wkb::<i64>(), to_type.clone()),
+            GeoArrowType::WkbView(_) => from_wkb(chunk.as_wkb_view(), to_type.clone()),
+            _ => unreachable!("sliced WKB array has non-WKB type"),
+        })
+        .collect::<GeoArrowResult<Vec<_>>>()?;
+    concat_chunks(&arrays, &to_type)
+}
+
+/// Parse a [`GenericWktArray`] or [`WktViewArray`] to a [`GeoArrowArray`] with the designated
+/// [`GeoArrowType`], using multiple threads.
+///
+/// The input is split into contiguous chunks which are parsed concurrently on the rayon thread
+/// pool, each into a builder pre-sized from that chunk's capacity. The resulting arrays are then
+/// concatenated. The output is identical to [`from_wkt`].
+///
+/// A [`GeoArrowType::Geometry`] output is only parsed in parallel: the parsed chunks are then
+/// copied, geometry by geometry, into a single builder on the calling thread so that nulls are
+/// laid out as in the sequential path. This extra pass means `Geometry` gains less from
+/// parallelism than the other types.
+#[cfg(feature = "rayon")]
+pub fn from_wkt_parallel<A: GenericWktArrayType>(
+    arr: &A,
+    to_type: GeoArrowType,
+) -> GeoArrowResult<Arc<dyn GeoArrowArray>> {
+    from_wkt_chunked(arr, to_type, parallel_chunk_len(arr.len()))
+}
+
+#[cfg(feature = "rayon")]
+fn from_wkt_chunked<A: GenericWktArrayType>(
+    arr: &A,
+    to_type: GeoArrowType,
+    chunk_len: usize,
+) -> GeoArrowResult<Arc<dyn GeoArrowArray>> {
+    use rayon::prelude::*;
+
+    if arr.len() <= chunk_len {
+        return from_wkt(arr, to_type);
+    }
+
+    let chunks = slice_chunks(arr, chunk_len);
+    let arrays = chunks
+        .par_iter()
+        .map(|chunk| match chunk.data_type() {
+            GeoArrowType::Wkt(_) => from_wkt(chunk.as_wkt::<i32>(), to_type.clone()),
+            GeoArrowType::LargeWkt(_) => from_wkt(chunk.as_wkt::<i64>(), to_type.clone()),
+            GeoArrowType::WktView(_) => from_wkt(chunk.as_wkt_view(), to_type.clone()),
+            _ => unreachable!("sliced WKT array has non-WKT type"),
+        })
+        .collect::<GeoArrowResult<Vec<_>>>()?;
+    concat_chunks(&arrays, &to_type)
+}
+
+/// Choose a chunk length that gives each thread in the pool one chunk.
+#[cfg(feature = "rayon")]
+fn parallel_chunk_len(len: usize) -> usize {
+    len.div_ceil(rayon::current_num_threads())
+        .max(MIN_PARALLEL_CHUNK_LEN)
+}
+
+#[cfg(feature = "rayon")]
+fn slice_chunks(arr: &dyn GeoArrowArray, chunk_len: usize) -> Vec<Arc<dyn GeoArrowArray>> {
+    (0..arr.len())
+        .step_by(chunk_len)
+        .map(|offset| arr.slice(offset, chunk_len.min(arr.len() - offset)))
+        .collect()
+}
+
+/// Concatenate the chunks parsed in parallel, stitching together their offsets and validity.
+#[cfg(feature = "rayon")]
+fn concat_chunks(
+    arrays: &[Arc<dyn GeoArrowArray>],
+    to_type: &GeoArrowType,
+) -> GeoArrowResult<Arc<dyn GeoArrowArray>> {
+    // A `GeometryBuilder` assigns each null to a child array based on the geometries pushed before
+    // it, so concatenating the dense unions of each chunk would not reproduce the sequential
+    // layout. Instead, the parsed chunks are pushed into a single builder, pre-sized from the
+    // buffer lengths of every chunk.
+    if let GeoArrowType::Geometry(typ) = to_type {
+        let mut capacity = crate::capacity::GeometryCapacity::new_empty();
+        for arr in arrays {
+            capacity += arr.as_geometry().buffer_lengths();
+        }
+        let mut builder = GeometryBuilder::with_capacity(typ.clone(), capacity);
+        for arr in arrays {
+            for geom in arr.as_geometry().iter() {
+                builder.push_geometry(geom.transpose()?.as_ref())?;
+            }
+        }
+        return Ok(Arc::new(builder.finish()));
+    }
+
+    let arrow_arrays = arrays
+        .iter()
+        .map(|arr| arr.to_array_ref())
+        .collect::<Vec<_>>();
+    let arrow_refs = arrow_arrays
+        .iter()
+        .map(|arr| arr.as_ref())
+        .collect::<Vec<_>>();
+    let concatenated = arrow_select::concat::concat(&arrow_refs)?;
+    from_arrow_array(&concatenated, &to_type.to_field("", true))
+}
+
 /// Re-export symbols needed for downcast macros
 ///
 /// Name follows `serde` convention
@@ -886,7 +1036,7 @@ macro_rules! downcast_geoarrow_array {
 mod test {
     use std::sync::Arc;
 
-    use geoarrow_schema::{CoordType, Dimension, WkbType};
+    use geoarrow_schema::{CoordType, Dimension, GeometryType, WkbType};
 
     use super::*;
     use crate::test;
@@ -1289,4 +1439,93 @@ mod test {
     ) -> GeoArrowResult<f64> {
         Ok(param)
     }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let arrays: Vec<Arc<dyn GeoArrowArray>> = vec![
+            Arc::new(test::point::array(CoordType::Separated, Dimension::XYZ)),
+            Arc::new(test::linestring::array(
+                CoordType::Interleaved,
+                Dimension::XY,
+            )),
+            Arc::new(test::polygon::array(CoordType::Separated, Dimension::XYM)),
+            Arc::new(test::multipolygon::array(
+                CoordType::Interleaved,
+                Dimension::XYZM,
+            )),
+            Arc::new(test::geometry::array(CoordType::Separated, false)),
+        ];
+
+        for arr in arrays {
+            let to_type = arr.data_type();
+            let wkb_arr = to_wkb::<i32>(arr.as_ref()).unwrap();
+            let wkt_arr = to_wkt_view(arr.as_ref()).unwrap();
+            let sequential = from_wkb(&wkb_arr, to_type.clone()).unwrap();
+
+            for chunk_len in [1, 2, 3] {
+                let parallel = from_wkb_chunked(&wkb_arr, to_type.clone(), chunk_len).unwrap();
+                assert_eq!(parallel.data_type(), to_type);
+                assert_eq!(
+                    parallel.to_array_ref().as_ref(),
+                    sequential.to_array_ref().as_ref()
+                );
+
+                let parallel = from_wkt_chunked(&wkt_arr, to_type.clone(), chunk_len).unwrap();
+                assert_eq!(
+                    parallel.to_array_ref().as_ref(),
+                    sequential.to_array_ref().as_ref()
+                );
+            }
+
+            let parallel = from_wkb_parallel(&wkb_arr, to_type.clone()).unwrap();
+            assert_eq!(
+                parallel.to_array_ref().as_ref(),
+                sequential.to_array_ref().as_ref()
+            );
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_geometry_with_nulls() {
+        use wkt::wkt;
+
+        // Nulls at the start of the array, at chunk boundaries and between different geometry
+        // types, so that each chunk would assign them to a different child on its own.
+        let geoms: Vec<Option<wkt::Wkt<f64>>> = vec![
+            None,
+            Some(wkt! { POINT (1. 2.) }.into()),
+            None,
+            Some(wkt! { LINESTRING (1. 2., 3. 4.) }.into()),
+            None,
+            None,
+            Some(wkt! { POLYGON ((0. 0., 1. 0., 1. 1., 0. 0.)) }.into()),
+            Some(wkt! { POINT Z (1. 2. 3.) }.into()),
+            None,
+        ];
+        let typ = GeometryType::new(Default::default());
+        let arr = GeometryBuilder::from_nullable_geometries(&geoms, typ.clone())
+            .unwrap()
+            .finish();
+        let wkb_arr = to_wkb::<i64>(&arr).unwrap();
+        let wkt_arr = to_wkt::<i32>(&arr).unwrap();
+        let to_type = GeoArrowType::Geometry(typ);
+        let sequential = from_wkb(&wkb_arr, to_type.clone()).unwrap();
+
+        for chunk_len in [2, 4] {
+            let parallel = from_wkb_chunked(&wkb_arr, to_type.clone(), chunk_len).unwrap();
+            assert_eq!(
+                parallel.to_array_ref().as_ref(),
+                sequential.to_array_ref().as_ref()
+            );
+            assert_eq!(parallel.logical_null_count(), 5);
+
+            let parallel = from_wkt_chunked(&wkt_arr, to_type.clone(), chunk_len).unwrap();
+            assert_eq!(
+                parallel.to_array_ref().as_ref(),
+                sequential.to_array_ref().as_ref()
+            );
+        }
+    }
 }
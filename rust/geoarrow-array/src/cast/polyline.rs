@@ -0,0 +1,297 @@
+//! Google encoded polyline encoding and decoding.
+//!
+//! The [encoded polyline algorithm](https://developers.google.com/maps/documentation/utilities/polylinealgorithm)
+//! stores a sequence of 2D coordinates as an ASCII string of delta-encoded integers. It is the
+//! interchange format of many routing services.
+
+use std::sync::Arc;
+
+use arrow_array::builder::GenericStringBuilder;
+use arrow_array::{GenericStringArray, OffsetSizeTrait};
+use arrow_schema::ArrowError;
+use geo_traits::{CoordTrait, LineStringTrait, MultiPointTrait, PointTrait};
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+use geoarrow_schema::{Dimension, GeoArrowType};
+use wkt::types::{Coord, Dimension as WktDimension, LineString, MultiPoint, Point};
+
+use super::AsGeoArrowArray;
+use crate::builder::{LineStringBuilder, MultiPointBuilder};
+use crate::{GeoArrowArray, GeoArrowArrayAccessor};
+
+/// The largest supported polyline precision. Scaled values must fit comfortably within an `i64`.
+const MAX_PRECISION: u32 = 10;
+
+/// Convert a `LineString` or `MultiPoint` array to a [`GenericStringArray`] of encoded polylines.
+///
+/// `precision` is the number of decimal digits kept, usually 5 (as used by Google) or 6 (as used
+/// by OSRM and Valhalla). Only X (longitude) and Y (latitude) are encoded; Z and M values are
+/// dropped. Null geometries remain null.
+///
+/// Encoded polylines are not a GeoArrow extension type, so the output array carries no GeoArrow
+/// metadata. Pass the input's [`GeoArrowType`] to [`from_polyline`] to restore it.
+pub fn to_polyline<O: OffsetSizeTrait>(
+    arr: &dyn GeoArrowArray,
+    precision: u32,
+) -> GeoArrowResult<GenericStringArray<O>> {
+    let factor = scale_factor(precision)?;
+    let mut builder = GenericStringBuilder::<O>::with_capacity(arr.len(), 0);
+    let mut buf = String::new();
+    match arr.data_type() {
+        GeoArrowType::LineString(_) => {
+            for line_string in arr.as_line_string().iter() {
+                let Some(line_string) = line_string.transpose()? else {
+                    builder.append_null();
+                    continue;
+                };
+                buf.clear();
+                encode_coords(&mut buf, line_string.coords(), factor);
+                builder.append_value(&buf);
+            }
+        }
+        GeoArrowType::MultiPoint(_) => {
+            for multi_point in arr.as_multi_point().iter() {
+                let Some(multi_point) = multi_point.transpose()? else {
+                    builder.append_null();
+                    continue;
+                };
+                let coords = multi_point
+                    .points()
+                    .map(|point| {
+                        let coord = point.coord().ok_or_else(|| {
+                            GeoArrowError::IncorrectGeometryType(
+                                "Cannot encode an empty point within a MultiPoint as a polyline"
+                                    .to_string(),
+                            )
+                        })?;
+                        Ok((coord.x(), coord.y()))
+                    })
+                    .collect::<GeoArrowResult<Vec<_>>>()?;
+                buf.clear();
+                encode_coords(&mut buf, coords.into_iter(), factor);
+                builder.append_value(&buf);
+            }
+        }
+        other => {
+            return Err(GeoArrowError::IncorrectGeometryType(format!(
+                "Only LineString and MultiPoint arrays can be encoded as polylines, got {other:?}"
+            )));
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Parse a [`GenericStringArray`] of encoded polylines to a `LineString` or `MultiPoint` array.
+///
+/// `to_type` must be an XY [`GeoArrowType::LineString`] or [`GeoArrowType::MultiPoint`]; the
+/// GeoArrow metadata on the new array is taken from it. `precision` must match the precision the
+/// polylines were encoded with. Null values remain null.
+pub fn from_polyline<O: OffsetSizeTrait>(
+    arr: &GenericStringArray<O>,
+    to_type: GeoArrowType,
+    precision: u32,
+) -> GeoArrowResult<Arc<dyn GeoArrowArray>> {
+    let factor = scale_factor(precision)?;
+    let result: Arc<dyn GeoArrowArray> = match to_type {
+        GeoArrowType::LineString(typ) if typ.dimension() == Dimension::XY => {
+            let mut builder = LineStringBuilder::new(typ);
+            for value in arr.iter() {
+                let line_string = value
+                    .map(|value| {
+                        let coords = decode_coords(value, factor)?;
+                        Ok::<_, GeoArrowError>(LineString::new(coords, WktDimension::XY))
+                    })
+                    .transpose()?;
+                builder.push_line_string(line_string.as_ref())?;
+            }
+            Arc::new(builder.finish())
+        }
+        GeoArrowType::MultiPoint(typ) if typ.dimension() == Dimension::XY => {
+            let mut builder = MultiPointBuilder::new(typ);
+            for value in arr.iter() {
+                let multi_point = value
+                    .map(|value| {
+                        let points = decode_coords(value, factor)?
+                            .into_iter()
+                            .map(Point::from_coord)
+                            .collect();
+                        Ok::<_, GeoArrowError>(MultiPoint::new(points, WktDimension::XY))
+                    })
+                    .transpose()?;
+                builder.push_multi_point(multi_point.as_ref())?;
+            }
+            Arc::new(builder.finish())
+        }
+        other => {
+            return Err(GeoArrowError::IncorrectGeometryType(format!(
+                "Polylines can only be decoded to XY LineString or MultiPoint types, got {other:?}"
+            )));
+        }
+    };
+    Ok(result)
+}
+
+fn scale_factor(precision: u32) -> GeoArrowResult<f64> {
+    if precision > MAX_PRECISION {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Polyline precision must be at most {MAX_PRECISION}, got {precision}"
+        ))
+        .into());
+    }
+    Ok(10f64.powi(precision as i32))
+}
+
+fn encode_coords(
+    out: &mut String,
+    coords: impl Iterator<Item = impl CoordTrait<T = f64>>,
+    factor: f64,
+) {
+    let (mut last_lat, mut last_lng) = (0i64, 0i64);
+    for coord in coords {
+        // Polylines store latitude before longitude.
+        let lat = (coord.y() * factor).round() as i64;
+        let lng = (coord.x() * factor).round() as i64;
+        encode_value(out, lat - last_lat);
+        encode_value(out, lng - last_lng);
+        (last_lat, last_lng) = (lat, lng);
+    }
+}
+
+fn encode_value(out: &mut String, value: i64) {
+    let mut value = if value < 0 {
+        !((value as u64) << 1)
+    } else {
+        (value as u64) << 1
+    };
+    while value >= 0x20 {
+        out.push((((value & 0x1F) | 0x20) as u8 + 63) as char);
+        value >>= 5;
+    }
+    out.push((value as u8 + 63) as char);
+}
+
+fn decode_coords(value: &str, factor: f64) -> GeoArrowResult<Vec<Coord<f64>>> {
+    let mut bytes = value.bytes();
+    let mut coords = Vec::new();
+    let (mut lat, mut lng) = (0i64, 0i64);
+    while let Some(delta) = decode_value(&mut bytes, value)? {
+        lat += delta;
+        lng += decode_value(&mut bytes, value)?.ok_or_else(|| {
+            GeoArrowError::InvalidGeoArrow(format!(
+                "Invalid encoded polyline {value:?}: missing longitude"
+            ))
+        })?;
+        coords.push(Coord {
+            x: lng as f64 / factor,
+            y: lat as f64 / factor,
+            z: None,
+            m: None,
+        });
+    }
+    Ok(coords)
+}
+
+/// Decode the next value, or `None` at the end of the input.
+fn decode_value(bytes: &mut impl Iterator<Item = u8>, value: &str) -> GeoArrowResult<Option<i64>> {
+    let mut result = 0u64;
+    let mut shift = 0;
+    loop {
+        let Some(byte) = bytes.next() else {
+            if shift == 0 {
+                return Ok(None);
+            }
+            return Err(GeoArrowError::InvalidGeoArrow(format!(
+                "Invalid encoded polyline {value:?}: truncated value"
+            )));
+        };
+        if !(63..127).contains(&byte) || shift > 60 {
+            return Err(GeoArrowError::InvalidGeoArrow(format!(
+                "Invalid encoded polyline {value:?}"
+            )));
+        }
+        let chunk = (byte - 63) as u64;
+        result |= (chunk & 0x1F) << shift;
+        shift += 5;
+        if chunk < 0x20 {
+            break;
+        }
+    }
+    let decoded = if result & 1 == 1 {
+        !(result >> 1) as i64
+    } else {
+        (result >> 1) as i64
+    };
+    Ok(Some(decoded))
+}
+
+#[cfg(test)]
+mod test {
+    use arrow_array::{Array, StringArray};
+    use geoarrow_schema::{CoordType, Crs, LineStringType, Metadata, MultiPointType};
+
+    use super::*;
+    use crate::test;
+
+    #[test]
+    fn known_polyline() {
+        // The example from the encoded polyline algorithm documentation.
+        let encoded = "_p~iF~ps|U_ulLnnqC_mqNvxq`@";
+        let typ = LineStringType::new(Dimension::XY, Default::default());
+        let arr = StringArray::from(vec![Some(encoded), None]);
+        let decoded = from_polyline(&arr, typ.into(), 5).unwrap();
+
+        let line_string = decoded.as_line_string().value(0).unwrap();
+        let coords = line_string
+            .coords()
+            .map(|c| (c.x(), c.y()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            coords,
+            vec![(-120.2, 38.5), (-120.95, 40.7), (-126.453, 43.252)]
+        );
+        assert!(decoded.is_null(1));
+
+        let encoded_again = to_polyline::<i32>(decoded.as_ref(), 5).unwrap();
+        assert_eq!(encoded_again.value(0), encoded);
+        assert!(encoded_again.is_null(1));
+    }
+
+    #[test]
+    fn round_trip_line_string() {
+        let metadata = Arc::new(Metadata::new(
+            Crs::from_authority_code("EPSG:4326".to_string()),
+            None,
+        ));
+        for coord_type in [CoordType::Interleaved, CoordType::Separated] {
+            let arr = test::linestring::array(coord_type, Dimension::XY);
+            let arr = arr.with_metadata(metadata.clone());
+            let encoded = to_polyline::<i64>(&arr, 6).unwrap();
+            let decoded = from_polyline(&encoded, arr.data_type(), 6).unwrap();
+            assert_eq!(&arr, decoded.as_line_string());
+            assert_eq!(decoded.data_type().metadata(), &metadata);
+        }
+    }
+
+    #[test]
+    fn round_trip_multi_point() {
+        for coord_type in [CoordType::Interleaved, CoordType::Separated] {
+            let arr = test::multipoint::array(coord_type, Dimension::XY);
+            let encoded = to_polyline::<i32>(&arr, 5).unwrap();
+            let decoded = from_polyline(&encoded, arr.data_type(), 5).unwrap();
+            assert_eq!(&arr, decoded.as_multi_point());
+        }
+    }
+
+    #[test]
+    fn invalid_polyline() {
+        let typ: GeoArrowType = MultiPointType::new(Dimension::XY, Default::default()).into();
+        let arr = StringArray::from(vec!["_p~iF"]);
+        assert!(from_polyline(&arr, typ.clone(), 5).is_err());
+
+        let arr = StringArray::from(vec!["_p~iF~ps|U "]);
+        assert!(from_polyline(&arr, typ, 5).is_err());
+
+        let typ = LineStringType::new(Dimension::XYZ, Default::default());
+        let arr = StringArray::from(vec!["_p~iF~ps|U"]);
+        assert!(from_polyline(&arr, typ.into(), 5).is_err());
+    }
+}
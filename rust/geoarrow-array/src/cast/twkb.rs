@@ -0,0 +1,823 @@
+//! Tiny WKB (TWKB) encoding and decoding.
+//!
+//! [TWKB](https://github.com/TWKB/Specification/blob/master/twkb.md) stores coordinates as
+//! varint-encoded deltas of integers scaled by a fixed decimal precision. It is typically several
+//! times smaller than WKB, at the cost of rounding coordinates to that precision.
+
+use std::sync::Arc;
+
+use arrow_array::builder::GenericBinaryBuilder;
+use arrow_array::{GenericBinaryArray, OffsetSizeTrait};
+use arrow_schema::ArrowError;
+use geo_traits::{
+    CoordTrait, GeometryCollectionTrait, GeometryTrait, GeometryType, LineStringTrait,
+    MultiLineStringTrait, MultiPointTrait, MultiPolygonTrait, PointTrait, PolygonTrait, RectTrait,
+};
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+use geoarrow_schema::{GeoArrowType, GeometryType as GeometryDataType};
+use wkt::Wkt;
+use wkt::types::{
+    Coord, Dimension, GeometryCollection, LineString, MultiLineString, MultiPoint, MultiPolygon,
+    Point, Polygon,
+};
+
+use super::{AsGeoArrowArray, to_wkb, to_wkb_view, to_wkt, to_wkt_view};
+use crate::builder::{
+    GeometryBuilder, GeometryCollectionBuilder, LineStringBuilder, MultiLineStringBuilder,
+    MultiPointBuilder, MultiPolygonBuilder, PointBuilder, PolygonBuilder,
+};
+use crate::trait_::GeoArrowArrayBuilder;
+use crate::{GeoArrowArray, GeoArrowArrayAccessor};
+
+const BBOX_FLAG: u8 = 0b0000_0001;
+const SIZE_FLAG: u8 = 0b0000_0010;
+const ID_LIST_FLAG: u8 = 0b0000_0100;
+const EXTENDED_DIMS_FLAG: u8 = 0b0000_1000;
+const EMPTY_FLAG: u8 = 0b0001_0000;
+/// The maximum nesting depth of geometry collections when decoding, to guard the stack against
+/// corrupt or malicious input.
+const MAX_NESTING_DEPTH: usize = 64;
+
+/// Options for [`to_twkb`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TwkbOptions {
+    xy_precision: i8,
+    z_precision: u8,
+    m_precision: u8,
+    include_bbox: bool,
+    include_size: bool,
+}
+
+impl TwkbOptions {
+    /// Keep `xy_precision` decimal digits of X and Y values.
+    ///
+    /// The precision must be between -7 and 7. A negative precision rounds to tens, hundreds, and
+    /// so on. Z and M values are rounded to integers by default.
+    pub fn new(xy_precision: i8) -> Self {
+        Self {
+            xy_precision,
+            z_precision: 0,
+            m_precision: 0,
+            include_bbox: false,
+            include_size: false,
+        }
+    }
+
+    /// Keep `z_precision` decimal digits of Z values. Must be between 0 and 7.
+    pub fn with_z_precision(self, z_precision: u8) -> Self {
+        Self {
+            z_precision,
+            ..self
+        }
+    }
+
+    /// Keep `m_precision` decimal digits of M values. Must be between 0 and 7.
+    pub fn with_m_precision(self, m_precision: u8) -> Self {
+        Self {
+            m_precision,
+            ..self
+        }
+    }
+
+    /// Set whether to write the bounding box of each geometry in its header.
+    pub fn with_bbox(self, include_bbox: bool) -> Self {
+        Self {
+            include_bbox,
+            ..self
+        }
+    }
+
+    /// Set whether to write the encoded size of each geometry in its header, which lets readers
+    /// skip over geometries without decoding them.
+    pub fn with_size(self, include_size: bool) -> Self {
+        Self {
+            include_size,
+            ..self
+        }
+    }
+
+    fn validate(&self) -> GeoArrowResult<()> {
+        if !(-7..=7).contains(&self.xy_precision) {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "TWKB XY precision must be between -7 and 7, got {}",
+                self.xy_precision
+            ))
+            .into());
+        }
+        if self.z_precision > 7 || self.m_precision > 7 {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "TWKB Z and M precision must be between 0 and 7, got {} and {}",
+                self.z_precision, self.m_precision
+            ))
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Convert a [GeoArrowArray] to a [`GenericBinaryArray`] of TWKB values.
+///
+/// Coordinates are rounded to the precision set in `options`. Rect geometries are written as 2D
+/// polygons.
+///
+/// TWKB is not a GeoArrow extension type, so the output array carries no GeoArrow metadata.
+pub fn to_twkb<O: OffsetSizeTrait>(
+    arr: &dyn GeoArrowArray,
+    options: &TwkbOptions,
+) -> GeoArrowResult<GenericBinaryArray<O>> {
+    options.validate()?;
+
+    use GeoArrowType::*;
+    match arr.data_type() {
+        Point(_) => impl_to_twkb(arr.as_point(), options),
+        LineString(_) => impl_to_twkb(arr.as_line_string(), options),
+        Polygon(_) => impl_to_twkb(arr.as_polygon(), options),
+        MultiPoint(_) => impl_to_twkb(arr.as_multi_point(), options),
+        MultiLineString(_) => impl_to_twkb(arr.as_multi_line_string(), options),
+        MultiPolygon(_) => impl_to_twkb(arr.as_multi_polygon(), options),
+        Geometry(_) => impl_to_twkb(arr.as_geometry(), options),
+        GeometryCollection(_) => impl_to_twkb(arr.as_geometry_collection(), options),
+        Rect(_) => impl_to_twkb(arr.as_rect(), options),
+        Wkb(_) => impl_to_twkb(arr.as_wkb::<i32>(), options),
+        LargeWkb(_) => impl_to_twkb(arr.as_wkb::<i64>(), options),
+        WkbView(_) => impl_to_twkb(arr.as_wkb_view(), options),
+        Wkt(_) => impl_to_twkb(arr.as_wkt::<i32>(), options),
+        LargeWkt(_) => impl_to_twkb(arr.as_wkt::<i64>(), options),
+        WktView(_) => impl_to_twkb(arr.as_wkt_view(), options),
+    }
+}
+
+fn impl_to_twkb<'a, O: OffsetSizeTrait>(
+    geo_arr: &'a impl GeoArrowArrayAccessor<'a>,
+    options: &TwkbOptions,
+) -> GeoArrowResult<GenericBinaryArray<O>> {
+    let mut builder = GenericBinaryBuilder::<O>::with_capacity(geo_arr.len(), 0);
+    let mut buf = Vec::new();
+    for geom in geo_arr.iter() {
+        match geom.transpose()? {
+            Some(geom) => {
+                buf.clear();
+                write_geometry(&mut buf, &geom, options)?;
+                builder.append_value(&buf);
+            }
+            None => builder.append_null(),
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Parse a [`GenericBinaryArray`] of TWKB values to a [`GeoArrowArray`] with the designated
+/// [`GeoArrowType`].
+///
+/// The GeoArrow metadata on the new array is taken from `to_type`. Decoding to a Rect type is not
+/// supported.
+pub fn from_twkb<O: OffsetSizeTrait>(
+    arr: &GenericBinaryArray<O>,
+    to_type: GeoArrowType,
+) -> GeoArrowResult<Arc<dyn GeoArrowArray>> {
+    let geoms = arr
+        .iter()
+        .map(|value| {
+            value
+                .map(|bytes| Reader::new(bytes).read_geometry(0))
+                .transpose()
+        })
+        .collect::<GeoArrowResult<Vec<_>>>()?;
+
+    use GeoArrowType::*;
+    let result: Arc<dyn GeoArrowArray> = match to_type {
+        Point(typ) => build_native(&geoms, PointBuilder::new(typ))?,
+        LineString(typ) => build_native(&geoms, LineStringBuilder::new(typ))?,
+        Polygon(typ) => build_native(&geoms, PolygonBuilder::new(typ))?,
+        MultiPoint(typ) => build_native(&geoms, MultiPointBuilder::new(typ))?,
+        MultiLineString(typ) => build_native(&geoms, MultiLineStringBuilder::new(typ))?,
+        MultiPolygon(typ) => build_native(&geoms, MultiPolygonBuilder::new(typ))?,
+        GeometryCollection(typ) => build_native(&geoms, GeometryCollectionBuilder::new(typ))?,
+        Geometry(typ) => build_native(&geoms, GeometryBuilder::new(typ))?,
+        Rect(_) => {
+            return Err(GeoArrowError::IncorrectGeometryType(format!(
+                "Cannot decode TWKB geometries to Rect geometry type in from_twkb {to_type:?}",
+            )));
+        }
+        Wkb(_) | LargeWkb(_) | WkbView(_) | Wkt(_) | LargeWkt(_) | WktView(_) => {
+            let geometry_type = GeometryDataType::new(to_type.metadata().clone());
+            let geometries = build_native(&geoms, GeometryBuilder::new(geometry_type))?;
+            let geometries = geometries.as_ref();
+            match to_type {
+                Wkb(_) => Arc::new(to_wkb::<i32>(geometries)?),
+                LargeWkb(_) => Arc::new(to_wkb::<i64>(geometries)?),
+                WkbView(_) => Arc::new(to_wkb_view(geometries)?),
+                Wkt(_) => Arc::new(to_wkt::<i32>(geometries)?),
+                LargeWkt(_) => Arc::new(to_wkt::<i64>(geometries)?),
+                _ => Arc::new(to_wkt_view(geometries)?),
+            }
+        }
+    };
+    Ok(result)
+}
+
+fn build_native(
+    geoms: &[Option<Wkt<f64>>],
+    mut builder: impl GeoArrowArrayBuilder,
+) -> GeoArrowResult<Arc<dyn GeoArrowArray>> {
+    for geom in geoms {
+        builder.push_geometry(geom.as_ref())?;
+    }
+    Ok(builder.finish())
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Encoder state for a single TWKB geometry: the scale factors, the last written coordinate
+/// (coordinates are delta-encoded) and the running bounding box.
+struct Encoder {
+    num_dims: usize,
+    scales: [f64; 4],
+    last: [i64; 4],
+    bbox: Option<[(i64, i64); 4]>,
+}
+
+impl Encoder {
+    fn new(num_dims: usize, has_z: bool, options: &TwkbOptions) -> Self {
+        let xy_scale = 10f64.powi(options.xy_precision.into());
+        let z_scale = 10f64.powi(options.z_precision.into());
+        let m_scale = 10f64.powi(options.m_precision.into());
+        // The third ordinate is Z if present, otherwise M.
+        let scales = [
+            xy_scale,
+            xy_scale,
+            if has_z { z_scale } else { m_scale },
+            m_scale,
+        ];
+        Self {
+            num_dims,
+            scales,
+            last: [0; 4],
+            bbox: None,
+        }
+    }
+
+    fn write_coord(&mut self, out: &mut Vec<u8>, coord: &impl CoordTrait<T = f64>) {
+        for i in 0..self.num_dims {
+            let value = (coord.nth_or_panic(i) * self.scales[i]).round() as i64;
+            write_varint(out, zigzag(value - self.last[i]));
+            self.last[i] = value;
+        }
+        let bbox = self.bbox.get_or_insert([(i64::MAX, i64::MIN); 4]);
+        for (i, (min, max)) in bbox.iter_mut().enumerate().take(self.num_dims) {
+            *min = (*min).min(self.last[i]);
+            *max = (*max).max(self.last[i]);
+        }
+    }
+
+    fn write_coords(
+        &mut self,
+        out: &mut Vec<u8>,
+        coords: impl ExactSizeIterator<Item = impl CoordTrait<T = f64>>,
+    ) {
+        write_varint(out, coords.len() as u64);
+        for coord in coords {
+            self.write_coord(out, &coord);
+        }
+    }
+
+    fn write_polygon(&mut self, out: &mut Vec<u8>, polygon: &impl PolygonTrait<T = f64>) {
+        let Some(exterior) = polygon.exterior() else {
+            write_varint(out, 0);
+            return;
+        };
+        write_varint(out, 1 + polygon.num_interiors() as u64);
+        self.write_coords(out, exterior.coords());
+        for interior in polygon.interiors() {
+            self.write_coords(out, interior.coords());
+        }
+    }
+}
+
+fn write_geometry(
+    out: &mut Vec<u8>,
+    geom: &impl GeometryTrait<T = f64>,
+    options: &TwkbOptions,
+) -> GeoArrowResult<()> {
+    let (mut has_z, mut has_m) = match geom.dim() {
+        geo_traits::Dimensions::Xyz | geo_traits::Dimensions::Unknown(3) => (true, false),
+        geo_traits::Dimensions::Xym => (false, true),
+        geo_traits::Dimensions::Xyzm | geo_traits::Dimensions::Unknown(4) => (true, true),
+        _ => (false, false),
+    };
+
+    let mut body = Vec::new();
+    let geometry_type = match geom.as_type() {
+        GeometryType::Rect(_) => {
+            has_z = false;
+            has_m = false;
+            3
+        }
+        GeometryType::Point(_) => 1,
+        GeometryType::LineString(_) => 2,
+        GeometryType::Polygon(_) => 3,
+        GeometryType::MultiPoint(_) => 4,
+        GeometryType::MultiLineString(_) => 5,
+        GeometryType::MultiPolygon(_) => 6,
+        GeometryType::GeometryCollection(_) => 7,
+        _ => {
+            return Err(GeoArrowError::IncorrectGeometryType(
+                "Only Point, LineString, Polygon, MultiPoint, MultiLineString, MultiPolygon, GeometryCollection, and Rect geometries can be encoded as TWKB".to_string(),
+            ));
+        }
+    };
+    let num_dims = 2 + has_z as usize + has_m as usize;
+    let mut encoder = Encoder::new(num_dims, has_z, options);
+
+    let is_empty = match geom.as_type() {
+        GeometryType::Point(point) => match point.coord() {
+            Some(coord) => {
+                encoder.write_coord(&mut body, &coord);
+                false
+            }
+            None => true,
+        },
+        GeometryType::LineString(line_string) => {
+            encoder.write_coords(&mut body, line_string.coords());
+            line_string.num_coords() == 0
+        }
+        GeometryType::Polygon(polygon) => {
+            encoder.write_polygon(&mut body, polygon);
+            polygon.exterior().is_none()
+        }
+        GeometryType::MultiPoint(multi_point) => {
+            write_varint(&mut body, multi_point.num_points() as u64);
+            for point in multi_point.points() {
+                // TWKB cannot represent empty points within a multi point.
+                let coord = point.coord().ok_or_else(|| {
+                    GeoArrowError::IncorrectGeometryType(
+                        "Cannot encode an empty point within a MultiPoint as TWKB".to_string(),
+                    )
+                })?;
+                encoder.write_coord(&mut body, &coord);
+            }
+            multi_point.num_points() == 0
+        }
+        GeometryType::MultiLineString(multi_line_string) => {
+            write_varint(&mut body, multi_line_string.num_line_strings() as u64);
+            for line_string in multi_line_string.line_strings() {
+                encoder.write_coords(&mut body, line_string.coords());
+            }
+            multi_line_string.num_line_strings() == 0
+        }
+        GeometryType::MultiPolygon(multi_polygon) => {
+            write_varint(&mut body, multi_polygon.num_polygons() as u64);
+            for polygon in multi_polygon.polygons() {
+                encoder.write_polygon(&mut body, &polygon);
+            }
+            multi_polygon.num_polygons() == 0
+        }
+        GeometryType::GeometryCollection(collection) => {
+            write_varint(&mut body, collection.num_geometries() as u64);
+            for geometry in collection.geometries() {
+                write_geometry(&mut body, &geometry, options)?;
+            }
+            collection.num_geometries() == 0
+        }
+        GeometryType::Rect(rect) => {
+            let (min, max) = (rect.min(), rect.max());
+            let ring = [
+                (min.x(), min.y()),
+                (max.x(), min.y()),
+                (max.x(), max.y()),
+                (min.x(), max.y()),
+                (min.x(), min.y()),
+            ];
+            write_varint(&mut body, 1);
+            encoder.write_coords(&mut body, ring.into_iter());
+            false
+        }
+        _ => unreachable!(),
+    };
+
+    out.push(geometry_type | ((zigzag(options.xy_precision.into()) as u8) << 4));
+
+    let mut metadata = 0;
+    if has_z || has_m {
+        metadata |= EXTENDED_DIMS_FLAG;
+    }
+    if is_empty {
+        metadata |= EMPTY_FLAG;
+        body.clear();
+    }
+    let bbox = encoder.bbox.filter(|_| options.include_bbox && !is_empty);
+    if bbox.is_some() {
+        metadata |= BBOX_FLAG;
+    }
+    if options.include_size && !is_empty {
+        metadata |= SIZE_FLAG;
+    }
+    out.push(metadata);
+
+    if has_z || has_m {
+        out.push(
+            (has_z as u8)
+                | ((has_m as u8) << 1)
+                | (options.z_precision << 2)
+                | (options.m_precision << 5),
+        );
+    }
+
+    let mut bbox_bytes = Vec::new();
+    if let Some(bbox) = bbox {
+        for (min, max) in bbox.iter().take(num_dims) {
+            write_varint(&mut bbox_bytes, zigzag(*min));
+            write_varint(&mut bbox_bytes, zigzag(max - min));
+        }
+    }
+    if metadata & SIZE_FLAG != 0 {
+        write_varint(out, (bbox_bytes.len() + body.len()) as u64);
+    }
+    out.extend_from_slice(&bbox_bytes);
+    out.extend_from_slice(&body);
+    Ok(())
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+/// Decoder state for a single TWKB geometry.
+struct Decoder {
+    dim: Dimension,
+    has_z: bool,
+    has_m: bool,
+    scales: [f64; 4],
+    last: [i64; 4],
+}
+
+impl<'a> Reader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    fn read_u8(&mut self) -> GeoArrowResult<u8> {
+        let byte = *self
+            .buf
+            .get(self.pos)
+            .ok_or_else(|| GeoArrowError::Wkb("Unexpected end of TWKB".to_string()))?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn read_varint(&mut self) -> GeoArrowResult<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_u8()?;
+            value |= ((byte & 0x7F) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(GeoArrowError::Wkb("Invalid TWKB varint".to_string()))
+    }
+
+    fn read_count(&mut self) -> GeoArrowResult<usize> {
+        let count = self.read_varint()? as usize;
+        // Every element takes at least one byte, so this guards allocations against corrupt input.
+        if count > self.buf.len() - self.pos {
+            return Err(GeoArrowError::Wkb(format!(
+                "Invalid TWKB: count {count} exceeds remaining input"
+            )));
+        }
+        Ok(count)
+    }
+
+    fn read_coord(&mut self, decoder: &mut Decoder) -> GeoArrowResult<Coord<f64>> {
+        let num_dims = 2 + decoder.has_z as usize + decoder.has_m as usize;
+        let mut values = [0.0; 4];
+        for (i, value) in values.iter_mut().enumerate().take(num_dims) {
+            decoder.last[i] = decoder.last[i]
+                .checked_add(unzigzag(self.read_varint()?))
+                .ok_or_else(|| {
+                    GeoArrowError::Wkb("Invalid TWKB: coordinate value overflows".to_string())
+                })?;
+            *value = decoder.last[i] as f64 / decoder.scales[i];
+        }
+        Ok(Coord {
+            x: values[0],
+            y: values[1],
+            z: decoder.has_z.then_some(values[2]),
+            m: decoder
+                .has_m
+                .then_some(values[if decoder.has_z { 3 } else { 2 }]),
+        })
+    }
+
+    fn read_line_string(&mut self, decoder: &mut Decoder) -> GeoArrowResult<LineString<f64>> {
+        let num_coords = self.read_count()?;
+        let coords = (0..num_coords)
+            .map(|_| self.read_coord(decoder))
+            .collect::<GeoArrowResult<_>>()?;
+        Ok(LineString::new(coords, decoder.dim))
+    }
+
+    fn read_polygon(&mut self, decoder: &mut Decoder) -> GeoArrowResult<Polygon<f64>> {
+        let num_rings = self.read_count()?;
+        let rings = (0..num_rings)
+            .map(|_| self.read_line_string(decoder))
+            .collect::<GeoArrowResult<_>>()?;
+        Ok(Polygon::new(rings, decoder.dim))
+    }
+
+    /// Read the number of parts of a multi geometry, skipping the ID list if present.
+    fn read_parts(&mut self, metadata: u8) -> GeoArrowResult<usize> {
+        let num_parts = self.read_count()?;
+        if metadata & ID_LIST_FLAG != 0 {
+            for _ in 0..num_parts {
+                self.read_varint()?;
+            }
+        }
+        Ok(num_parts)
+    }
+
+    /// Read a geometry nested in `depth` geometry collections.
+    fn read_geometry(&mut self, depth: usize) -> GeoArrowResult<Wkt<f64>> {
+        if depth > MAX_NESTING_DEPTH {
+            return Err(GeoArrowError::Wkb(format!(
+                "Invalid TWKB: geometry collections nested more than {MAX_NESTING_DEPTH} deep"
+            )));
+        }
+        let header = self.read_u8()?;
+        let geometry_type = header & 0x0F;
+        let xy_precision = unzigzag((header >> 4).into());
+        let metadata = self.read_u8()?;
+
+        let (has_z, has_m, z_precision, m_precision) = if metadata & EXTENDED_DIMS_FLAG != 0 {
+            let dims = self.read_u8()?;
+            (
+                dims & 0b01 != 0,
+                dims & 0b10 != 0,
+                (dims >> 2) & 0b111,
+                (dims >> 5) & 0b111,
+            )
+        } else {
+            (false, false, 0, 0)
+        };
+        let dim = match (has_z, has_m) {
+            (false, false) => Dimension::XY,
+            (true, false) => Dimension::XYZ,
+            (false, true) => Dimension::XYM,
+            (true, true) => Dimension::XYZM,
+        };
+        let num_dims = 2 + has_z as usize + has_m as usize;
+
+        if metadata & SIZE_FLAG != 0 {
+            self.read_varint()?;
+        }
+        if metadata & BBOX_FLAG != 0 {
+            for _ in 0..2 * num_dims {
+                self.read_varint()?;
+            }
+        }
+
+        let xy_scale = 10f64.powi(xy_precision as i32);
+        let z_scale = 10f64.powi(z_precision.into());
+        let m_scale = 10f64.powi(m_precision.into());
+        let mut decoder = Decoder {
+            dim,
+            has_z,
+            has_m,
+            scales: [
+                xy_scale,
+                xy_scale,
+                if has_z { z_scale } else { m_scale },
+                m_scale,
+            ],
+            last: [0; 4],
+        };
+
+        let is_empty = metadata & EMPTY_FLAG != 0;
+        let geometry = match geometry_type {
+            1 if is_empty => Wkt::Point(Point::empty(dim)),
+            1 => Wkt::Point(Point::new(Some(self.read_coord(&mut decoder)?), dim)),
+            2 if is_empty => Wkt::LineString(LineString::empty(dim)),
+            2 => Wkt::LineString(self.read_line_string(&mut decoder)?),
+            3 if is_empty => Wkt::Polygon(Polygon::empty(dim)),
+            3 => Wkt::Polygon(self.read_polygon(&mut decoder)?),
+            4 if is_empty => Wkt::MultiPoint(MultiPoint::empty(dim)),
+            4 => {
+                let num_points = self.read_parts(metadata)?;
+                let points = (0..num_points)
+                    .map(|_| Ok(Point::new(Some(self.read_coord(&mut decoder)?), dim)))
+                    .collect::<GeoArrowResult<_>>()?;
+                Wkt::MultiPoint(MultiPoint::new(points, dim))
+            }
+            5 if is_empty => Wkt::MultiLineString(MultiLineString::empty(dim)),
+            5 => {
+                let num_line_strings = self.read_parts(metadata)?;
+                let line_strings = (0..num_line_strings)
+                    .map(|_| self.read_line_string(&mut decoder))
+                    .collect::<GeoArrowResult<_>>()?;
+                Wkt::MultiLineString(MultiLineString::new(line_strings, dim))
+            }
+            6 if is_empty => Wkt::MultiPolygon(MultiPolygon::empty(dim)),
+            6 => {
+                let num_polygons = self.read_parts(metadata)?;
+                let polygons = (0..num_polygons)
+                    .map(|_| self.read_polygon(&mut decoder))
+                    .collect::<GeoArrowResult<_>>()?;
+                Wkt::MultiPolygon(MultiPolygon::new(polygons, dim))
+            }
+            7 if is_empty => Wkt::GeometryCollection(GeometryCollection::empty(dim)),
+            7 => {
+                let num_geometries = self.read_parts(metadata)?;
+                let geometries = (0..num_geometries)
+                    .map(|_| self.read_geometry(depth + 1))
+                    .collect::<GeoArrowResult<_>>()?;
+                Wkt::GeometryCollection(GeometryCollection::new(geometries, dim))
+            }
+            other => {
+                return Err(GeoArrowError::Wkb(format!(
+                    "Invalid TWKB geometry type: {other}"
+                )));
+            }
+        };
+        Ok(geometry)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use arrow_array::BinaryArray;
+    use geoarrow_schema::{CoordType, Dimension, LineStringType, WkbType};
+
+    use super::*;
+    use crate::test;
+
+    const DIMS: [Dimension; 4] = [
+        Dimension::XY,
+        Dimension::XYZ,
+        Dimension::XYM,
+        Dimension::XYZM,
+    ];
+
+    fn round_trip(arr: &dyn GeoArrowArray) -> Arc<dyn GeoArrowArray> {
+        let options = TwkbOptions::new(3)
+            .with_z_precision(2)
+            .with_m_precision(1)
+            .with_bbox(true)
+            .with_size(true);
+        let twkb = to_twkb::<i32>(arr, &options).unwrap();
+        from_twkb(&twkb, arr.data_type()).unwrap()
+    }
+
+    #[test]
+    fn round_trip_point() {
+        for coord_type in [CoordType::Interleaved, CoordType::Separated] {
+            for dim in DIMS {
+                let arr = test::point::array(coord_type, dim);
+                assert_eq!(&arr, round_trip(&arr).as_point());
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_line_string() {
+        for coord_type in [CoordType::Interleaved, CoordType::Separated] {
+            for dim in DIMS {
+                let arr = test::linestring::array(coord_type, dim);
+                assert_eq!(&arr, round_trip(&arr).as_line_string());
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_polygon() {
+        for coord_type in [CoordType::Interleaved, CoordType::Separated] {
+            for dim in DIMS {
+                let arr = test::polygon::array(coord_type, dim);
+                assert_eq!(&arr, round_trip(&arr).as_polygon());
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_multi_point() {
+        for coord_type in [CoordType::Interleaved, CoordType::Separated] {
+            for dim in DIMS {
+                let arr = test::multipoint::array(coord_type, dim);
+                assert_eq!(&arr, round_trip(&arr).as_multi_point());
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_multi_line_string() {
+        for coord_type in [CoordType::Interleaved, CoordType::Separated] {
+            for dim in DIMS {
+                let arr = test::multilinestring::array(coord_type, dim);
+                assert_eq!(&arr, round_trip(&arr).as_multi_line_string());
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_multi_polygon() {
+        for coord_type in [CoordType::Interleaved, CoordType::Separated] {
+            for dim in DIMS {
+                let arr = test::multipolygon::array(coord_type, dim);
+                assert_eq!(&arr, round_trip(&arr).as_multi_polygon());
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_geometry_collection() {
+        for coord_type in [CoordType::Interleaved, CoordType::Separated] {
+            for dim in DIMS {
+                let arr = test::geometrycollection::array(coord_type, dim, false);
+                assert_eq!(&arr, round_trip(&arr).as_geometry_collection());
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_geometry() {
+        for coord_type in [CoordType::Interleaved, CoordType::Separated] {
+            let arr = test::geometry::array(coord_type, false);
+            assert_eq!(&arr, round_trip(&arr).as_geometry());
+        }
+    }
+
+    #[test]
+    fn known_encoding() {
+        // POINT (30 10) at precision 0: header, metadata, then zigzag varints 60 and 20.
+        let arr = test::point::array(CoordType::Separated, Dimension::XY).slice(0, 1);
+        let twkb = to_twkb::<i32>(&arr, &TwkbOptions::new(0)).unwrap();
+        let expected = [0x01, 0x00, 60, 20];
+        assert_eq!(twkb.value(0), expected);
+    }
+
+    #[test]
+    fn rounds_to_precision() {
+        let typ = LineStringType::new(Dimension::XY, Default::default());
+        let line_strings = [wkt::wkt! { LINESTRING (1.26 2.34, 123.45 -456.04) }];
+        let arr = LineStringBuilder::from_line_strings(&line_strings, typ.clone()).finish();
+
+        for (precision, expected) in [
+            (1, [(1.3, 2.3), (123.5, -456.0)]),
+            (-1, [(0.0, 0.0), (120.0, -460.0)]),
+        ] {
+            let twkb = to_twkb::<i64>(&arr, &TwkbOptions::new(precision)).unwrap();
+            let decoded = from_twkb(&twkb, typ.clone().into()).unwrap();
+            let line_string = decoded.as_line_string().value(0).unwrap();
+            let coords = line_string
+                .coords()
+                .map(|coord| (coord.x(), coord.y()))
+                .collect::<Vec<_>>();
+            assert_eq!(coords, expected);
+
+            let wkb_type = GeoArrowType::Wkb(WkbType::new(Default::default()));
+            assert_eq!(from_twkb(&twkb, wkb_type).unwrap().len(), 1);
+        }
+
+        assert!(to_twkb::<i32>(&arr, &TwkbOptions::new(8)).is_err());
+    }
+
+    #[test]
+    fn invalid_input() {
+        let typ = GeoArrowType::Wkb(WkbType::new(Default::default()));
+        let decode = |value: &[u8]| from_twkb(&BinaryArray::from(vec![value]), typ.clone());
+
+        // A multi point whose first X value is i64::MAX, so that adding the next delta overflows.
+        let mut multi_point = vec![0x04, 0x00, 2];
+        for delta in [i64::MAX, 0, 1, 0] {
+            write_varint(&mut multi_point, zigzag(delta));
+        }
+        let err = decode(&multi_point).unwrap_err();
+        assert!(err.to_string().contains("overflows"), "{err}");
+        let mut multi_point = vec![0x04, 0x00, 2];
+        for delta in [i64::MAX, 0, -1, 0] {
+            write_varint(&mut multi_point, zigzag(delta));
+        }
+        assert!(decode(&multi_point).is_ok());
+
+        // Geometry collections nested deeper than the limit.
+        let mut nested = [0x07, 0x00, 1].repeat(MAX_NESTING_DEPTH + 1);
+        nested.extend_from_slice(&[0x07, EMPTY_FLAG]);
+        let err = decode(&nested).unwrap_err();
+        assert!(err.to_string().contains("nested"), "{err}");
+        assert!(decode(&nested[3..]).is_ok());
+    }
+}
@@ -0,0 +1,634 @@
+//! Display GeoArrow arrays and scalars as abbreviated WKT.
+//!
+//! All scalars implement [`Display`][fmt::Display], writing their WKT representation truncated
+//! to 80 characters. Arrays implement [`Display`][fmt::Display] by listing their values, eliding
+//! the middle of long arrays.
+//!
+#![cfg_attr(
+    feature = "prettyprint",
+    doc = "With the `prettyprint` feature, [`GeoArrowFormatterFactory`] plugs into `arrow_cast`'s
+[`FormatOptions`][arrow_cast::display::FormatOptions] so that geometry columns of a
+[`RecordBatch`][arrow_array::RecordBatch] are rendered as WKT instead of as raw buffers."
+)]
+#![cfg_attr(
+    not(feature = "prettyprint"),
+    doc = "With the `prettyprint` feature, `GeoArrowFormatterFactory` plugs into `arrow_cast`'s
+`FormatOptions` so that geometry columns of a [`RecordBatch`][arrow_array::RecordBatch] are
+rendered as WKT instead of as raw buffers."
+)]
+
+use std::fmt::{self, Write};
+
+use arrow_array::OffsetSizeTrait;
+use geo_traits::{
+    CoordTrait, Dimensions, GeometryCollectionTrait, GeometryTrait, LineStringTrait, LineTrait,
+    MultiLineStringTrait, MultiPointTrait, MultiPolygonTrait, PointTrait, PolygonTrait, RectTrait,
+    TriangleTrait,
+};
+
+use crate::GeoArrowArrayAccessor;
+use crate::array::*;
+use crate::scalar::*;
+
+/// Options for displaying geometries as abbreviated WKT.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayOptions {
+    max_width: Option<usize>,
+    precision: Option<usize>,
+}
+
+impl DisplayOptions {
+    /// Create new display options with a max width of 80 characters and full coordinate
+    /// precision.
+    pub fn new() -> Self {
+        Self {
+            max_width: Some(80),
+            precision: None,
+        }
+    }
+
+    /// Set the maximum number of characters written for each geometry.
+    ///
+    /// Longer WKT strings are truncated and end with `...`. Pass `None` to never truncate.
+    pub fn with_max_width(self, max_width: Option<usize>) -> Self {
+        Self { max_width, ..self }
+    }
+
+    /// Set the number of decimal places written for each coordinate.
+    ///
+    /// Trailing zeros are removed. Pass `None` to write the shortest representation that
+    /// round-trips.
+    pub fn with_precision(self, precision: Option<usize>) -> Self {
+        Self { precision, ..self }
+    }
+
+    /// The maximum number of characters written for each geometry.
+    pub fn max_width(&self) -> Option<usize> {
+        self.max_width
+    }
+
+    /// The number of decimal places written for each coordinate.
+    pub fn precision(&self) -> Option<usize> {
+        self.precision
+    }
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Write a geometry as WKT, abbreviated according to the provided [`DisplayOptions`].
+pub fn write_geometry(
+    f: &mut impl Write,
+    geometry: &impl GeometryTrait<T = f64>,
+    options: &DisplayOptions,
+) -> fmt::Result {
+    let Some(max_width) = options.max_width else {
+        return write_wkt(f, geometry, options.precision);
+    };
+
+    let mut writer = TruncatingWriter {
+        buf: String::with_capacity(max_width + 1),
+        max_width,
+        truncated: false,
+    };
+    match write_wkt(&mut writer, geometry, options.precision) {
+        Ok(()) => {}
+        // The writer errors to stop writing as soon as it's full
+        Err(_) if writer.truncated => {}
+        Err(err) => return Err(err),
+    }
+
+    if writer.truncated {
+        let keep = max_width.saturating_sub(3);
+        f.write_str(&writer.buf[..keep.min(writer.buf.len())])?;
+        f.write_str(&"..."[..max_width.min(3)])
+    } else {
+        f.write_str(&writer.buf)
+    }
+}
+
+/// A writer that buffers at most `max_width` characters, then errors.
+struct TruncatingWriter {
+    buf: String,
+    max_width: usize,
+    truncated: bool,
+}
+
+impl Write for TruncatingWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        // WKT is always ASCII, so bytes and characters are interchangeable
+        let remaining = self.max_width - self.buf.len();
+        if s.len() > remaining {
+            self.buf.push_str(&s[..remaining]);
+            self.truncated = true;
+            Err(fmt::Error)
+        } else {
+            self.buf.push_str(s);
+            Ok(())
+        }
+    }
+}
+
+fn write_wkt(
+    f: &mut impl Write,
+    geometry: &impl GeometryTrait<T = f64>,
+    precision: Option<usize>,
+) -> fmt::Result {
+    use geo_traits::GeometryType as G;
+
+    match geometry.as_type() {
+        G::Point(g) => write_point(f, g, precision),
+        G::LineString(g) => {
+            write_tag(f, "LINESTRING", g.dim())?;
+            write_coords_or_empty(f, g.coords(), precision)
+        }
+        G::Polygon(g) => {
+            write_tag(f, "POLYGON", g.dim())?;
+            write_polygon_body(f, g, precision)
+        }
+        G::MultiPoint(g) => {
+            write_tag(f, "MULTIPOINT", g.dim())?;
+            write_list_or_empty(f, g.points(), |f, point| match point.coord() {
+                Some(coord) => {
+                    f.write_char('(')?;
+                    write_coord(f, &coord, precision)?;
+                    f.write_char(')')
+                }
+                None => f.write_str("EMPTY"),
+            })
+        }
+        G::MultiLineString(g) => {
+            write_tag(f, "MULTILINESTRING", g.dim())?;
+            write_list_or_empty(f, g.line_strings(), |f, line_string| {
+                write_coords_or_empty(f, line_string.coords(), precision)
+            })
+        }
+        G::MultiPolygon(g) => {
+            write_tag(f, "MULTIPOLYGON", g.dim())?;
+            write_list_or_empty(f, g.polygons(), |f, polygon| {
+                write_polygon_body(f, &polygon, precision)
+            })
+        }
+        G::GeometryCollection(g) => {
+            write_tag(f, "GEOMETRYCOLLECTION", g.dim())?;
+            write_list_or_empty(f, g.geometries(), |f, geometry| {
+                write_wkt(f, &geometry, precision)
+            })
+        }
+        G::Rect(g) => {
+            // Rects are written as a 2D polygon
+            let min = g.min();
+            let max = g.max();
+            let (x0, y0, x1, y1) = (min.x(), min.y(), max.x(), max.y());
+            f.write_str("POLYGON(")?;
+            write_coords_or_empty(
+                f,
+                [(x0, y0), (x0, y1), (x1, y1), (x1, y0), (x0, y0)].into_iter(),
+                precision,
+            )?;
+            f.write_char(')')
+        }
+        G::Triangle(g) => {
+            write_tag(f, "POLYGON", g.dim())?;
+            f.write_char('(')?;
+            write_coords_or_empty(
+                f,
+                g.coords().into_iter().chain(std::iter::once(g.first())),
+                precision,
+            )?;
+            f.write_char(')')
+        }
+        G::Line(g) => {
+            write_tag(f, "LINESTRING", g.dim())?;
+            write_coords_or_empty(f, g.coords().into_iter(), precision)
+        }
+    }
+}
+
+fn write_point(
+    f: &mut impl Write,
+    point: &impl PointTrait<T = f64>,
+    precision: Option<usize>,
+) -> fmt::Result {
+    write_tag(f, "POINT", point.dim())?;
+    match point.coord() {
+        Some(coord) => {
+            f.write_char('(')?;
+            write_coord(f, &coord, precision)?;
+            f.write_char(')')
+        }
+        None => f.write_str(" EMPTY"),
+    }
+}
+
+fn write_polygon_body(
+    f: &mut impl Write,
+    polygon: &impl PolygonTrait<T = f64>,
+    precision: Option<usize>,
+) -> fmt::Result {
+    match polygon.exterior() {
+        Some(exterior) if exterior.num_coords() > 0 => {
+            f.write_char('(')?;
+            write_coords_or_empty(f, exterior.coords(), precision)?;
+            for interior in polygon.interiors() {
+                f.write_char(',')?;
+                write_coords_or_empty(f, interior.coords(), precision)?;
+            }
+            f.write_char(')')
+        }
+        _ => f.write_str(" EMPTY"),
+    }
+}
+
+fn write_tag(f: &mut impl Write, name: &str, dim: Dimensions) -> fmt::Result {
+    f.write_str(name)?;
+    match dim {
+        Dimensions::Xyz | Dimensions::Unknown(3) => f.write_str(" Z"),
+        Dimensions::Xym => f.write_str(" M"),
+        Dimensions::Xyzm | Dimensions::Unknown(4) => f.write_str(" ZM"),
+        _ => Ok(()),
+    }
+}
+
+/// Write a parenthesized, comma-separated list of items, or ` EMPTY` if there are none.
+fn write_list_or_empty<W: Write, T>(
+    f: &mut W,
+    items: impl Iterator<Item = T>,
+    mut write_item: impl FnMut(&mut W, T) -> fmt::Result,
+) -> fmt::Result {
+    let mut items = items.peekable();
+    if items.peek().is_none() {
+        return f.write_str(" EMPTY");
+    }
+
+    f.write_char('(')?;
+    for (i, item) in items.enumerate() {
+        if i > 0 {
+            f.write_char(',')?;
+        }
+        write_item(f, item)?;
+    }
+    f.write_char(')')
+}
+
+fn write_coords_or_empty(
+    f: &mut impl Write,
+    coords: impl Iterator<Item = impl CoordTrait<T = f64>>,
+    precision: Option<usize>,
+) -> fmt::Result {
+    write_list_or_empty(f, coords, |f, coord| write_coord(f, &coord, precision))
+}
+
+fn write_coord(
+    f: &mut impl Write,
+    coord: &impl CoordTrait<T = f64>,
+    precision: Option<usize>,
+) -> fmt::Result {
+    for i in 0..coord.dim().size() {
+        if i > 0 {
+            f.write_char(' ')?;
+        }
+        write_number(f, coord.nth_or_panic(i), precision)?;
+    }
+    Ok(())
+}
+
+fn write_number(f: &mut impl Write, value: f64, precision: Option<usize>) -> fmt::Result {
+    let Some(precision) = precision else {
+        return write!(f, "{value}");
+    };
+
+    let formatted = format!("{value:.precision$}");
+    let trimmed = if formatted.contains('.') {
+        formatted.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        formatted.as_str()
+    };
+    // Avoid writing negative zero for small negative values rounded to zero
+    if trimmed == "-0" {
+        f.write_char('0')
+    } else {
+        f.write_str(trimmed)
+    }
+}
+
+/// Write an array as a list of abbreviated WKT values, eliding the middle of long arrays.
+fn write_array<'a>(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    array: &'a impl GeoArrowArrayAccessor<'a>,
+) -> fmt::Result {
+    let options = DisplayOptions::default();
+    let write_row = |f: &mut fmt::Formatter<'_>, i: usize| -> fmt::Result {
+        f.write_str("    ")?;
+        match array.get(i) {
+            Ok(Some(geom)) => {
+                f.write_char('<')?;
+                write_geometry(f, &geom, &options)?;
+                f.write_str(">,\n")
+            }
+            Ok(None) => f.write_str("null,\n"),
+            Err(_) => f.write_str("<invalid>,\n"),
+        }
+    };
+
+    writeln!(f, "{name}([")?;
+    let len = array.len();
+    if len > 6 {
+        (0..3).try_for_each(|i| write_row(f, i))?;
+        f.write_str("    ...,\n")?;
+        (len - 3..len).try_for_each(|i| write_row(f, i))?;
+    } else {
+        (0..len).try_for_each(|i| write_row(f, i))?;
+    }
+    f.write_str("])")
+}
+
+macro_rules! impl_display_array {
+    ($array_type:ty, $name:expr) => {
+        impl fmt::Display for $array_type {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write_array(f, $name, self)
+            }
+        }
+    };
+}
+
+impl_display_array!(PointArray, "PointArray");
+impl_display_array!(LineStringArray, "LineStringArray");
+impl_display_array!(PolygonArray, "PolygonArray");
+impl_display_array!(MultiPointArray, "MultiPointArray");
+impl_display_array!(MultiLineStringArray, "MultiLineStringArray");
+impl_display_array!(MultiPolygonArray, "MultiPolygonArray");
+impl_display_array!(GeometryCollectionArray, "GeometryCollectionArray");
+impl_display_array!(GeometryArray, "GeometryArray");
+impl_display_array!(RectArray, "RectArray");
+impl_display_array!(WkbViewArray, "WkbViewArray");
+impl_display_array!(WktViewArray, "WktViewArray");
+
+impl<O: OffsetSizeTrait> fmt::Display for GenericWkbArray<O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = if O::IS_LARGE {
+            "LargeWkbArray"
+        } else {
+            "WkbArray"
+        };
+        write_array(f, name, self)
+    }
+}
+
+impl<O: OffsetSizeTrait> fmt::Display for GenericWktArray<O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = if O::IS_LARGE {
+            "LargeWktArray"
+        } else {
+            "WktArray"
+        };
+        write_array(f, name, self)
+    }
+}
+
+macro_rules! impl_display_scalar {
+    ($scalar_type:ty) => {
+        impl fmt::Display for $scalar_type {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write_geometry(f, self, &DisplayOptions::default())
+            }
+        }
+    };
+}
+
+impl_display_scalar!(Point<'_>);
+impl_display_scalar!(LineString<'_>);
+impl_display_scalar!(Polygon<'_>);
+impl_display_scalar!(MultiPoint<'_>);
+impl_display_scalar!(MultiLineString<'_>);
+impl_display_scalar!(MultiPolygon<'_>);
+impl_display_scalar!(GeometryCollection<'_>);
+impl_display_scalar!(Geometry<'_>);
+impl_display_scalar!(Rect<'_>);
+
+#[cfg(feature = "prettyprint")]
+pub use prettyprint::{GeoArrowFormatterFactory, pretty_format_batches};
+
+#[cfg(feature = "prettyprint")]
+mod prettyprint {
+    use std::fmt;
+    use std::sync::Arc;
+
+    use arrow_array::{Array, RecordBatch};
+    use arrow_cast::display::{
+        ArrayFormatter, ArrayFormatterFactory, DisplayIndex, FormatError, FormatOptions,
+        FormatResult,
+    };
+    use arrow_schema::{ArrowError, Field};
+
+    use super::{DisplayOptions, write_geometry};
+    use crate::array::from_arrow_array;
+    use crate::{GeoArrowArray, GeoArrowArrayAccessor, downcast_geoarrow_array};
+
+    /// An [`ArrayFormatterFactory`] that renders GeoArrow extension columns as abbreviated WKT.
+    ///
+    /// Columns without a `geoarrow.*` extension type fall back to the default formatter.
+    ///
+    /// ```
+    /// use arrow_cast::display::FormatOptions;
+    /// use arrow_cast::pretty::pretty_format_batches_with_options;
+    /// use geoarrow_array::display::{DisplayOptions, GeoArrowFormatterFactory};
+    ///
+    /// let factory = GeoArrowFormatterFactory::new(DisplayOptions::new().with_precision(Some(3)));
+    /// let options = FormatOptions::new().with_formatter_factory(Some(&factory));
+    /// let table = pretty_format_batches_with_options(&[], &options).unwrap();
+    /// ```
+    #[derive(Debug, Clone, Default)]
+    pub struct GeoArrowFormatterFactory {
+        options: DisplayOptions,
+    }
+
+    impl GeoArrowFormatterFactory {
+        /// Create a new factory with the given display options.
+        pub fn new(options: DisplayOptions) -> Self {
+            Self { options }
+        }
+    }
+
+    impl ArrayFormatterFactory for GeoArrowFormatterFactory {
+        fn create_array_formatter<'formatter>(
+            &self,
+            array: &'formatter dyn Array,
+            options: &FormatOptions<'formatter>,
+            field: Option<&'formatter Field>,
+        ) -> Result<Option<ArrayFormatter<'formatter>>, ArrowError> {
+            let Some(field) = field else {
+                return Ok(None);
+            };
+            if !field
+                .extension_type_name()
+                .is_some_and(|name| name.starts_with("geoarrow."))
+            {
+                return Ok(None);
+            }
+
+            let display_index = GeoArrowDisplayIndex {
+                array: from_arrow_array(array, field)?,
+                options: self.options.clone(),
+                null: options.null(),
+            };
+            Ok(Some(ArrayFormatter::new(
+                Box::new(display_index),
+                options.safe(),
+            )))
+        }
+    }
+
+    struct GeoArrowDisplayIndex<'a> {
+        array: Arc<dyn GeoArrowArray>,
+        options: DisplayOptions,
+        null: &'a str,
+    }
+
+    impl DisplayIndex for GeoArrowDisplayIndex<'_> {
+        fn write(&self, idx: usize, f: &mut dyn fmt::Write) -> FormatResult {
+            if self.array.is_null(idx) {
+                f.write_str(self.null)?;
+                return Ok(());
+            }
+
+            let array = self.array.as_ref();
+            downcast_geoarrow_array!(array, write_value, idx, f, &self.options)
+        }
+    }
+
+    fn write_value<'a>(
+        array: &'a impl GeoArrowArrayAccessor<'a>,
+        idx: usize,
+        mut f: &mut dyn fmt::Write,
+        options: &DisplayOptions,
+    ) -> FormatResult {
+        let geom = array
+            .value(idx)
+            .map_err(|err| FormatError::Arrow(err.into()))?;
+        write_geometry(&mut f, &geom, options)?;
+        Ok(())
+    }
+
+    /// Format record batches as a table, rendering geometry columns as abbreviated WKT.
+    pub fn pretty_format_batches(
+        batches: &[RecordBatch],
+        options: &DisplayOptions,
+    ) -> Result<impl fmt::Display + use<>, ArrowError> {
+        let factory = GeoArrowFormatterFactory::new(options.clone());
+        let format_options = FormatOptions::new().with_formatter_factory(Some(&factory));
+        arrow_cast::pretty::pretty_format_batches_with_options(batches, &format_options)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use geoarrow_schema::{CoordType, Dimension};
+
+    use super::*;
+    use crate::cast::to_wkb;
+    use crate::test;
+
+    fn to_string(geometry: &impl GeometryTrait<T = f64>, options: &DisplayOptions) -> String {
+        let mut s = String::new();
+        write_geometry(&mut s, geometry, options).unwrap();
+        s
+    }
+
+    #[test]
+    fn display_point_array() {
+        let array = test::point::array(CoordType::Separated, Dimension::XY);
+        let expected = "PointArray([
+    <POINT(30 10)>,
+    <POINT(40 20)>,
+    null,
+    <POINT EMPTY>,
+])";
+        assert_eq!(array.to_string(), expected);
+
+        let wkb_array = to_wkb::<i32>(&array).unwrap();
+        assert_eq!(
+            wkb_array.to_string(),
+            expected.replace("PointArray", "WkbArray")
+        );
+    }
+
+    #[test]
+    fn display_long_array() {
+        let points = (0..8)
+            .map(|i| geo_types::point!(x: i as f64, y: i as f64))
+            .collect::<Vec<_>>();
+        let typ = geoarrow_schema::PointType::new(Dimension::XY, Default::default());
+        let array = crate::builder::PointBuilder::from_points(points.iter(), typ).finish();
+
+        let expected = "PointArray([
+    <POINT(0 0)>,
+    <POINT(1 1)>,
+    <POINT(2 2)>,
+    ...,
+    <POINT(5 5)>,
+    <POINT(6 6)>,
+    <POINT(7 7)>,
+])";
+        assert_eq!(array.to_string(), expected);
+    }
+
+    #[test]
+    fn precision_and_width() {
+        let point = wkt::types::Point::from_coord(wkt::types::Coord {
+            x: 1.23456,
+            y: -0.0001,
+            z: None,
+            m: None,
+        });
+
+        let options = DisplayOptions::new().with_precision(Some(2));
+        assert_eq!(to_string(&point, &options), "POINT(1.23 0)");
+
+        let options = DisplayOptions::new().with_max_width(Some(10));
+        assert_eq!(to_string(&point, &options), "POINT(1...");
+
+        let options = DisplayOptions::new().with_max_width(None);
+        assert_eq!(to_string(&point, &options), "POINT(1.23456 -0.0001)");
+    }
+
+    #[test]
+    fn display_scalar() {
+        let array = test::multipoint::array(CoordType::Separated, Dimension::XYZ);
+        let geom = array.value(0).unwrap();
+        assert!(geom.to_string().starts_with("MULTIPOINT Z(("));
+    }
+
+    #[cfg(feature = "prettyprint")]
+    #[test]
+    fn pretty_print_batch() {
+        use std::sync::Arc;
+
+        use arrow_array::RecordBatch;
+        use arrow_schema::Schema;
+
+        use crate::GeoArrowArray;
+
+        let array = test::point::array(CoordType::Separated, Dimension::XY);
+        let schema = Schema::new(vec![array.data_type().to_field("geometry", true)]);
+        let batch = RecordBatch::try_new(Arc::new(schema), vec![array.to_array_ref()]).unwrap();
+
+        let table = pretty_format_batches(&[batch], &DisplayOptions::default())
+            .unwrap()
+            .to_string();
+        let expected = "+--------------+
+| geometry     |
++--------------+
+| POINT(30 10) |
+| POINT(40 20) |
+|              |
+| POINT EMPTY  |
++--------------+";
+        assert_eq!(table, expected);
+    }
+}
@@ -8,15 +8,19 @@
 )]
 
 pub mod array;
+pub mod bounds;
 pub mod builder;
 pub mod capacity;
 pub mod cast;
+pub mod display;
 mod eq;
 #[cfg(feature = "geozero")]
 pub mod geozero;
 pub mod scalar;
+pub mod snap;
 mod trait_;
 pub(crate) mod util;
+pub mod wkb_dialect;
 mod wrap_array;
 
 pub use trait_::{
@@ -0,0 +1,564 @@
+//! Snap the coordinates of GeoArrow arrays to a grid.
+//!
+//! Reducing coordinate precision before writing makes output compress far better, e.g. in
+//! Parquet, and avoids near-duplicate vertices. See [`snap_to_grid`].
+
+use std::sync::Arc;
+
+use arrow_array::OffsetSizeTrait;
+use geo_traits::{
+    CoordTrait, GeometryCollectionTrait, GeometryTrait, GeometryType, LineStringTrait,
+    MultiLineStringTrait, MultiPointTrait, MultiPolygonTrait, PointTrait, PolygonTrait, RectTrait,
+};
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+use geoarrow_schema::{GeoArrowType, WkbType};
+use wkt::Wkt;
+use wkt::types::{
+    Coord, Dimension, GeometryCollection, LineString, MultiLineString, MultiPoint, MultiPolygon,
+    Point, Polygon,
+};
+
+use crate::array::GenericWkbArray;
+use crate::builder::{
+    GeometryBuilder, GeometryCollectionBuilder, LineStringBuilder, MultiLineStringBuilder,
+    MultiPointBuilder, MultiPolygonBuilder, PointBuilder, PolygonBuilder, RectBuilder, WkbBuilder,
+};
+use crate::cast::{AsGeoArrowArray, to_wkb_view, to_wkt, to_wkt_view};
+use crate::trait_::GeoArrowArrayBuilder;
+use crate::{GeoArrowArray, GeoArrowArrayAccessor};
+
+/// Options for [`snap_to_grid`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapOptions {
+    grid_size: f64,
+    z_grid_size: Option<f64>,
+    remove_repeated_points: bool,
+}
+
+impl SnapOptions {
+    /// Snap X and Y values to a grid with the given cell size, e.g. `1e-7` for coordinates in
+    /// degrees.
+    ///
+    /// Z and M values are left unchanged by default.
+    pub fn new(grid_size: f64) -> Self {
+        Self {
+            grid_size,
+            z_grid_size: None,
+            remove_repeated_points: false,
+        }
+    }
+
+    /// Also snap Z values to a grid with the given cell size.
+    pub fn with_z_grid_size(self, z_grid_size: f64) -> Self {
+        Self {
+            z_grid_size: Some(z_grid_size),
+            ..self
+        }
+    }
+
+    /// Set whether to remove consecutive points made identical by snapping.
+    ///
+    /// When enabled, line strings with fewer than 2 points and rings with fewer than 4 points
+    /// after snapping have collapsed. Collapsed line strings and polygon exteriors become empty,
+    /// and collapsed interior rings, parts of multi geometries, and empty members of geometry
+    /// collections are removed.
+    pub fn with_remove_repeated_points(self, remove_repeated_points: bool) -> Self {
+        Self {
+            remove_repeated_points,
+            ..self
+        }
+    }
+
+    /// The cell size used for X and Y values.
+    pub fn grid_size(&self) -> f64 {
+        self.grid_size
+    }
+
+    fn snap(value: f64, size: f64) -> f64 {
+        if size > 0.0 {
+            (value / size).round() * size
+        } else {
+            value
+        }
+    }
+
+    fn snap_coord(&self, coord: &impl CoordTrait<T = f64>) -> Coord<f64> {
+        let (z, m) = match coord.dim() {
+            geo_traits::Dimensions::Xyz | geo_traits::Dimensions::Unknown(3) => (Some(2), None),
+            geo_traits::Dimensions::Xym => (None, Some(2)),
+            geo_traits::Dimensions::Xyzm | geo_traits::Dimensions::Unknown(4) => (Some(2), Some(3)),
+            _ => (None, None),
+        };
+        Coord {
+            x: Self::snap(coord.x(), self.grid_size),
+            y: Self::snap(coord.y(), self.grid_size),
+            z: z.map(|n| {
+                let z = coord.nth_or_panic(n);
+                self.z_grid_size.map_or(z, |size| Self::snap(z, size))
+            }),
+            m: m.map(|n| coord.nth_or_panic(n)),
+        }
+    }
+
+    fn snap_coords(
+        &self,
+        coords: impl Iterator<Item = impl CoordTrait<T = f64>>,
+    ) -> Vec<Coord<f64>> {
+        let mut out: Vec<Coord<f64>> = Vec::new();
+        for coord in coords {
+            let coord = self.snap_coord(&coord);
+            if self.remove_repeated_points && out.last() == Some(&coord) {
+                continue;
+            }
+            out.push(coord);
+        }
+        out
+    }
+
+    fn snap_line_string(
+        &self,
+        line_string: &impl LineStringTrait<T = f64>,
+        min_coords: usize,
+    ) -> Option<LineString<f64>> {
+        let dim = wkt_dimension(line_string.dim());
+        let coords = self.snap_coords(line_string.coords());
+        if self.remove_repeated_points && coords.len() < min_coords {
+            None
+        } else {
+            Some(LineString::new(coords, dim))
+        }
+    }
+
+    fn snap_polygon(&self, polygon: &impl PolygonTrait<T = f64>) -> Polygon<f64> {
+        let dim = wkt_dimension(polygon.dim());
+        let Some(exterior) = polygon
+            .exterior()
+            .and_then(|ring| self.snap_line_string(&ring, 4))
+        else {
+            return Polygon::empty(dim);
+        };
+        let mut rings = vec![exterior];
+        rings.extend(
+            polygon
+                .interiors()
+                .filter_map(|ring| self.snap_line_string(&ring, 4)),
+        );
+        Polygon::new(rings, dim)
+    }
+
+    /// Snap the coordinates of a single geometry.
+    ///
+    /// The geometry type never changes: a line string or polygon that collapses when removing
+    /// repeated points becomes empty rather than a point.
+    pub fn snap_geometry(
+        &self,
+        geometry: &impl GeometryTrait<T = f64>,
+    ) -> GeoArrowResult<Wkt<f64>> {
+        let dim = wkt_dimension(geometry.dim());
+        let out = match geometry.as_type() {
+            GeometryType::Point(point) => Wkt::Point(Point::new(
+                point.coord().map(|coord| self.snap_coord(&coord)),
+                dim,
+            )),
+            GeometryType::LineString(line_string) => Wkt::LineString(
+                self.snap_line_string(line_string, 2)
+                    .unwrap_or_else(|| LineString::empty(dim)),
+            ),
+            GeometryType::Polygon(polygon) => Wkt::Polygon(self.snap_polygon(polygon)),
+            GeometryType::MultiPoint(multi_point) => {
+                let mut points: Vec<Point<f64>> = Vec::new();
+                for point in multi_point.points() {
+                    let point = Point::new(point.coord().map(|coord| self.snap_coord(&coord)), dim);
+                    if self.remove_repeated_points && points.last() == Some(&point) {
+                        continue;
+                    }
+                    points.push(point);
+                }
+                Wkt::MultiPoint(MultiPoint::new(points, dim))
+            }
+            GeometryType::MultiLineString(multi_line_string) => {
+                let line_strings = multi_line_string
+                    .line_strings()
+                    .filter_map(|line_string| self.snap_line_string(&line_string, 2))
+                    .collect();
+                Wkt::MultiLineString(MultiLineString::new(line_strings, dim))
+            }
+            GeometryType::MultiPolygon(multi_polygon) => {
+                let polygons = multi_polygon
+                    .polygons()
+                    .map(|polygon| self.snap_polygon(&polygon))
+                    .filter(|polygon| !self.remove_repeated_points || !polygon.rings().is_empty())
+                    .collect();
+                Wkt::MultiPolygon(MultiPolygon::new(polygons, dim))
+            }
+            GeometryType::GeometryCollection(collection) => {
+                let mut geometries = Vec::with_capacity(collection.num_geometries());
+                for geometry in collection.geometries() {
+                    let geometry = self.snap_geometry(&geometry)?;
+                    if self.remove_repeated_points && is_empty(&geometry) {
+                        continue;
+                    }
+                    geometries.push(geometry);
+                }
+                Wkt::GeometryCollection(GeometryCollection::new(geometries, dim))
+            }
+            _ => {
+                return Err(GeoArrowError::IncorrectGeometryType(
+                    "Only Point, LineString, Polygon, MultiPoint, MultiLineString, MultiPolygon, and GeometryCollection geometries can be snapped to a grid".to_string(),
+                ));
+            }
+        };
+        Ok(out)
+    }
+}
+
+fn wkt_dimension(dim: geo_traits::Dimensions) -> Dimension {
+    match dim {
+        geo_traits::Dimensions::Xyz | geo_traits::Dimensions::Unknown(3) => Dimension::XYZ,
+        geo_traits::Dimensions::Xym => Dimension::XYM,
+        geo_traits::Dimensions::Xyzm | geo_traits::Dimensions::Unknown(4) => Dimension::XYZM,
+        _ => Dimension::XY,
+    }
+}
+
+fn is_empty(geometry: &Wkt<f64>) -> bool {
+    match geometry {
+        Wkt::Point(g) => g.coord().is_none(),
+        Wkt::LineString(g) => g.coords().is_empty(),
+        Wkt::Polygon(g) => g.rings().is_empty(),
+        Wkt::MultiPoint(g) => g.points().is_empty(),
+        Wkt::MultiLineString(g) => g.line_strings().is_empty(),
+        Wkt::MultiPolygon(g) => g.polygons().is_empty(),
+        Wkt::GeometryCollection(g) => g.geometries().is_empty(),
+    }
+}
+
+/// Snap the coordinates of every geometry in an array to a grid.
+///
+/// The output array has the same [`GeoArrowType`] as the input. Serialized arrays are parsed,
+/// snapped, and serialized again.
+///
+/// ## Examples
+///
+/// ```
+/// use geoarrow_array::builder::LineStringBuilder;
+/// use geoarrow_array::cast::AsGeoArrowArray;
+/// use geoarrow_array::snap::{SnapOptions, snap_to_grid};
+/// use geoarrow_array::GeoArrowArrayAccessor;
+/// use geoarrow_schema::{Dimension, LineStringType};
+/// use geo_traits::LineStringTrait;
+/// use wkt::wkt;
+///
+/// let typ = LineStringType::new(Dimension::XY, Default::default());
+/// let array = LineStringBuilder::from_line_strings(
+///     &[wkt!(LINESTRING (0.12 0.13, 0.14 0.11, 1.01 2.02))],
+///     typ,
+/// )
+/// .finish();
+///
+/// let options = SnapOptions::new(0.5).with_remove_repeated_points(true);
+/// let snapped = snap_to_grid(&array, &options).unwrap();
+/// let line_string = snapped.as_line_string().value(0).unwrap();
+/// assert_eq!(line_string.num_coords(), 2);
+/// ```
+pub fn snap_to_grid(
+    array: &dyn GeoArrowArray,
+    options: &SnapOptions,
+) -> GeoArrowResult<Arc<dyn GeoArrowArray>> {
+    use GeoArrowType::*;
+    match array.data_type() {
+        Point(typ) => snap_native(array.as_point(), PointBuilder::new(typ), options),
+        LineString(typ) => {
+            snap_native(array.as_line_string(), LineStringBuilder::new(typ), options)
+        }
+        Polygon(typ) => snap_native(array.as_polygon(), PolygonBuilder::new(typ), options),
+        MultiPoint(typ) => {
+            snap_native(array.as_multi_point(), MultiPointBuilder::new(typ), options)
+        }
+        MultiLineString(typ) => snap_native(
+            array.as_multi_line_string(),
+            MultiLineStringBuilder::new(typ),
+            options,
+        ),
+        MultiPolygon(typ) => snap_native(
+            array.as_multi_polygon(),
+            MultiPolygonBuilder::new(typ),
+            options,
+        ),
+        GeometryCollection(typ) => snap_native(
+            array.as_geometry_collection(),
+            GeometryCollectionBuilder::new(typ),
+            options,
+        ),
+        Geometry(typ) => {
+            let mut builder = GeometryBuilder::new(typ);
+            for geometry in array.as_geometry().iter() {
+                match geometry.transpose()? {
+                    // `push_geometry` would unwrap a collection with a single member
+                    Some(geometry) => match options.snap_geometry(&geometry)? {
+                        wkt::Wkt::GeometryCollection(collection) => {
+                            builder.push_geometry_collection(Some(&collection))?
+                        }
+                        snapped => builder.push_geometry(Some(&snapped))?,
+                    },
+                    None => builder.push_null(),
+                }
+            }
+            Ok(Arc::new(builder.finish()))
+        }
+        Rect(typ) => {
+            let mut builder = RectBuilder::with_capacity(typ, array.len());
+            for rect in array.as_rect().iter() {
+                match rect.transpose()? {
+                    Some(rect) => builder.push_min_max(
+                        &options.snap_coord(&rect.min()),
+                        &options.snap_coord(&rect.max()),
+                    ),
+                    None => builder.push_null(),
+                }
+            }
+            Ok(Arc::new(builder.finish()))
+        }
+        Wkb(typ) => Ok(Arc::new(snap_serialized::<i32>(
+            array.as_wkb::<i32>(),
+            typ,
+            options,
+        )?)),
+        LargeWkb(typ) => Ok(Arc::new(snap_serialized::<i64>(
+            array.as_wkb::<i64>(),
+            typ,
+            options,
+        )?)),
+        WkbView(typ) => {
+            let snapped = snap_serialized::<i64>(array.as_wkb_view(), typ, options)?;
+            Ok(Arc::new(to_wkb_view(&snapped)?))
+        }
+        Wkt(typ) => {
+            let typ = WkbType::new(typ.metadata().clone());
+            let snapped = snap_serialized::<i32>(array.as_wkt::<i32>(), typ, options)?;
+            Ok(Arc::new(to_wkt::<i32>(&snapped)?))
+        }
+        LargeWkt(typ) => {
+            let typ = WkbType::new(typ.metadata().clone());
+            let snapped = snap_serialized::<i64>(array.as_wkt::<i64>(), typ, options)?;
+            Ok(Arc::new(to_wkt::<i64>(&snapped)?))
+        }
+        WktView(typ) => {
+            let typ = WkbType::new(typ.metadata().clone());
+            let snapped = snap_serialized::<i64>(array.as_wkt_view(), typ, options)?;
+            Ok(Arc::new(to_wkt_view(&snapped)?))
+        }
+    }
+}
+
+/// Snap serialized geometries one at a time, keeping the structure of each geometry.
+///
+/// Parsing into a [`GeometryArray`][crate::array::GeometryArray] instead would unwrap geometry
+/// collections with a single member.
+fn snap_serialized<'a, O: OffsetSizeTrait>(
+    array: &'a impl GeoArrowArrayAccessor<'a>,
+    typ: WkbType,
+    options: &SnapOptions,
+) -> GeoArrowResult<GenericWkbArray<O>> {
+    let mut builder = WkbBuilder::<O>::new(typ);
+    for geometry in array.iter() {
+        match geometry.transpose()? {
+            Some(geometry) => builder.push_geometry(Some(&options.snap_geometry(&geometry)?))?,
+            None => builder.push_geometry(None::<&Wkt<f64>>)?,
+        }
+    }
+    Ok(builder.finish())
+}
+
+fn snap_native<'a>(
+    array: &'a impl GeoArrowArrayAccessor<'a>,
+    mut builder: impl GeoArrowArrayBuilder,
+    options: &SnapOptions,
+) -> GeoArrowResult<Arc<dyn GeoArrowArray>> {
+    for geometry in array.iter() {
+        match geometry.transpose()? {
+            Some(geometry) => builder.push_geometry(Some(&options.snap_geometry(&geometry)?))?,
+            None => builder.push_null(),
+        }
+    }
+    Ok(builder.finish())
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use arrow_array::StringArray;
+    use geoarrow_schema::{
+        BoxType, CoordType, Dimension, GeometryType as GeometryDataType, PolygonType,
+    };
+
+    use super::*;
+    use crate::array::WktArray;
+    use crate::cast::from_wkt;
+    use crate::test;
+
+    fn wkt_values(array: &dyn GeoArrowArray) -> Vec<Option<Wkt<f64>>> {
+        to_wkt::<i32>(array)
+            .unwrap()
+            .iter()
+            .map(|geom| geom.map(|geom| geom.unwrap()))
+            .collect()
+    }
+
+    fn wkt_array(geoms: &[Option<&str>]) -> WktArray {
+        WktArray::new(StringArray::from(geoms.to_vec()), Default::default())
+    }
+
+    fn parse(geoms: &[Option<&str>]) -> Vec<Option<Wkt<f64>>> {
+        geoms
+            .iter()
+            .map(|geom| geom.map(|s| Wkt::from_str(s).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn snap_keeps_type() {
+        let options = SnapOptions::new(0.5);
+        for coord_type in [CoordType::Interleaved, CoordType::Separated] {
+            for dim in [
+                Dimension::XY,
+                Dimension::XYZ,
+                Dimension::XYM,
+                Dimension::XYZM,
+            ] {
+                let arrays: Vec<Arc<dyn GeoArrowArray>> = vec![
+                    Arc::new(test::point::array(coord_type, dim)),
+                    Arc::new(test::linestring::array(coord_type, dim)),
+                    Arc::new(test::polygon::array(coord_type, dim)),
+                    Arc::new(test::multipoint::array(coord_type, dim)),
+                    Arc::new(test::multilinestring::array(coord_type, dim)),
+                    Arc::new(test::multipolygon::array(coord_type, dim)),
+                ];
+                for array in arrays {
+                    let snapped = snap_to_grid(array.as_ref(), &options).unwrap();
+                    assert_eq!(snapped.data_type(), array.data_type());
+                    assert_eq!(snapped.len(), array.len());
+                    assert_eq!(snapped.logical_nulls(), array.logical_nulls());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn snap_wkt() {
+        let array = wkt_array(&[
+            Some("POINT (1.26 2.74)"),
+            None,
+            Some("LINESTRING Z (0.1 0.1 0.33, 0.9 1.1 0.66)"),
+        ]);
+        let snapped = snap_to_grid(&array, &SnapOptions::new(0.5)).unwrap();
+        assert_eq!(snapped.data_type(), array.data_type());
+        assert_eq!(
+            wkt_values(snapped.as_ref()),
+            parse(&[
+                Some("POINT (1.5 2.5)"),
+                None,
+                Some("LINESTRING Z (0 0 0.33, 1 1 0.66)"),
+            ])
+        );
+    }
+
+    #[test]
+    fn snap_z_grid_size() {
+        let array = wkt_array(&[Some("POINT Z (0.1 0.1 0.33)")]);
+        let options = SnapOptions::new(1.0).with_z_grid_size(0.25);
+        let snapped = snap_to_grid(&array, &options).unwrap();
+        assert_eq!(
+            wkt_values(snapped.as_ref()),
+            parse(&[Some("POINT Z (0 0 0.25)")])
+        );
+    }
+
+    #[test]
+    fn snap_remove_repeated_points() {
+        let polygon_type = PolygonType::new(Dimension::XY, Default::default());
+        let geoms = [
+            // Repeated vertices are removed
+            Some("POLYGON ((0 0, 0.1 0.1, 0 2, 2 2, 2 0, 0 0))"),
+            // A collapsed interior ring is removed
+            Some("POLYGON ((0 0, 0 4, 4 4, 4 0, 0 0), (1 1, 1 1.1, 1.1 1.1, 1 1))"),
+            // A collapsed polygon becomes empty
+            Some("POLYGON ((0 0, 0 0.1, 0.1 0.1, 0 0))"),
+        ];
+        let polygons = from_wkt(&wkt_array(&geoms), polygon_type.into()).unwrap();
+
+        let options = SnapOptions::new(1.0).with_remove_repeated_points(true);
+        let snapped = snap_to_grid(polygons.as_ref(), &options).unwrap();
+        assert_eq!(
+            wkt_values(snapped.as_ref()),
+            parse(&[
+                Some("POLYGON ((0 0, 0 2, 2 2, 2 0, 0 0))"),
+                Some("POLYGON ((0 0, 0 4, 4 4, 4 0, 0 0))"),
+                Some("POLYGON EMPTY"),
+            ])
+        );
+
+        // Without removal, only coordinates change
+        let snapped = snap_to_grid(polygons.as_ref(), &SnapOptions::new(1.0)).unwrap();
+        assert_eq!(
+            wkt_values(snapped.as_ref())[2],
+            parse(&[Some("POLYGON ((0 0, 0 0, 0 0, 0 0))")])[0]
+        );
+    }
+
+    #[test]
+    fn snap_multi_geometries() {
+        let array = wkt_array(&[
+            Some("MULTIPOINT ((0 0), (0.1 0.1), (3 3))"),
+            Some("MULTILINESTRING ((0 0, 0.1 0.1), (0 0, 5 5))"),
+            Some("GEOMETRYCOLLECTION (LINESTRING (0 0, 0.2 0.2), POINT (1 1))"),
+        ]);
+        let options = SnapOptions::new(1.0).with_remove_repeated_points(true);
+        let snapped = snap_to_grid(&array, &options).unwrap();
+        assert_eq!(
+            wkt_values(snapped.as_ref()),
+            parse(&[
+                Some("MULTIPOINT ((0 0), (3 3))"),
+                Some("MULTILINESTRING ((0 0, 5 5))"),
+                Some("GEOMETRYCOLLECTION (POINT (1 1))"),
+            ])
+        );
+
+        let geometry_type = GeometryDataType::new(Default::default());
+        let geometries = from_wkt(&array, geometry_type.into()).unwrap();
+        let snapped = snap_to_grid(geometries.as_ref(), &options).unwrap();
+        assert_eq!(
+            wkt_values(snapped.as_ref())[2],
+            parse(&[Some("GEOMETRYCOLLECTION (POINT (1 1))")])[0]
+        );
+    }
+
+    #[test]
+    fn snap_rect() {
+        let typ = BoxType::new(Dimension::XY, Default::default());
+        let mut builder = RectBuilder::new(typ);
+        builder.push_min_max(
+            &Coord {
+                x: 0.2,
+                y: 0.7,
+                z: None,
+                m: None,
+            },
+            &Coord {
+                x: 1.4,
+                y: 2.6,
+                z: None,
+                m: None,
+            },
+        );
+        builder.push_null();
+        let array = builder.finish();
+
+        let snapped = snap_to_grid(&array, &SnapOptions::new(1.0)).unwrap();
+        let rect = snapped.as_rect().value(0).unwrap();
+        assert_eq!((rect.min().x(), rect.min().y()), (0., 1.));
+        assert_eq!((rect.max().x(), rect.max().y()), (1., 3.));
+        assert!(snapped.is_null(1));
+    }
+}
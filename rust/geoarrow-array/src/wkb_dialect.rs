@@ -0,0 +1,820 @@
+//! Read and write dialects of WKB other than ISO WKB.
+//!
+//! [`GenericWkbArray`] and [`WkbViewArray`] hold ISO/OGC WKB. Two other dialects are common in
+//! the wild:
+//!
+//! - **EWKB**, the extended WKB exported by PostGIS. Dimensions are stored as high bit flags on the
+//!   geometry type, and the geometry may embed an SRID.
+//! - **GeoPackage** geometry blobs, which prefix standard WKB with a `GP` header holding an
+//!   `srs_id` and an optional envelope.
+//!
+//! [`from_wkb_dialect`] parses either dialect, lifting the SRID into the array's [`Metadata`].
+//! [`to_wkb_dialect`] writes an array back out as EWKB or GeoPackage blobs.
+//! [`read_geopackage_header`] and [`write_geopackage_header`] handle the header of a single
+//! GeoPackage blob, for readers and writers that work row by row.
+
+use std::sync::Arc;
+
+use arrow_array::builder::GenericBinaryBuilder;
+use arrow_array::{GenericBinaryArray, OffsetSizeTrait};
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+use geoarrow_schema::{Crs, CrsType, GeoArrowType, Metadata};
+
+use wkb::reader::Wkb;
+
+use crate::array::{GenericWkbArray, WkbViewArray};
+use crate::bounds::BoundingRect;
+use crate::cast::{AsGeoArrowArray, from_wkb, to_wkb};
+use crate::{GeoArrowArray, IntoArrow};
+
+const EWKB_Z_FLAG: u32 = 0x8000_0000;
+const EWKB_M_FLAG: u32 = 0x4000_0000;
+const EWKB_SRID_FLAG: u32 = 0x2000_0000;
+
+const GPKG_MAGIC: &[u8; 2] = b"GP";
+const GPKG_LITTLE_ENDIAN_FLAG: u8 = 0b0000_0001;
+/// The envelope contents indicator for an `[minx, maxx, miny, maxy]` envelope.
+const GPKG_ENVELOPE_XY_FLAG: u8 = 0b0000_0010;
+const GPKG_EMPTY_FLAG: u8 = 0b0001_0000;
+const GPKG_EXTENDED_FLAG: u8 = 0b0010_0000;
+
+/// A dialect of well-known binary.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum WkbDialect {
+    /// ISO/OGC WKB, as stored in [`GenericWkbArray`] and [`WkbViewArray`].
+    #[default]
+    Iso,
+
+    /// PostGIS extended WKB, with dimension flags and an optional embedded SRID.
+    Ewkb,
+
+    /// GeoPackage geometry blobs: a `GP` header with an `srs_id` and optional envelope, followed
+    /// by WKB.
+    GeoPackage,
+}
+
+/// Options for [`from_wkb_dialect`] and [`to_wkb_dialect_with_options`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WkbDialectOptions {
+    dialect: WkbDialect,
+    srid_authority: Option<String>,
+    envelope: bool,
+}
+
+impl WkbDialectOptions {
+    /// Read the given WKB dialect.
+    pub fn new(dialect: WkbDialect) -> Self {
+        Self {
+            dialect,
+            srid_authority: None,
+            envelope: false,
+        }
+    }
+
+    /// Interpret embedded SRIDs as codes of the given authority, e.g. `"EPSG"`.
+    ///
+    /// By default an SRID is stored as an opaque [`Crs::from_srid`]. With an authority set, SRID
+    /// `4326` is stored as [`Crs::from_authority_code`] with the value `EPSG:4326`.
+    pub fn with_srid_authority(self, authority: impl Into<String>) -> Self {
+        Self {
+            srid_authority: Some(authority.into()),
+            ..self
+        }
+    }
+
+    /// Write an XY envelope into the header of each non-empty GeoPackage blob.
+    ///
+    /// Defaults to `false`. Ignored when reading and for other dialects.
+    pub fn with_envelope(self, envelope: bool) -> Self {
+        Self { envelope, ..self }
+    }
+
+    /// The WKB dialect to read or write.
+    pub fn dialect(&self) -> WkbDialect {
+        self.dialect
+    }
+
+    fn srid_to_crs(&self, srid: i32) -> Crs {
+        match &self.srid_authority {
+            Some(authority) => Crs::from_authority_code(format!("{authority}:{srid}")),
+            None => Crs::from_srid(srid.to_string()),
+        }
+    }
+}
+
+/// Parse a WKB-typed array holding the given [`WkbDialect`] to a [`GeoArrowArray`] with the
+/// designated [`GeoArrowType`].
+///
+/// Each value is first rewritten to ISO WKB and then parsed as with [`from_wkb`]. Converting to
+/// a `Wkb` or `LargeWkb` type does not parse geometries beyond that rewrite.
+///
+/// If the values embed an SRID and `to_type` has no CRS, the SRID is stored in the output
+/// [`Metadata`]; see [`WkbDialectOptions::with_srid_authority`]. SRIDs of zero or less denote an
+/// unknown CRS (in PostGIS and GeoPackage alike) and are ignored. Values without an SRID are
+/// accepted alongside values with one, but values with differing SRIDs are an error.
+pub fn from_wkb_dialect(
+    arr: &dyn GeoArrowArray,
+    to_type: GeoArrowType,
+    options: &WkbDialectOptions,
+) -> GeoArrowResult<Arc<dyn GeoArrowArray>> {
+    let mut srid = None;
+    let metadata = to_type.metadata().clone();
+    let result: Arc<dyn GeoArrowArray> = match &to_type {
+        GeoArrowType::Wkb(_) => {
+            let array = rewrite_to_iso::<i32>(arr, options.dialect, &mut srid)?;
+            let metadata = lift_srid(metadata, srid, options);
+            Arc::new(GenericWkbArray::new(array, metadata))
+        }
+        GeoArrowType::LargeWkb(_) => {
+            let array = rewrite_to_iso::<i64>(arr, options.dialect, &mut srid)?;
+            let metadata = lift_srid(metadata, srid, options);
+            Arc::new(GenericWkbArray::new(array, metadata))
+        }
+        _ => {
+            let array = rewrite_to_iso::<i64>(arr, options.dialect, &mut srid)?;
+            let metadata = lift_srid(metadata, srid, options);
+            let wkb_array = GenericWkbArray::new(array, metadata.clone());
+            from_wkb(&wkb_array, to_type.with_metadata(metadata))?
+        }
+    };
+    Ok(result)
+}
+
+/// Convert a [`GeoArrowArray`] to a [`GenericWkbArray`] holding the given [`WkbDialect`].
+///
+/// For [`WkbDialect::Ewkb`] and [`WkbDialect::GeoPackage`], the SRID written to each value is
+/// derived from the array's CRS: either a [`CrsType::Srid`] or an `EPSG` authority code
+/// (`OGC:CRS84` is written as `4326`). If no SRID can be derived, EWKB values are written
+/// without one and GeoPackage values use `srs_id` 0. GeoPackage blobs are written without an
+/// envelope; use [`to_wkb_dialect_with_options`] to add one.
+///
+/// Note that the output array carries a `geoarrow.wkb` type, so it should only be handed to
+/// consumers that expect the chosen dialect.
+pub fn to_wkb_dialect<O: OffsetSizeTrait>(
+    arr: &dyn GeoArrowArray,
+    dialect: WkbDialect,
+) -> GeoArrowResult<GenericWkbArray<O>> {
+    to_wkb_dialect_with_options(arr, &WkbDialectOptions::new(dialect))
+}
+
+/// Convert a [`GeoArrowArray`] to a [`GenericWkbArray`] holding the dialect of `options`.
+///
+/// This is [`to_wkb_dialect`], but GeoPackage blobs get an envelope if
+/// [`WkbDialectOptions::with_envelope`] is set.
+pub fn to_wkb_dialect_with_options<O: OffsetSizeTrait>(
+    arr: &dyn GeoArrowArray,
+    options: &WkbDialectOptions,
+) -> GeoArrowResult<GenericWkbArray<O>> {
+    let dialect = options.dialect;
+    let iso = to_wkb::<O>(arr)?;
+    if dialect == WkbDialect::Iso {
+        return Ok(iso);
+    }
+
+    let metadata = iso.data_type().metadata().clone();
+    let srid = crs_to_srid(metadata.crs());
+
+    let mut builder = GenericBinaryBuilder::<O>::with_capacity(iso.len(), iso.num_bytes());
+    let mut buf = Vec::new();
+    for value in iso.inner().iter() {
+        let Some(bytes) = value else {
+            builder.append_null();
+            continue;
+        };
+        buf.clear();
+        match dialect {
+            WkbDialect::Iso => unreachable!(),
+            WkbDialect::Ewkb => {
+                let mut reader = Reader::new(bytes);
+                transcode(&mut reader, &mut buf, Target::Ewkb, srid)?;
+            }
+            WkbDialect::GeoPackage => {
+                let empty = is_empty(bytes)?;
+                let envelope = if options.envelope && !empty {
+                    let geometry = Wkb::try_new(bytes)
+                        .map_err(|err| GeoArrowError::External(Box::new(err)))?;
+                    let mut bounds = BoundingRect::new();
+                    bounds.add_geometry(&geometry);
+                    Some([bounds.minx(), bounds.miny(), bounds.maxx(), bounds.maxy()])
+                } else {
+                    None
+                };
+                write_geopackage_header(&mut buf, srid.unwrap_or(0), empty, envelope);
+                buf.extend_from_slice(bytes);
+            }
+        }
+        builder.append_value(&buf);
+    }
+
+    Ok(GenericWkbArray::new(builder.finish(), metadata))
+}
+
+/// Rewrite each value of a WKB-typed array to ISO WKB, collecting the SRID shared by all values.
+fn rewrite_to_iso<O: OffsetSizeTrait>(
+    arr: &dyn GeoArrowArray,
+    dialect: WkbDialect,
+    srid: &mut Option<i32>,
+) -> GeoArrowResult<GenericBinaryArray<O>> {
+    let mut builder = GenericBinaryBuilder::<O>::with_capacity(arr.len(), 0);
+    let mut buf = Vec::new();
+    let mut push = |index: usize, value: Option<&[u8]>| -> GeoArrowResult<()> {
+        let Some(bytes) = value else {
+            builder.append_null();
+            return Ok(());
+        };
+        buf.clear();
+        let row_srid = match dialect {
+            WkbDialect::Iso | WkbDialect::Ewkb => {
+                let mut reader = Reader::new(bytes);
+                transcode(&mut reader, &mut buf, Target::Iso, None)?
+            }
+            WkbDialect::GeoPackage => {
+                let header = read_geopackage_header(bytes)?;
+                let mut reader = Reader::new(&bytes[header.wkb_offset..]);
+                transcode(&mut reader, &mut buf, Target::Iso, None)?;
+                Some(header.srs_id)
+            }
+        };
+        match (row_srid.filter(|s| *s > 0), *srid) {
+            (Some(row_srid), Some(existing)) if row_srid != existing => {
+                return Err(GeoArrowError::Wkb(format!(
+                    "Inconsistent SRIDs: value at index {index} has SRID {row_srid}, but earlier values have SRID {existing}"
+                )));
+            }
+            (Some(row_srid), None) => *srid = Some(row_srid),
+            _ => {}
+        }
+        builder.append_value(&buf);
+        Ok(())
+    };
+
+    match arr.data_type() {
+        GeoArrowType::Wkb(_) => {
+            for (i, value) in arr.as_wkb::<i32>().inner().iter().enumerate() {
+                push(i, value)?;
+            }
+        }
+        GeoArrowType::LargeWkb(_) => {
+            for (i, value) in arr.as_wkb::<i64>().inner().iter().enumerate() {
+                push(i, value)?;
+            }
+        }
+        GeoArrowType::WkbView(_) => {
+            let array = WkbViewArray::clone(arr.as_wkb_view()).into_arrow();
+            for (i, value) in array.iter().enumerate() {
+                push(i, value)?;
+            }
+        }
+        other => {
+            return Err(GeoArrowError::IncorrectGeometryType(format!(
+                "Expected a WKB array, got {other:?}"
+            )));
+        }
+    }
+
+    Ok(builder.finish())
+}
+
+fn lift_srid(
+    metadata: Arc<Metadata>,
+    srid: Option<i32>,
+    options: &WkbDialectOptions,
+) -> Arc<Metadata> {
+    match srid {
+        Some(srid) if metadata.crs().crs_value().is_none() => {
+            Arc::new(Metadata::new(options.srid_to_crs(srid), metadata.edges()))
+        }
+        _ => metadata,
+    }
+}
+
+fn crs_to_srid(crs: &Crs) -> Option<i32> {
+    if crs.crs_type() == Some(CrsType::Srid) {
+        return crs.crs_value()?.as_str()?.parse().ok();
+    }
+    let (authority, code) = crs.authority_code()?;
+    match (authority.as_str(), code.as_str()) {
+        ("OGC", "CRS84") => Some(4326),
+        ("EPSG", code) => code.parse().ok(),
+        _ => None,
+    }
+}
+
+/// The header of a GeoPackage geometry blob.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoPackageHeader {
+    /// The `srs_id` of the geometry.
+    pub srs_id: i32,
+
+    /// Whether the empty geometry flag is set.
+    pub empty: bool,
+
+    /// The XY extent of the envelope as `[minx, miny, maxx, maxy]`, if the header has one.
+    ///
+    /// Envelopes with Z or M ranges are read as their XY extent.
+    pub envelope: Option<[f64; 4]>,
+
+    /// The offset of the WKB payload from the start of the blob.
+    pub wkb_offset: usize,
+}
+
+/// Parse the header of a GeoPackage geometry blob.
+///
+/// Extended (non-standard) geometry blobs are an error.
+pub fn read_geopackage_header(bytes: &[u8]) -> GeoArrowResult<GeoPackageHeader> {
+    if bytes.len() < 8 || &bytes[0..2] != GPKG_MAGIC {
+        return Err(GeoArrowError::Wkb(
+            "Invalid GeoPackage geometry: missing 'GP' magic bytes".to_string(),
+        ));
+    }
+    let flags = bytes[3];
+    if flags & GPKG_EXTENDED_FLAG != 0 {
+        return Err(GeoArrowError::Wkb(
+            "Extended GeoPackage geometries are not supported".to_string(),
+        ));
+    }
+    let little_endian = flags & GPKG_LITTLE_ENDIAN_FLAG != 0;
+    let srs_id_bytes = [bytes[4], bytes[5], bytes[6], bytes[7]];
+    let srs_id = if little_endian {
+        i32::from_le_bytes(srs_id_bytes)
+    } else {
+        i32::from_be_bytes(srs_id_bytes)
+    };
+    let envelope_len = match (flags >> 1) & 0b111 {
+        0 => 0,
+        1 => 32,
+        2 | 3 => 48,
+        4 => 64,
+        code => {
+            return Err(GeoArrowError::Wkb(format!(
+                "Invalid GeoPackage envelope contents indicator: {code}"
+            )));
+        }
+    };
+    let wkb_offset = 8 + envelope_len;
+    if bytes.len() < wkb_offset {
+        return Err(GeoArrowError::Wkb(
+            "Invalid GeoPackage geometry: truncated envelope".to_string(),
+        ));
+    }
+
+    // Every envelope starts with minx, maxx, miny and maxy.
+    let envelope = (envelope_len > 0).then(|| {
+        let [minx, maxx, miny, maxy] = std::array::from_fn(|i| {
+            let bytes = bytes[8 + 8 * i..16 + 8 * i].try_into().unwrap();
+            if little_endian {
+                f64::from_le_bytes(bytes)
+            } else {
+                f64::from_be_bytes(bytes)
+            }
+        });
+        [minx, miny, maxx, maxy]
+    });
+
+    Ok(GeoPackageHeader {
+        srs_id,
+        empty: flags & GPKG_EMPTY_FLAG != 0,
+        envelope,
+        wkb_offset,
+    })
+}
+
+/// Append a little-endian GeoPackage geometry blob header to `out`.
+///
+/// The header gets the empty geometry flag if `empty` is set, and an XY envelope if `envelope`
+/// holds the `[minx, miny, maxx, maxy]` extent of the geometry. The WKB payload should be
+/// appended after it.
+pub fn write_geopackage_header(
+    out: &mut Vec<u8>,
+    srs_id: i32,
+    empty: bool,
+    envelope: Option<[f64; 4]>,
+) {
+    let mut flags = GPKG_LITTLE_ENDIAN_FLAG;
+    if empty {
+        flags |= GPKG_EMPTY_FLAG;
+    }
+    if envelope.is_some() {
+        flags |= GPKG_ENVELOPE_XY_FLAG;
+    }
+    out.extend_from_slice(GPKG_MAGIC);
+    // Version 1 of the blob format.
+    out.push(0);
+    out.push(flags);
+    out.extend_from_slice(&srs_id.to_le_bytes());
+    if let Some([minx, miny, maxx, maxy]) = envelope {
+        for value in [minx, maxx, miny, maxy] {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+}
+
+/// Whether an ISO WKB value is empty: a point with NaN coordinates or a zero-length geometry.
+fn is_empty(bytes: &[u8]) -> GeoArrowResult<bool> {
+    let mut reader = Reader::new(bytes);
+    let header = Header::read(&mut reader)?;
+    if header.geometry_type == 1 {
+        for _ in 0..header.num_dims() {
+            let value = reader.take(8)?;
+            let value = f64::from_bits(reader.decode_u64(value, header.little_endian));
+            if !value.is_nan() {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    } else {
+        Ok(reader.read_u32(header.little_endian)? == 0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    Iso,
+    Ewkb,
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> GeoArrowResult<&'a [u8]> {
+        let end = self.pos.checked_add(n).filter(|end| *end <= self.buf.len());
+        let end = end.ok_or_else(|| {
+            GeoArrowError::Wkb(format!(
+                "Unexpected end of WKB: needed {n} bytes at offset {}",
+                self.pos
+            ))
+        })?;
+        let bytes = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn read_u32(&mut self, little_endian: bool) -> GeoArrowResult<u32> {
+        let bytes: [u8; 4] = self.take(4)?.try_into().unwrap();
+        Ok(if little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn decode_u64(&self, bytes: &[u8], little_endian: bool) -> u64 {
+        let bytes: [u8; 8] = bytes.try_into().unwrap();
+        if little_endian {
+            u64::from_le_bytes(bytes)
+        } else {
+            u64::from_be_bytes(bytes)
+        }
+    }
+}
+
+struct Header {
+    little_endian: bool,
+    /// The base geometry type code, 1 (Point) through 7 (GeometryCollection).
+    geometry_type: u32,
+    has_z: bool,
+    has_m: bool,
+    srid: Option<u32>,
+}
+
+impl Header {
+    /// Read an ISO or EWKB geometry header.
+    fn read(reader: &mut Reader) -> GeoArrowResult<Self> {
+        let little_endian = match reader.take(1)?[0] {
+            0 => false,
+            1 => true,
+            other => {
+                return Err(GeoArrowError::Wkb(format!(
+                    "Invalid WKB byte order marker: {other}"
+                )));
+            }
+        };
+        let code = reader.read_u32(little_endian)?;
+        let mut has_z = code & EWKB_Z_FLAG != 0;
+        let mut has_m = code & EWKB_M_FLAG != 0;
+        let iso_code = code & 0x0FFF_FFFF;
+        match iso_code / 1000 {
+            0 => {}
+            1 => has_z = true,
+            2 => has_m = true,
+            3 => {
+                has_z = true;
+                has_m = true;
+            }
+            _ => {
+                return Err(GeoArrowError::Wkb(format!(
+                    "Invalid WKB geometry type: {code:#x}"
+                )));
+            }
+        }
+        let geometry_type = iso_code % 1000;
+        if !(1..=7).contains(&geometry_type) {
+            return Err(GeoArrowError::Wkb(format!(
+                "Unsupported WKB geometry type: {code:#x}"
+            )));
+        }
+        let srid = if code & EWKB_SRID_FLAG != 0 {
+            Some(reader.read_u32(little_endian)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            little_endian,
+            geometry_type,
+            has_z,
+            has_m,
+            srid,
+        })
+    }
+
+    fn num_dims(&self) -> usize {
+        2 + self.has_z as usize + self.has_m as usize
+    }
+
+    fn write(&self, out: &mut Vec<u8>, target: Target, srid: Option<i32>) {
+        out.push(self.little_endian as u8);
+        let code = match target {
+            Target::Iso => self.geometry_type + 1000 * self.has_z as u32 + 2000 * self.has_m as u32,
+            Target::Ewkb => {
+                let mut code = self.geometry_type;
+                if self.has_z {
+                    code |= EWKB_Z_FLAG;
+                }
+                if self.has_m {
+                    code |= EWKB_M_FLAG;
+                }
+                if srid.is_some() {
+                    code |= EWKB_SRID_FLAG;
+                }
+                code
+            }
+        };
+        write_u32(out, code, self.little_endian);
+        if let Some(srid) = srid {
+            write_u32(out, srid as u32, self.little_endian);
+        }
+    }
+}
+
+fn write_u32(out: &mut Vec<u8>, value: u32, little_endian: bool) {
+    if little_endian {
+        out.extend_from_slice(&value.to_le_bytes());
+    } else {
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+/// Copy one geometry from `reader` to `out`, rewriting its header (and those of any children) to
+/// the target dialect. Coordinates are copied as-is, keeping each geometry's byte order.
+///
+/// `srid` is written to the top-level header only, and only for [`Target::Ewkb`]. Returns the
+/// SRID embedded in the input's top-level header, if any.
+fn transcode(
+    reader: &mut Reader,
+    out: &mut Vec<u8>,
+    target: Target,
+    srid: Option<i32>,
+) -> GeoArrowResult<Option<i32>> {
+    let header = Header::read(reader)?;
+    header.write(out, target, srid.filter(|_| target == Target::Ewkb));
+
+    let little_endian = header.little_endian;
+    let coord_len = 8 * header.num_dims();
+    match header.geometry_type {
+        1 => out.extend_from_slice(reader.take(coord_len)?),
+        2 => {
+            let num_coords = reader.read_u32(little_endian)?;
+            write_u32(out, num_coords, little_endian);
+            out.extend_from_slice(reader.take(coord_len * num_coords as usize)?);
+        }
+        3 => {
+            let num_rings = reader.read_u32(little_endian)?;
+            write_u32(out, num_rings, little_endian);
+            for _ in 0..num_rings {
+                let num_coords = reader.read_u32(little_endian)?;
+                write_u32(out, num_coords, little_endian);
+                out.extend_from_slice(reader.take(coord_len * num_coords as usize)?);
+            }
+        }
+        _ => {
+            let num_geometries = reader.read_u32(little_endian)?;
+            write_u32(out, num_geometries, little_endian);
+            for _ in 0..num_geometries {
+                // SRIDs on child geometries are dropped; only the top-level SRID is meaningful.
+                transcode(reader, out, target, None)?;
+            }
+        }
+    }
+
+    Ok(header.srid.map(|srid| srid as i32))
+}
+
+#[cfg(test)]
+mod test {
+    use arrow_array::BinaryArray;
+    use geo_traits::{CoordTrait, PointTrait};
+    use geoarrow_schema::{CoordType, Dimension, PointType, WkbType};
+
+    use super::*;
+    use crate::GeoArrowArrayAccessor;
+    use crate::array::WkbArray;
+    use crate::builder::PointBuilder;
+
+    fn point_wkb(code: u32, srid: Option<u32>, coords: &[f64]) -> Vec<u8> {
+        let mut buf = vec![1];
+        buf.extend_from_slice(&code.to_le_bytes());
+        if let Some(srid) = srid {
+            buf.extend_from_slice(&srid.to_le_bytes());
+        }
+        for coord in coords {
+            buf.extend_from_slice(&coord.to_le_bytes());
+        }
+        buf
+    }
+
+    fn gpkg(srs_id: i32, wkb: &[u8]) -> Vec<u8> {
+        // Little-endian header with an XY envelope.
+        let mut buf = vec![b'G', b'P', 0, 0b0000_0011];
+        buf.extend_from_slice(&srs_id.to_le_bytes());
+        for value in [1.0f64, 1.0, 2.0, 2.0] {
+            buf.extend_from_slice(&value.to_le_bytes());
+        }
+        buf.extend_from_slice(wkb);
+        buf
+    }
+
+    fn wkb_array(values: &[Option<&[u8]>]) -> WkbArray {
+        WkbArray::new(BinaryArray::from(values.to_vec()), Default::default())
+    }
+
+    fn point_type(dim: Dimension) -> GeoArrowType {
+        PointType::new(dim, Default::default())
+            .with_coord_type(CoordType::Separated)
+            .into()
+    }
+
+    #[test]
+    fn ewkb_to_point() {
+        let xy = point_wkb(0x2000_0001, Some(4326), &[1.0, 2.0]);
+        let xyz = point_wkb(0xA000_0001, Some(4326), &[1.0, 2.0, 3.0]);
+        let array = wkb_array(&[Some(&xy), None, Some(&xy)]);
+
+        let options = WkbDialectOptions::new(WkbDialect::Ewkb);
+        let result = from_wkb_dialect(&array, point_type(Dimension::XY), &options).unwrap();
+        let result = result.as_point();
+        assert_eq!(result.len(), 3);
+        assert!(result.is_null(1));
+        assert_eq!(
+            result.data_type().metadata().crs(),
+            &Crs::from_srid("4326".to_string())
+        );
+
+        let array = wkb_array(&[Some(&xyz)]);
+        let options = options.with_srid_authority("EPSG");
+        let result = from_wkb_dialect(&array, point_type(Dimension::XYZ), &options).unwrap();
+        assert_eq!(
+            result.data_type().metadata().crs(),
+            &Crs::from_authority_code("EPSG:4326".to_string())
+        );
+    }
+
+    #[test]
+    fn ewkb_to_wkb() {
+        let ewkb = point_wkb(0xE000_0001, Some(3857), &[1.0, 2.0, 3.0, 4.0]);
+        let iso = point_wkb(3001, None, &[1.0, 2.0, 3.0, 4.0]);
+        let array = wkb_array(&[Some(&ewkb)]);
+
+        let to_type = GeoArrowType::Wkb(WkbType::new(Default::default()));
+        let options = WkbDialectOptions::new(WkbDialect::Ewkb);
+        let result = from_wkb_dialect(&array, to_type, &options).unwrap();
+        let result = result.as_wkb::<i32>();
+        assert_eq!(result.inner().value(0), iso.as_slice());
+        assert_eq!(
+            result.data_type().metadata().crs(),
+            &Crs::from_srid("3857".to_string())
+        );
+    }
+
+    #[test]
+    fn ewkb_inconsistent_srid() {
+        let a = point_wkb(0x2000_0001, Some(4326), &[1.0, 2.0]);
+        let b = point_wkb(0x2000_0001, Some(3857), &[1.0, 2.0]);
+        let no_srid = point_wkb(1, None, &[1.0, 2.0]);
+
+        let options = WkbDialectOptions::new(WkbDialect::Ewkb);
+        let array = wkb_array(&[Some(&a), Some(&no_srid), Some(&a)]);
+        assert!(from_wkb_dialect(&array, point_type(Dimension::XY), &options).is_ok());
+
+        let array = wkb_array(&[Some(&a), Some(&b)]);
+        let err = from_wkb_dialect(&array, point_type(Dimension::XY), &options).unwrap_err();
+        assert!(err.to_string().contains("index 1"));
+    }
+
+    #[test]
+    fn ewkb_keeps_existing_crs() {
+        let ewkb = point_wkb(0x2000_0001, Some(4326), &[1.0, 2.0]);
+        let array = wkb_array(&[Some(&ewkb)]);
+
+        let crs = Crs::from_authority_code("OGC:CRS84".to_string());
+        let metadata = Arc::new(Metadata::new(crs.clone(), None));
+        let to_type = point_type(Dimension::XY).with_metadata(metadata);
+        let options = WkbDialectOptions::new(WkbDialect::Ewkb);
+        let result = from_wkb_dialect(&array, to_type, &options).unwrap();
+        assert_eq!(result.data_type().metadata().crs(), &crs);
+    }
+
+    #[test]
+    fn geopackage_to_point() {
+        let wkb = point_wkb(1, None, &[1.0, 2.0]);
+        let blob = gpkg(4326, &wkb);
+        let array = wkb_array(&[Some(&blob), None]);
+
+        let options = WkbDialectOptions::new(WkbDialect::GeoPackage).with_srid_authority("EPSG");
+        let result = from_wkb_dialect(&array, point_type(Dimension::XY), &options).unwrap();
+        assert_eq!(
+            result.data_type().metadata().crs(),
+            &Crs::from_authority_code("EPSG:4326".to_string())
+        );
+        let point = result.as_point().value(0).unwrap();
+        assert_eq!(point.coord().unwrap().x(), 1.0);
+
+        let array = wkb_array(&[Some(&wkb)]);
+        assert!(from_wkb_dialect(&array, point_type(Dimension::XY), &options).is_err());
+    }
+
+    #[test]
+    fn to_ewkb_roundtrip() {
+        let typ = PointType::new(Dimension::XY, Default::default()).with_metadata(Arc::new(
+            Metadata::new(Crs::from_authority_code("EPSG:4326".to_string()), None),
+        ));
+        let mut builder = PointBuilder::new(typ.clone());
+        builder.push_point(Some(&wkt::types::Point::from_coord(wkt::types::Coord {
+            x: 1.0,
+            y: 2.0,
+            z: None,
+            m: None,
+        })));
+        builder.push_null();
+        let array = builder.finish();
+
+        let ewkb = to_wkb_dialect::<i32>(&array, WkbDialect::Ewkb).unwrap();
+        assert_eq!(
+            ewkb.inner().value(0),
+            point_wkb(0x2000_0001, Some(4326), &[1.0, 2.0]).as_slice()
+        );
+        assert!(ewkb.is_null(1));
+
+        let options = WkbDialectOptions::new(WkbDialect::Ewkb);
+        let result = from_wkb_dialect(&ewkb, typ.clone().into(), &options).unwrap();
+        assert_eq!(result.as_point(), &array);
+
+        let gpkg = to_wkb_dialect::<i32>(&array, WkbDialect::GeoPackage).unwrap();
+        let options = WkbDialectOptions::new(WkbDialect::GeoPackage);
+        let result = from_wkb_dialect(&gpkg, typ.clone().into(), &options).unwrap();
+        assert_eq!(result.as_point(), &array);
+        let header = read_geopackage_header(gpkg.inner().value(0)).unwrap();
+        assert_eq!(header.envelope, None);
+
+        let options = options.with_envelope(true);
+        let gpkg = to_wkb_dialect_with_options::<i32>(&array, &options).unwrap();
+        let header = read_geopackage_header(gpkg.inner().value(0)).unwrap();
+        assert_eq!(header.srs_id, 4326);
+        assert!(!header.empty);
+        assert_eq!(header.envelope, Some([1.0, 2.0, 1.0, 2.0]));
+        assert_eq!(header.wkb_offset, 40);
+        let result = from_wkb_dialect(&gpkg, typ.into(), &options).unwrap();
+        assert_eq!(result.as_point(), &array);
+    }
+
+    #[test]
+    fn geopackage_header() {
+        let wkb = point_wkb(1, None, &[1.0, 2.0]);
+        let header = read_geopackage_header(&gpkg(3857, &wkb)).unwrap();
+        assert_eq!(header.srs_id, 3857);
+        assert_eq!(header.envelope, Some([1.0, 2.0, 1.0, 2.0]));
+        assert_eq!(&gpkg(3857, &wkb)[header.wkb_offset..], wkb.as_slice());
+
+        // A big-endian header with the empty flag and no envelope.
+        let mut blob = vec![b'G', b'P', 0, GPKG_EMPTY_FLAG];
+        blob.extend_from_slice(&3857_i32.to_be_bytes());
+        let header = read_geopackage_header(&blob).unwrap();
+        assert_eq!(header.srs_id, 3857);
+        assert!(header.empty);
+        assert_eq!(header.envelope, None);
+
+        let mut out = Vec::new();
+        write_geopackage_header(&mut out, 3857, true, None);
+        assert_eq!(read_geopackage_header(&out).unwrap(), header);
+
+        assert!(read_geopackage_header(b"XX\x00\x01\x00\x00\x00\x00").is_err());
+        assert!(read_geopackage_header(&[b'G', b'P', 0, GPKG_EXTENDED_FLAG, 0, 0, 0, 0]).is_err());
+        assert!(read_geopackage_header(&[b'G', b'P', 0, 0b0000_0011, 0, 0, 0, 0]).is_err());
+    }
+}
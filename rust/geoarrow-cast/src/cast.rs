@@ -3,20 +3,83 @@
 use std::sync::Arc;
 
 use arrow_schema::ArrowError;
+use geo_traits::{CoordTrait, GeometryTrait, LineStringTrait, PolygonTrait};
 use geoarrow_array::array::{
-    GeometryArray, MultiLineStringArray, MultiPointArray, MultiPolygonArray,
+    DimensionFill, GeometryArray, MultiLineStringArray, MultiPointArray, MultiPolygonArray,
+    PolygonArray, RectArray,
 };
 use geoarrow_array::builder::{
-    GeometryCollectionBuilder, LineStringBuilder, MultiLineStringBuilder, MultiPointBuilder,
-    MultiPolygonBuilder, PointBuilder, PolygonBuilder,
+    GeometryBuilder, GeometryCollectionBuilder, LineStringBuilder, MultiLineStringBuilder,
+    MultiPointBuilder, MultiPolygonBuilder, PointBuilder, PolygonBuilder, RectBuilder,
 };
 use geoarrow_array::capacity::{LineStringCapacity, PolygonCapacity};
 use geoarrow_array::cast::{
     AsGeoArrowArray, from_wkb, from_wkt, to_wkb, to_wkb_view, to_wkt, to_wkt_view,
 };
-use geoarrow_array::{GeoArrowArray, GeoArrowArrayAccessor};
-use geoarrow_schema::GeoArrowType;
+use geoarrow_array::{GeoArrowArray, GeoArrowArrayAccessor, IntoArrow};
 use geoarrow_schema::error::GeoArrowResult;
+use geoarrow_schema::{BoxType, CoordType, Dimension, GeoArrowType, GeometryType, PolygonType};
+
+/// Options for [`cast_with_options`].
+///
+/// By default, casts never change the dimension of coordinates. Dimension changes are opted into
+/// either by [coercing][Self::with_coerce_dimension] to the dimension of the target type or by
+/// [forcing][Self::with_dimension] a specific dimension.
+///
+/// When coordinates gain a Z or M dimension, its values are taken from the fill given to
+/// [`with_z`][Self::with_z] or [`with_m`][Self::with_m], which default to `0.0`. Values from a
+/// column can be supplied with [`DimensionFill::PerGeometry`], e.g. from the values of a
+/// `Float64Array` with one value per row.
+#[derive(Debug, Clone, Default)]
+pub struct CastOptions {
+    dimension: Option<Dimension>,
+    coerce_dimension: bool,
+    z: DimensionFill,
+    m: DimensionFill,
+}
+
+impl CastOptions {
+    /// Create new cast options that don't change the dimension of coordinates.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether casting to a dimension-aware type (e.g. `Point` or `Polygon`) converts
+    /// coordinates to the dimension of that type instead of erroring when it differs from the
+    /// dimension of the source array.
+    ///
+    /// For `Geometry`, WKB and WKT source arrays, this also converts geometries of other
+    /// dimensions stored in the same array.
+    pub fn with_coerce_dimension(self, coerce_dimension: bool) -> Self {
+        Self {
+            coerce_dimension,
+            ..self
+        }
+    }
+
+    /// Convert all coordinates to the given dimension, e.g. [`Dimension::XY`] to force
+    /// geometries to 2D.
+    ///
+    /// Unlike [`with_coerce_dimension`][Self::with_coerce_dimension], this also applies when
+    /// casting to types that don't have a fixed dimension, such as `Geometry`, WKB and WKT.
+    /// Casting to a dimension-aware type of a different dimension is an error.
+    pub fn with_dimension(self, dimension: Dimension) -> Self {
+        Self {
+            dimension: Some(dimension),
+            ..self
+        }
+    }
+
+    /// Set the values used for a Z dimension added by the cast.
+    pub fn with_z(self, z: DimensionFill) -> Self {
+        Self { z, ..self }
+    }
+
+    /// Set the values used for an M dimension added by the cast.
+    pub fn with_m(self, m: DimensionFill) -> Self {
+        Self { m, ..self }
+    }
+}
 
 /// Cast a [`GeoArrowArray`] to another [`GeoArrowType`].
 ///
@@ -24,12 +87,14 @@ use geoarrow_schema::error::GeoArrowResult;
 ///
 /// - Dimension must be compatible:
 ///     - If the source array and destination type are both dimension-aware, then their dimensions
-///       must match.
+///       must match. Use [`cast_with_options`] to change the dimension of coordinates.
 ///     - Casts from dimension-aware to dimensionless arrays (`GeometryArray`, `WkbArray`,
 ///       `WkbViewArray`, `WktArray`, `WktViewArray`) are always allowed.
-/// - GeoArrow [`Metadata`][geoarrow_schema::Metadata] on the [`GeoArrowType`] must match. Use
-///   [`GeoArrowArray::with_metadata`]
-///   to change the metadata on an array.
+/// - GeoArrow [`Metadata`][geoarrow_schema::Metadata] on the [`GeoArrowType`] must be
+///   [equivalent][geoarrow_schema::Metadata::is_equivalent]: the edges must match and the CRS
+///   must identify the same logical CRS, even if represented differently. The output array always
+///   carries the metadata of `to_type`. Use [`GeoArrowArray::with_metadata`] to change the
+///   metadata on an array.
 ///
 /// ### Infallible casts:
 ///
@@ -41,6 +106,8 @@ use geoarrow_schema::error::GeoArrowResult;
 /// - `Point` to `MultiPoint`
 /// - `LineString` to `MultiLineString`
 /// - `Polygon` to `MultiPolygon`
+/// - `Rect` to `Polygon`, `MultiPolygon`, `Geometry`, or any serialized type. Each rect becomes a
+///   polygon with a single closed ring of 5 vertices.
 ///
 /// ### Fallible casts:
 ///
@@ -49,6 +116,8 @@ use geoarrow_schema::error::GeoArrowResult;
 /// - `MultiPoint` to `Point`
 /// - `MultiLineString` to `LineString`
 /// - `MultiPolygon` to `Polygon`
+/// - `Polygon` to `Rect`, which succeeds only if every polygon is an axis-aligned rectangle without
+///   interior rings.
 ///
 // TODO: need to check this behavior:
 //
@@ -69,7 +138,11 @@ pub fn cast(
         .into());
     }
 
-    if array.data_type().metadata() != to_type.metadata() {
+    if !array
+        .data_type()
+        .metadata()
+        .is_equivalent(to_type.metadata())
+    {
         return Err(ArrowError::CastError(format!(
             "Cannot cast from {:?} to {:?}: incompatible metadata",
             array.data_type().metadata(),
@@ -252,6 +325,23 @@ pub fn cast(
             let geom_array = GeometryArray::from(array.as_geometry_collection().clone());
             Arc::new(geom_array.into_coord_type(to_type.coord_type()))
         }
+        (Rect(_), Rect(_)) => Arc::new(array.as_rect().clone()),
+        (Rect(_), Polygon(to_type)) => {
+            Arc::new(rect_to_polygon(array.as_rect(), to_type.coord_type())?)
+        }
+        (Rect(_), MultiPolygon(to_type)) => {
+            let p_array = rect_to_polygon(array.as_rect(), to_type.coord_type())?;
+            Arc::new(MultiPolygonArray::from(p_array))
+        }
+        (Rect(_), Geometry(to_type)) => {
+            let p_array = rect_to_polygon(array.as_rect(), to_type.coord_type())?;
+            Arc::new(GeometryArray::from(p_array))
+        }
+        (Rect(_), Wkb(_) | LargeWkb(_) | WkbView(_) | Wkt(_) | LargeWkt(_) | WktView(_)) => {
+            let p_array = rect_to_polygon(array.as_rect(), CoordType::Separated)?;
+            return cast(&p_array, to_type);
+        }
+        (Polygon(_), Rect(to_type)) => Arc::new(polygon_to_rect(array.as_polygon(), to_type)?),
         (_, Wkb(_)) => Arc::new(to_wkb::<i32>(array)?),
         (_, LargeWkb(_)) => Arc::new(to_wkb::<i64>(array)?),
         (_, WkbView(_)) => Arc::new(to_wkb_view(array)?),
@@ -273,18 +363,236 @@ pub fn cast(
             .into());
         }
     };
+
+    // Equivalent but differently-represented metadata is replaced with that of the target type
+    if out.data_type().metadata() != to_type.metadata() {
+        return Ok(out.with_metadata(to_type.metadata().clone()));
+    }
     Ok(out)
 }
 
+/// Convert each rect to a polygon with a single closed ring.
+fn rect_to_polygon(array: &RectArray, coord_type: CoordType) -> GeoArrowResult<PolygonArray> {
+    let rect_type = array.extension_type();
+    let typ = PolygonType::new(rect_type.dimension(), rect_type.metadata().clone())
+        .with_coord_type(coord_type);
+    let capacity = PolygonCapacity::new(array.len() * 5, array.len(), array.len());
+    let mut builder = PolygonBuilder::with_capacity(typ, capacity);
+    for rect in array.iter() {
+        builder.push_rect(rect.transpose()?.as_ref())?;
+    }
+    Ok(builder.finish())
+}
+
+/// Convert each polygon to a rect, erroring if any polygon is not an axis-aligned rectangle.
+fn polygon_to_rect(array: &PolygonArray, to_type: &BoxType) -> GeoArrowResult<RectArray> {
+    let mut builder = RectBuilder::with_capacity(to_type.clone(), array.len());
+    for (i, polygon) in array.iter().enumerate() {
+        match polygon.transpose()? {
+            Some(polygon) => {
+                let (min, max) = polygon_bounds(&polygon).ok_or_else(|| {
+                    ArrowError::CastError(format!(
+                        "Cannot cast polygon at index {i} to Rect: not an axis-aligned rectangle"
+                    ))
+                })?;
+                builder.push_min_max(&min, &max);
+            }
+            None => builder.push_null(),
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// The min and max corners of a polygon, if it is an axis-aligned rectangle.
+///
+/// The polygon must have no interiors and an exterior ring of 4 vertices, or 5 if the ring is
+/// closed, visiting each corner of its bounding box once along axis-aligned edges. Z and M values,
+/// if any, are only used for the bounds.
+fn polygon_bounds(
+    polygon: &impl PolygonTrait<T = f64>,
+) -> Option<(wkt::types::Coord<f64>, wkt::types::Coord<f64>)> {
+    if polygon.num_interiors() > 0 {
+        return None;
+    }
+    let exterior = polygon.exterior()?;
+    let coords = exterior
+        .coords()
+        .map(|c| {
+            (0..c.dim().size())
+                .map(|n| c.nth_or_panic(n))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let corners = match coords.len() {
+        4 => &coords[..],
+        5 if coords[0] == coords[4] => &coords[..4],
+        _ => return None,
+    };
+
+    let size = corners[0].len();
+    let mut min = vec![f64::INFINITY; size];
+    let mut max = vec![f64::NEG_INFINITY; size];
+    for corner in corners {
+        for (n, value) in corner.iter().enumerate() {
+            min[n] = min[n].min(*value);
+            max[n] = max[n].max(*value);
+        }
+    }
+
+    // Every vertex must be a corner of the bounding box and every edge parallel to an axis
+    let is_corner =
+        |c: &[f64]| (c[0] == min[0] || c[0] == max[0]) && (c[1] == min[1] || c[1] == max[1]);
+    let is_aligned = |a: &[f64], b: &[f64]| a[0] == b[0] || a[1] == b[1];
+    if !corners.iter().all(|c| is_corner(c))
+        || !(0..4).all(|n| is_aligned(&corners[n], &corners[(n + 1) % 4]))
+    {
+        return None;
+    }
+    // Each corner must be visited, ruling out degenerate rings that double back on themselves
+    for x in [min[0], max[0]] {
+        for y in [min[1], max[1]] {
+            if !corners.iter().any(|c| c[0] == x && c[1] == y) {
+                return None;
+            }
+        }
+    }
+
+    let (z, m) = match exterior.dim() {
+        geo_traits::Dimensions::Xyz => (Some(2), None),
+        geo_traits::Dimensions::Xym => (None, Some(2)),
+        geo_traits::Dimensions::Xyzm => (Some(2), Some(3)),
+        _ => (None, None),
+    };
+    let to_coord = |values: &[f64]| wkt::types::Coord {
+        x: values[0],
+        y: values[1],
+        z: z.map(|n| values[n]),
+        m: m.map(|n| values[n]),
+    };
+    Some((to_coord(&min), to_coord(&max)))
+}
+
+/// Cast a [`GeoArrowArray`] to another [`GeoArrowType`], with the given [`CastOptions`].
+///
+/// This follows the same criteria as [`cast`], except that the options may allow the dimension of
+/// coordinates to change. When they do, coordinates are converted before the cast:
+///
+/// - Z and M values are dropped if the target dimension doesn't have them.
+/// - Z and M values are filled from [`CastOptions::with_z`] and [`CastOptions::with_m`] if the
+///   source doesn't have them.
+/// - WKB and WKT input is parsed to a `Geometry` array first, so geometries of mixed dimensions
+///   are supported.
+pub fn cast_with_options(
+    array: &dyn GeoArrowArray,
+    to_type: &GeoArrowType,
+    options: &CastOptions,
+) -> GeoArrowResult<Arc<dyn GeoArrowArray>> {
+    let target_dim = match (options.dimension, to_type.dimension()) {
+        (Some(dim), Some(to_dim)) if dim != to_dim => {
+            return Err(ArrowError::CastError(format!(
+                "Cannot cast to {to_dim:?} when forcing dimension {dim:?}",
+            ))
+            .into());
+        }
+        (Some(dim), _) => Some(dim),
+        (None, Some(to_dim)) if options.coerce_dimension => Some(to_dim),
+        (None, _) => None,
+    };
+
+    match target_dim {
+        Some(dim) => {
+            let coord_type = to_type.coord_type().unwrap_or_default();
+            let array = into_dimension(array, dim, coord_type, &options.z, &options.m)?;
+            cast(array.as_ref(), to_type)
+        }
+        None => cast(array, to_type),
+    }
+}
+
+/// Convert the coordinates of an array to the given dimension.
+///
+/// Serialized arrays are parsed to a `Geometry` array with the given coord type first.
+fn into_dimension(
+    array: &dyn GeoArrowArray,
+    dim: Dimension,
+    coord_type: CoordType,
+    z: &DimensionFill,
+    m: &DimensionFill,
+) -> GeoArrowResult<Arc<dyn GeoArrowArray>> {
+    use GeoArrowType::*;
+
+    let geometry_type =
+        GeometryType::new(array.data_type().metadata().clone()).with_coord_type(coord_type);
+    let out: Arc<dyn GeoArrowArray> = match array.data_type() {
+        Point(_) => Arc::new(array.as_point().clone().into_dimension(dim, z, m)?),
+        LineString(_) => Arc::new(array.as_line_string().clone().into_dimension(dim, z, m)?),
+        Polygon(_) => Arc::new(array.as_polygon().clone().into_dimension(dim, z, m)?),
+        MultiPoint(_) => Arc::new(array.as_multi_point().clone().into_dimension(dim, z, m)?),
+        MultiLineString(_) => Arc::new(
+            array
+                .as_multi_line_string()
+                .clone()
+                .into_dimension(dim, z, m)?,
+        ),
+        MultiPolygon(_) => Arc::new(array.as_multi_polygon().clone().into_dimension(dim, z, m)?),
+        GeometryCollection(_) => Arc::new(
+            array
+                .as_geometry_collection()
+                .clone()
+                .into_dimension(dim, z, m)?,
+        ),
+        Rect(_) => Arc::new(array.as_rect().clone().into_dimension(dim, z, m)?),
+        Geometry(_) => Arc::new(array.as_geometry().clone().into_dimension(dim, z, m)?),
+        Wkb(_) | LargeWkb(_) | WkbView(_) | Wkt(_) | LargeWkt(_) | WktView(_) => {
+            let parsed = match array.data_type() {
+                Wkb(_) => parse_geometries(array.as_wkb::<i32>(), geometry_type)?,
+                LargeWkb(_) => parse_geometries(array.as_wkb::<i64>(), geometry_type)?,
+                WkbView(_) => parse_geometries(array.as_wkb_view(), geometry_type)?,
+                Wkt(_) => parse_geometries(array.as_wkt::<i32>(), geometry_type)?,
+                LargeWkt(_) => parse_geometries(array.as_wkt::<i64>(), geometry_type)?,
+                _ => parse_geometries(array.as_wkt_view(), geometry_type)?,
+            };
+            Arc::new(parsed.into_dimension(dim, z, m)?)
+        }
+    };
+    Ok(out)
+}
+
+/// Parse serialized geometries to a `Geometry` array, keeping geometry collections with a single
+/// member, which [`from_wkb`] and [`from_wkt`] unwrap.
+fn parse_geometries<'a>(
+    array: &'a impl GeoArrowArrayAccessor<'a>,
+    typ: GeometryType,
+) -> GeoArrowResult<GeometryArray> {
+    let mut builder = GeometryBuilder::new(typ);
+    for geometry in array.iter() {
+        match geometry.transpose()? {
+            Some(geometry) => match geometry.as_type() {
+                geo_traits::GeometryType::GeometryCollection(collection) => {
+                    builder.push_geometry_collection(Some(collection))?
+                }
+                _ => builder.push_geometry(Some(&geometry))?,
+            },
+            None => builder.push_null(),
+        }
+    }
+    Ok(builder.finish())
+}
+
 #[cfg(test)]
 mod test {
+    use std::str::FromStr;
+
+    use arrow_array::StringArray;
+    use geo_traits::RectTrait;
+    use geoarrow_array::array::WktArray;
     use geoarrow_array::builder::MultiPointBuilder;
     use geoarrow_array::{IntoArrow, test};
     use geoarrow_schema::{
-        CoordType, Dimension, GeometryType, LineStringType, MultiLineStringType, MultiPointType,
-        MultiPolygonType, PointType, PolygonType, WkbType,
+        CoordType, Crs, Dimension, GeometryType, LineStringType, Metadata, MultiLineStringType,
+        MultiPointType, MultiPolygonType, PointType, PolygonType, WkbType, WktType,
     };
-    use wkt::wkt;
+    use wkt::{Wkt, wkt};
 
     use super::*;
 
@@ -564,4 +872,285 @@ mod test {
         let point_type = PointType::new(Dimension::XY, Default::default());
         assert!(cast(&array, &point_type.into()).is_err());
     }
+
+    #[test]
+    fn cast_with_equivalent_crs() {
+        let source_metadata =
+            Metadata::new(Crs::from_authority_code("EPSG:4326".to_string()), None);
+        let array = test::point::array(CoordType::Interleaved, Dimension::XY)
+            .with_metadata(Arc::new(source_metadata));
+
+        let target_metadata = Arc::new(Metadata::new(
+            Crs::from_wkt2_2019(r#"GEOGCRS["WGS 84",ID["EPSG",4326]]"#.to_string()),
+            None,
+        ));
+        let target_type = GeometryType::new(target_metadata.clone()).into();
+        let casted = cast(&array, &target_type).unwrap();
+        assert_eq!(casted.data_type().metadata(), &target_metadata);
+
+        let other_metadata = Arc::new(Metadata::new(
+            Crs::from_authority_code("EPSG:3857".to_string()),
+            None,
+        ));
+        let other_type = GeometryType::new(other_metadata).into();
+        assert!(cast(&array, &other_type).is_err());
+    }
+
+    fn wkt_array(geoms: &[&str]) -> WktArray {
+        WktArray::new(StringArray::from(geoms.to_vec()), Default::default())
+    }
+
+    fn wkt_values(array: &dyn GeoArrowArray) -> Vec<Option<Wkt<f64>>> {
+        let wkt_type = GeoArrowType::Wkt(WktType::new(array.data_type().metadata().clone()));
+        let out = cast(array, &wkt_type).unwrap();
+        out.as_wkt::<i32>()
+            .iter()
+            .map(|geom| geom.transpose().unwrap())
+            .collect()
+    }
+
+    fn parse(geoms: &[&str]) -> Vec<Option<Wkt<f64>>> {
+        geoms
+            .iter()
+            .map(|s| Some(Wkt::from_str(s).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn cast_force_2d() {
+        let array = wkt_array(&[
+            "POINT Z (1 2 3)",
+            "LINESTRING Z (0 0 1, 1 1 2)",
+            "POINT (5 6)",
+        ]);
+        let options = CastOptions::new().with_dimension(Dimension::XY);
+        let wkb_type = GeoArrowType::Wkb(WkbType::new(Default::default()));
+        let out = cast_with_options(&array, &wkb_type, &options).unwrap();
+        assert_eq!(
+            wkt_values(out.as_ref()),
+            parse(&["POINT (1 2)", "LINESTRING (0 0, 1 1)", "POINT (5 6)"])
+        );
+    }
+
+    #[test]
+    fn cast_coerce_dimension() {
+        let array = wkt_array(&["POINT (1 2)", "POINT (3 4)"]);
+        let xy_type = PointType::new(Dimension::XY, Default::default()).into();
+        let points = cast(&array, &xy_type).unwrap();
+
+        let xyz_type = PointType::new(Dimension::XYZ, Default::default()).into();
+        assert!(cast(points.as_ref(), &xyz_type).is_err());
+
+        let options = CastOptions::new()
+            .with_coerce_dimension(true)
+            .with_z(DimensionFill::Constant(10.0));
+        let out = cast_with_options(points.as_ref(), &xyz_type, &options).unwrap();
+        assert_eq!(out.data_type().dimension(), Some(Dimension::XYZ));
+        assert_eq!(
+            wkt_values(out.as_ref()),
+            parse(&["POINT Z (1 2 10)", "POINT Z (3 4 10)"])
+        );
+
+        let options = options.with_z(DimensionFill::PerGeometry(vec![5.0, 6.0].into()));
+        let out = cast_with_options(points.as_ref(), &xyz_type, &options).unwrap();
+        assert_eq!(
+            wkt_values(out.as_ref()),
+            parse(&["POINT Z (1 2 5)", "POINT Z (3 4 6)"])
+        );
+
+        // Per-geometry values must match the length of the array
+        let options = options.with_z(DimensionFill::PerGeometry(vec![5.0].into()));
+        assert!(cast_with_options(points.as_ref(), &xyz_type, &options).is_err());
+
+        // Forcing a dimension that conflicts with the target type is an error
+        let options = CastOptions::new().with_dimension(Dimension::XY);
+        assert!(cast_with_options(points.as_ref(), &xyz_type, &options).is_err());
+    }
+
+    #[test]
+    fn cast_sliced_polygons_per_geometry_z() {
+        let array = wkt_array(&[
+            "POLYGON ((0 0, 1 0, 1 1, 0 0))",
+            "POLYGON ((0 0, 2 0, 2 2, 0 0), (0.5 0.5, 1 0.5, 1 1, 0.5 0.5))",
+        ]);
+        let xy_type = PolygonType::new(Dimension::XY, Default::default()).into();
+        let polygons = cast(&array, &xy_type).unwrap().slice(1, 1);
+
+        let xyz_type = PolygonType::new(Dimension::XYZ, Default::default()).into();
+        let options = CastOptions::new()
+            .with_coerce_dimension(true)
+            .with_z(DimensionFill::PerGeometry(vec![7.0].into()));
+        let out = cast_with_options(polygons.as_ref(), &xyz_type, &options).unwrap();
+        assert_eq!(
+            wkt_values(out.as_ref()),
+            parse(&[
+                "POLYGON Z ((0 0 7, 2 0 7, 2 2 7, 0 0 7), (0.5 0.5 7, 1 0.5 7, 1 1 7, 0.5 0.5 7))"
+            ])
+        );
+    }
+
+    #[test]
+    fn cast_m_to_z() {
+        let array = wkt_array(&["POINT M (1 2 3)", "LINESTRING M (0 0 1, 1 1 2)"]);
+        let geometry_type = GeometryType::new(Default::default()).into();
+
+        let options = CastOptions::new()
+            .with_dimension(Dimension::XYZ)
+            .with_z(DimensionFill::OtherDimension);
+        let out = cast_with_options(&array, &geometry_type, &options).unwrap();
+        assert_eq!(
+            wkt_values(out.as_ref()),
+            parse(&["POINT Z (1 2 3)", "LINESTRING Z (0 0 1, 1 1 2)"])
+        );
+
+        // XY coordinates have no M values to take Z values from
+        let array = wkt_array(&["POINT (1 2)"]);
+        assert!(cast_with_options(&array, &geometry_type, &options).is_err());
+    }
+
+    #[test]
+    fn cast_geometry_mixed_dimensions() {
+        let array = wkt_array(&[
+            "POINT (1 2)",
+            "POINT Z (3 4 5)",
+            "GEOMETRYCOLLECTION Z (POINT Z (1 1 1))",
+            "POINT (6 7)",
+        ]);
+        let geometry_type = GeometryType::new(Default::default()).into();
+
+        let options =
+            CastOptions::new()
+                .with_dimension(Dimension::XYZ)
+                .with_z(DimensionFill::PerGeometry(
+                    vec![10.0, 20.0, 30.0, 40.0].into(),
+                ));
+        let out = cast_with_options(&array, &geometry_type, &options).unwrap();
+        assert!(out.as_geometry().has_only_dimension(Dimension::XYZ));
+        assert_eq!(
+            wkt_values(out.as_ref()),
+            parse(&[
+                "POINT Z (1 2 10)",
+                "POINT Z (3 4 5)",
+                "GEOMETRYCOLLECTION Z (POINT Z (1 1 1))",
+                "POINT Z (6 7 40)",
+            ])
+        );
+    }
+
+    fn rect_array() -> RectArray {
+        let mut builder = RectBuilder::new(BoxType::new(Dimension::XY, Default::default()));
+        let coord = |x, y| wkt::types::Coord {
+            x,
+            y,
+            z: None,
+            m: None,
+        };
+        builder.push_min_max(&coord(0., 0.), &coord(2., 1.));
+        builder.push_null();
+        builder.push_min_max(&coord(-1., -1.), &coord(1., 1.));
+        builder.finish()
+    }
+
+    #[test]
+    fn cast_rect() {
+        let array = rect_array();
+        let polygons = [
+            "POLYGON ((0 0,0 1,2 1,2 0,0 0))",
+            "POLYGON ((-1 -1,-1 1,1 1,1 -1,-1 -1))",
+        ];
+        let multi_polygons = [
+            "MULTIPOLYGON (((0 0,0 1,2 1,2 0,0 0)))",
+            "MULTIPOLYGON (((-1 -1,-1 1,1 1,1 -1,-1 -1)))",
+        ];
+
+        let targets: [(GeoArrowType, &[&str]); 5] = [
+            (
+                PolygonType::new(Dimension::XY, Default::default()).into(),
+                &polygons,
+            ),
+            (
+                MultiPolygonType::new(Dimension::XY, Default::default()).into(),
+                &multi_polygons,
+            ),
+            (GeometryType::new(Default::default()).into(), &polygons),
+            (
+                GeoArrowType::Wkb(WkbType::new(Default::default())),
+                &polygons,
+            ),
+            (
+                GeoArrowType::Wkt(WktType::new(Default::default())),
+                &polygons,
+            ),
+        ];
+        for (to_type, expected) in targets {
+            let out = cast(&array, &to_type).unwrap();
+            assert_eq!(out.data_type(), to_type);
+            assert_eq!(out.len(), 3);
+            assert_eq!(out.logical_nulls(), array.logical_nulls());
+
+            let mut expected = parse(expected);
+            expected.insert(1, None);
+            assert_eq!(wkt_values(out.as_ref()), expected);
+        }
+    }
+
+    #[test]
+    fn cast_polygon_to_rect() {
+        let wkt = WktArray::new(
+            StringArray::from(vec![
+                Some("POLYGON ((0 0,0 1,2 1,2 0,0 0))"),
+                None,
+                Some("POLYGON ((1 1,-1 1,-1 -1,1 -1))"),
+            ]),
+            Default::default(),
+        );
+        let polygons = cast(
+            &wkt,
+            &PolygonType::new(Dimension::XY, Default::default()).into(),
+        )
+        .unwrap();
+
+        let rect_type = BoxType::new(Dimension::XY, Default::default());
+        let out = cast(polygons.as_ref(), &rect_type.into()).unwrap();
+        let rects = out.as_rect();
+        assert_eq!(rects.len(), 3);
+        assert!(rects.is_null(1));
+
+        let rect = rects.value(0).unwrap();
+        assert_eq!((rect.min().x(), rect.min().y()), (0., 0.));
+        assert_eq!((rect.max().x(), rect.max().y()), (2., 1.));
+        let rect = rects.value(2).unwrap();
+        assert_eq!((rect.min().x(), rect.min().y()), (-1., -1.));
+        assert_eq!((rect.max().x(), rect.max().y()), (1., 1.));
+
+        // Round trip back to polygons
+        let round_trip = cast(out.as_ref(), &polygons.data_type()).unwrap();
+        assert_eq!(
+            wkt_values(round_trip.as_ref()),
+            vec![
+                parse(&["POLYGON ((0 0,0 1,2 1,2 0,0 0))"])[0].clone(),
+                None,
+                parse(&["POLYGON ((-1 -1,-1 1,1 1,1 -1,-1 -1))"])[0].clone(),
+            ]
+        );
+    }
+
+    #[test]
+    fn cast_polygon_to_rect_not_rectangle() {
+        let rect_type = GeoArrowType::Rect(BoxType::new(Dimension::XY, Default::default()));
+        let polygon_type = PolygonType::new(Dimension::XY, Default::default()).into();
+        for geom in [
+            // Rotated square
+            "POLYGON ((1 0,2 1,1 2,0 1,1 0))",
+            // Interior ring
+            "POLYGON ((0 0,0 4,4 4,4 0,0 0),(1 1,1 2,2 2,2 1,1 1))",
+            // Too many vertices
+            "POLYGON ((0 0,0 1,1 1,2 1,2 0,0 0))",
+            // Doubles back on itself
+            "POLYGON ((0 0,1 0,0 0,0 1,0 0))",
+        ] {
+            let polygons = cast(&wkt_array(&[geom]), &polygon_type).unwrap();
+            assert!(cast(polygons.as_ref(), &rect_type).is_err(), "{geom}");
+        }
+    }
 }
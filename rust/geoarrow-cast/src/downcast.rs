@@ -99,7 +99,9 @@ pub fn infer_downcast_type<'a>(
 }
 
 /// Get GeoArrow type ids from an array
-fn get_type_ids(array: &dyn GeoArrowArray) -> GeoArrowResult<HashSet<NativeTypeAndDimension>> {
+pub(crate) fn get_type_ids(
+    array: &dyn GeoArrowArray,
+) -> GeoArrowResult<HashSet<NativeTypeAndDimension>> {
     use GeoArrowType::*;
     let type_ids: HashSet<NativeTypeAndDimension> = match array.data_type() {
         Point(typ) => [NativeTypeAndDimension::new(
@@ -284,7 +286,7 @@ fn wkt_dim_to_geoarrow_dim(wkt_dim: wkt::types::Dimension) -> Dimension {
     }
 }
 
-fn infer_from_native_type_and_dimension(
+pub(crate) fn infer_from_native_type_and_dimension(
     type_ids: HashSet<NativeTypeAndDimension>,
 ) -> GeoArrowResult<Option<(NativeType, Dimension)>> {
     // Easy, if there's only one type, return that
@@ -377,13 +379,13 @@ impl From<wkt::types::GeometryType> for NativeType {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct NativeTypeAndDimension {
+pub(crate) struct NativeTypeAndDimension {
     geometry_type: NativeType,
     dim: Dimension,
 }
 
 impl NativeTypeAndDimension {
-    fn new(geometry_type: NativeType, dim: Dimension) -> Self {
+    pub(crate) fn new(geometry_type: NativeType, dim: Dimension) -> Self {
         Self { geometry_type, dim }
     }
 
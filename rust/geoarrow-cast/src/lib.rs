@@ -9,3 +9,4 @@
 
 pub mod cast;
 pub mod downcast;
+pub mod reader;
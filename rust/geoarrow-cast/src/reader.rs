@@ -0,0 +1,468 @@
+//! A [`RecordBatchReader`] adapter that downcasts geometry columns of a stream.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use arrow_array::{RecordBatch, RecordBatchReader};
+use arrow_schema::{ArrowError, Field, Schema, SchemaRef};
+use geoarrow_array::array::from_arrow_array;
+use geoarrow_schema::error::GeoArrowResult;
+use geoarrow_schema::{
+    BoxType, CoordType, Dimension, GeoArrowType, GeometryCollectionType, GeometryType,
+    LineStringType, Metadata, MultiLineStringType, MultiPointType, MultiPolygonType, PointType,
+    PolygonType, WkbType,
+};
+
+use crate::cast::cast;
+use crate::downcast::{
+    NativeType, NativeTypeAndDimension, get_type_ids, infer_from_native_type_and_dimension,
+};
+
+/// The type used for a geometry column whose geometries don't share a single native type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DowncastFallback {
+    /// Use a [`GeometryArray`][geoarrow_array::array::GeometryArray].
+    #[default]
+    Geometry,
+
+    /// Use a [`WkbArray`][geoarrow_array::array::WkbArray].
+    Wkb,
+}
+
+/// What to do when geometries after the sampled batches may not fit the type inferred from the
+/// sample.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DowncastMismatch {
+    /// Use the type inferred from the sample, and yield an error for a later batch that doesn't
+    /// fit it.
+    #[default]
+    Error,
+
+    /// Always widen sampled columns to the [fallback type][DowncastOptions::with_fallback], so
+    /// that every batch fits.
+    ///
+    /// This doesn't downcast at all unless the stream ends within the sample, since the reader
+    /// can't know that later batches fit a narrower type. In particular, a sampled column of an
+    /// unbounded stream always uses the fallback type.
+    Widen,
+}
+
+#[derive(Debug, Clone)]
+enum ColumnHint {
+    Type(GeoArrowType),
+    GeometryTypes(HashSet<NativeTypeAndDimension>),
+}
+
+/// Options for [`DowncastRecordBatchReader`].
+#[derive(Debug, Clone)]
+pub struct DowncastOptions {
+    sample_batches: usize,
+    coord_type: CoordType,
+    fallback: DowncastFallback,
+    mismatch: DowncastMismatch,
+    hints: HashMap<String, ColumnHint>,
+}
+
+impl Default for DowncastOptions {
+    fn default() -> Self {
+        Self {
+            sample_batches: 1,
+            coord_type: Default::default(),
+            fallback: Default::default(),
+            mismatch: Default::default(),
+            hints: HashMap::new(),
+        }
+    }
+}
+
+impl DowncastOptions {
+    /// Create new options with the defaults: sample the first batch, use the default coord type,
+    /// fall back to `Geometry`, and error on batches that don't fit the sample.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of batches to read up front to infer the type of each geometry column.
+    ///
+    /// Sampled batches are buffered and yielded as usual. A larger sample makes it less likely
+    /// that a later batch doesn't fit the inferred type.
+    pub fn with_sample_batches(mut self, sample_batches: usize) -> Self {
+        self.sample_batches = sample_batches;
+        self
+    }
+
+    /// Set the coord type of native output columns.
+    pub fn with_coord_type(mut self, coord_type: CoordType) -> Self {
+        self.coord_type = coord_type;
+        self
+    }
+
+    /// Set the type used for columns without a single native type.
+    pub fn with_fallback(mut self, fallback: DowncastFallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Set what to do when batches after the sample may not fit the inferred type.
+    pub fn with_mismatch(mut self, mismatch: DowncastMismatch) -> Self {
+        self.mismatch = mismatch;
+        self
+    }
+
+    /// Use the given output type for a column, instead of inferring it.
+    pub fn with_column_type(mut self, column: impl Into<String>, to_type: GeoArrowType) -> Self {
+        self.hints.insert(column.into(), ColumnHint::Type(to_type));
+        self
+    }
+
+    /// Infer the output type for a column from the geometry types it is known to contain instead
+    /// of sampling, e.g. from the `geometry_types` of GeoParquet metadata or the geometry type in
+    /// a FlatGeobuf header.
+    ///
+    /// An empty list means the geometry types are unknown, and the column is sampled as usual.
+    pub fn with_geometry_types(
+        mut self,
+        column: impl Into<String>,
+        geometry_types: impl IntoIterator<Item = (NativeType, Dimension)>,
+    ) -> Self {
+        let geometry_types = geometry_types.into_iter().map(Into::into).collect();
+        self.hints
+            .insert(column.into(), ColumnHint::GeometryTypes(geometry_types));
+        self
+    }
+}
+
+/// A [`RecordBatchReader`] that casts each geometry column to the simplest native type that fits
+/// its data.
+///
+/// Unlike [`infer_downcast_type`][crate::downcast::infer_downcast_type], this doesn't need the
+/// whole stream up front. Instead, the output type of each geometry column is fixed when the
+/// reader is created, from [type hints][DowncastOptions::with_column_type], [known geometry
+/// types][DowncastOptions::with_geometry_types], or a sample of the first batches. Every batch,
+/// sampled or not, is then cast to those types. Non-geometry columns pass through unchanged.
+///
+/// If the geometries of a column don't share a single native type, the column uses the
+/// [fallback type][DowncastOptions::with_fallback] instead.
+///
+/// ### Batches that don't fit
+///
+/// A [`RecordBatchReader`] must keep the same schema for the whole stream, so a column's output
+/// type can't change after the first batch has been yielded. What happens to geometries after the
+/// sample is set with [`DowncastOptions::with_mismatch`]:
+///
+/// - [`DowncastMismatch::Error`] (the default) fails fast: a later batch with geometries that
+///   don't fit the inferred type (e.g. a `MultiPoint` in a column inferred as `Point`) yields a
+///   [`ArrowError::CastError`] naming the column. To avoid this, sample more batches or provide
+///   type hints.
+/// - [`DowncastMismatch::Widen`] uses the fallback type for every sampled column, unless the
+///   stream ended within the sample. Every batch then fits, but the column is not downcast.
+///
+/// Columns with [known geometry types][DowncastOptions::with_geometry_types] use the type inferred
+/// from them under either policy, and yield an error if a batch doesn't fit.
+pub struct DowncastRecordBatchReader<R> {
+    reader: R,
+    schema: SchemaRef,
+    to_types: Vec<Option<GeoArrowType>>,
+    buffered: VecDeque<RecordBatch>,
+}
+
+impl<R: RecordBatchReader> DowncastRecordBatchReader<R> {
+    /// Create a new reader, sampling batches from `reader` as needed.
+    pub fn try_new(mut reader: R, options: DowncastOptions) -> GeoArrowResult<Self> {
+        let input_schema = reader.schema();
+        let mut geometry_columns = vec![];
+        for (i, field) in input_schema.fields().iter().enumerate() {
+            if let Some(data_type) = GeoArrowType::from_extension_field(field)? {
+                geometry_columns.push((i, data_type));
+            }
+        }
+
+        let needs_sample = geometry_columns.iter().any(|(i, _)| {
+            !matches!(
+                options.hints.get(input_schema.field(*i).name()),
+                Some(ColumnHint::Type(_))
+            )
+        });
+        let mut buffered = VecDeque::new();
+        let mut exhausted = false;
+        if needs_sample {
+            while buffered.len() < options.sample_batches {
+                match reader.next() {
+                    Some(batch) => buffered.push_back(batch?),
+                    None => {
+                        exhausted = true;
+                        break;
+                    }
+                }
+            }
+        }
+        let fallback_type = |metadata| match options.fallback {
+            DowncastFallback::Geometry => GeoArrowType::Geometry(
+                GeometryType::new(metadata).with_coord_type(options.coord_type),
+            ),
+            DowncastFallback::Wkb => GeoArrowType::Wkb(WkbType::new(metadata)),
+        };
+
+        let mut to_types = vec![None; input_schema.fields().len()];
+        for (i, data_type) in geometry_columns {
+            let field = input_schema.field(i);
+            let type_ids = match options.hints.get(field.name()) {
+                Some(ColumnHint::Type(to_type)) => {
+                    to_types[i] = Some(to_type.clone());
+                    continue;
+                }
+                Some(ColumnHint::GeometryTypes(geometry_types)) if !geometry_types.is_empty() => {
+                    geometry_types.clone()
+                }
+                _ if options.mismatch == DowncastMismatch::Widen && !exhausted => {
+                    to_types[i] = Some(fallback_type(data_type.metadata().clone()));
+                    continue;
+                }
+                _ => {
+                    let mut type_ids = HashSet::new();
+                    for batch in buffered.iter() {
+                        let array = from_arrow_array(batch.column(i), field)?;
+                        type_ids.extend(get_type_ids(array.as_ref())?);
+                    }
+                    type_ids
+                }
+            };
+
+            let metadata = data_type.metadata().clone();
+            let native_type = if type_ids.is_empty() {
+                None
+            } else {
+                infer_from_native_type_and_dimension(type_ids)?
+            };
+            to_types[i] = Some(match native_type {
+                Some((native_type, dim)) => {
+                    native_data_type(native_type, dim, options.coord_type, metadata)
+                }
+                None => fallback_type(metadata),
+            });
+        }
+
+        let fields = input_schema
+            .fields()
+            .iter()
+            .zip(to_types.iter())
+            .map(|(field, to_type)| match to_type {
+                Some(to_type) => Arc::new(to_type.to_field(field.name(), field.is_nullable())),
+                None => field.clone(),
+            })
+            .collect::<Vec<_>>();
+        let schema = Arc::new(Schema::new_with_metadata(
+            fields,
+            input_schema.metadata().clone(),
+        ));
+
+        Ok(Self {
+            reader,
+            schema,
+            to_types,
+            buffered,
+        })
+    }
+
+    /// Access the output type of each column, or `None` for non-geometry columns.
+    pub fn to_types(&self) -> &[Option<GeoArrowType>] {
+        &self.to_types
+    }
+
+    /// Consume this reader, returning the wrapped reader.
+    ///
+    /// Any batches buffered while sampling are dropped.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn cast_batch(&self, batch: RecordBatch) -> Result<RecordBatch, ArrowError> {
+        let input_schema = batch.schema();
+        let columns = batch
+            .columns()
+            .iter()
+            .zip(input_schema.fields())
+            .zip(self.to_types.iter())
+            .map(|((column, field), to_type)| match to_type {
+                Some(to_type) => cast_column(column, field, to_type),
+                None => Ok(column.clone()),
+            })
+            .collect::<Result<Vec<_>, ArrowError>>()?;
+        RecordBatch::try_new(self.schema.clone(), columns)
+    }
+}
+
+fn cast_column(
+    column: &arrow_array::ArrayRef,
+    field: &Field,
+    to_type: &GeoArrowType,
+) -> Result<arrow_array::ArrayRef, ArrowError> {
+    let array = from_arrow_array(column, field)?;
+    let out = cast(array.as_ref(), to_type).map_err(|err| {
+        ArrowError::CastError(format!(
+            "Geometry column '{}' does not fit its inferred type {to_type:?}. Sample more batches, provide a type hint, or use DowncastMismatch::Widen. {err}",
+            field.name()
+        ))
+    })?;
+    Ok(out.to_array_ref())
+}
+
+fn native_data_type(
+    native_type: NativeType,
+    dim: Dimension,
+    coord_type: CoordType,
+    metadata: Arc<Metadata>,
+) -> GeoArrowType {
+    use NativeType::*;
+    let data_type: GeoArrowType = match native_type {
+        Point => PointType::new(dim, metadata).into(),
+        LineString => LineStringType::new(dim, metadata).into(),
+        Polygon => PolygonType::new(dim, metadata).into(),
+        MultiPoint => MultiPointType::new(dim, metadata).into(),
+        MultiLineString => MultiLineStringType::new(dim, metadata).into(),
+        MultiPolygon => MultiPolygonType::new(dim, metadata).into(),
+        GeometryCollection => GeometryCollectionType::new(dim, metadata).into(),
+        Rect => BoxType::new(dim, metadata).into(),
+    };
+    data_type.with_coord_type(coord_type)
+}
+
+impl<R: RecordBatchReader> Iterator for DowncastRecordBatchReader<R> {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let batch = match self.buffered.pop_front() {
+            Some(batch) => batch,
+            None => match self.reader.next()? {
+                Ok(batch) => batch,
+                Err(err) => return Some(Err(err)),
+            },
+        };
+        Some(self.cast_batch(batch))
+    }
+}
+
+impl<R: RecordBatchReader> RecordBatchReader for DowncastRecordBatchReader<R> {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use arrow_array::{Int32Array, RecordBatchIterator, StringArray};
+    use geoarrow_array::GeoArrowArray;
+    use geoarrow_array::array::WktArray;
+    use geoarrow_schema::WktType;
+
+    use super::*;
+
+    fn batch(geoms: &[&str]) -> RecordBatch {
+        let wkt = WktArray::new(StringArray::from(geoms.to_vec()), Default::default());
+        let ids = Int32Array::from_iter_values(0..geoms.len() as i32);
+        RecordBatch::try_new(schema(), vec![Arc::new(ids), wkt.to_array_ref()]).unwrap()
+    }
+
+    fn schema() -> SchemaRef {
+        let wkt_type = GeoArrowType::Wkt(WktType::new(Default::default()));
+        Arc::new(Schema::new(vec![
+            Field::new("id", arrow_schema::DataType::Int32, false),
+            wkt_type.to_field("geometry", true),
+        ]))
+    }
+
+    fn reader(batches: Vec<RecordBatch>) -> impl RecordBatchReader {
+        RecordBatchIterator::new(batches.into_iter().map(Ok), schema())
+    }
+
+    #[test]
+    fn downcast_stream() {
+        let batches = vec![
+            batch(&["POINT (1 2)", "POINT (3 4)"]),
+            batch(&["POINT (5 6)"]),
+        ];
+        let reader =
+            DowncastRecordBatchReader::try_new(reader(batches), DowncastOptions::new()).unwrap();
+        let point_type: GeoArrowType = PointType::new(Dimension::XY, Default::default()).into();
+        assert_eq!(reader.to_types(), &[None, Some(point_type.clone())]);
+
+        let schema = reader.schema();
+        assert_eq!(schema.field(0).data_type(), &arrow_schema::DataType::Int32);
+        assert_eq!(GeoArrowType::try_from(schema.field(1)).unwrap(), point_type);
+
+        let out = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].num_rows(), 2);
+        assert_eq!(out[1].num_rows(), 1);
+        assert!(out.iter().all(|batch| batch.schema() == schema));
+    }
+
+    #[test]
+    fn downcast_stream_multi() {
+        let batches = vec![
+            batch(&["POINT (1 2)"]),
+            batch(&["MULTIPOINT ((3 4),(5 6))"]),
+        ];
+        let options = DowncastOptions::new().with_sample_batches(2);
+        let reader = DowncastRecordBatchReader::try_new(reader(batches), options).unwrap();
+        let multi_point_type: GeoArrowType =
+            MultiPointType::new(Dimension::XY, Default::default()).into();
+        assert_eq!(reader.to_types()[1], Some(multi_point_type));
+        assert_eq!(reader.count(), 2);
+    }
+
+    #[test]
+    fn downcast_stream_fallback() {
+        let batches = vec![batch(&["POINT (1 2)", "LINESTRING (0 0,1 1)"])];
+        let options = DowncastOptions::new().with_fallback(DowncastFallback::Wkb);
+        let reader = DowncastRecordBatchReader::try_new(reader(batches), options).unwrap();
+        assert_eq!(
+            reader.to_types()[1],
+            Some(GeoArrowType::Wkb(WkbType::new(Default::default())))
+        );
+        assert!(reader.collect::<Result<Vec<_>, _>>().is_ok());
+    }
+
+    #[test]
+    fn downcast_stream_mismatch() {
+        let batches = vec![batch(&["POINT (1 2)"]), batch(&["LINESTRING (0 0,1 1)"])];
+        let mut reader =
+            DowncastRecordBatchReader::try_new(reader(batches), DowncastOptions::new()).unwrap();
+        assert!(reader.next().unwrap().is_ok());
+        let err = reader.next().unwrap().unwrap_err();
+        assert!(err.to_string().contains("geometry"), "{err}");
+    }
+
+    #[test]
+    fn downcast_stream_mismatch_widen() {
+        let batches = vec![batch(&["POINT (1 2)"]), batch(&["LINESTRING (0 0,1 1)"])];
+        let options = DowncastOptions::new().with_mismatch(DowncastMismatch::Widen);
+        let downcast = DowncastRecordBatchReader::try_new(reader(batches), options).unwrap();
+        assert_eq!(
+            downcast.to_types()[1],
+            Some(GeometryType::new(Default::default()).into())
+        );
+        assert_eq!(downcast.collect::<Result<Vec<_>, _>>().unwrap().len(), 2);
+
+        // The sample covers the whole stream, so the native type is used
+        let batches = vec![batch(&["POINT (1 2)"])];
+        let options = DowncastOptions::new()
+            .with_sample_batches(2)
+            .with_mismatch(DowncastMismatch::Widen);
+        let reader = DowncastRecordBatchReader::try_new(reader(batches), options).unwrap();
+        let point_type: GeoArrowType = PointType::new(Dimension::XY, Default::default()).into();
+        assert_eq!(reader.to_types()[1], Some(point_type));
+    }
+
+    #[test]
+    fn downcast_stream_hints() {
+        let batches = vec![batch(&["POINT (1 2)"])];
+        let options = DowncastOptions::new()
+            .with_geometry_types("geometry", [(NativeType::MultiPoint, Dimension::XY)]);
+        let reader = DowncastRecordBatchReader::try_new(reader(batches), options).unwrap();
+        let multi_point_type: GeoArrowType =
+            MultiPointType::new(Dimension::XY, Default::default()).into();
+        assert_eq!(reader.to_types()[1], Some(multi_point_type));
+        assert_eq!(reader.count(), 1);
+    }
+}
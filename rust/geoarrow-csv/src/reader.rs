@@ -1,39 +1,226 @@
-//! Read from CSV files with a geometry column encoded as Well-Known Text.
+//! Read from CSV files with a geometry column encoded as Well-Known Text or hex/base64 Well-Known
+//! Binary, or with point coordinates stored in separate numeric columns.
 //!
 //! The CSV reader implements [`RecordBatchReader`], so you can iterate over the batches of the CSV
 //! without materializing the entire file in memory.
 //!
 //! [`RecordBatchReader`]: arrow_array::RecordBatchReader
 
+use std::collections::VecDeque;
 use std::io::Read;
 use std::sync::Arc;
 
-use arrow_array::RecordBatch;
-use arrow_schema::{ArrowError, DataType, Schema, SchemaRef};
+use arrow_array::builder::LargeBinaryBuilder;
+use arrow_array::cast::AsArray;
+use arrow_array::types::Float64Type;
+use arrow_array::{Array, ArrayRef, Float64Array, RecordBatch};
+use arrow_schema::{ArrowError, DataType, FieldRef, Schema, SchemaRef};
 use geoarrow_array::GeoArrowArray;
-use geoarrow_array::array::{LargeWktArray, WktArray, WktViewArray};
-use geoarrow_array::cast::from_wkt;
+use geoarrow_array::array::{LargeWkbArray, LargeWktArray, WktArray, WktViewArray};
+use geoarrow_array::builder::PointBuilder;
+use geoarrow_array::cast::{AsGeoArrowArray, from_wkb, from_wkt};
+use geoarrow_array::wkb_dialect::{WkbDialect, WkbDialectOptions, from_wkb_dialect};
+use geoarrow_cast::downcast::{NativeType, infer_downcast_type};
 use geoarrow_schema::error::GeoArrowResult;
-use geoarrow_schema::{GeoArrowType, WktType};
+use geoarrow_schema::{
+    BoxType, Dimension, GeoArrowType, GeometryCollectionType, LineStringType, Metadata,
+    MultiLineStringType, MultiPointType, MultiPolygonType, PointType, PolygonType, WkbType,
+    WktType,
+};
+use wkt::types::Coord;
 
 /// Options for the CSV reader.
+///
+/// Create one with [`CsvReaderOptions::new`] and adjust it with the `with_*` methods.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct CsvReaderOptions {
     /// The name of the geometry column in the CSV
     ///
-    /// Defaults to `"geometry"`
+    /// Defaults to `"geometry"`. When reading from [`coordinate_columns`][Self::coordinate_columns],
+    /// this is the name of the output point column instead.
     pub geometry_column_name: Option<String>,
 
-    /// The target geometry type to convert the WKT strings to.
+    /// The target geometry type to convert the geometries to.
+    ///
+    /// When reading from [`coordinate_columns`][Self::coordinate_columns], this must be a
+    /// [`GeoArrowType::Point`] with a dimension matching the coordinate columns.
     pub to_type: GeoArrowType,
+
+    /// Build points from separate numeric coordinate columns instead of parsing a WKT column.
+    pub coordinate_columns: Option<CoordinateColumns>,
+
+    /// How geometries are encoded in the geometry column.
+    ///
+    /// Defaults to [`GeometryEncoding::Wkt`].
+    pub geometry_encoding: GeometryEncoding,
+
+    /// Infer the narrowest geometry type from the first `n` rows of the geometry column.
+    ///
+    /// When set, the geometry type and dimension of [`to_type`][Self::to_type] are replaced by
+    /// the narrowest native type that fits the sampled geometries, keeping the coordinate type
+    /// and metadata of `to_type`. If the sample mixes geometry types that no single native type
+    /// can hold, or holds no geometries at all, `to_type` is used unchanged, so a
+    /// [`GeoArrowType::Geometry`] there acts as the fallback.
+    ///
+    /// Only the sampled rows are checked. A later row that does not fit the inferred type is an
+    /// error when its batch is read. Ignored when reading from
+    /// [`coordinate_columns`][Self::coordinate_columns].
+    pub type_inference_rows: Option<usize>,
+}
+
+impl CsvReaderOptions {
+    /// Read a WKT geometry column named `"geometry"`, converting it to `to_type`.
+    pub fn new(to_type: GeoArrowType) -> Self {
+        Self {
+            geometry_column_name: None,
+            to_type,
+            coordinate_columns: None,
+            geometry_encoding: GeometryEncoding::default(),
+            type_inference_rows: None,
+        }
+    }
+
+    /// Set the name of the geometry column.
+    pub fn with_geometry_column_name(self, geometry_column_name: impl Into<String>) -> Self {
+        Self {
+            geometry_column_name: Some(geometry_column_name.into()),
+            ..self
+        }
+    }
+
+    /// Build points from separate numeric coordinate columns instead of parsing a geometry
+    /// column.
+    pub fn with_coordinate_columns(self, coordinate_columns: CoordinateColumns) -> Self {
+        Self {
+            coordinate_columns: Some(coordinate_columns),
+            ..self
+        }
+    }
+
+    /// Set how geometries are encoded in the geometry column.
+    pub fn with_geometry_encoding(self, geometry_encoding: GeometryEncoding) -> Self {
+        Self {
+            geometry_encoding,
+            ..self
+        }
+    }
+
+    /// Infer the narrowest geometry type from the first `type_inference_rows` rows.
+    pub fn with_type_inference_rows(self, type_inference_rows: usize) -> Self {
+        Self {
+            type_inference_rows: Some(type_inference_rows),
+            ..self
+        }
+    }
+}
+
+/// The encoding of a CSV geometry column.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GeometryEncoding {
+    /// Well-Known Text.
+    #[default]
+    Wkt,
+
+    /// Hex-encoded Well-Known Binary, as written by PostGIS `COPY`.
+    ///
+    /// Both ISO WKB and PostGIS EWKB are accepted. SRIDs embedded in EWKB are not lifted into the
+    /// output; set the CRS on [`CsvReaderOptions::to_type`] instead. A leading `\x`, as in
+    /// PostgreSQL `bytea` output, is ignored.
+    HexWkb,
+
+    /// Base64-encoded Well-Known Binary, with the same WKB dialects as
+    /// [`HexWkb`][Self::HexWkb].
+    Base64Wkb,
 }
 
-/// A CSV reader that parses a WKT-encoded geometry column
+/// Names of the CSV columns holding point coordinates, such as `lon`/`lat` or `x`/`y`/`z`.
+///
+/// A row whose coordinate cells are blank produces a null point. A row with only some of its
+/// coordinate cells blank is an error, as is a cell that is not a number.
+#[derive(Debug, Clone)]
+pub struct CoordinateColumns {
+    /// The name of the column holding X (longitude) values.
+    pub x: String,
+
+    /// The name of the column holding Y (latitude) values.
+    pub y: String,
+
+    /// The name of the column holding Z values, if any.
+    pub z: Option<String>,
+
+    /// Whether to keep the coordinate columns in the output.
+    ///
+    /// If `false` (the default), the point column takes the place of the X column and the other
+    /// coordinate columns are dropped. If `true`, the point column is appended after all input
+    /// columns.
+    pub keep_source_columns: bool,
+}
+
+impl CoordinateColumns {
+    /// Read 2D points from the given X and Y columns.
+    pub fn new(x: impl Into<String>, y: impl Into<String>) -> Self {
+        Self {
+            x: x.into(),
+            y: y.into(),
+            z: None,
+            keep_source_columns: false,
+        }
+    }
+
+    /// Read 3D points, taking Z values from the given column.
+    pub fn with_z(self, z: impl Into<String>) -> Self {
+        Self {
+            z: Some(z.into()),
+            ..self
+        }
+    }
+
+    /// Set whether to keep the coordinate columns in the output.
+    pub fn with_keep_source_columns(self, keep_source_columns: bool) -> Self {
+        Self {
+            keep_source_columns,
+            ..self
+        }
+    }
+
+    fn names(&self) -> impl Iterator<Item = &str> {
+        [
+            Some(self.x.as_str()),
+            Some(self.y.as_str()),
+            self.z.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+/// Where the geometries of each batch come from.
+#[derive(Debug, Clone)]
+enum GeometrySource {
+    /// A column of encoded geometries, replaced in place by parsed geometries.
+    Column {
+        column_index: usize,
+        to_type: GeoArrowType,
+        encoding: GeometryEncoding,
+    },
+    /// Numeric coordinate columns, combined into a point column.
+    Coordinates {
+        /// The indices of the X, Y and, optionally, Z columns in the input.
+        column_indices: Vec<usize>,
+        keep_source_columns: bool,
+        to_type: PointType,
+    },
+}
+
+/// A CSV reader that parses a geometry column encoded as WKT or hex/base64 WKB, or builds points
+/// from coordinate columns.
 pub struct CsvReader<R> {
     reader: arrow_csv::Reader<R>,
     output_schema: SchemaRef,
-    geometry_column_index: usize,
-    to_type: GeoArrowType,
+    source: GeometrySource,
+    /// Batches read ahead of time to infer the geometry type, returned before any others.
+    sampled_batches: VecDeque<RecordBatch>,
 }
 
 impl<R> CsvReader<R> {
@@ -44,20 +231,113 @@ impl<R> CsvReader<R> {
 }
 
 impl<R: Read> CsvReader<R> {
-    /// Wrap an upstream `arrow_csv::Reader` in an iterator that parses WKT geometries.
+    /// Wrap an upstream `arrow_csv::Reader` in an iterator that parses encoded geometries, or
+    /// builds points from coordinate columns if [`CsvReaderOptions::coordinate_columns`] is set.
     pub fn try_new(
-        reader: arrow_csv::Reader<R>,
+        mut reader: arrow_csv::Reader<R>,
         options: CsvReaderOptions,
     ) -> GeoArrowResult<Self> {
+        if let Some(coordinate_columns) = options.coordinate_columns.clone() {
+            return Self::try_new_from_coordinates(reader, &coordinate_columns, options);
+        }
+
         let schema = reader.schema();
+
         let geometry_column_name =
             find_geometry_column(&schema, options.geometry_column_name.as_deref())?;
         let geometry_column_index = schema.index_of(&geometry_column_name)?;
 
+        let mut sampled_batches = VecDeque::new();
+        let mut to_type = options.to_type;
+        if let Some(num_rows) = options.type_inference_rows {
+            let mut sampled_rows = 0;
+            while sampled_rows < num_rows {
+                let Some(batch) = reader.next().transpose()? else {
+                    break;
+                };
+                sampled_rows += batch.num_rows();
+                sampled_batches.push_back(batch);
+            }
+            to_type = infer_geometry_type(
+                &sampled_batches,
+                geometry_column_index,
+                num_rows,
+                options.geometry_encoding,
+                to_type,
+            )?;
+        }
+
         // Transform to output schema
         let mut output_fields = schema.fields().to_vec();
-        output_fields[geometry_column_index] =
-            options.to_type.to_field(geometry_column_name, true).into();
+        output_fields[geometry_column_index] = to_type.to_field(geometry_column_name, true).into();
+
+        let output_schema = Arc::new(Schema::new_with_metadata(
+            output_fields,
+            schema.metadata().clone(),
+        ));
+
+        Ok(Self {
+            reader,
+            output_schema,
+            source: GeometrySource::Column {
+                column_index: geometry_column_index,
+                to_type,
+                encoding: options.geometry_encoding,
+            },
+            sampled_batches,
+        })
+    }
+
+    fn try_new_from_coordinates(
+        reader: arrow_csv::Reader<R>,
+        coordinate_columns: &CoordinateColumns,
+        options: CsvReaderOptions,
+    ) -> GeoArrowResult<Self> {
+        let schema = reader.schema();
+        let expected_dim = if coordinate_columns.z.is_some() {
+            Dimension::XYZ
+        } else {
+            Dimension::XY
+        };
+        let to_type = match options.to_type {
+            GeoArrowType::Point(typ) if typ.dimension() == expected_dim => typ,
+            to_type => {
+                return Err(ArrowError::CsvError(format!(
+                    "Reading from coordinate columns requires a {expected_dim:?} Point target type, got {to_type:?}"
+                ))
+                .into());
+            }
+        };
+
+        let column_indices = coordinate_columns
+            .names()
+            .map(|name| {
+                schema.index_of(name).map_err(|_| {
+                    ArrowError::CsvError(format!(
+                        "CSV coordinate column specified to have name '{name}' but no such column found"
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let geometry_column_name = options
+            .geometry_column_name
+            .unwrap_or_else(|| "geometry".to_string());
+        let geometry_field: FieldRef = GeoArrowType::Point(to_type.clone())
+            .to_field(geometry_column_name, true)
+            .into();
+
+        let mut output_fields = Vec::with_capacity(schema.fields().len() + 1);
+        for (i, field) in schema.fields().iter().enumerate() {
+            if coordinate_columns.keep_source_columns || !column_indices.contains(&i) {
+                output_fields.push(field.clone());
+            } else if i == column_indices[0] {
+                output_fields.push(geometry_field.clone());
+            }
+        }
+        if coordinate_columns.keep_source_columns {
+            output_fields.push(geometry_field);
+        }
 
         let output_schema = Arc::new(Schema::new_with_metadata(
             output_fields,
@@ -67,8 +347,12 @@ impl<R: Read> CsvReader<R> {
         Ok(Self {
             reader,
             output_schema,
-            geometry_column_index,
-            to_type: options.to_type,
+            source: GeometrySource::Coordinates {
+                column_indices,
+                keep_source_columns: coordinate_columns.keep_source_columns,
+                to_type,
+            },
+            sampled_batches: VecDeque::new(),
         })
     }
 }
@@ -77,15 +361,34 @@ impl<R: Read> Iterator for CsvReader<R> {
     type Item = Result<RecordBatch, ArrowError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let reader = &mut self.reader;
-        reader.next().map(move |batch| {
-            parse_batch(
+        let batch = match self.sampled_batches.pop_front() {
+            Some(batch) => Ok(batch),
+            None => self.reader.next()?,
+        };
+        Some(batch.and_then(|batch| match &self.source {
+            GeometrySource::Column {
+                column_index,
+                to_type,
+                encoding,
+            } => parse_batch(
                 batch,
                 self.output_schema.clone(),
-                self.geometry_column_index,
-                self.to_type.clone(),
-            )
-        })
+                *column_index,
+                to_type.clone(),
+                *encoding,
+            ),
+            GeometrySource::Coordinates {
+                column_indices,
+                keep_source_columns,
+                to_type,
+            } => points_from_coordinates(
+                batch,
+                self.output_schema.clone(),
+                column_indices,
+                *keep_source_columns,
+                to_type.clone(),
+            ),
+        }))
     }
 }
 
@@ -96,37 +399,280 @@ impl<R: Read> arrow_array::RecordBatchReader for CsvReader<R> {
 }
 
 fn parse_batch(
-    batch: Result<RecordBatch, ArrowError>,
+    batch: RecordBatch,
     output_schema: SchemaRef,
     geometry_column_index: usize,
     to_type: GeoArrowType,
+    encoding: GeometryEncoding,
 ) -> Result<RecordBatch, ArrowError> {
-    let batch = batch?;
+    let encoded = encoded_geometries(
+        &batch,
+        geometry_column_index,
+        encoding,
+        to_type.metadata().clone(),
+    )?;
+    let parsed_arr = match encoded.data_type() {
+        GeoArrowType::Wkt(_) => from_wkt(encoded.as_wkt::<i32>(), to_type),
+        GeoArrowType::LargeWkt(_) => from_wkt(encoded.as_wkt::<i64>(), to_type),
+        GeoArrowType::WktView(_) => from_wkt(encoded.as_wkt_view(), to_type),
+        _ => from_wkb(encoded.as_wkb::<i64>(), to_type),
+    }?;
+
+    // Replace column in record batch
+    let mut columns = batch.columns().to_vec();
+    columns[geometry_column_index] = parsed_arr.into_array_ref();
+
+    RecordBatch::try_new(output_schema, columns)
+}
+
+/// Wrap the geometry column of a batch as a WKT array, or as an ISO WKB array for the WKB
+/// encodings.
+fn encoded_geometries(
+    batch: &RecordBatch,
+    geometry_column_index: usize,
+    encoding: GeometryEncoding,
+    metadata: Arc<Metadata>,
+) -> Result<Arc<dyn GeoArrowArray>, ArrowError> {
     let column = batch.column(geometry_column_index);
+    let wkt_type = WktType::new(metadata.clone());
+    let arr: Arc<dyn GeoArrowArray> = match (encoding, column.data_type()) {
+        (GeometryEncoding::Wkt, DataType::Utf8) => {
+            Arc::new(WktArray::try_from((column.as_ref(), wkt_type))?)
+        }
+        (GeometryEncoding::Wkt, DataType::LargeUtf8) => {
+            Arc::new(LargeWktArray::try_from((column.as_ref(), wkt_type))?)
+        }
+        (GeometryEncoding::Wkt, DataType::Utf8View) => {
+            Arc::new(WktViewArray::try_from((column.as_ref(), wkt_type))?)
+        }
+        (
+            GeometryEncoding::HexWkb | GeometryEncoding::Base64Wkb,
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View,
+        ) => {
+            let arr = decode_wkb_strings(column, encoding)?;
+            // Normalize EWKB to ISO WKB, keeping the given metadata.
+            let wkb_type = GeoArrowType::LargeWkb(WkbType::new(metadata));
+            let options = WkbDialectOptions::new(WkbDialect::Ewkb);
+            from_wkb_dialect(&arr, wkb_type, &options)?
+        }
+        (_, data_type) => {
+            return Err(ArrowError::CsvError(format!(
+                "Expected CSV geometry column '{}' to be a string column, got {data_type}",
+                batch.schema().field(geometry_column_index).name(),
+            )));
+        }
+    };
+    Ok(arr)
+}
+
+/// Infer the narrowest geometry type of the first `num_rows` rows of the sampled batches, falling
+/// back to `to_type`.
+fn infer_geometry_type(
+    batches: &VecDeque<RecordBatch>,
+    geometry_column_index: usize,
+    num_rows: usize,
+    encoding: GeometryEncoding,
+    to_type: GeoArrowType,
+) -> GeoArrowResult<GeoArrowType> {
+    let mut remaining = num_rows;
+    let mut arrays = Vec::with_capacity(batches.len());
+    for batch in batches {
+        if remaining == 0 {
+            break;
+        }
+        let batch = batch.slice(0, remaining.min(batch.num_rows()));
+        remaining -= batch.num_rows();
+        let arr = encoded_geometries(&batch, geometry_column_index, encoding, Default::default())?;
+        if arr.logical_null_count() < arr.len() {
+            arrays.push(arr);
+        }
+    }
+
+    // infer_downcast_type errors when given no geometries.
+    if arrays.is_empty() {
+        return Ok(to_type);
+    }
+    let Some((native_type, dim)) = infer_downcast_type(arrays.iter().map(|arr| arr.as_ref()))?
+    else {
+        return Ok(to_type);
+    };
+
+    let metadata = to_type.metadata().clone();
+    let coord_type = to_type.coord_type().unwrap_or_default();
+    let inferred = match native_type {
+        NativeType::Point => PointType::new(dim, metadata)
+            .with_coord_type(coord_type)
+            .into(),
+        NativeType::LineString => LineStringType::new(dim, metadata)
+            .with_coord_type(coord_type)
+            .into(),
+        NativeType::Polygon => PolygonType::new(dim, metadata)
+            .with_coord_type(coord_type)
+            .into(),
+        NativeType::MultiPoint => MultiPointType::new(dim, metadata)
+            .with_coord_type(coord_type)
+            .into(),
+        NativeType::MultiLineString => MultiLineStringType::new(dim, metadata)
+            .with_coord_type(coord_type)
+            .into(),
+        NativeType::MultiPolygon => MultiPolygonType::new(dim, metadata)
+            .with_coord_type(coord_type)
+            .into(),
+        NativeType::GeometryCollection => GeometryCollectionType::new(dim, metadata)
+            .with_coord_type(coord_type)
+            .into(),
+        NativeType::Rect => BoxType::new(dim, metadata).into(),
+    };
+    Ok(inferred)
+}
 
-    let parsed_arr = match column.data_type() {
-        DataType::Utf8 => {
-            let arr = WktArray::try_from((column.as_ref(), WktType::default()))?;
-            from_wkt(&arr, to_type)
+/// Decode a column of hex or base64 strings into WKB bytes, with blank cells as nulls.
+fn decode_wkb_strings(
+    column: &ArrayRef,
+    encoding: GeometryEncoding,
+) -> Result<LargeWkbArray, ArrowError> {
+    let mut builder = LargeBinaryBuilder::with_capacity(column.len(), 0);
+    let mut buf = Vec::new();
+    let mut append = |value: Option<&str>| -> Result<(), ArrowError> {
+        match value.map(str::trim) {
+            None | Some("") => builder.append_null(),
+            Some(value) => {
+                buf.clear();
+                match encoding {
+                    GeometryEncoding::HexWkb => decode_hex(value, &mut buf)?,
+                    _ => decode_base64(value, &mut buf)?,
+                }
+                builder.append_value(&buf);
+            }
         }
-        DataType::LargeUtf8 => {
-            let arr = LargeWktArray::try_from((column.as_ref(), WktType::default()))?;
-            from_wkt(&arr, to_type)
+        Ok(())
+    };
+    match column.data_type() {
+        DataType::Utf8 => column.as_string::<i32>().iter().try_for_each(&mut append)?,
+        DataType::LargeUtf8 => column.as_string::<i64>().iter().try_for_each(&mut append)?,
+        _ => column.as_string_view().iter().try_for_each(&mut append)?,
+    }
+    Ok(LargeWkbArray::new(builder.finish(), Default::default()))
+}
+
+fn decode_hex(value: &str, out: &mut Vec<u8>) -> Result<(), ArrowError> {
+    let digits = value.strip_prefix("\\x").unwrap_or(value).as_bytes();
+    let invalid = || ArrowError::CsvError(format!("Invalid hex-encoded WKB: '{value}'"));
+    if !digits.len().is_multiple_of(2) {
+        return Err(invalid());
+    }
+    let nibble = |digit: u8| (digit as char).to_digit(16).map(|n| n as u8);
+    for pair in digits.chunks_exact(2) {
+        let (Some(high), Some(low)) = (nibble(pair[0]), nibble(pair[1])) else {
+            return Err(invalid());
+        };
+        out.push((high << 4) | low);
+    }
+    Ok(())
+}
+
+fn decode_base64(value: &str, out: &mut Vec<u8>) -> Result<(), ArrowError> {
+    let invalid = || ArrowError::CsvError(format!("Invalid base64-encoded WKB: '{value}'"));
+    let sextet = |c: u8| match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    };
+    let digits = value.trim_end_matches('=').as_bytes();
+    if digits.len() % 4 == 1 {
+        return Err(invalid());
+    }
+    for chunk in digits.chunks(4) {
+        let mut bits = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            bits |= (sextet(*c).ok_or_else(invalid)? as u32) << (18 - 6 * i);
         }
-        DataType::Utf8View => {
-            let arr = WktViewArray::try_from((column.as_ref(), WktType::default()))?;
-            from_wkt(&arr, to_type)
+        let bytes = bits.to_be_bytes();
+        out.extend_from_slice(&bytes[1..chunk.len()]);
+    }
+    Ok(())
+}
+
+fn points_from_coordinates(
+    batch: RecordBatch,
+    output_schema: SchemaRef,
+    column_indices: &[usize],
+    keep_source_columns: bool,
+    to_type: PointType,
+) -> Result<RecordBatch, ArrowError> {
+    let schema = batch.schema();
+    let values = column_indices
+        .iter()
+        .map(|i| coordinate_values(batch.column(*i), schema.field(*i).name()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut builder = PointBuilder::with_capacity(to_type, batch.num_rows());
+    for row in 0..batch.num_rows() {
+        let num_null = values.iter().filter(|arr| arr.is_null(row)).count();
+        if num_null == values.len() {
+            builder.push_null();
+            continue;
+        } else if num_null > 0 {
+            return Err(ArrowError::CsvError(format!(
+                "Row {row} has some but not all coordinate values set"
+            )));
         }
-        _ => unreachable!(),
-    }?;
+        builder.push_coord(Some(&Coord {
+            x: values[0].value(row),
+            y: values[1].value(row),
+            z: values.get(2).map(|z| z.value(row)),
+            m: None,
+        }));
+    }
+    let points = builder.finish().into_array_ref();
 
-    // Replace column in record batch
-    let mut columns = batch.columns().to_vec();
-    columns[geometry_column_index] = parsed_arr.into_array_ref();
+    let mut columns = Vec::with_capacity(output_schema.fields().len());
+    for (i, column) in batch.columns().iter().enumerate() {
+        if keep_source_columns || !column_indices.contains(&i) {
+            columns.push(column.clone());
+        } else if i == column_indices[0] {
+            columns.push(points.clone());
+        }
+    }
+    if keep_source_columns {
+        columns.push(points);
+    }
 
     RecordBatch::try_new(output_schema, columns)
 }
 
+/// Read a CSV column as `f64` coordinate values, with blank cells as nulls.
+fn coordinate_values(column: &ArrayRef, name: &str) -> Result<Float64Array, ArrowError> {
+    match column.data_type() {
+        DataType::Float64 => Ok(column.as_primitive::<Float64Type>().clone()),
+        data_type if data_type.is_numeric() || data_type == &DataType::Null => {
+            let column = arrow_cast::cast(column, &DataType::Float64)?;
+            Ok(column.as_primitive::<Float64Type>().clone())
+        }
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => {
+            let column = arrow_cast::cast(column, &DataType::Utf8)?;
+            column
+                .as_string::<i32>()
+                .iter()
+                .map(|value| match value.map(str::trim) {
+                    None | Some("") => Ok(None),
+                    Some(value) => value.parse::<f64>().map(Some).map_err(|_| {
+                        ArrowError::CsvError(format!(
+                            "Invalid value '{value}' in coordinate column '{name}'"
+                        ))
+                    }),
+                })
+                .collect()
+        }
+        data_type => Err(ArrowError::CsvError(format!(
+            "Coordinate column '{name}' must be numeric, got {data_type}"
+        ))),
+    }
+}
+
 fn find_geometry_column(
     schema: &Schema,
     geometry_column_name: Option<&str>,
@@ -171,7 +717,7 @@ mod tests {
     use geo_traits::{CoordTrait, PointTrait};
     use geoarrow_array::GeoArrowArrayAccessor;
     use geoarrow_array::array::PointArray;
-    use geoarrow_schema::{Dimension, PointType};
+    use geoarrow_schema::{CoordType, Dimension, GeometryType, PointType};
 
     use super::*;
 
@@ -191,10 +737,8 @@ address,type,datetime,report location,incident number
 
         let point_type = PointType::new(Dimension::XY, Default::default());
         let to_type = GeoArrowType::Point(point_type.clone());
-        let geo_options = CsvReaderOptions {
-            geometry_column_name: Some("report location".to_string()),
-            to_type: to_type.clone(),
-        };
+        let geo_options =
+            CsvReaderOptions::new(to_type.clone()).with_geometry_column_name("report location");
         let geo_reader = CsvReader::try_new(reader, geo_options).unwrap();
 
         let batches: Vec<_> = geo_reader.collect::<Result<Vec<_>, _>>().unwrap();
@@ -222,4 +766,221 @@ address,type,datetime,report location,incident number
         // arrow_csv::reader::infer_schema_from_files(files, delimiter, max_read_records, has_header)
         //         infer_schema_from_files(files, delimiter, max_read_records, has_header)
     }
+
+    fn geo_csv_reader(
+        s: &str,
+        options: CsvReaderOptions,
+    ) -> GeoArrowResult<CsvReader<Cursor<String>>> {
+        let format = Format::default().with_header(true);
+        let (schema, _num_read_records) = format.infer_schema(Cursor::new(s), None).unwrap();
+        let reader = ReaderBuilder::new(schema.into())
+            .with_format(format)
+            .build(Cursor::new(s.to_string()))
+            .unwrap();
+        CsvReader::try_new(reader, options)
+    }
+
+    #[test]
+    fn read_csv_coordinate_columns() {
+        let s = r#"name,lon,lat
+a,-122.329051,47.6069
+b,,
+c,-122.266529,47.515984"#;
+
+        let point_type = PointType::new(Dimension::XY, Default::default());
+        let options = CsvReaderOptions::new(point_type.clone().into())
+            .with_coordinate_columns(CoordinateColumns::new("lon", "lat"));
+        let geo_reader = geo_csv_reader(s, options).unwrap();
+        let schema = geo_reader.schema();
+        let field_names = schema
+            .fields()
+            .iter()
+            .map(|field| field.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(field_names, vec!["name", "geometry"]);
+
+        let batches: Vec<_> = geo_reader.collect::<Result<Vec<_>, _>>().unwrap();
+        let batch = &batches[0];
+        let point_arr = PointArray::try_from((batch.column(1).as_ref(), point_type)).unwrap();
+        assert_eq!(point_arr.len(), 3);
+        assert!(point_arr.is_null(1));
+        let point = point_arr.value(2).unwrap();
+        assert_eq!(point.coord().unwrap().x(), -122.266529);
+        assert_eq!(point.coord().unwrap().y(), 47.515984);
+    }
+
+    #[test]
+    fn read_csv_coordinate_columns_xyz() {
+        let s = r#"x,y,z,id
+1.0,2.0,3.0,1
+4.0,5.0,6.0,2"#;
+
+        let point_type = PointType::new(Dimension::XYZ, Default::default());
+        let options = CsvReaderOptions::new(point_type.clone().into())
+            .with_geometry_column_name("location")
+            .with_coordinate_columns(
+                CoordinateColumns::new("x", "y")
+                    .with_z("z")
+                    .with_keep_source_columns(true),
+            );
+        let geo_reader = geo_csv_reader(s, options).unwrap();
+        let batches: Vec<_> = geo_reader.collect::<Result<Vec<_>, _>>().unwrap();
+        let batch = &batches[0];
+        assert_eq!(batch.num_columns(), 5);
+        assert_eq!(batch.schema().field(4).name(), "location");
+
+        let point_arr = PointArray::try_from((batch.column(4).as_ref(), point_type)).unwrap();
+        let point = point_arr.value(1).unwrap();
+        assert_eq!(point.coord().unwrap().nth_or_panic(2), 6.0);
+    }
+
+    #[test]
+    fn read_csv_coordinate_columns_errors() {
+        let s = r#"lon,lat
+1.0,
+2.0,3.0"#;
+
+        let point_type = PointType::new(Dimension::XY, Default::default());
+        let options = CsvReaderOptions::new(point_type.clone().into())
+            .with_coordinate_columns(CoordinateColumns::new("lon", "lat"));
+        let geo_reader = geo_csv_reader(s, options.clone()).unwrap();
+        assert!(geo_reader.collect::<Result<Vec<_>, _>>().is_err());
+
+        let missing = options
+            .clone()
+            .with_coordinate_columns(CoordinateColumns::new("lon", "latitude"));
+        assert!(geo_csv_reader(s, missing).is_err());
+
+        let wrong_dim =
+            CsvReaderOptions::new(PointType::new(Dimension::XYZ, Default::default()).into())
+                .with_coordinate_columns(CoordinateColumns::new("lon", "lat"));
+        assert!(geo_csv_reader(s, wrong_dim).is_err());
+    }
+
+    #[test]
+    fn read_csv_wkb() {
+        // POINT (1 2) as ISO WKB, and as EWKB with SRID 4326.
+        let hex = "0101000000000000000000F03F0000000000000040";
+        let ewkb_hex = "0101000020E6100000000000000000F03F0000000000000040";
+        let base64 = "AQEAAAAAAAAAAADwPwAAAAAAAABA";
+
+        let point_type = PointType::new(Dimension::XY, Default::default());
+        for (encoding, values) in [
+            (GeometryEncoding::HexWkb, [hex, ewkb_hex]),
+            (GeometryEncoding::HexWkb, [&format!("\\x{hex}"), hex]),
+            (GeometryEncoding::Base64Wkb, [base64, base64]),
+        ] {
+            let s = format!("id,geometry\n1,{}\n2,\n3,{}", values[0], values[1]);
+            let options =
+                CsvReaderOptions::new(point_type.clone().into()).with_geometry_encoding(encoding);
+            let geo_reader = geo_csv_reader(&s, options).unwrap();
+            let batches: Vec<_> = geo_reader.collect::<Result<Vec<_>, _>>().unwrap();
+            let batch = &batches[0];
+            let point_arr =
+                PointArray::try_from((batch.column(1).as_ref(), point_type.clone())).unwrap();
+            assert!(point_arr.is_null(1));
+            for i in [0, 2] {
+                let point = point_arr.value(i).unwrap();
+                assert_eq!(point.coord().unwrap().x(), 1.0);
+                assert_eq!(point.coord().unwrap().y(), 2.0);
+            }
+        }
+    }
+
+    #[test]
+    fn read_csv_invalid_geometry_column() {
+        let point_type = PointType::new(Dimension::XY, Default::default());
+        let options = CsvReaderOptions::new(point_type.into())
+            .with_geometry_encoding(GeometryEncoding::HexWkb);
+
+        let geo_reader = geo_csv_reader("id,geometry\n1,0101zz", options.clone()).unwrap();
+        assert!(geo_reader.collect::<Result<Vec<_>, _>>().is_err());
+
+        // A numeric geometry column is an error rather than a panic.
+        let geo_reader = geo_csv_reader("id,geometry\n1,2", options).unwrap();
+        assert!(geo_reader.collect::<Result<Vec<_>, _>>().is_err());
+    }
+
+    fn inferring_reader(
+        s: &str,
+        batch_size: usize,
+        type_inference_rows: usize,
+    ) -> GeoArrowResult<CsvReader<Cursor<String>>> {
+        let format = Format::default().with_header(true);
+        let (schema, _num_read_records) = format.infer_schema(Cursor::new(s), None).unwrap();
+        let reader = ReaderBuilder::new(schema.into())
+            .with_format(format)
+            .with_batch_size(batch_size)
+            .build(Cursor::new(s.to_string()))
+            .unwrap();
+        let options = CsvReaderOptions::new(
+            GeometryType::new(Default::default())
+                .with_coord_type(CoordType::Interleaved)
+                .into(),
+        )
+        .with_type_inference_rows(type_inference_rows);
+        CsvReader::try_new(reader, options)
+    }
+
+    #[test]
+    fn read_csv_infer_type() {
+        let s = "id,geometry\n1,\n2,POINT Z (1 2 3)\n3,POINT Z (4 5 6)";
+        let geo_reader = inferring_reader(s, 1, 3).unwrap();
+        let expected_type = GeoArrowType::Point(
+            PointType::new(Dimension::XYZ, Default::default())
+                .with_coord_type(CoordType::Interleaved),
+        );
+        assert_eq!(
+            GeoArrowType::from_arrow_field(geo_reader.schema().field(1)).unwrap(),
+            expected_type
+        );
+        let batches = geo_reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(batches.len(), 3);
+        let schema = batches[2].schema();
+        let point_arr =
+            PointArray::try_from((batches[2].column(1).as_ref(), schema.field(1))).unwrap();
+        assert_eq!(
+            point_arr.value(0).unwrap().coord().unwrap().nth_or_panic(2),
+            6.0
+        );
+    }
+
+    #[test]
+    fn read_csv_infer_multi_type() {
+        let s = "id,geometry\n1,POINT (1 2)\n2,\"MULTIPOINT ((1 2), (3 4))\"";
+        let geo_reader = inferring_reader(s, 1024, 10).unwrap();
+        let field = geo_reader.schema().field(1).clone();
+        assert!(matches!(
+            GeoArrowType::from_arrow_field(&field).unwrap(),
+            GeoArrowType::MultiPoint(_)
+        ));
+        assert_eq!(geo_reader.count(), 1);
+
+        // Incompatible geometry types fall back to to_type.
+        let s = "id,geometry\n1,POINT (1 2)\n2,\"LINESTRING (1 2, 3 4)\"";
+        let geo_reader = inferring_reader(s, 1024, 10).unwrap();
+        let field = geo_reader.schema().field(1).clone();
+        assert!(matches!(
+            GeoArrowType::from_arrow_field(&field).unwrap(),
+            GeoArrowType::Geometry(_)
+        ));
+
+        // So does a sample with no geometries.
+        let s = "id,geometry\n1,\n2,POINT (1 2)";
+        let geo_reader = inferring_reader(s, 1024, 1).unwrap();
+        let field = geo_reader.schema().field(1).clone();
+        assert!(matches!(
+            GeoArrowType::from_arrow_field(&field).unwrap(),
+            GeoArrowType::Geometry(_)
+        ));
+    }
+
+    #[test]
+    fn read_csv_infer_type_unsampled_rows() {
+        // Only the first row is sampled, so the line string in the second batch doesn't fit.
+        let s = "id,geometry\n1,POINT (1 2)\n2,\"LINESTRING (1 2, 3 4)\"";
+        let mut geo_reader = inferring_reader(s, 1, 1).unwrap();
+        assert!(geo_reader.next().unwrap().is_ok());
+        assert!(geo_reader.next().unwrap().is_err());
+    }
 }
@@ -1,29 +1,160 @@
 //! Write GeoArrow data to CSV
+//!
+//! By default, every geometry column is written as full-precision WKT. Use [`CsvWriterOptions`]
+//! to write hex-encoded WKB, to limit the number of WKT decimal digits, or to split point columns
+//! into separate numeric coordinate columns, either for all geometry columns or per column.
 
+use std::collections::HashMap;
 use std::io::Write;
 use std::sync::Arc;
 
-use arrow_array::{RecordBatch, RecordBatchReader};
-use arrow_schema::Schema;
-use geoarrow_array::cast::to_wkt_view;
-use geoarrow_array::{GeoArrowArray, WrapArray};
-use geoarrow_schema::GeoArrowType;
-use geoarrow_schema::error::GeoArrowResult;
+use arrow_array::builder::{Float64Builder, StringBuilder, StringViewBuilder};
+use arrow_array::{ArrayRef, RecordBatch, RecordBatchReader};
+use arrow_schema::{DataType, Field, FieldRef, Schema};
+use geo_traits::{CoordTrait, PointTrait};
+use geoarrow_array::array::{PointArray, WktViewArray};
+use geoarrow_array::cast::{AsGeoArrowArray, to_wkb, to_wkt_view};
+use geoarrow_array::display::{DisplayOptions, write_geometry};
+use geoarrow_array::{
+    GeoArrowArray, GeoArrowArrayAccessor, IntoArrow, WrapArray, downcast_geoarrow_array,
+};
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+use geoarrow_schema::{Dimension, GeoArrowType};
 
-/// A CSV writer that encodes geometries as WKT strings
+/// Options for the CSV writer.
+#[derive(Debug, Clone, Default)]
+pub struct CsvWriterOptions {
+    /// The format of geometry columns without an entry in
+    /// [`column_formats`][Self::column_formats].
+    ///
+    /// Defaults to full-precision WKT.
+    pub default_format: GeometryFormat,
+
+    /// Formats of individual geometry columns, keyed by column name.
+    pub column_formats: HashMap<String, GeometryFormat>,
+}
+
+impl CsvWriterOptions {
+    /// Set the format of geometry columns without a column-specific format.
+    pub fn with_default_format(self, default_format: GeometryFormat) -> Self {
+        Self {
+            default_format,
+            ..self
+        }
+    }
+
+    /// Set the format of the geometry column with the given name.
+    pub fn with_column_format(mut self, column: impl Into<String>, format: GeometryFormat) -> Self {
+        self.column_formats.insert(column.into(), format);
+        self
+    }
+
+    fn format(&self, column: &str) -> &GeometryFormat {
+        self.column_formats
+            .get(column)
+            .unwrap_or(&self.default_format)
+    }
+}
+
+/// How a geometry column is written to CSV.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeometryFormat {
+    /// Well-Known Text.
+    Wkt {
+        /// The maximum number of decimal digits of each coordinate. Trailing zeros are dropped.
+        ///
+        /// If `None`, coordinates are written at full precision.
+        precision: Option<usize>,
+    },
+
+    /// Hex-encoded ISO Well-Known Binary, in upper case.
+    HexWkb,
+
+    /// Numeric coordinate columns, such as `lon`/`lat`. Only point columns can be written this
+    /// way.
+    ///
+    /// The coordinate columns take the place of the geometry column. Null and empty points are
+    /// written as blank cells.
+    Coordinates(CoordinateColumnNames),
+}
+
+impl Default for GeometryFormat {
+    fn default() -> Self {
+        Self::Wkt { precision: None }
+    }
+}
+
+/// Names of the columns to write point coordinates to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoordinateColumnNames {
+    /// The name of the column for X (longitude) values.
+    pub x: String,
+
+    /// The name of the column for Y (latitude) values.
+    pub y: String,
+
+    /// The name of the column for Z values.
+    ///
+    /// Required when writing points with a Z dimension, and ignored otherwise.
+    pub z: Option<String>,
+
+    /// The name of the column for M values.
+    ///
+    /// Required when writing points with an M dimension, and ignored otherwise.
+    pub m: Option<String>,
+}
+
+impl CoordinateColumnNames {
+    /// Write X and Y values to the given columns.
+    pub fn new(x: impl Into<String>, y: impl Into<String>) -> Self {
+        Self {
+            x: x.into(),
+            y: y.into(),
+            z: None,
+            m: None,
+        }
+    }
+
+    /// Write Z values to the given column.
+    pub fn with_z(self, z: impl Into<String>) -> Self {
+        Self {
+            z: Some(z.into()),
+            ..self
+        }
+    }
+
+    /// Write M values to the given column.
+    pub fn with_m(self, m: impl Into<String>) -> Self {
+        Self {
+            m: Some(m.into()),
+            ..self
+        }
+    }
+}
+
+/// A CSV writer that encodes geometries as WKT strings, hex-encoded WKB or coordinate columns
 pub struct CsvWriter<W: Write> {
     inner: arrow_csv::Writer<W>,
+    options: CsvWriterOptions,
 }
 
 impl<W: Write> CsvWriter<W> {
-    /// Create a new CSV writer
+    /// Create a new CSV writer that writes geometries as full-precision WKT
     pub fn new(writer: arrow_csv::Writer<W>) -> Self {
-        Self { inner: writer }
+        Self::new_with_options(writer, Default::default())
+    }
+
+    /// Create a new CSV writer with the given options
+    pub fn new_with_options(writer: arrow_csv::Writer<W>, options: CsvWriterOptions) -> Self {
+        Self {
+            inner: writer,
+            options,
+        }
     }
 
     /// Write a record batch to the CSV
     pub fn write(&mut self, batch: &RecordBatch) -> GeoArrowResult<()> {
-        let batch = encode_batch(batch)?;
+        let batch = encode_batch(batch, &self.options)?;
         self.inner.write(&batch)?;
         Ok(())
     }
@@ -38,15 +169,24 @@ impl<W: Write> CsvWriter<W> {
 pub fn write_csv<W: Write, S: RecordBatchReader>(
     stream: S,
     writer: &mut arrow_csv::Writer<W>,
+) -> GeoArrowResult<()> {
+    write_csv_with_options(stream, writer, &Default::default())
+}
+
+/// Write a Table to CSV with the given options
+pub fn write_csv_with_options<W: Write, S: RecordBatchReader>(
+    stream: S,
+    writer: &mut arrow_csv::Writer<W>,
+    options: &CsvWriterOptions,
 ) -> GeoArrowResult<()> {
     for batch in stream {
-        writer.write(&encode_batch(&batch?)?)?;
+        writer.write(&encode_batch(&batch?, options)?)?;
     }
 
     Ok(())
 }
 
-fn encode_batch(batch: &RecordBatch) -> GeoArrowResult<RecordBatch> {
+fn encode_batch(batch: &RecordBatch, options: &CsvWriterOptions) -> GeoArrowResult<RecordBatch> {
     let schema = batch.schema();
     let fields = schema.fields();
 
@@ -56,15 +196,39 @@ fn encode_batch(batch: &RecordBatch) -> GeoArrowResult<RecordBatch> {
     for (field, column) in schema.fields().iter().zip(batch.columns()) {
         if let Some(typ) = GeoArrowType::from_extension_field(field)? {
             let geo_arr = typ.wrap_array(&column)?;
-            let wkt_view_arr = to_wkt_view(&geo_arr)?;
-
-            new_fields.push(
-                wkt_view_arr
-                    .data_type()
-                    .to_field(field.name(), field.is_nullable())
-                    .into(),
-            );
-            new_columns.push(wkt_view_arr.into_array_ref());
+            match options.format(field.name()) {
+                GeometryFormat::Wkt { precision } => {
+                    let wkt_view_arr = match precision {
+                        Some(precision) => to_wkt_with_precision(&geo_arr, *precision)?,
+                        None => to_wkt_view(&geo_arr)?,
+                    };
+                    new_fields.push(
+                        wkt_view_arr
+                            .data_type()
+                            .to_field(field.name(), field.is_nullable())
+                            .into(),
+                    );
+                    new_columns.push(wkt_view_arr.into_array_ref());
+                }
+                GeometryFormat::HexWkb => {
+                    new_fields
+                        .push(Field::new(field.name(), DataType::Utf8, field.is_nullable()).into());
+                    new_columns.push(encode_hex_wkb(&geo_arr)?);
+                }
+                GeometryFormat::Coordinates(names) => {
+                    let GeoArrowType::Point(_) = geo_arr.data_type() else {
+                        return Err(GeoArrowError::IncorrectGeometryType(format!(
+                            "Only point columns can be written as coordinate columns, but column '{}' has type {:?}",
+                            field.name(),
+                            geo_arr.data_type()
+                        )));
+                    };
+                    for (field, column) in coordinate_columns(geo_arr.as_point(), names)? {
+                        new_fields.push(field);
+                        new_columns.push(column);
+                    }
+                }
+            }
         } else {
             new_fields.push(field.clone());
             new_columns.push(column.clone());
@@ -76,3 +240,250 @@ fn encode_batch(batch: &RecordBatch) -> GeoArrowResult<RecordBatch> {
         new_columns,
     )?)
 }
+
+/// Write each geometry as WKT with at most `precision` decimal digits per coordinate.
+fn to_wkt_with_precision(
+    arr: &dyn GeoArrowArray,
+    precision: usize,
+) -> GeoArrowResult<WktViewArray> {
+    downcast_geoarrow_array!(arr, impl_to_wkt_with_precision, precision)
+}
+
+fn impl_to_wkt_with_precision<'a>(
+    arr: &'a impl GeoArrowArrayAccessor<'a>,
+    precision: usize,
+) -> GeoArrowResult<WktViewArray> {
+    let options = DisplayOptions::new()
+        .with_max_width(None)
+        .with_precision(Some(precision));
+    let mut builder = StringViewBuilder::with_capacity(arr.len());
+    let mut buf = String::new();
+    for geometry in arr.iter() {
+        match geometry.transpose()? {
+            Some(geometry) => {
+                buf.clear();
+                write_geometry(&mut buf, &geometry, &options)
+                    .map_err(|err| GeoArrowError::Wkt(err.to_string()))?;
+                builder.append_value(&buf);
+            }
+            None => builder.append_null(),
+        }
+    }
+    Ok(WktViewArray::new(
+        builder.finish(),
+        arr.data_type().metadata().clone(),
+    ))
+}
+
+fn encode_hex_wkb(arr: &dyn GeoArrowArray) -> GeoArrowResult<ArrayRef> {
+    const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+    let wkb_arr = to_wkb::<i32>(arr)?;
+    let mut builder = StringBuilder::with_capacity(wkb_arr.len(), wkb_arr.num_bytes() * 2);
+    let mut buf = String::new();
+    for value in wkb_arr.inner().iter() {
+        if let Some(value) = value {
+            buf.clear();
+            for byte in value {
+                buf.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+                buf.push(HEX_DIGITS[(byte & 0x0F) as usize] as char);
+            }
+            builder.append_value(&buf);
+        } else {
+            builder.append_null();
+        }
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+fn coordinate_columns(
+    arr: &PointArray,
+    names: &CoordinateColumnNames,
+) -> GeoArrowResult<Vec<(FieldRef, ArrayRef)>> {
+    let dim = arr.extension_type().dimension();
+    let z_name = match dim {
+        Dimension::XYZ | Dimension::XYZM => Some(names.z.as_deref().ok_or_else(|| {
+            GeoArrowError::IncorrectGeometryType(
+                "Writing points with a Z dimension requires a Z coordinate column name".to_string(),
+            )
+        })?),
+        Dimension::XY | Dimension::XYM => None,
+    };
+    let m_name = match dim {
+        Dimension::XYM | Dimension::XYZM => Some(names.m.as_deref().ok_or_else(|| {
+            GeoArrowError::IncorrectGeometryType(
+                "Writing points with an M dimension requires an M coordinate column name"
+                    .to_string(),
+            )
+        })?),
+        Dimension::XY | Dimension::XYZ => None,
+    };
+
+    let num_columns = dim.size();
+    let mut builders = (0..num_columns)
+        .map(|_| Float64Builder::with_capacity(arr.len()))
+        .collect::<Vec<_>>();
+    for point in arr.iter() {
+        let point = point.transpose()?;
+        match point.as_ref().and_then(|point| point.coord()) {
+            Some(coord) => {
+                for (i, builder) in builders.iter_mut().enumerate() {
+                    builder.append_value(coord.nth_or_panic(i));
+                }
+            }
+            None => builders
+                .iter_mut()
+                .for_each(|builder| builder.append_null()),
+        }
+    }
+
+    let names = [
+        Some(names.x.as_str()),
+        Some(names.y.as_str()),
+        z_name,
+        m_name,
+    ];
+    Ok(names
+        .into_iter()
+        .flatten()
+        .zip(builders)
+        .map(|(name, mut builder)| {
+            let field: FieldRef = Field::new(name, DataType::Float64, true).into();
+            let column: ArrayRef = Arc::new(builder.finish());
+            (field, column)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use arrow_array::StringArray;
+    use arrow_csv::WriterBuilder;
+    use geoarrow_array::builder::PointBuilder;
+    use geoarrow_schema::PointType;
+    use wkt::types::Coord;
+
+    use super::*;
+
+    fn point_batch(dim: Dimension) -> RecordBatch {
+        let point_type = PointType::new(dim, Default::default());
+        let mut builder = PointBuilder::new(point_type);
+        let z = matches!(dim, Dimension::XYZ | Dimension::XYZM).then_some(3.5);
+        let m = matches!(dim, Dimension::XYM | Dimension::XYZM).then_some(7.0);
+        builder.push_coord(Some(&Coord {
+            x: 1.123456,
+            y: -2.5,
+            z,
+            m,
+        }));
+        builder.push_null();
+        let points = builder.finish();
+
+        let names = StringArray::from(vec!["a", "b"]);
+        let schema = Schema::new(vec![
+            Field::new("name", DataType::Utf8, false),
+            points.data_type().to_field("geometry", true),
+            points.data_type().to_field("location", true),
+        ]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(names),
+                points.to_array_ref(),
+                points.into_array_ref(),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn write_to_string(batch: &RecordBatch, options: CsvWriterOptions) -> GeoArrowResult<String> {
+        let arrow_writer = WriterBuilder::new().with_header(true).build(Vec::new());
+        let mut writer = CsvWriter::new_with_options(arrow_writer, options);
+        writer.write(batch)?;
+        Ok(String::from_utf8(writer.into_inner()).unwrap())
+    }
+
+    #[test]
+    fn write_default_wkt() {
+        let out = write_to_string(&point_batch(Dimension::XY), Default::default()).unwrap();
+        assert_eq!(
+            out,
+            "name,geometry,location\na,POINT(1.123456 -2.5),POINT(1.123456 -2.5)\nb,,\n"
+        );
+    }
+
+    #[test]
+    fn write_per_column_formats() {
+        let options = CsvWriterOptions::default()
+            .with_default_format(GeometryFormat::Wkt { precision: Some(2) })
+            .with_column_format("location", GeometryFormat::HexWkb);
+        let out = write_to_string(&point_batch(Dimension::XY), options).unwrap();
+        assert_eq!(
+            out,
+            "name,geometry,location\na,POINT(1.12 -2.5),01010000006CEBA7FFACF9F13F00000000000004C0\nb,,\n"
+        );
+    }
+
+    #[test]
+    fn write_coordinate_columns() {
+        let names = CoordinateColumnNames::new("lon", "lat").with_z("alt");
+        let options = CsvWriterOptions::default()
+            .with_column_format("geometry", GeometryFormat::Coordinates(names.clone()));
+
+        let out = write_to_string(&point_batch(Dimension::XY), options.clone()).unwrap();
+        assert_eq!(
+            out,
+            "name,lon,lat,location\na,1.123456,-2.5,POINT(1.123456 -2.5)\nb,,,\n"
+        );
+
+        let out = write_to_string(&point_batch(Dimension::XYZ), options).unwrap();
+        assert_eq!(
+            out,
+            "name,lon,lat,alt,location\na,1.123456,-2.5,3.5,POINT Z(1.123456 -2.5 3.5)\nb,,,,\n"
+        );
+
+        let names = names.with_m("measure");
+        let options = CsvWriterOptions::default()
+            .with_column_format("geometry", GeometryFormat::Coordinates(names));
+        let out = write_to_string(&point_batch(Dimension::XYZM), options).unwrap();
+        assert_eq!(
+            out,
+            "name,lon,lat,alt,measure,location\na,1.123456,-2.5,3.5,7.0,POINT ZM(1.123456 -2.5 3.5 7)\nb,,,,,\n"
+        );
+
+        // Points with an M dimension need an M column name.
+        let options = CsvWriterOptions::default().with_column_format(
+            "geometry",
+            GeometryFormat::Coordinates(CoordinateColumnNames::new("lon", "lat")),
+        );
+        assert!(write_to_string(&point_batch(Dimension::XYM), options).is_err());
+
+        // Points with a Z dimension need a Z column name.
+        let options = CsvWriterOptions::default().with_column_format(
+            "geometry",
+            GeometryFormat::Coordinates(CoordinateColumnNames::new("lon", "lat")),
+        );
+        assert!(write_to_string(&point_batch(Dimension::XYZ), options).is_err());
+    }
+
+    #[test]
+    fn wkt_precision() {
+        let wkt = geoarrow_array::array::WktArray::new(
+            StringArray::from(vec![
+                Some("POINT (-0.0001 0.0000001)"),
+                None,
+                Some("LINESTRING (1.25 2, 10.999 -3.14159)"),
+            ]),
+            Default::default(),
+        );
+        let out = to_wkt_with_precision(&wkt, 2).unwrap();
+        assert_eq!(
+            out.inner().iter().collect::<Vec<_>>(),
+            vec![
+                Some("POINT(0 0)"),
+                None,
+                Some("LINESTRING(1.25 2,11 -3.14)")
+            ]
+        );
+    }
+}
@@ -0,0 +1,420 @@
+//! Geohash, quadkey and Web Mercator tile cell IDs.
+//!
+//! All functions assume geometries are in longitude/latitude (WGS84) coordinates.
+
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+use arrow_array::builder::StringBuilder;
+use arrow_array::cast::AsArray;
+use arrow_array::types::{UInt8Type, UInt32Type};
+use arrow_array::{Array, ArrayRef, StringArray, StructArray, UInt8Array, UInt32Array};
+use arrow_buffer::NullBuffer;
+use arrow_schema::{ArrowError, DataType, Field, Fields};
+use geo::{Centroid, Coord, Rect};
+use geoarrow_array::array::RectArray;
+use geoarrow_array::bounds::BoundingRect;
+use geoarrow_array::builder::RectBuilder;
+use geoarrow_array::{GeoArrowArray, GeoArrowArrayAccessor, downcast_geoarrow_array};
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+use geoarrow_schema::{BoxType, Crs, Dimension, Metadata};
+
+use crate::util::to_geo::geometry_to_geo;
+
+/// The maximum latitude representable in Web Mercator.
+const MAX_MERCATOR_LATITUDE: f64 = 85.051_128_779_806_59;
+
+/// The maximum quadkey and tile zoom level, so that tile indices fit in a `u32`.
+const MAX_ZOOM: u8 = 31;
+
+/// The maximum geohash precision, beyond which `f64` coordinates can't resolve the cells.
+const MAX_GEOHASH_PRECISION: usize = 12;
+
+/// The point of each geometry used to compute its cell ID.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CellAnchor {
+    /// The centroid of the geometry. For points, this is the point itself.
+    #[default]
+    Centroid,
+
+    /// The center of the geometry's bounding box.
+    BoundsCenter,
+}
+
+/// Compute the geohash of each geometry with `precision` characters (1 to 12).
+///
+/// Null and empty geometries produce null values. A precision outside 1 to 12 is an error.
+pub fn geohash(
+    array: &dyn GeoArrowArray,
+    precision: usize,
+    anchor: CellAnchor,
+) -> GeoArrowResult<StringArray> {
+    if !(1..=MAX_GEOHASH_PRECISION).contains(&precision) {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Geohash precision must be between 1 and {MAX_GEOHASH_PRECISION}, got {precision}"
+        ))
+        .into());
+    }
+    let mut builder = StringBuilder::with_capacity(array.len(), array.len() * precision);
+    for coord in anchor_coords(array, anchor)? {
+        match coord {
+            Some(coord) => {
+                let hash = ::geohash::encode(coord, precision).map_err(|err| {
+                    ArrowError::ComputeError(format!("Failed to compute geohash: {err}"))
+                })?;
+                builder.append_value(hash);
+            }
+            None => builder.append_null(),
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Compute the Web Mercator (Bing Maps) quadkey of each geometry at the given level (1 to 31).
+///
+/// Latitudes beyond ±85.0511° are clamped to the edge of the Web Mercator extent. Null and empty
+/// geometries produce null values. Level 0, whose single tile has an empty quadkey, is an error;
+/// use [`tile_index`] for zoom 0.
+pub fn quadkey(
+    array: &dyn GeoArrowArray,
+    level: u8,
+    anchor: CellAnchor,
+) -> GeoArrowResult<StringArray> {
+    if level == 0 {
+        return Err(ArrowError::InvalidArgumentError(
+            "Quadkey level must be at least 1, got 0".to_string(),
+        )
+        .into());
+    }
+    validate_zoom(level)?;
+    let mut builder = StringBuilder::with_capacity(array.len(), array.len() * level as usize);
+    for coord in anchor_coords(array, anchor)? {
+        match coord {
+            Some(coord) => {
+                let (x, y) = lon_lat_to_tile(coord, level);
+                builder.append_value(tile_to_quadkey(x, y, level));
+            }
+            None => builder.append_null(),
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Compute the Web Mercator `z/x/y` tile index of each geometry at the given zoom (0 to 31).
+///
+/// Returns a [`StructArray`] with fields `z` (`UInt8`), `x` and `y` (`UInt32`), where `y` counts
+/// from the north, as in XYZ tile servers. Null and empty geometries produce null values.
+pub fn tile_index(
+    array: &dyn GeoArrowArray,
+    zoom: u8,
+    anchor: CellAnchor,
+) -> GeoArrowResult<StructArray> {
+    validate_zoom(zoom)?;
+    let coords = anchor_coords(array, anchor)?;
+    let tiles = coords
+        .iter()
+        .map(|coord| coord.map(|coord| lon_lat_to_tile(coord, zoom)))
+        .collect::<Vec<_>>();
+
+    let z = UInt8Array::from(
+        tiles
+            .iter()
+            .map(|tile| tile.map(|_| zoom))
+            .collect::<Vec<_>>(),
+    );
+    let x = UInt32Array::from(
+        tiles
+            .iter()
+            .map(|tile| tile.map(|(x, _)| x))
+            .collect::<Vec<_>>(),
+    );
+    let y = UInt32Array::from(
+        tiles
+            .iter()
+            .map(|tile| tile.map(|(_, y)| y))
+            .collect::<Vec<_>>(),
+    );
+    let nulls = NullBuffer::from(tiles.iter().map(|tile| tile.is_some()).collect::<Vec<_>>());
+    let columns: Vec<ArrayRef> = vec![Arc::new(z), Arc::new(x), Arc::new(y)];
+    Ok(StructArray::try_new(tile_fields(), columns, Some(nulls))?)
+}
+
+/// Convert geohash strings to the [`RectArray`] of the cells they identify.
+///
+/// Null values produce null rects. Invalid geohashes are an error.
+pub fn geohash_to_rect(array: &StringArray) -> GeoArrowResult<RectArray> {
+    let mut builder = RectBuilder::with_capacity(cell_box_type(), array.len());
+    for value in array.iter() {
+        match value {
+            Some(value) => {
+                let rect = ::geohash::decode_bbox(value).map_err(|err| {
+                    ArrowError::InvalidArgumentError(format!("Invalid geohash {value:?}: {err}"))
+                })?;
+                builder.push_rect(Some(&rect));
+            }
+            None => builder.push_null(),
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Convert quadkey strings to the [`RectArray`] of the tiles they identify, in longitude/latitude.
+///
+/// Null values produce null rects. Invalid quadkeys, including empty strings, are an error.
+pub fn quadkey_to_rect(array: &StringArray) -> GeoArrowResult<RectArray> {
+    let mut builder = RectBuilder::with_capacity(cell_box_type(), array.len());
+    for value in array.iter() {
+        match value {
+            Some(value) => {
+                let (x, y, zoom) = quadkey_to_tile(value)?;
+                builder.push_rect(Some(&tile_to_rect(x, y, zoom)));
+            }
+            None => builder.push_null(),
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Convert `z/x/y` tile indices, as returned by [`tile_index`], to the [`RectArray`] of the tiles
+/// they identify, in longitude/latitude.
+///
+/// `tiles` must have `z`, `x` and `y` fields of types `UInt8`, `UInt32` and `UInt32`. Null values
+/// produce null rects.
+pub fn tile_index_to_rect(tiles: &StructArray) -> GeoArrowResult<RectArray> {
+    let column = |name: &str, data_type: DataType| {
+        tiles
+            .column_by_name(name)
+            .filter(|column| column.data_type() == &data_type)
+            .ok_or_else(|| {
+                GeoArrowError::from(ArrowError::InvalidArgumentError(format!(
+                    "Expected tile index struct with a {data_type} field named '{name}'"
+                )))
+            })
+    };
+    let z = column("z", DataType::UInt8)?.as_primitive::<UInt8Type>();
+    let x = column("x", DataType::UInt32)?.as_primitive::<UInt32Type>();
+    let y = column("y", DataType::UInt32)?.as_primitive::<UInt32Type>();
+
+    let mut builder = RectBuilder::with_capacity(cell_box_type(), tiles.len());
+    for i in 0..tiles.len() {
+        if tiles.is_null(i) || z.is_null(i) || x.is_null(i) || y.is_null(i) {
+            builder.push_null();
+            continue;
+        }
+        let (zoom, x, y) = (z.value(i), x.value(i), y.value(i));
+        validate_zoom(zoom)?;
+        if (x as u64) >> zoom != 0 || (y as u64) >> zoom != 0 {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Invalid tile {zoom}/{x}/{y}: x and y must be less than 2^{zoom}"
+            ))
+            .into());
+        }
+        builder.push_rect(Some(&tile_to_rect(x, y, zoom)));
+    }
+    Ok(builder.finish())
+}
+
+fn tile_fields() -> Fields {
+    Fields::from(vec![
+        Field::new("z", DataType::UInt8, true),
+        Field::new("x", DataType::UInt32, true),
+        Field::new("y", DataType::UInt32, true),
+    ])
+}
+
+fn cell_box_type() -> BoxType {
+    let crs = Crs::from_authority_code("OGC:CRS84".to_string());
+    BoxType::new(Dimension::XY, Arc::new(Metadata::new(crs, None)))
+}
+
+fn validate_zoom(zoom: u8) -> GeoArrowResult<()> {
+    if zoom > MAX_ZOOM {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Zoom level must be at most {MAX_ZOOM}, got {zoom}"
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+fn anchor_coords(
+    array: &dyn GeoArrowArray,
+    anchor: CellAnchor,
+) -> GeoArrowResult<Vec<Option<Coord>>> {
+    downcast_geoarrow_array!(array, anchor_coords_impl, anchor)
+}
+
+fn anchor_coords_impl<'a>(
+    array: &'a impl GeoArrowArrayAccessor<'a>,
+    anchor: CellAnchor,
+) -> GeoArrowResult<Vec<Option<Coord>>> {
+    let mut coords = Vec::with_capacity(array.len());
+    for item in array.iter() {
+        let Some(geom) = item.transpose()? else {
+            coords.push(None);
+            continue;
+        };
+        let coord = match anchor {
+            CellAnchor::Centroid => geometry_to_geo(&geom)?.centroid().map(|point| point.0),
+            CellAnchor::BoundsCenter => {
+                let mut bounds = BoundingRect::new();
+                bounds.add_geometry(&geom);
+                (!bounds.is_empty()).then(|| Coord {
+                    x: (bounds.minx() + bounds.maxx()) / 2.0,
+                    y: (bounds.miny() + bounds.maxy()) / 2.0,
+                })
+            }
+        };
+        coords.push(coord.filter(|coord| coord.x.is_finite() && coord.y.is_finite()));
+    }
+    Ok(coords)
+}
+
+fn lon_lat_to_tile(coord: Coord, zoom: u8) -> (u32, u32) {
+    let n = (1u64 << zoom) as f64;
+    let max_index = (1u64 << zoom) - 1;
+    let lat = coord
+        .y
+        .clamp(-MAX_MERCATOR_LATITUDE, MAX_MERCATOR_LATITUDE)
+        .to_radians();
+    let x = ((coord.x + 180.0) / 360.0 * n).floor();
+    let y = ((1.0 - lat.tan().asinh() / PI) / 2.0 * n).floor();
+    let clamp = |value: f64| (value.max(0.0) as u64).min(max_index) as u32;
+    (clamp(x), clamp(y))
+}
+
+fn tile_to_rect(x: u32, y: u32, zoom: u8) -> Rect {
+    let n = (1u64 << zoom) as f64;
+    let lon = |x: f64| x / n * 360.0 - 180.0;
+    let lat = |y: f64| (PI * (1.0 - 2.0 * y / n)).sinh().atan().to_degrees();
+    Rect::new(
+        Coord {
+            x: lon(x as f64),
+            y: lat(y as f64 + 1.0),
+        },
+        Coord {
+            x: lon(x as f64 + 1.0),
+            y: lat(y as f64),
+        },
+    )
+}
+
+fn tile_to_quadkey(x: u32, y: u32, zoom: u8) -> String {
+    (1..=zoom)
+        .rev()
+        .map(|i| {
+            let mask = 1 << (i - 1);
+            let mut digit = b'0';
+            if x & mask != 0 {
+                digit += 1;
+            }
+            if y & mask != 0 {
+                digit += 2;
+            }
+            digit as char
+        })
+        .collect()
+}
+
+fn quadkey_to_tile(quadkey: &str) -> GeoArrowResult<(u32, u32, u8)> {
+    let zoom = u8::try_from(quadkey.len())
+        .ok()
+        .filter(|zoom| (1..=MAX_ZOOM).contains(zoom))
+        .ok_or_else(|| {
+            ArrowError::InvalidArgumentError(format!(
+                "Invalid quadkey {quadkey:?}: must have 1 to {MAX_ZOOM} digits"
+            ))
+        })?;
+    let (mut x, mut y) = (0u32, 0u32);
+    for digit in quadkey.bytes() {
+        let digit = match digit {
+            b'0'..=b'3' => digit - b'0',
+            _ => {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "Invalid quadkey {quadkey:?}: digits must be 0 to 3"
+                ))
+                .into());
+            }
+        };
+        x = (x << 1) | (digit & 1) as u32;
+        y = (y << 1) | (digit >> 1) as u32;
+    }
+    Ok((x, y, zoom))
+}
+
+#[cfg(test)]
+mod test {
+    use geo::Point;
+    use geo_traits::{CoordTrait, RectTrait};
+    use geoarrow_array::builder::PointBuilder;
+    use geoarrow_schema::PointType;
+
+    use super::*;
+
+    fn points(coords: &[Option<(f64, f64)>]) -> geoarrow_array::array::PointArray {
+        let typ = PointType::new(Dimension::XY, Default::default());
+        let mut builder = PointBuilder::new(typ);
+        for coord in coords {
+            builder.push_point(coord.map(|(x, y)| Point::new(x, y)).as_ref());
+        }
+        builder.finish()
+    }
+
+    #[test]
+    fn test_geohash() {
+        let array = points(&[Some((-120.6623, 35.3003)), None]);
+        let hashes = geohash(&array, 5, CellAnchor::Centroid).unwrap();
+        assert_eq!(hashes.value(0), "9q60y");
+        assert!(hashes.is_null(1));
+        assert!(geohash(&array, 0, CellAnchor::Centroid).is_err());
+        assert!(geohash(&array, 13, CellAnchor::Centroid).is_err());
+
+        let rects = geohash_to_rect(&hashes).unwrap();
+        let rect = rects.value(0).unwrap();
+        assert!(rect.min().x() <= -120.6623 && -120.6623 <= rect.max().x());
+        assert!(rect.min().y() <= 35.3003 && 35.3003 <= rect.max().y());
+        assert!(rects.is_null(1));
+    }
+
+    #[test]
+    fn test_quadkey() {
+        // Tile 3/3/5 from the Bing Maps tile system documentation.
+        let rect = tile_to_rect(3, 5, 3);
+        let center = Coord {
+            x: (rect.min().x + rect.max().x) / 2.0,
+            y: (rect.min().y + rect.max().y) / 2.0,
+        };
+        let array = points(&[Some((center.x, center.y))]);
+        let quadkeys = quadkey(&array, 3, CellAnchor::Centroid).unwrap();
+        assert_eq!(quadkeys.value(0), "213");
+        assert_eq!(quadkey_to_tile("213").unwrap(), (3, 5, 3));
+
+        let rects = quadkey_to_rect(&quadkeys).unwrap();
+        let decoded = rects.value(0).unwrap();
+        assert_eq!(decoded.min().x(), rect.min().x);
+        assert_eq!(decoded.max().y(), rect.max().y);
+
+        assert!(quadkey_to_rect(&StringArray::from(vec!["124"])).is_err());
+        assert!(quadkey_to_rect(&StringArray::from(vec![""])).is_err());
+        assert!(quadkey(&array, 0, CellAnchor::Centroid).is_err());
+        assert!(quadkey(&array, 32, CellAnchor::Centroid).is_err());
+    }
+
+    #[test]
+    fn test_tile_index() {
+        let array = points(&[Some((0.1, 0.1)), Some((-179.9, 89.0)), None]);
+        let tiles = tile_index(&array, 1, CellAnchor::BoundsCenter).unwrap();
+        let x = tiles.column(1).as_primitive::<UInt32Type>();
+        let y = tiles.column(2).as_primitive::<UInt32Type>();
+        assert_eq!((x.value(0), y.value(0)), (1, 0));
+        assert_eq!((x.value(1), y.value(1)), (0, 0));
+        assert!(tiles.is_null(2));
+
+        let rects = tile_index_to_rect(&tiles).unwrap();
+        let rect = rects.value(0).unwrap();
+        assert_eq!((rect.min().x(), rect.max().x()), (0.0, 180.0));
+        assert_eq!(rect.min().y(), 0.0);
+        assert!((rect.max().y() - MAX_MERCATOR_LATITUDE).abs() < 1e-9);
+        assert!(rects.is_null(2));
+    }
+}
@@ -10,6 +10,7 @@ mod centroid;
 mod contains;
 mod convex_hull;
 mod distance;
+pub mod grid;
 mod interior_point;
 mod intersects;
 mod length;
@@ -18,6 +19,7 @@ mod relate;
 mod simplify;
 mod simplify_vw;
 mod simplify_vw_preserve;
+mod spatial_join;
 pub mod util;
 pub mod validation;
 
@@ -34,3 +36,4 @@ pub use relate::relate_boolean;
 pub use simplify::simplify;
 pub use simplify_vw::simplify_vw;
 pub use simplify_vw_preserve::simplify_vw_preserve;
+pub use spatial_join::{SpatialJoinPredicate, spatial_join, spatial_join_batches};
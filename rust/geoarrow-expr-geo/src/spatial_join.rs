@@ -0,0 +1,488 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use arrow_array::{RecordBatch, UInt64Array};
+use arrow_schema::Schema;
+use geo::{Contains, Distance, Euclidean, Geometry, Intersects, PreparedGeometry, Relate};
+use geoarrow_array::array::from_arrow_array;
+use geoarrow_array::bounds::BoundingRect;
+use geoarrow_array::{GeoArrowArray, GeoArrowArrayAccessor, downcast_geoarrow_array};
+use geoarrow_index::RTree;
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+
+use crate::util::to_geo::geometry_to_geo;
+
+/// The spatial relationship used to match rows in [`spatial_join`].
+///
+/// Each predicate is evaluated as `predicate(left, right)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpatialJoinPredicate {
+    /// The left and right geometries intersect.
+    Intersects,
+    /// The left geometry contains the right geometry.
+    Contains,
+    /// The left geometry is within the right geometry.
+    Within,
+    /// The euclidean distance between the left and right geometries is less than or equal to
+    /// the given distance.
+    DWithin(f64),
+}
+
+impl SpatialJoinPredicate {
+    fn evaluate(&self, left: &Geometry, right: &Geometry) -> bool {
+        match self {
+            Self::Intersects => left.intersects(right),
+            Self::Contains => left.contains(right),
+            Self::Within => right.contains(left),
+            Self::DWithin(distance) => Euclidean.distance(left, right) <= *distance,
+        }
+    }
+
+    /// Evaluate the predicate between a geometry of the indexed side and a probe geometry.
+    fn evaluate_indexed(
+        &self,
+        indexed: &IndexedGeometry,
+        probe: &Geometry,
+        indexed_is_left: bool,
+    ) -> bool {
+        match indexed {
+            // Points are tested directly against the polygon rings, which is cheaper than
+            // relating them to the prepared graph.
+            IndexedGeometry::Prepared(prepared)
+                if !matches!(probe, Geometry::Point(_) | Geometry::MultiPoint(_)) =>
+            {
+                let matrix = prepared.relate(probe);
+                match (self, indexed_is_left) {
+                    (Self::Contains, true) | (Self::Within, false) => matrix.is_contains(),
+                    (Self::Contains, false) | (Self::Within, true) => matrix.is_within(),
+                    (Self::Intersects, _) => matrix.is_intersects(),
+                    (Self::DWithin(distance), _) => {
+                        Euclidean.distance(prepared.geometry(), probe) <= *distance
+                    }
+                }
+            }
+            _ if indexed_is_left => self.evaluate(indexed.geometry(), probe),
+            _ => self.evaluate(probe, indexed.geometry()),
+        }
+    }
+
+    /// Whether the predicate is computed from the DE-9IM matrix, so that preparing the indexed
+    /// geometries saves rebuilding their topology graph for every candidate pair.
+    fn uses_relate(&self) -> bool {
+        matches!(self, Self::Contains | Self::Within)
+    }
+
+    /// The distance by which to expand bounding boxes when searching the index.
+    fn search_distance(&self) -> f64 {
+        match self {
+            Self::DWithin(distance) => *distance,
+            _ => 0.0,
+        }
+    }
+}
+
+/// Join two GeoArrow arrays on a spatial predicate.
+///
+/// Returns the indices of every pair of rows for which `predicate(left, right)` holds, as
+/// `(left_indices, right_indices)`, sorted by left index and then right index. Null and empty
+/// geometries never match.
+///
+/// Candidate pairs are found with a bounding box index over the smaller of the two arrays and
+/// then refined with the exact predicate. For [`Contains`][SpatialJoinPredicate::Contains] and
+/// [`Within`][SpatialJoinPredicate::Within], the non-point geometries of the indexed array are
+/// prepared once and reused for every candidate pair.
+pub fn spatial_join(
+    left_array: &dyn GeoArrowArray,
+    right_array: &dyn GeoArrowArray,
+    predicate: SpatialJoinPredicate,
+) -> GeoArrowResult<(UInt64Array, UInt64Array)> {
+    if let SpatialJoinPredicate::DWithin(distance) = predicate
+        && (distance.is_nan() || distance < 0.0)
+    {
+        return Err(GeoArrowError::InvalidGeoArrow(format!(
+            "DWithin distance must be non-negative, got {distance}"
+        )));
+    }
+
+    // Index the smaller side and probe it with each row of the larger side
+    let swapped = left_array.len() < right_array.len();
+    let (indexed, probe) = if swapped {
+        (left_array, right_array)
+    } else {
+        (right_array, left_array)
+    };
+
+    let tree = RTree::try_new(indexed)?;
+    let indexed_geoms = downcast_geoarrow_array!(indexed, _to_geo_impl)?
+        .into_iter()
+        .map(|geom| geom.map(|geom| IndexedGeometry::new(geom, predicate)))
+        .collect::<Vec<_>>();
+    let mut pairs = downcast_geoarrow_array!(
+        probe,
+        _probe_impl,
+        &tree,
+        &indexed_geoms,
+        predicate,
+        swapped
+    )?;
+    pairs.sort_unstable();
+
+    let (left_indices, right_indices): (Vec<u64>, Vec<u64>) = pairs.into_iter().unzip();
+    Ok((
+        UInt64Array::from(left_indices),
+        UInt64Array::from(right_indices),
+    ))
+}
+
+/// Join two record batches on a spatial predicate between their geometry columns.
+///
+/// The output contains every column of `left` followed by every column of `right`, with one
+/// row per matching pair as returned by [`spatial_join`]. Right columns whose name is already
+/// used by a left column are suffixed with `_right`, or with `_right_1`, `_right_2` and so on if
+/// that name is taken as well.
+pub fn spatial_join_batches(
+    left: &RecordBatch,
+    left_geometry_column: usize,
+    right: &RecordBatch,
+    right_geometry_column: usize,
+    predicate: SpatialJoinPredicate,
+) -> GeoArrowResult<RecordBatch> {
+    let left_geometry = from_arrow_array(
+        left.column(left_geometry_column),
+        left.schema_ref().field(left_geometry_column),
+    )?;
+    let right_geometry = from_arrow_array(
+        right.column(right_geometry_column),
+        right.schema_ref().field(right_geometry_column),
+    )?;
+    let (left_indices, right_indices) =
+        spatial_join(left_geometry.as_ref(), right_geometry.as_ref(), predicate)?;
+
+    let left_schema = left.schema();
+    let mut fields = left_schema.fields().iter().cloned().collect::<Vec<_>>();
+    let mut used_names = left_schema
+        .fields()
+        .iter()
+        .chain(right.schema_ref().fields())
+        .map(|field| field.name().clone())
+        .collect::<HashSet<_>>();
+    for field in right.schema_ref().fields() {
+        if left_schema.field_with_name(field.name()).is_ok() {
+            let mut name = format!("{}_right", field.name());
+            let mut counter = 0;
+            while used_names.contains(&name) {
+                counter += 1;
+                name = format!("{}_right_{counter}", field.name());
+            }
+            used_names.insert(name.clone());
+            fields.push(Arc::new(field.as_ref().clone().with_name(name)));
+        } else {
+            fields.push(field.clone());
+        }
+    }
+
+    let mut columns = Vec::with_capacity(fields.len());
+    for column in left.columns() {
+        columns.push(arrow_select::take::take(column, &left_indices, None)?);
+    }
+    for column in right.columns() {
+        columns.push(arrow_select::take::take(column, &right_indices, None)?);
+    }
+
+    let schema = Schema::new_with_metadata(fields, left_schema.metadata().clone());
+    Ok(RecordBatch::try_new(Arc::new(schema), columns)?)
+}
+
+fn _to_geo_impl<'a>(
+    array: &'a impl GeoArrowArrayAccessor<'a>,
+) -> GeoArrowResult<Vec<Option<Geometry>>> {
+    array
+        .iter()
+        .map(|item| match item {
+            Some(geom) => {
+                let geom = geom?;
+                let mut rect = BoundingRect::new();
+                rect.add_geometry(&geom);
+                if rect.is_empty() {
+                    Ok(None)
+                } else {
+                    Ok(Some(geometry_to_geo(&geom)?))
+                }
+            }
+            None => Ok(None),
+        })
+        .collect()
+}
+
+/// A geometry of the indexed side of the join.
+enum IndexedGeometry {
+    Plain(Geometry),
+    /// A non-point geometry, prepared when the predicate is computed with [`Relate`].
+    Prepared(PreparedGeometry<'static, Geometry>),
+}
+
+impl IndexedGeometry {
+    fn new(geom: Geometry, predicate: SpatialJoinPredicate) -> Self {
+        if predicate.uses_relate() && !matches!(geom, Geometry::Point(_) | Geometry::MultiPoint(_))
+        {
+            Self::Prepared(PreparedGeometry::from(geom))
+        } else {
+            Self::Plain(geom)
+        }
+    }
+
+    fn geometry(&self) -> &Geometry {
+        match self {
+            Self::Plain(geom) => geom,
+            Self::Prepared(prepared) => prepared.geometry(),
+        }
+    }
+}
+
+fn _probe_impl<'a>(
+    probe: &'a impl GeoArrowArrayAccessor<'a>,
+    tree: &RTree,
+    indexed_geoms: &[Option<IndexedGeometry>],
+    predicate: SpatialJoinPredicate,
+    swapped: bool,
+) -> GeoArrowResult<Vec<(u64, u64)>> {
+    let distance = predicate.search_distance();
+    let mut pairs = vec![];
+
+    for (probe_idx, item) in probe.iter().enumerate() {
+        let Some(geom) = item else {
+            continue;
+        };
+        let geom = geom?;
+        let mut rect = BoundingRect::new();
+        rect.add_geometry(&geom);
+        if rect.is_empty() {
+            continue;
+        }
+
+        let candidates = tree.search(
+            rect.minx() - distance,
+            rect.miny() - distance,
+            rect.maxx() + distance,
+            rect.maxy() + distance,
+        );
+        if candidates.is_empty() {
+            continue;
+        }
+
+        let probe_geom = geometry_to_geo(&geom)?;
+        for indexed_idx in candidates {
+            let Some(indexed_geom) = &indexed_geoms[indexed_idx as usize] else {
+                continue;
+            };
+            if predicate.evaluate_indexed(indexed_geom, &probe_geom, swapped) {
+                let (left_idx, right_idx) = if swapped {
+                    (indexed_idx as u64, probe_idx as u64)
+                } else {
+                    (probe_idx as u64, indexed_idx as u64)
+                };
+                pairs.push((left_idx, right_idx));
+            }
+        }
+    }
+
+    Ok(pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::{Array, Int32Array, StringArray};
+    use arrow_schema::{DataType, Field};
+    use geo::{point, polygon};
+    use geoarrow_array::builder::{PointBuilder, PolygonBuilder};
+    use geoarrow_schema::{Dimension, PointType, PolygonType};
+
+    use super::*;
+
+    fn points() -> geoarrow_array::array::PointArray {
+        let points = [
+            Some(point!(x: 0.5, y: 0.5)),
+            Some(point!(x: 1.5, y: 0.5)),
+            None,
+            Some(point!(x: 5.0, y: 5.0)),
+            Some(point!(x: 0.5, y: 1.5)),
+        ];
+        let typ = PointType::new(Dimension::XY, Default::default());
+        PointBuilder::from_nullable_points(points.iter().map(Option::as_ref), typ).finish()
+    }
+
+    fn polygons() -> geoarrow_array::array::PolygonArray {
+        let polygons = [
+            polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 1.)],
+            polygon![(x: 1., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 1., y: 2.)],
+        ];
+        let typ = PolygonType::new(Dimension::XY, Default::default());
+        PolygonBuilder::from_polygons(&polygons, typ).finish()
+    }
+
+    fn pairs(result: (UInt64Array, UInt64Array)) -> Vec<(u64, u64)> {
+        result
+            .0
+            .values()
+            .iter()
+            .copied()
+            .zip(result.1.values().iter().copied())
+            .collect()
+    }
+
+    #[test]
+    fn point_in_polygon() {
+        let points = points();
+        let polygons = polygons();
+
+        let result = spatial_join(&points, &polygons, SpatialJoinPredicate::Within).unwrap();
+        assert_eq!(pairs(result), vec![(0, 0), (1, 1)]);
+
+        let result = spatial_join(&polygons, &points, SpatialJoinPredicate::Contains).unwrap();
+        assert_eq!(pairs(result), vec![(0, 0), (1, 1)]);
+
+        let result = spatial_join(&points, &polygons, SpatialJoinPredicate::Intersects).unwrap();
+        assert_eq!(pairs(result), vec![(0, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn polygon_in_polygon() {
+        let inner = [
+            polygon![(x: 0.25, y: 0.25), (x: 0.75, y: 0.25), (x: 0.75, y: 0.75)],
+            polygon![(x: 0.5, y: 0.5), (x: 1.5, y: 0.5), (x: 1.5, y: 1.5)],
+            polygon![(x: 1.25, y: 0.25), (x: 1.75, y: 0.25), (x: 1.75, y: 1.75)],
+        ];
+        let typ = PolygonType::new(Dimension::XY, Default::default());
+        let inner = PolygonBuilder::from_polygons(&inner, typ).finish();
+        let polygons = polygons();
+
+        // Index each side in turn, so that both the prepared left and right geometries are used
+        let result = spatial_join(&inner, &polygons, SpatialJoinPredicate::Within).unwrap();
+        assert_eq!(pairs(result), vec![(0, 0), (2, 1)]);
+        let result = spatial_join(&polygons, &inner, SpatialJoinPredicate::Contains).unwrap();
+        assert_eq!(pairs(result), vec![(0, 0), (1, 2)]);
+        let result = spatial_join(&inner, &polygons, SpatialJoinPredicate::Intersects).unwrap();
+        assert_eq!(pairs(result), vec![(0, 0), (1, 0), (1, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn dwithin() {
+        let points = points();
+        let polygons = polygons();
+
+        let result = spatial_join(&points, &polygons, SpatialJoinPredicate::DWithin(0.5)).unwrap();
+        assert_eq!(
+            pairs(result),
+            vec![(0, 0), (0, 1), (1, 0), (1, 1), (4, 0), (4, 1)]
+        );
+
+        assert!(spatial_join(&points, &polygons, SpatialJoinPredicate::DWithin(-1.0)).is_err());
+    }
+
+    #[test]
+    fn join_batches() {
+        let points = points();
+        let polygons = polygons();
+
+        let left_schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            points.data_type().to_field("geometry", true),
+        ]);
+        let left = RecordBatch::try_new(
+            Arc::new(left_schema),
+            vec![
+                Arc::new(Int32Array::from(vec![10, 11, 12, 13, 14])),
+                points.to_array_ref(),
+            ],
+        )
+        .unwrap();
+
+        let right_schema = Schema::new(vec![
+            Field::new("name", DataType::Utf8, false),
+            polygons.data_type().to_field("geometry", true),
+        ]);
+        let right = RecordBatch::try_new(
+            Arc::new(right_schema),
+            vec![
+                Arc::new(StringArray::from(vec!["a", "b"])),
+                polygons.to_array_ref(),
+            ],
+        )
+        .unwrap();
+
+        let joined =
+            spatial_join_batches(&left, 1, &right, 1, SpatialJoinPredicate::Within).unwrap();
+        assert_eq!(joined.num_rows(), 2);
+        let names = joined
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["id", "geometry", "name", "geometry_right"]);
+
+        let ids = joined
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(ids.values(), &[10, 11]);
+        let names = joined
+            .column(2)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(names.value(0), "a");
+        assert_eq!(names.value(1), "b");
+    }
+
+    #[test]
+    fn join_batches_name_clash() {
+        let points = points();
+        let polygons = polygons();
+
+        let left_schema = Schema::new(vec![
+            points.data_type().to_field("geometry", true),
+            Field::new("geometry_right", DataType::Int32, false),
+        ]);
+        let left = RecordBatch::try_new(
+            Arc::new(left_schema),
+            vec![
+                points.to_array_ref(),
+                Arc::new(Int32Array::from(vec![10, 11, 12, 13, 14])),
+            ],
+        )
+        .unwrap();
+
+        let right_schema = Schema::new(vec![
+            polygons.data_type().to_field("geometry", true),
+            Field::new("geometry_right_1", DataType::Utf8, false),
+        ]);
+        let right = RecordBatch::try_new(
+            Arc::new(right_schema),
+            vec![
+                polygons.to_array_ref(),
+                Arc::new(StringArray::from(vec!["a", "b"])),
+            ],
+        )
+        .unwrap();
+
+        let joined =
+            spatial_join_batches(&left, 0, &right, 0, SpatialJoinPredicate::Within).unwrap();
+        let names = joined
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "geometry",
+                "geometry_right",
+                "geometry_right_2",
+                "geometry_right_1"
+            ]
+        );
+    }
+}
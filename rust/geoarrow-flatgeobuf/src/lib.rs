@@ -11,4 +11,5 @@
 )]
 
 pub mod reader;
+mod spill;
 pub mod writer;
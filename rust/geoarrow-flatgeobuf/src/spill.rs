@@ -0,0 +1,578 @@
+//! Writing FlatGeobuf files with bounded memory.
+//!
+//! [`FgbWriter`][flatgeobuf::FgbWriter] keeps an index entry for every feature in memory, to build
+//! the packed R-tree when the file is finished. Instead, [`SpillWriter`] spills serialized
+//! features to a temporary file as they are written. On finish, the features are sorted by the
+//! Hilbert value of the center of their bounding box with an external merge sort, and the header,
+//! index and features are streamed to the output. Sorted runs are merged in as many passes as
+//! needed to keep the read buffers of the runs being merged within the memory budget.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+
+use flatbuffers::FlatBufferBuilder;
+use flatgeobuf::{Column, ColumnArgs, Crs, CrsArgs, Header, HeaderArgs};
+use geoarrow_array::bounds::BoundingRect;
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+
+/// The number of children of each node of the packed R-tree.
+const NODE_SIZE: u64 = 16;
+/// The size of a serialized R-tree node: a bounding box and an offset.
+const NODE_LEN: usize = 40;
+const MAGIC_LEN: usize = 8;
+const HILBERT_MAX: f64 = ((1 << 16) - 1) as f64;
+/// The size of the read buffer of each run in a merge.
+const MERGE_BUFFER_LEN: usize = 8 * 1024;
+
+/// A feature along with its bounding box.
+#[derive(Debug)]
+struct Record {
+    /// `[minx, miny, maxx, maxy]`, with infinite bounds for an empty geometry.
+    bounds: [f64; 4],
+    /// The size-prefixed feature flatbuffer.
+    feature: Vec<u8>,
+}
+
+/// A FlatGeobuf writer that spills features to temporary files.
+pub(crate) struct SpillWriter {
+    memory_budget: usize,
+    temp_dir: Option<PathBuf>,
+    write_index: bool,
+    /// A FlatGeobuf file without features, whose header is copied into the output.
+    template: Vec<u8>,
+    /// The features in the order they were written.
+    features: BufWriter<File>,
+    num_features: u64,
+    extent: BoundingRect,
+}
+
+impl SpillWriter {
+    /// Create a new writer.
+    ///
+    /// `template` is a FlatGeobuf file without features, written with the same header options as
+    /// the files passed to [`SpillWriter::push_file`].
+    pub(crate) fn try_new(
+        template: Vec<u8>,
+        memory_budget: usize,
+        temp_dir: Option<PathBuf>,
+        write_index: bool,
+    ) -> GeoArrowResult<Self> {
+        let features = BufWriter::new(temp_file(temp_dir.as_ref())?);
+        Ok(Self {
+            memory_budget,
+            temp_dir,
+            write_index,
+            template,
+            features,
+            num_features: 0,
+            extent: BoundingRect::new(),
+        })
+    }
+
+    /// Spill the features of a FlatGeobuf file written without an index, given the bounds of each
+    /// of its features.
+    pub(crate) fn push_file(&mut self, file: &[u8], bounds: &[BoundingRect]) -> GeoArrowResult<()> {
+        let mut offset = MAGIC_LEN + size_prefixed_len(file, MAGIC_LEN)?;
+        for feature_bounds in bounds {
+            let len = size_prefixed_len(file, offset)?;
+            let bounds = [
+                feature_bounds.minx(),
+                feature_bounds.miny(),
+                feature_bounds.maxx(),
+                feature_bounds.maxy(),
+            ];
+            write_bounds(&mut self.features, &bounds)?;
+            self.features.write_all(&file[offset..offset + len])?;
+            if !feature_bounds.is_empty() {
+                self.extent.update(feature_bounds);
+            }
+            offset += len;
+        }
+        if offset != file.len() {
+            return Err(GeoArrowError::FlatGeobuf(
+                "Expected one FlatGeobuf feature for each row".to_string(),
+            ));
+        }
+        self.num_features += bounds.len() as u64;
+        Ok(())
+    }
+
+    /// Sort the spilled features and write the FlatGeobuf file to `out`.
+    pub(crate) fn finish<W: Write>(mut self, out: W) -> GeoArrowResult<W> {
+        let mut out = BufWriter::new(out);
+        self.features.flush()?;
+        let mut features = self.features.get_ref();
+        features.seek(SeekFrom::Start(0))?;
+
+        let write_index = self.write_index && self.num_features > 0;
+        out.write_all(&self.template[..MAGIC_LEN])?;
+        out.write_all(&self.header(write_index)?)?;
+
+        if !write_index {
+            let mut reader = BufReader::new(features);
+            while let Some(record) = read_record(&mut reader)? {
+                out.write_all(&record.feature)?;
+            }
+            return Ok(out.into_inner().map_err(|err| err.into_error())?);
+        }
+
+        let runs = self.merge_runs(self.sorted_runs(features)?)?;
+
+        // The leaves of the index point to the byte offset of their feature, so the features are
+        // merged once to build the index, and again to write them after it.
+        let mut leaves = BufWriter::new(temp_file(self.temp_dir.as_ref())?);
+        let mut merge = Merge::try_new(&runs, &self.extent)?;
+        let mut offset = 0;
+        while let Some(record) = merge.next_record()? {
+            write_node(&mut leaves, &record.bounds, offset)?;
+            offset += record.feature.len() as u64;
+        }
+        let leaves = leaves.into_inner().map_err(|err| err.into_error())?;
+
+        for level in self.build_index(leaves)?.iter().rev() {
+            let mut level = level;
+            level.seek(SeekFrom::Start(0))?;
+            io::copy(&mut level, &mut out)?;
+        }
+
+        let mut merge = Merge::try_new(&runs, &self.extent)?;
+        while let Some(record) = merge.next_record()? {
+            out.write_all(&record.feature)?;
+        }
+        Ok(out.into_inner().map_err(|err| err.into_error())?)
+    }
+
+    /// The size-prefixed output header, copied from the template with the feature count, extent
+    /// and index node size of the spilled features.
+    fn header(&self, write_index: bool) -> GeoArrowResult<Vec<u8>> {
+        let template = flatgeobuf::size_prefixed_root_as_header(&self.template[MAGIC_LEN..])
+            .map_err(|err| GeoArrowError::External(Box::new(err)))?;
+
+        let mut fbb = FlatBufferBuilder::new();
+        let mut columns = Vec::new();
+        for column in template.columns().into_iter().flatten() {
+            let args = ColumnArgs {
+                name: Some(fbb.create_string(column.name())),
+                type_: column.type_(),
+                title: column.title().map(|title| fbb.create_string(title)),
+                description: column
+                    .description()
+                    .map(|description| fbb.create_string(description)),
+                width: column.width(),
+                precision: column.precision(),
+                scale: column.scale(),
+                nullable: column.nullable(),
+                unique: column.unique(),
+                primary_key: column.primary_key(),
+                metadata: column
+                    .metadata()
+                    .map(|metadata| fbb.create_string(metadata)),
+            };
+            columns.push(Column::create(&mut fbb, &args));
+        }
+        let columns = fbb.create_vector(&columns);
+        let crs = template.crs().map(|crs| {
+            let args = CrsArgs {
+                org: crs.org().map(|org| fbb.create_string(org)),
+                code: crs.code(),
+                name: crs.name().map(|name| fbb.create_string(name)),
+                description: crs
+                    .description()
+                    .map(|description| fbb.create_string(description)),
+                wkt: crs.wkt().map(|wkt| fbb.create_string(wkt)),
+                code_string: crs
+                    .code_string()
+                    .map(|code_string| fbb.create_string(code_string)),
+            };
+            Crs::create(&mut fbb, &args)
+        });
+        let envelope = (!self.extent.is_empty()).then(|| {
+            fbb.create_vector(&[
+                self.extent.minx(),
+                self.extent.miny(),
+                self.extent.maxx(),
+                self.extent.maxy(),
+            ])
+        });
+        let args = HeaderArgs {
+            name: template.name().map(|name| fbb.create_string(name)),
+            envelope,
+            geometry_type: template.geometry_type(),
+            has_z: template.has_z(),
+            has_m: template.has_m(),
+            has_t: template.has_t(),
+            has_tm: template.has_tm(),
+            columns: Some(columns),
+            features_count: self.num_features,
+            index_node_size: if write_index { NODE_SIZE as u16 } else { 0 },
+            crs,
+            title: template.title().map(|title| fbb.create_string(title)),
+            description: template
+                .description()
+                .map(|description| fbb.create_string(description)),
+            metadata: template
+                .metadata()
+                .map(|metadata| fbb.create_string(metadata)),
+        };
+        let header = Header::create(&mut fbb, &args);
+        fbb.finish_size_prefixed(header, None);
+        Ok(fbb.finished_data().to_vec())
+    }
+
+    /// Split the spilled features into runs that fit the memory budget, each sorted by Hilbert
+    /// value.
+    fn sorted_runs(&self, features: &File) -> GeoArrowResult<Vec<File>> {
+        let mut runs = Vec::new();
+        let mut reader = BufReader::new(features);
+        let mut buffer = Vec::new();
+        let mut buffered_len = 0;
+        while let Some(record) = read_record(&mut reader)? {
+            buffered_len += size_of::<Record>() + record.feature.len();
+            buffer.push(record);
+            if buffered_len >= self.memory_budget {
+                runs.push(self.write_run(&mut buffer)?);
+                buffered_len = 0;
+            }
+        }
+        if !buffer.is_empty() {
+            runs.push(self.write_run(&mut buffer)?);
+        }
+        Ok(runs)
+    }
+
+    fn write_run(&self, buffer: &mut Vec<Record>) -> GeoArrowResult<File> {
+        buffer.sort_by_cached_key(|record| hilbert_value(&record.bounds, &self.extent));
+        let mut run = BufWriter::new(temp_file(self.temp_dir.as_ref())?);
+        for record in buffer.drain(..) {
+            write_record(&mut run, &record)?;
+        }
+        Ok(run.into_inner().map_err(|err| err.into_error())?)
+    }
+
+    /// The number of runs merged at once: as many as have read buffers that fit the memory
+    /// budget, and at least two.
+    fn fan_in(&self) -> usize {
+        (self.memory_budget / MERGE_BUFFER_LEN).max(2)
+    }
+
+    /// Merge consecutive groups of runs into longer runs until at most [`Self::fan_in`] remain,
+    /// which the final merges read at once.
+    fn merge_runs(&self, mut runs: Vec<File>) -> GeoArrowResult<Vec<File>> {
+        let fan_in = self.fan_in();
+        while runs.len() > fan_in {
+            runs = runs
+                .chunks(fan_in)
+                .map(|group| self.merge_group(group))
+                .collect::<GeoArrowResult<_>>()?;
+        }
+        Ok(runs)
+    }
+
+    fn merge_group(&self, runs: &[File]) -> GeoArrowResult<File> {
+        if let [run] = runs {
+            return Ok(run.try_clone()?);
+        }
+        let mut merge = Merge::try_new(runs, &self.extent)?;
+        let mut merged = BufWriter::new(temp_file(self.temp_dir.as_ref())?);
+        while let Some(record) = merge.next_record()? {
+            write_record(&mut merged, &record)?;
+        }
+        Ok(merged.into_inner().map_err(|err| err.into_error())?)
+    }
+
+    /// Build the levels of the packed R-tree above the leaves, returning every level from the
+    /// leaves up to the root.
+    ///
+    /// A parent node covers up to [`NODE_SIZE`] consecutive nodes of the level below, and its
+    /// offset is the position of its first child in the index, which stores the root level first.
+    fn build_index(&self, leaves: File) -> GeoArrowResult<Vec<File>> {
+        let mut level_lens = vec![self.num_features];
+        loop {
+            let len = level_lens.last().unwrap().div_ceil(NODE_SIZE);
+            level_lens.push(len);
+            if len == 1 {
+                break;
+            }
+        }
+
+        let mut levels = vec![leaves];
+        for (level, &len) in level_lens[..level_lens.len() - 1].iter().enumerate() {
+            let level_start: u64 = level_lens[level + 1..].iter().sum();
+            let parents = {
+                let mut children = &levels[level];
+                children.seek(SeekFrom::Start(0))?;
+                let mut children = BufReader::new(children);
+                let mut parents = BufWriter::new(temp_file(self.temp_dir.as_ref())?);
+
+                let mut child = 0;
+                while child < len {
+                    let first_child = child;
+                    // Start from the infinite bounds of an empty node, which the infinite bounds
+                    // of the leaves of empty geometries don't change.
+                    let mut bounds = [f64::INFINITY, f64::INFINITY, -f64::INFINITY, -f64::INFINITY];
+                    while child < len && child < first_child + NODE_SIZE {
+                        let [minx, miny, maxx, maxy] = read_node_bounds(&mut children)?;
+                        bounds = [
+                            bounds[0].min(minx),
+                            bounds[1].min(miny),
+                            bounds[2].max(maxx),
+                            bounds[3].max(maxy),
+                        ];
+                        child += 1;
+                    }
+                    write_node(&mut parents, &bounds, level_start + first_child)?;
+                }
+                parents.into_inner().map_err(|err| err.into_error())?
+            };
+            levels.push(parents);
+        }
+        Ok(levels)
+    }
+}
+
+/// A k-way merge of sorted runs.
+struct Merge<'a> {
+    readers: Vec<BufReader<&'a File>>,
+    heads: Vec<Option<Record>>,
+    /// The Hilbert value of the head of each run, with the run index to keep the merge stable.
+    heap: BinaryHeap<Reverse<(u32, usize)>>,
+    extent: &'a BoundingRect,
+}
+
+impl<'a> Merge<'a> {
+    fn try_new(runs: &'a [File], extent: &'a BoundingRect) -> GeoArrowResult<Self> {
+        let mut merge = Self {
+            readers: Vec::with_capacity(runs.len()),
+            heads: Vec::with_capacity(runs.len()),
+            heap: BinaryHeap::with_capacity(runs.len()),
+            extent,
+        };
+        for (run_idx, mut run) in runs.iter().enumerate() {
+            run.seek(SeekFrom::Start(0))?;
+            merge
+                .readers
+                .push(BufReader::with_capacity(MERGE_BUFFER_LEN, run));
+            merge.heads.push(None);
+            merge.advance(run_idx)?;
+        }
+        Ok(merge)
+    }
+
+    fn advance(&mut self, run_idx: usize) -> GeoArrowResult<()> {
+        let record = read_record(&mut self.readers[run_idx])?;
+        if let Some(record) = &record {
+            let hilbert = hilbert_value(&record.bounds, self.extent);
+            self.heap.push(Reverse((hilbert, run_idx)));
+        }
+        self.heads[run_idx] = record;
+        Ok(())
+    }
+
+    fn next_record(&mut self) -> GeoArrowResult<Option<Record>> {
+        let Some(Reverse((_, run_idx))) = self.heap.pop() else {
+            return Ok(None);
+        };
+        let record = self.heads[run_idx].take();
+        self.advance(run_idx)?;
+        Ok(record)
+    }
+}
+
+fn temp_file(temp_dir: Option<&PathBuf>) -> io::Result<File> {
+    match temp_dir {
+        Some(temp_dir) => tempfile::tempfile_in(temp_dir),
+        None => tempfile::tempfile(),
+    }
+}
+
+/// The length, including the 4-byte prefix, of the size-prefixed flatbuffer at `offset`.
+fn size_prefixed_len(file: &[u8], offset: usize) -> GeoArrowResult<usize> {
+    let truncated = || GeoArrowError::FlatGeobuf("Truncated FlatGeobuf buffer".to_string());
+    let prefix = file.get(offset..offset + 4).ok_or_else(truncated)?;
+    let len = u32::from_le_bytes(prefix.try_into().unwrap()) as usize + 4;
+    if offset + len > file.len() {
+        return Err(truncated());
+    }
+    Ok(len)
+}
+
+fn write_bounds(writer: &mut impl Write, bounds: &[f64; 4]) -> io::Result<()> {
+    for value in bounds {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn write_record(writer: &mut impl Write, record: &Record) -> io::Result<()> {
+    write_bounds(writer, &record.bounds)?;
+    writer.write_all(&record.feature)
+}
+
+fn write_node(writer: &mut impl Write, bounds: &[f64; 4], offset: u64) -> io::Result<()> {
+    write_bounds(writer, bounds)?;
+    writer.write_all(&offset.to_le_bytes())
+}
+
+fn read_bounds(reader: &mut impl Read) -> io::Result<[f64; 4]> {
+    let mut buf = [0; 32];
+    reader.read_exact(&mut buf)?;
+    Ok(std::array::from_fn(|i| {
+        f64::from_le_bytes(buf[8 * i..8 * i + 8].try_into().unwrap())
+    }))
+}
+
+fn read_node_bounds(reader: &mut impl Read) -> io::Result<[f64; 4]> {
+    let mut buf = [0; NODE_LEN];
+    reader.read_exact(&mut buf)?;
+    read_bounds(&mut &buf[..32])
+}
+
+/// Read the next record, or `None` at the end of the file.
+fn read_record(reader: &mut impl BufRead) -> io::Result<Option<Record>> {
+    if reader.fill_buf()?.is_empty() {
+        return Ok(None);
+    }
+    let bounds = read_bounds(reader)?;
+    let mut prefix = [0; 4];
+    reader.read_exact(&mut prefix)?;
+    let len = u32::from_le_bytes(prefix) as usize;
+    let mut feature = Vec::with_capacity(len + 4);
+    feature.extend_from_slice(&prefix);
+    feature.resize(len + 4, 0);
+    reader.read_exact(&mut feature[4..])?;
+    Ok(Some(Record { bounds, feature }))
+}
+
+/// The Hilbert value of the center of `bounds`, on a 2^16 by 2^16 grid covering `extent`.
+fn hilbert_value(bounds: &[f64; 4], extent: &BoundingRect) -> u32 {
+    let [minx, miny, maxx, maxy] = *bounds;
+    let width = extent.maxx() - extent.minx();
+    let height = extent.maxy() - extent.miny();
+    // Float to int casts saturate, and map the NaN of empty bounds or a zero-width extent to 0.
+    let x = (HILBERT_MAX * ((minx + maxx) / 2. - extent.minx()) / width).floor() as u32;
+    let y = (HILBERT_MAX * ((miny + maxy) / 2. - extent.miny()) / height).floor() as u32;
+    hilbert(x, y)
+}
+
+/// The position of `(x, y)` along a Hilbert curve.
+///
+/// Based on public domain code at <https://github.com/rawrunprotected/hilbert_curves>, as used
+/// by the FlatGeobuf reference implementations.
+fn hilbert(x: u32, y: u32) -> u32 {
+    let mut a = x ^ y;
+    let mut b = 0xFFFF ^ a;
+    let mut c = 0xFFFF ^ (x | y);
+    let mut d = x & (y ^ 0xFFFF);
+
+    let mut aa = a | (b >> 1);
+    let mut bb = (a >> 1) ^ a;
+    let mut cc = ((c >> 1) ^ (b & (d >> 1))) ^ c;
+    let mut dd = ((a & (c >> 1)) ^ (d >> 1)) ^ d;
+
+    a = aa;
+    b = bb;
+    c = cc;
+    d = dd;
+    aa = (a & (a >> 2)) ^ (b & (b >> 2));
+    bb = (a & (b >> 2)) ^ (b & ((a ^ b) >> 2));
+    cc ^= (a & (c >> 2)) ^ (b & (d >> 2));
+    dd ^= (b & (c >> 2)) ^ ((a ^ b) & (d >> 2));
+
+    a = aa;
+    b = bb;
+    c = cc;
+    d = dd;
+    aa = (a & (a >> 4)) ^ (b & (b >> 4));
+    bb = (a & (b >> 4)) ^ (b & ((a ^ b) >> 4));
+    cc ^= (a & (c >> 4)) ^ (b & (d >> 4));
+    dd ^= (b & (c >> 4)) ^ ((a ^ b) & (d >> 4));
+
+    a = aa;
+    b = bb;
+    c = cc;
+    d = dd;
+    cc ^= (a & (c >> 8)) ^ (b & (d >> 8));
+    dd ^= (b & (c >> 8)) ^ ((a ^ b) & (d >> 8));
+
+    a = cc ^ (cc >> 1);
+    b = dd ^ (dd >> 1);
+
+    let mut i0 = x ^ y;
+    let mut i1 = b | (0xFFFF ^ (i0 | a));
+
+    i0 = (i0 | (i0 << 8)) & 0x00FF00FF;
+    i0 = (i0 | (i0 << 4)) & 0x0F0F0F0F;
+    i0 = (i0 | (i0 << 2)) & 0x33333333;
+    i0 = (i0 | (i0 << 1)) & 0x55555555;
+
+    i1 = (i1 | (i1 << 8)) & 0x00FF00FF;
+    i1 = (i1 | (i1 << 4)) & 0x0F0F0F0F;
+    i1 = (i1 | (i1 << 2)) & 0x33333333;
+    i1 = (i1 | (i1 << 1)) & 0x55555555;
+
+    (i1 << 1) | i0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hilbert_curve() {
+        // The curve fills the 4x4 cells in the corner of the grid before moving on.
+        let mut values = (0..4)
+            .flat_map(|x| (0..4).map(move |y| hilbert(x, y)))
+            .collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, (0..16).collect::<Vec<_>>());
+        assert_eq!(hilbert(0xFFFF, 0), u32::MAX);
+    }
+
+    #[test]
+    fn multi_pass_merge() {
+        // A budget for the read buffers of two runs, so that ten runs take several passes.
+        let mut spill = SpillWriter::try_new(Vec::new(), 2 * MERGE_BUFFER_LEN, None, true).unwrap();
+        spill.extent.add_coord(&(0., 0.));
+        spill.extent.add_coord(&(100., 100.));
+        let runs = (0..10)
+            .map(|run| {
+                let mut buffer = (0..10)
+                    .map(|i| {
+                        let x = (i * 10 + run) as f64;
+                        Record {
+                            bounds: [x, x, x, x],
+                            feature: vec![1, 0, 0, 0, run as u8],
+                        }
+                    })
+                    .collect();
+                spill.write_run(&mut buffer).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let runs = spill.merge_runs(runs).unwrap();
+        assert_eq!(runs.len(), 2);
+        let mut merge = Merge::try_new(&runs, &spill.extent).unwrap();
+        let mut hilbert_values = Vec::new();
+        while let Some(record) = merge.next_record().unwrap() {
+            hilbert_values.push(hilbert_value(&record.bounds, &spill.extent));
+        }
+        assert_eq!(hilbert_values.len(), 100);
+        assert!(hilbert_values.is_sorted());
+    }
+
+    #[test]
+    fn record_roundtrip() {
+        let mut buf = Vec::new();
+        write_bounds(&mut buf, &[1., 2., 3., 4.]).unwrap();
+        buf.extend_from_slice(&[3, 0, 0, 0, 7, 8, 9]);
+
+        let mut reader = BufReader::new(buf.as_slice());
+        let record = read_record(&mut reader).unwrap().unwrap();
+        assert_eq!(record.bounds, [1., 2., 3., 4.]);
+        assert_eq!(record.feature, [3, 0, 0, 0, 7, 8, 9]);
+        assert!(read_record(&mut reader).unwrap().is_none());
+    }
+}
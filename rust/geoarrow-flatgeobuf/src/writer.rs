@@ -1,15 +1,27 @@
 //! Write to [FlatGeobuf](https://flatgeobuf.org/) files.
 
 use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
 
-use arrow_array::RecordBatch;
-use arrow_schema::{DataType, Schema, SchemaRef};
+use arrow_array::{ArrayRef, RecordBatch, RecordBatchReader};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
 use flatgeobuf::{ColumnType, FgbCrs, FgbWriter, FgbWriterOptions};
+use geo_traits::{Dimensions, GeometryTrait};
+use geoarrow_array::array::from_arrow_array;
+use geoarrow_array::bounds::BoundingRect;
+use geoarrow_array::builder::{MultiLineStringBuilder, MultiPointBuilder, MultiPolygonBuilder};
+use geoarrow_array::cast::to_wkb;
 use geoarrow_array::geozero::export::{GeozeroRecordBatchReader, GeozeroRecordBatchWriter};
+use geoarrow_array::snap::{SnapOptions, snap_to_grid};
+use geoarrow_array::{GeoArrowArray, GeoArrowArrayAccessor, downcast_geoarrow_array};
 use geoarrow_schema::crs::{CrsTransform, DefaultCrsTransform};
 use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
-use geoarrow_schema::{Dimension, GeoArrowType, Metadata};
-use geozero::GeozeroDatasource;
+use geoarrow_schema::{
+    Dimension, GeoArrowType, Metadata, MultiLineStringType, MultiPointType, MultiPolygonType,
+};
+
+use crate::spill::SpillWriter;
 
 /// Options for the FlatGeobuf writer
 #[derive(Debug)]
@@ -22,6 +34,10 @@ pub struct FlatGeobufWriterOptions {
     description: Option<String>,
     metadata: Option<String>,
     crs_transform: Option<Box<dyn CrsTransform>>,
+    snap: Option<SnapOptions>,
+    geometry_column: Option<String>,
+    memory_budget: Option<usize>,
+    temp_dir: Option<PathBuf>,
 }
 
 impl FlatGeobufWriterOptions {
@@ -36,6 +52,10 @@ impl FlatGeobufWriterOptions {
             title: None,
             description: None,
             metadata: None,
+            snap: None,
+            geometry_column: None,
+            memory_budget: None,
+            temp_dir: None,
         }
     }
 
@@ -48,6 +68,10 @@ impl FlatGeobufWriterOptions {
     }
 
     /// Set whether to detect geometry type when `geometry_type` is Unknown.
+    ///
+    /// For WKB, WKT and mixed geometry columns, the geometry type is detected by scanning all of
+    /// the batches that are written. With a [memory budget][Self::with_memory_budget] the
+    /// geometry type isn't detected for these columns, and is Unknown.
     pub fn with_detect_type(self, detect_type: bool) -> Self {
         Self {
             detect_type,
@@ -99,6 +123,52 @@ impl FlatGeobufWriterOptions {
             ..self
         }
     }
+
+    /// Snap the coordinates of the geometry column to a grid before writing.
+    pub fn with_snap_to_grid(self, snap: SnapOptions) -> Self {
+        Self {
+            snap: Some(snap),
+            ..self
+        }
+    }
+
+    /// Set the name of the geometry column to write as the FlatGeobuf geometry.
+    ///
+    /// This is required when the schema has more than one geometry column. Any other geometry
+    /// columns are written as WKB in binary properties.
+    pub fn with_geometry_column(self, geometry_column: String) -> Self {
+        Self {
+            geometry_column: Some(geometry_column),
+            ..self
+        }
+    }
+
+    /// Spill features to temporary files while writing, keeping roughly at most `memory_budget`
+    /// bytes of features in memory, besides the batch being written.
+    ///
+    /// By default, all features are buffered until the file is finished, to build the spatial
+    /// index. With a memory budget, features are instead sorted for the index out of core, so that
+    /// datasets much larger than memory can be written.
+    ///
+    /// The budget bounds the spilled features read back into memory to be sorted. It does not
+    /// include the batch passed to [`FlatGeobufWriter::write`], nor the in-memory FlatGeobuf
+    /// copy of that batch that is encoded before its features are spilled, so writing also needs
+    /// memory for about twice the largest batch. Write smaller batches to lower the peak.
+    pub fn with_memory_budget(self, memory_budget: usize) -> Self {
+        Self {
+            memory_budget: Some(memory_budget),
+            ..self
+        }
+    }
+
+    /// Set the directory for the temporary files used with a
+    /// [memory budget][Self::with_memory_budget], instead of the system's temporary directory.
+    pub fn with_temp_dir(self, temp_dir: PathBuf) -> Self {
+        Self {
+            temp_dir: Some(temp_dir),
+            ..self
+        }
+    }
 }
 
 impl FlatGeobufWriterOptions {
@@ -118,30 +188,23 @@ impl FlatGeobufWriterOptions {
     /// Create [FgbWriterOptions]
     fn create_fgb_options<'a>(
         &'a self,
-        geo_data_type: GeoArrowType,
+        header: &HeaderType,
+        write_index: bool,
+        detect_type: bool,
         wkt_crs: Option<&'a str>,
     ) -> FgbWriterOptions<'a> {
-        let (has_z, has_m) = match geo_data_type.dimension() {
-            Some(Dimension::XY) => (false, false),
-            Some(Dimension::XYZ) => (true, false),
-            Some(Dimension::XYM) => (false, true),
-            Some(Dimension::XYZM) => (true, true),
-            // TODO: not sure how to handle geometry arrays
-            // Here, we declare them as not having z or m dimensions.
-            None => (false, false),
-        };
         let crs = FgbCrs {
             wkt: wkt_crs,
             ..Default::default()
         };
 
         FgbWriterOptions {
-            write_index: self.write_index,
-            detect_type: self.detect_type,
+            write_index,
+            detect_type,
             promote_to_multi: self.promote_to_multi,
             crs,
-            has_z,
-            has_m,
+            has_z: header.has_z,
+            has_m: header.has_m,
             has_t: false,
             has_tm: false,
             title: self.title.as_deref(),
@@ -157,61 +220,153 @@ impl FlatGeobufWriterOptions {
 /// **pull-based** iteration. The `stream` parameter of `write_flatgeobuf` only allows for
 /// `write_flatgeobuf` to pull data; whereas some environments may find it easier to use a
 /// push-based writer.
+///
+/// The geometry column may be a native, WKB or WKT column. For WKB, WKT and mixed geometry
+/// columns, the type declares neither a geometry type nor coordinate dimensions, so batches are
+/// held in memory until [`FlatGeobufWriter::finish`] and the header is detected from all of their
+/// geometries. With a memory budget, features are written before all geometries are seen, so the
+/// header's geometry type is Unknown and its dimensions are detected from the first batch, which
+/// later batches must fit.
+///
+/// By default the whole dataset is buffered until [`FlatGeobufWriter::finish`]; use
+/// [`FlatGeobufWriterOptions::with_memory_budget`] to bound memory use instead.
 pub struct FlatGeobufWriter<'a, W: Write> {
     file: W,
-    geozero_writer: GeozeroRecordBatchWriter<FgbWriter<'a>>,
+    schema: SchemaRef,
+    /// The schema passed to geozero, with any other geometry columns replaced by binary columns.
+    output_schema: SchemaRef,
+    geom_col_idx: usize,
+    geo_data_type: GeoArrowType,
+    options: FlatGeobufWriterOptions,
+    /// Set on the first write or, when it's detected from all geometries, on finish.
+    header: Option<HeaderType>,
+    /// The columns and geometries of the batches of a WKB, WKT or mixed geometry column, held
+    /// until [`FlatGeobufWriter::finish`] so that the header can be detected from all of them.
+    pending: Vec<(Vec<ArrayRef>, Arc<dyn GeoArrowArray>)>,
+    /// The geometries scanned from the pending batches.
+    pending_scan: GeometryScan,
+    /// The native multi type that geometries are converted to when the header detected from a
+    /// WKB, WKT or mixed column is a multi type, so that single geometries are promoted.
+    promoted_type: Option<GeoArrowType>,
+    wkt_crs: Option<String>,
+    /// Buffers all features, unless there's a memory budget.
+    geozero_writer: Option<GeozeroRecordBatchWriter<FgbWriter<'a>>>,
+    /// Spills features to temporary files, with a memory budget.
+    spill: Option<SpillWriter>,
 }
 
-impl<W: Write> FlatGeobufWriter<'_, W> {
+impl<'a, W: Write> FlatGeobufWriter<'a, W> {
     /// Create a new FlatGeobufWriter with the given options.
     pub fn try_new(
         file: W,
         schema: SchemaRef,
         options: FlatGeobufWriterOptions,
     ) -> GeoArrowResult<Self> {
-        let fields = &schema.fields;
-        let geom_col_idxs = geometry_columns(schema.as_ref());
-        if geom_col_idxs.len() != 1 {
-            return Err(GeoArrowError::FlatGeobuf(
-                "Only one geometry column currently supported in FlatGeobuf writer".to_string(),
-            ));
-        }
-
-        let geometry_field = &fields[geom_col_idxs[0]];
-        let geo_data_type = GeoArrowType::try_from(geometry_field.as_ref())?;
-
-        let wkt_crs_str = options.create_wkt_crs(geo_data_type.metadata())?;
-        let fgb_options = options.create_fgb_options(geo_data_type, wkt_crs_str.as_deref());
-
-        let geometry_type = infer_flatgeobuf_geometry_type(schema.as_ref())?;
-        let mut fgb_writer =
-            FgbWriter::create_with_options(&options.name, geometry_type, fgb_options)
-                .map_err(|err| GeoArrowError::External(Box::new(err)))?;
-        register_columns(&mut fgb_writer, schema.as_ref(), geom_col_idxs[0]);
-        let geozero_writer = GeozeroRecordBatchWriter::try_new(schema, fgb_writer, None).unwrap();
+        let geom_col_idx = select_geometry_column(&schema, options.geometry_column.as_deref())?;
+        let geo_data_type = GeoArrowType::try_from(schema.field(geom_col_idx))?;
+
+        let output_fields = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(col_idx, field)| {
+                if col_idx != geom_col_idx && is_geometry_field(field) {
+                    Field::new(field.name(), DataType::Binary, field.is_nullable()).into()
+                } else {
+                    field.clone()
+                }
+            })
+            .collect::<Vec<_>>();
+        let output_schema = Schema::new_with_metadata(output_fields, schema.metadata().clone());
 
         Ok(Self {
             file,
-            geozero_writer,
+            schema,
+            output_schema: output_schema.into(),
+            geom_col_idx,
+            geo_data_type,
+            options,
+            header: None,
+            pending: Vec::new(),
+            pending_scan: GeometryScan::default(),
+            promoted_type: None,
+            wkt_crs: None,
+            geozero_writer: None,
+            spill: None,
         })
     }
 
     /// Write a [`RecordBatch`] to the FlatGeobuf file.
     ///
     /// This will error if the schema of the `RecordBatch` does not match the schema originally
-    /// passed to [`FlatGeobufWriter::try_new`].
+    /// passed to [`FlatGeobufWriter::try_new`], or if, with a memory budget, the geometries of a
+    /// WKB, WKT or mixed geometry column have dimensions that the first batch didn't have.
     pub fn write(&mut self, batch: &RecordBatch) -> GeoArrowResult<()> {
-        self.geozero_writer
-            .write(batch)
-            .map_err(|err| GeoArrowError::External(Box::new(err)))?;
+        if batch.schema_ref() != &self.schema {
+            return Err(GeoArrowError::FlatGeobuf(
+                "Batch schema does not match writer schema".to_string(),
+            ));
+        }
 
-        Ok(())
+        let mut columns = batch.columns().to_vec();
+        for (col_idx, field) in self.schema.fields().iter().enumerate() {
+            if col_idx != self.geom_col_idx && is_geometry_field(field) {
+                let geometry = from_arrow_array(&columns[col_idx], field)?;
+                columns[col_idx] = to_wkb::<i32>(geometry.as_ref())?.into_array_ref();
+            }
+        }
+
+        let mut geometry = from_arrow_array(
+            &columns[self.geom_col_idx],
+            self.schema.field(self.geom_col_idx),
+        )?;
+        if let Some(snap) = &self.options.snap {
+            geometry = snap_to_grid(geometry.as_ref(), snap)?;
+            columns[self.geom_col_idx] = geometry.to_array_ref();
+        }
+
+        let scan = if self.geo_data_type.dimension().is_none() {
+            Some(scan_geometries(geometry.as_ref())?)
+        } else {
+            None
+        };
+        match (&self.header, scan) {
+            (None, Some(scan)) if self.options.memory_budget.is_none() => {
+                self.pending_scan.extend(scan);
+                self.pending.push((columns, geometry));
+                return Ok(());
+            }
+            (None, scan) => self.start(scan.as_ref())?,
+            (Some(header), Some(scan)) => header.check(&scan)?,
+            (Some(_), None) => {}
+        }
+
+        self.write_columns(columns, geometry)
     }
 
     /// Finish writing the FlatGeobuf file and return the underlying writer.
     pub fn finish(mut self) -> GeoArrowResult<W> {
+        if self.header.is_none() {
+            let scan = if self.geo_data_type.dimension().is_none()
+                && self.options.memory_budget.is_none()
+            {
+                Some(std::mem::take(&mut self.pending_scan))
+            } else {
+                None
+            };
+            self.start(scan.as_ref())?;
+        }
+        for (columns, geometry) in std::mem::take(&mut self.pending) {
+            self.write_columns(columns, geometry)?;
+        }
+
+        if let Some(spill) = self.spill.take() {
+            return spill.finish(self.file);
+        }
         let fgb_writer = self
             .geozero_writer
+            .take()
+            .unwrap()
             .finish()
             .map_err(|err| GeoArrowError::External(Box::new(err)))?;
         fgb_writer
@@ -219,6 +374,175 @@ impl<W: Write> FlatGeobufWriter<'_, W> {
             .map_err(|err| GeoArrowError::External(Box::new(err)))?;
         Ok(self.file)
     }
+
+    /// Write the columns of a batch and its geometry array, once the header is set.
+    fn write_columns(
+        &mut self,
+        mut columns: Vec<ArrayRef>,
+        mut geometry: Arc<dyn GeoArrowArray>,
+    ) -> GeoArrowResult<()> {
+        if let Some(promoted_type) = &self.promoted_type {
+            geometry = promote_geometries(geometry.as_ref(), promoted_type)?;
+            columns[self.geom_col_idx] = geometry.to_array_ref();
+        }
+
+        let batch = RecordBatch::try_new(self.output_schema.clone(), columns)?;
+        if let Some(mut spill) = self.spill.take() {
+            // `write_file` borrows the writer, so the spill writer is put back once it's done.
+            let result = self.spill_batch(&mut spill, geometry.as_ref(), &batch);
+            self.spill = Some(spill);
+            result?;
+        } else {
+            self.geozero_writer
+                .as_mut()
+                .unwrap()
+                .write(&batch)
+                .map_err(|err| GeoArrowError::External(Box::new(err)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Set the header, from the geometry column's type or, if the type doesn't define one, from
+    /// the scanned geometries, and create the underlying writer.
+    ///
+    /// With a memory budget, the scan is of the first batch only, so only its dimensions are used.
+    fn start(&mut self, scan: Option<&GeometryScan>) -> GeoArrowResult<()> {
+        let (header, detect_type) = match scan {
+            Some(scan) if self.options.memory_budget.is_some() => {
+                let header = HeaderType {
+                    geometry_type: flatgeobuf::GeometryType::Unknown,
+                    has_z: scan.has_z,
+                    has_m: scan.has_m,
+                };
+                (header, false)
+            }
+            Some(scan) => {
+                let mut header = scan.header_type(self.options.promote_to_multi);
+                if !self.options.detect_type {
+                    header.geometry_type = flatgeobuf::GeometryType::Unknown;
+                }
+                // If there were no geometries, leave detection to FgbWriter. Otherwise the scan
+                // already found the type, or found a mix that must stay Unknown.
+                let detect_type = self.options.detect_type && scan.geometry_types.is_empty();
+                (header, detect_type)
+            }
+            None => (
+                HeaderType::from_geoarrow_type(&self.geo_data_type),
+                self.options.detect_type,
+            ),
+        };
+        if scan.is_some() {
+            self.promote_to(&header);
+        }
+        self.header = Some(header);
+        self.wkt_crs = self.options.create_wkt_crs(self.geo_data_type.metadata())?;
+
+        if let Some(memory_budget) = self.options.memory_budget {
+            let template = self.write_file(None)?;
+            self.spill = Some(SpillWriter::try_new(
+                template,
+                memory_budget,
+                self.options.temp_dir.clone(),
+                self.options.write_index,
+            )?);
+        } else {
+            let fgb_writer = self.create_fgb_writer(self.options.write_index, detect_type)?;
+            let geozero_writer =
+                GeozeroRecordBatchWriter::try_new(self.output_schema.clone(), fgb_writer, None)
+                    .map_err(|err| GeoArrowError::External(Box::new(err)))?;
+            self.geozero_writer = Some(geozero_writer);
+        }
+        Ok(())
+    }
+
+    /// If the detected header is a multi type, write the geometry column as that native type.
+    fn promote_to(&mut self, header: &HeaderType) {
+        let dim = match (header.has_z, header.has_m) {
+            (false, false) => Dimension::XY,
+            (true, false) => Dimension::XYZ,
+            (false, true) => Dimension::XYM,
+            (true, true) => Dimension::XYZM,
+        };
+        let metadata = self.geo_data_type.metadata().clone();
+        let promoted_type = match header.geometry_type {
+            flatgeobuf::GeometryType::MultiPoint => {
+                GeoArrowType::MultiPoint(MultiPointType::new(dim, metadata))
+            }
+            flatgeobuf::GeometryType::MultiLineString => {
+                GeoArrowType::MultiLineString(MultiLineStringType::new(dim, metadata))
+            }
+            flatgeobuf::GeometryType::MultiPolygon => {
+                GeoArrowType::MultiPolygon(MultiPolygonType::new(dim, metadata))
+            }
+            _ => return,
+        };
+
+        let mut fields = self.output_schema.fields().to_vec();
+        let field = &fields[self.geom_col_idx];
+        fields[self.geom_col_idx] = promoted_type
+            .to_field(field.name(), field.is_nullable())
+            .into();
+        self.output_schema =
+            Schema::new_with_metadata(fields, self.output_schema.metadata().clone()).into();
+        self.promoted_type = Some(promoted_type);
+    }
+
+    /// Create an [`FgbWriter`] for the header set by [`FlatGeobufWriter::start`].
+    fn create_fgb_writer(
+        &self,
+        write_index: bool,
+        detect_type: bool,
+    ) -> GeoArrowResult<FgbWriter<'a>> {
+        let header = self.header.as_ref().unwrap();
+        let fgb_options = self.options.create_fgb_options(
+            header,
+            write_index,
+            detect_type,
+            self.wkt_crs.as_deref(),
+        );
+        let mut fgb_writer =
+            FgbWriter::create_with_options(&self.options.name, header.geometry_type, fgb_options)
+                .map_err(|err| GeoArrowError::External(Box::new(err)))?;
+        register_columns(&mut fgb_writer, &self.output_schema, self.geom_col_idx);
+        Ok(fgb_writer)
+    }
+
+    fn spill_batch(
+        &self,
+        spill: &mut SpillWriter,
+        geometry: &dyn GeoArrowArray,
+        batch: &RecordBatch,
+    ) -> GeoArrowResult<()> {
+        let bounds = geometry_bounds(geometry)?;
+        let file = self.write_file(Some(batch))?;
+        spill.push_file(&file, &bounds)
+    }
+
+    /// Write a batch, or no features at all, to an in-memory FlatGeobuf file without an index,
+    /// for the [`SpillWriter`].
+    ///
+    /// The file holds a full encoded copy of the batch, which isn't counted against the memory
+    /// budget.
+    fn write_file(&self, batch: Option<&RecordBatch>) -> GeoArrowResult<Vec<u8>> {
+        // Every file must have the same header, so the geometry type isn't detected per batch.
+        let fgb_writer = self.create_fgb_writer(false, false)?;
+        let mut geozero_writer =
+            GeozeroRecordBatchWriter::try_new(self.output_schema.clone(), fgb_writer, None)
+                .map_err(|err| GeoArrowError::External(Box::new(err)))?;
+        if let Some(batch) = batch {
+            geozero_writer
+                .write(batch)
+                .map_err(|err| GeoArrowError::External(Box::new(err)))?;
+        }
+        let mut file = Vec::new();
+        geozero_writer
+            .finish()
+            .map_err(|err| GeoArrowError::External(Box::new(err)))?
+            .write(&mut file)
+            .map_err(|err| GeoArrowError::External(Box::new(err)))?;
+        Ok(file)
+    }
 }
 
 /// Write an iterator of GeoArrow RecordBatches to a FlatGeobuf file.
@@ -230,62 +554,221 @@ pub fn write_flatgeobuf<W: Write, S: Into<GeozeroRecordBatchReader>>(
     writer: W,
     options: FlatGeobufWriterOptions,
 ) -> GeoArrowResult<()> {
-    let mut stream: GeozeroRecordBatchReader = stream.into();
-
-    let schema = stream.as_ref().schema();
-    let fields = &schema.fields;
-    let geom_col_idxs = geometry_columns(schema.as_ref());
-    if geom_col_idxs.len() != 1 {
-        return Err(GeoArrowError::FlatGeobuf(
-            "Only one geometry column currently supported in FlatGeobuf writer".to_string(),
-        ));
+    let stream: GeozeroRecordBatchReader = stream.into();
+    let stream = stream.into_inner();
+
+    let mut fgb_writer = FlatGeobufWriter::try_new(writer, stream.schema(), options)?;
+    for batch in stream {
+        fgb_writer.write(&batch?)?;
     }
+    fgb_writer.finish()?;
+    Ok(())
+}
+
+/// The geometry type and coordinate dimensions declared in a FlatGeobuf header.
+#[derive(Debug, Clone, Copy)]
+struct HeaderType {
+    geometry_type: flatgeobuf::GeometryType,
+    has_z: bool,
+    has_m: bool,
+}
 
-    let geometry_field = &fields[geom_col_idxs[0]];
-    let geo_data_type = GeoArrowType::try_from(geometry_field.as_ref())?;
+impl HeaderType {
+    fn from_geoarrow_type(geo_data_type: &GeoArrowType) -> Self {
+        use GeoArrowType::*;
+        let geometry_type = match geo_data_type {
+            Point(_) => flatgeobuf::GeometryType::Point,
+            LineString(_) => flatgeobuf::GeometryType::LineString,
+            Rect(_) | Polygon(_) => flatgeobuf::GeometryType::Polygon,
+            MultiPoint(_) => flatgeobuf::GeometryType::MultiPoint,
+            MultiLineString(_) => flatgeobuf::GeometryType::MultiLineString,
+            MultiPolygon(_) => flatgeobuf::GeometryType::MultiPolygon,
+            Geometry(_) | Wkb(_) | LargeWkb(_) | WkbView(_) | Wkt(_) | LargeWkt(_) | WktView(_) => {
+                flatgeobuf::GeometryType::Unknown
+            }
+            GeometryCollection(_) => flatgeobuf::GeometryType::GeometryCollection,
+        };
+        let (has_z, has_m) = match geo_data_type.dimension() {
+            Some(Dimension::XY) | None => (false, false),
+            Some(Dimension::XYZ) => (true, false),
+            Some(Dimension::XYM) => (false, true),
+            Some(Dimension::XYZM) => (true, true),
+        };
+        Self {
+            geometry_type,
+            has_z,
+            has_m,
+        }
+    }
 
-    let wkt_crs_str = options.create_wkt_crs(geo_data_type.metadata())?;
-    let fgb_options = options.create_fgb_options(geo_data_type, wkt_crs_str.as_deref());
+    /// Check that the scanned geometries of a later batch fit the dimensions of this header,
+    /// which were detected from the first batch.
+    fn check(&self, scan: &GeometryScan) -> GeoArrowResult<()> {
+        if (scan.has_z && !self.has_z) || (scan.has_m && !self.has_m) {
+            return Err(GeoArrowError::FlatGeobuf(format!(
+                "Found a geometry with {} coordinates, but the FlatGeobuf header dimensions detected from the first batch are {}",
+                dimension_name(scan.has_z, scan.has_m),
+                dimension_name(self.has_z, self.has_m),
+            )));
+        }
+        Ok(())
+    }
+}
 
-    let geometry_type = infer_flatgeobuf_geometry_type(stream.as_ref().schema().as_ref())?;
+fn dimension_name(has_z: bool, has_m: bool) -> &'static str {
+    match (has_z, has_m) {
+        (false, false) => "XY",
+        (true, false) => "XYZ",
+        (false, true) => "XYM",
+        (true, true) => "XYZM",
+    }
+}
 
-    let mut fgb = FgbWriter::create_with_options(&options.name, geometry_type, fgb_options)
-        .map_err(|err| GeoArrowError::External(Box::new(err)))?;
-    register_columns(&mut fgb, schema.as_ref(), geom_col_idxs[0]);
-    stream
-        .process(&mut fgb)
-        .map_err(|err| GeoArrowError::External(Box::new(err)))?;
-    fgb.write(writer)
-        .map_err(|err| GeoArrowError::External(Box::new(err)))?;
-    Ok(())
+/// The distinct FlatGeobuf geometry types and the coordinate dimensions of an array's geometries.
+#[derive(Debug, Default)]
+struct GeometryScan {
+    geometry_types: Vec<flatgeobuf::GeometryType>,
+    has_z: bool,
+    has_m: bool,
 }
 
-fn infer_flatgeobuf_geometry_type(schema: &Schema) -> GeoArrowResult<flatgeobuf::GeometryType> {
-    let fields = &schema.fields;
-    let geom_col_idxs = geometry_columns(schema);
-    if geom_col_idxs.len() != 1 {
-        return Err(GeoArrowError::FlatGeobuf(
-            "Only one geometry column currently supported in FlatGeobuf writer".to_string(),
-        ));
+impl GeometryScan {
+    /// Add the geometry types and dimensions of another scan.
+    fn extend(&mut self, other: GeometryScan) {
+        for typ in other.geometry_types {
+            if !self.geometry_types.contains(&typ) {
+                self.geometry_types.push(typ);
+            }
+        }
+        self.has_z |= other.has_z;
+        self.has_m |= other.has_m;
     }
 
-    let geometry_field = &fields[geom_col_idxs[0]];
-    let geo_data_type = GeoArrowType::try_from(geometry_field.as_ref())?;
+    /// The header type for the scanned geometries.
+    ///
+    /// A single geometry type is used as is; a mix of single and multi geometries of the same
+    /// kind becomes the multi type if `promote_to_multi` is set; any other mix is Unknown.
+    fn header_type(&self, promote_to_multi: bool) -> HeaderType {
+        let geometry_type = match self.geometry_types.as_slice() {
+            [typ] => *typ,
+            [first, rest @ ..]
+                if promote_to_multi
+                    && rest.iter().all(|typ| to_multi(*typ) == to_multi(*first)) =>
+            {
+                to_multi(*first)
+            }
+            _ => flatgeobuf::GeometryType::Unknown,
+        };
+        HeaderType {
+            geometry_type,
+            has_z: self.has_z,
+            has_m: self.has_m,
+        }
+    }
+}
+
+/// Convert geometries to a native multi type, promoting single geometries to multi geometries.
+fn promote_geometries(
+    arr: &dyn GeoArrowArray,
+    to_type: &GeoArrowType,
+) -> GeoArrowResult<Arc<dyn GeoArrowArray>> {
+    downcast_geoarrow_array!(arr, impl_promote_geometries, to_type)
+}
 
-    use GeoArrowType::*;
-    let geometry_type = match geo_data_type {
+fn impl_promote_geometries<'a>(
+    arr: &'a impl GeoArrowArrayAccessor<'a>,
+    to_type: &GeoArrowType,
+) -> GeoArrowResult<Arc<dyn GeoArrowArray>> {
+    let geometries = arr
+        .iter()
+        .map(|geometry| geometry.transpose())
+        .collect::<GeoArrowResult<Vec<_>>>()?;
+    let array: Arc<dyn GeoArrowArray> = match to_type {
+        GeoArrowType::MultiPoint(typ) => Arc::new(
+            MultiPointBuilder::from_nullable_geometries(&geometries, typ.clone())?.finish(),
+        ),
+        GeoArrowType::MultiLineString(typ) => Arc::new(
+            MultiLineStringBuilder::from_nullable_geometries(&geometries, typ.clone())?.finish(),
+        ),
+        GeoArrowType::MultiPolygon(typ) => Arc::new(
+            MultiPolygonBuilder::from_nullable_geometries(&geometries, typ.clone())?.finish(),
+        ),
+        _ => unreachable!("geometries are only promoted to multi types"),
+    };
+    Ok(array)
+}
+
+/// The bounds of each geometry of an array.
+fn geometry_bounds(arr: &dyn GeoArrowArray) -> GeoArrowResult<Vec<BoundingRect>> {
+    downcast_geoarrow_array!(arr, impl_geometry_bounds)
+}
+
+fn impl_geometry_bounds<'a>(
+    arr: &'a impl GeoArrowArrayAccessor<'a>,
+) -> GeoArrowResult<Vec<BoundingRect>> {
+    arr.iter()
+        .map(|geometry| {
+            let mut bounds = BoundingRect::new();
+            if let Some(geometry) = geometry {
+                bounds.add_geometry(&geometry?);
+            }
+            Ok(bounds)
+        })
+        .collect()
+}
+
+fn scan_geometries(arr: &dyn GeoArrowArray) -> GeoArrowResult<GeometryScan> {
+    downcast_geoarrow_array!(arr, impl_scan_geometries)
+}
+
+fn impl_scan_geometries<'a>(
+    arr: &'a impl GeoArrowArrayAccessor<'a>,
+) -> GeoArrowResult<GeometryScan> {
+    let mut scan = GeometryScan::default();
+    for geometry in arr.iter().flatten() {
+        let geometry = geometry?;
+        let typ = fgb_geometry_type(&geometry);
+        if !scan.geometry_types.contains(&typ) {
+            scan.geometry_types.push(typ);
+        }
+        let (has_z, has_m) = match geometry.dim() {
+            Dimensions::Xy | Dimensions::Unknown(2) => (false, false),
+            Dimensions::Xyz | Dimensions::Unknown(3) => (true, false),
+            Dimensions::Xym => (false, true),
+            Dimensions::Xyzm | Dimensions::Unknown(4) => (true, true),
+            Dimensions::Unknown(n) => {
+                return Err(GeoArrowError::FlatGeobuf(format!(
+                    "Unsupported number of dimensions: {n}"
+                )));
+            }
+        };
+        scan.has_z |= has_z;
+        scan.has_m |= has_m;
+    }
+    Ok(scan)
+}
+
+/// The FlatGeobuf geometry type that a geometry is written as.
+fn fgb_geometry_type(geometry: &impl GeometryTrait) -> flatgeobuf::GeometryType {
+    use geo_traits::GeometryType::*;
+    match geometry.as_type() {
         Point(_) => flatgeobuf::GeometryType::Point,
-        LineString(_) => flatgeobuf::GeometryType::LineString,
-        Rect(_) | Polygon(_) => flatgeobuf::GeometryType::Polygon,
+        Line(_) | LineString(_) => flatgeobuf::GeometryType::LineString,
+        Rect(_) | Triangle(_) | Polygon(_) => flatgeobuf::GeometryType::Polygon,
         MultiPoint(_) => flatgeobuf::GeometryType::MultiPoint,
         MultiLineString(_) => flatgeobuf::GeometryType::MultiLineString,
         MultiPolygon(_) => flatgeobuf::GeometryType::MultiPolygon,
-        Geometry(_) | Wkb(_) | LargeWkb(_) | WkbView(_) | Wkt(_) | LargeWkt(_) | WktView(_) => {
-            flatgeobuf::GeometryType::Unknown
-        }
         GeometryCollection(_) => flatgeobuf::GeometryType::GeometryCollection,
-    };
-    Ok(geometry_type)
+    }
+}
+
+fn to_multi(geometry_type: flatgeobuf::GeometryType) -> flatgeobuf::GeometryType {
+    match geometry_type {
+        flatgeobuf::GeometryType::Point => flatgeobuf::GeometryType::MultiPoint,
+        flatgeobuf::GeometryType::LineString => flatgeobuf::GeometryType::MultiLineString,
+        flatgeobuf::GeometryType::Polygon => flatgeobuf::GeometryType::MultiPolygon,
+        other => other,
+    }
 }
 
 /// Pre-register all non-geometry columns on the FgbWriter so that column indices in the
@@ -328,15 +811,41 @@ fn arrow_type_to_fgb_column_type(data_type: &DataType) -> ColumnType {
     }
 }
 
-// Note: this is duplicated from the `geoarrow-array` crate.
-fn geometry_columns(schema: &Schema) -> Vec<usize> {
-    let mut geom_indices = vec![];
-    for (field_idx, field) in schema.fields().iter().enumerate() {
-        if let Ok(Some(_)) = GeoArrowType::from_extension_field(field.as_ref()) {
-            geom_indices.push(field_idx);
+fn is_geometry_field(field: &Field) -> bool {
+    matches!(GeoArrowType::from_extension_field(field), Ok(Some(_)))
+}
+
+/// Find the index of the geometry column to write as the FlatGeobuf geometry.
+fn select_geometry_column(schema: &Schema, name: Option<&str>) -> GeoArrowResult<usize> {
+    if let Some(name) = name {
+        let (col_idx, field) = schema.column_with_name(name).ok_or_else(|| {
+            GeoArrowError::FlatGeobuf(format!("Geometry column '{name}' not found in schema"))
+        })?;
+        if !is_geometry_field(field) {
+            return Err(GeoArrowError::FlatGeobuf(format!(
+                "Column '{name}' is not a GeoArrow geometry column"
+            )));
         }
+        return Ok(col_idx);
+    }
+
+    let geom_col_idxs = schema
+        .fields()
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| is_geometry_field(field))
+        .map(|(col_idx, _)| col_idx)
+        .collect::<Vec<_>>();
+    match geom_col_idxs.as_slice() {
+        [col_idx] => Ok(*col_idx),
+        [] => Err(GeoArrowError::FlatGeobuf(
+            "No geometry column found in schema".to_string(),
+        )),
+        _ => Err(GeoArrowError::FlatGeobuf(format!(
+            "Found {} geometry columns; select the one to write with `with_geometry_column`",
+            geom_col_idxs.len()
+        ))),
     }
-    geom_indices
 }
 
 #[cfg(test)]
@@ -344,13 +853,16 @@ mod test {
     use std::io::{BufWriter, Cursor};
     use std::sync::Arc;
 
-    use arrow_array::{RecordBatch, RecordBatchIterator, RecordBatchReader, create_array};
+    use arrow_array::{
+        Array, Int32Array, RecordBatch, RecordBatchIterator, RecordBatchReader, StringArray,
+        create_array,
+    };
     use arrow_schema::{DataType, Field, Schema};
     use flatgeobuf::{FallibleStreamingIterator, FgbReader};
     use geoarrow_array::GeoArrowArray;
-    use geoarrow_array::array::PointArray;
+    use geoarrow_array::array::{PointArray, WktArray};
     use geoarrow_array::builder::PointBuilder;
-    use geoarrow_schema::PointType;
+    use geoarrow_schema::{PointType, WktType};
     use geozero::FeatureProperties;
     use wkt::wkt;
 
@@ -412,6 +924,26 @@ mod test {
         (vec![batch], schema)
     }
 
+    fn wkt_array(values: &[&str]) -> WktArray {
+        WktArray::from((
+            StringArray::from(values.to_vec()),
+            WktType::new(Default::default()),
+        ))
+    }
+
+    fn read_batches(buffer: Vec<u8>) -> Vec<RecordBatch> {
+        let fgb_reader = FgbReader::open(Cursor::new(buffer)).unwrap();
+        let fgb_header = fgb_reader.header();
+        let properties_schema = fgb_header.properties_schema(false).unwrap();
+        let geometry_type = fgb_header.geoarrow_type(Default::default()).unwrap();
+        let options = FlatGeobufReaderOptions::new(properties_schema, geometry_type);
+        let selection = fgb_reader.select_all_seq().unwrap();
+        FlatGeobufRecordBatchIterator::try_new(selection, options)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    }
+
     #[test]
     fn test_write() {
         for dim in [
@@ -514,6 +1046,48 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_write_snap_to_grid() {
+        let typ = PointType::new(Dimension::XY, Default::default());
+        let points = |geoms: &[wkt::types::Point<f64>]| {
+            Arc::new(PointBuilder::from_points(geoms.iter(), typ.clone()).finish())
+        };
+        let (orig_batches, orig_schema) = table(points(&[
+            wkt! { POINT (30.2 10.4) },
+            wkt! { POINT (40.6 20.1) },
+            wkt! { POINT (1.2 2.7) },
+            wkt! { POINT (1.2 2.7) },
+        ]));
+        let (expected_batches, _) = table(points(&[
+            wkt! { POINT (30. 10.) },
+            wkt! { POINT (41. 20.) },
+            wkt! { POINT (1. 3.) },
+            wkt! { POINT (1. 3.) },
+        ]));
+
+        let reader = Box::new(RecordBatchIterator::new(
+            orig_batches.into_iter().map(Ok),
+            orig_schema,
+        ));
+        let mut output_buffer = Vec::new();
+        let writer = BufWriter::new(&mut output_buffer);
+        let options = FlatGeobufWriterOptions::new("name".to_string())
+            .with_write_index(false)
+            .with_snap_to_grid(SnapOptions::new(1.0));
+        write_flatgeobuf(GeozeroRecordBatchReader::new(reader), writer, options).unwrap();
+
+        let fgb_reader = FgbReader::open(Cursor::new(output_buffer)).unwrap();
+        let fgb_header = fgb_reader.header();
+        let properties_schema = fgb_header.properties_schema(false).unwrap();
+        let geometry_type = fgb_header.geoarrow_type(Default::default()).unwrap();
+        let options = FlatGeobufReaderOptions::new(properties_schema, geometry_type);
+        let selection = fgb_reader.select_all_seq().unwrap();
+        let record_batch_reader =
+            FlatGeobufRecordBatchIterator::try_new(selection, options).unwrap();
+        let batches = record_batch_reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(batches, expected_batches);
+    }
+
     #[test]
     fn test_sparse_fields() {
         let typ = PointType::new(Dimension::XY, Default::default());
@@ -649,4 +1223,232 @@ mod test {
         assert_eq!(results[0], (1, None, "hello".to_string()));
         assert_eq!(results[1], (2, None, "world".to_string()));
     }
+
+    #[test]
+    fn test_write_wkb() {
+        let points = non_empty_point_array(Dimension::XYZ);
+        let (orig_batches, orig_schema) = table(Arc::new(to_wkb::<i32>(&points).unwrap()));
+        let (expected_batches, _) = table(Arc::new(points));
+
+        let options = FlatGeobufWriterOptions::new("name".to_string());
+        let mut fgb_writer = FlatGeobufWriter::try_new(Vec::new(), orig_schema, options).unwrap();
+        for batch in &orig_batches {
+            fgb_writer.write(batch).unwrap();
+        }
+        let output_buffer = fgb_writer.finish().unwrap();
+
+        let fgb_reader = FgbReader::open(Cursor::new(&output_buffer)).unwrap();
+        let fgb_header = fgb_reader.header();
+        assert_eq!(fgb_header.geometry_type(), flatgeobuf::GeometryType::Point);
+        assert!(fgb_header.has_z());
+        assert!(!fgb_header.has_m());
+
+        assert_eq!(read_batches(output_buffer), expected_batches);
+    }
+
+    #[test]
+    fn test_write_detect_type() {
+        // Single and multi points are promoted to a MultiPoint header.
+        let (batches, schema) = table(Arc::new(wkt_array(&[
+            "POINT (0 1)",
+            "MULTIPOINT ((1 2), (3 4))",
+            "POINT (5 6)",
+            "POINT (7 8)",
+        ])));
+        let options = FlatGeobufWriterOptions::new("name".to_string());
+        let mut fgb_writer = FlatGeobufWriter::try_new(Vec::new(), schema, options).unwrap();
+        fgb_writer.write(&batches[0]).unwrap();
+        let output_buffer = fgb_writer.finish().unwrap();
+        let fgb_reader = FgbReader::open(Cursor::new(&output_buffer)).unwrap();
+        assert_eq!(
+            fgb_reader.header().geometry_type(),
+            flatgeobuf::GeometryType::MultiPoint
+        );
+        assert_eq!(read_batches(output_buffer)[0].num_rows(), 4);
+
+        // Other mixes are written with an Unknown header.
+        let (batches, schema) = table(Arc::new(wkt_array(&[
+            "POINT Z (0 1 2)",
+            "LINESTRING Z (0 0 0, 1 1 1)",
+            "POINT Z (5 6 7)",
+            "POINT Z (7 8 9)",
+        ])));
+        let options = FlatGeobufWriterOptions::new("name".to_string());
+        let mut fgb_writer = FlatGeobufWriter::try_new(Vec::new(), schema, options).unwrap();
+        fgb_writer.write(&batches[0]).unwrap();
+        let output_buffer = fgb_writer.finish().unwrap();
+
+        let fgb_reader = FgbReader::open(Cursor::new(&output_buffer)).unwrap();
+        let fgb_header = fgb_reader.header();
+        assert_eq!(
+            fgb_header.geometry_type(),
+            flatgeobuf::GeometryType::Unknown
+        );
+        assert!(fgb_header.has_z());
+    }
+
+    #[test]
+    fn test_write_detect_type_across_batches() {
+        let first = wkt_array(&["POINT (0 1)", "POINT (2 3)"]);
+        let schema = Arc::new(Schema::new(vec![
+            first.data_type().to_field("geometry", true),
+        ]));
+        let batches = [
+            first,
+            wkt_array(&["POLYGON Z ((0 0 1, 1 0 1, 1 1 1, 0 0 1))"]),
+        ]
+        .map(|geometry| {
+            RecordBatch::try_new(schema.clone(), vec![geometry.to_array_ref()]).unwrap()
+        });
+
+        // The header is detected from all batches.
+        let options = FlatGeobufWriterOptions::new("name".to_string());
+        let mut fgb_writer =
+            FlatGeobufWriter::try_new(Vec::new(), schema.clone(), options).unwrap();
+        for batch in &batches {
+            fgb_writer.write(batch).unwrap();
+        }
+        let output_buffer = fgb_writer.finish().unwrap();
+        let fgb_reader = FgbReader::open(Cursor::new(&output_buffer)).unwrap();
+        let fgb_header = fgb_reader.header();
+        assert_eq!(
+            fgb_header.geometry_type(),
+            flatgeobuf::GeometryType::Unknown
+        );
+        assert!(fgb_header.has_z());
+        assert_eq!(fgb_header.features_count(), 3);
+
+        // With a memory budget, the geometry type is Unknown, but the dimensions are detected
+        // from the first batch.
+        let options = FlatGeobufWriterOptions::new("name".to_string()).with_memory_budget(4096);
+        let mut fgb_writer =
+            FlatGeobufWriter::try_new(Vec::new(), schema.clone(), options).unwrap();
+        fgb_writer.write(&batches[0]).unwrap();
+        assert!(fgb_writer.write(&batches[1]).is_err());
+        let lines = wkt_array(&["LINESTRING (0 0, 1 1)"]);
+        let batch = RecordBatch::try_new(schema, vec![lines.to_array_ref()]).unwrap();
+        fgb_writer.write(&batch).unwrap();
+        let output_buffer = fgb_writer.finish().unwrap();
+        let fgb_reader = FgbReader::open(Cursor::new(&output_buffer)).unwrap();
+        let fgb_header = fgb_reader.header();
+        assert_eq!(
+            fgb_header.geometry_type(),
+            flatgeobuf::GeometryType::Unknown
+        );
+        assert!(!fgb_header.has_z());
+        assert_eq!(fgb_header.features_count(), 3);
+    }
+
+    #[test]
+    fn test_write_multiple_geometry_columns() {
+        let points = non_empty_point_array(Dimension::XY);
+        let lines = wkt_array(&["LINESTRING (0 0, 1 1)"; 4]);
+        let schema = Arc::new(Schema::new(vec![
+            lines.data_type().to_field("other", true),
+            points.data_type().to_field("geometry", true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![lines.to_array_ref(), points.to_array_ref()],
+        )
+        .unwrap();
+
+        let options = FlatGeobufWriterOptions::new("name".to_string());
+        assert!(FlatGeobufWriter::try_new(Vec::new(), schema.clone(), options).is_err());
+
+        let options = FlatGeobufWriterOptions::new("name".to_string())
+            .with_geometry_column("geometry".to_string());
+        let mut fgb_writer = FlatGeobufWriter::try_new(Vec::new(), schema, options).unwrap();
+        fgb_writer.write(&batch).unwrap();
+        let output_buffer = fgb_writer.finish().unwrap();
+
+        let batches = read_batches(output_buffer);
+        assert_eq!(
+            batches[0].column_by_name("geometry").unwrap(),
+            &points.into_array_ref()
+        );
+        // The other geometry column is written as WKB.
+        assert_eq!(
+            batches[0].column_by_name("other").unwrap(),
+            &to_wkb::<i32>(&lines).unwrap().into_array_ref()
+        );
+    }
+
+    #[test]
+    fn test_write_memory_budget() {
+        let mut builder = PointBuilder::new(PointType::new(Dimension::XY, Default::default()));
+        for i in 0..1000 {
+            let coord = wkt::types::Coord {
+                x: (i % 32) as f64,
+                y: (i / 32) as f64,
+                z: None,
+                m: None,
+            };
+            builder.push_coord(Some(&coord));
+        }
+        let points = builder.finish();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            points.data_type().to_field("geometry", true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from_iter_values(0..1000)),
+                points.into_array_ref(),
+            ],
+        )
+        .unwrap();
+
+        for write_index in [true, false] {
+            // A budget small enough that the features are sorted in many runs.
+            let options = FlatGeobufWriterOptions::new("name".to_string())
+                .with_write_index(write_index)
+                .with_memory_budget(4096);
+            let mut fgb_writer =
+                FlatGeobufWriter::try_new(Vec::new(), schema.clone(), options).unwrap();
+            fgb_writer.write(&batch.slice(0, 600)).unwrap();
+            fgb_writer.write(&batch.slice(600, 400)).unwrap();
+            let output_buffer = fgb_writer.finish().unwrap();
+
+            let fgb_reader = FgbReader::open(Cursor::new(&output_buffer)).unwrap();
+            assert_eq!(fgb_reader.header().features_count(), 1000);
+            if !write_index {
+                assert_eq!(fgb_reader.header().index_node_size(), 0);
+                // Without an index, the features keep their order.
+                let batches = read_batches(output_buffer);
+                let ids = batches
+                    .iter()
+                    .flat_map(|batch| {
+                        let ids = batch.column_by_name("id").unwrap();
+                        ids.as_any()
+                            .downcast_ref::<Int32Array>()
+                            .unwrap()
+                            .values()
+                            .to_vec()
+                    })
+                    .collect::<Vec<_>>();
+                assert_eq!(ids, (0..1000).collect::<Vec<_>>());
+                continue;
+            }
+            assert_eq!(fgb_reader.header().index_node_size(), 16);
+
+            let mut features = fgb_reader.select_bbox(2.5, 2.5, 5.5, 4.5).unwrap();
+            let mut ids = vec![];
+            while let Some(feature) = features.next().unwrap() {
+                ids.push(feature.property::<i32>("id").unwrap());
+            }
+            ids.sort();
+            assert_eq!(ids, [99, 100, 101, 131, 132, 133]);
+
+            let fgb_reader = FgbReader::open(Cursor::new(&output_buffer)).unwrap();
+            let mut features = fgb_reader.select_all().unwrap();
+            let mut ids = vec![];
+            while let Some(feature) = features.next().unwrap() {
+                ids.push(feature.property::<i32>("id").unwrap());
+            }
+            ids.sort();
+            assert_eq!(ids, (0..1000).collect::<Vec<_>>());
+        }
+    }
 }
@@ -0,0 +1,100 @@
+//! GeoPackage [geometry blobs](https://www.geopackage.org/spec/#gpb_format).
+//!
+//! A blob is a header, holding the `GP` magic bytes, a version, flags, the `srs_id` and an
+//! optional envelope, followed by standard WKB. Blobs are decoded into arrays with
+//! [`from_wkb_dialect`][geoarrow_array::wkb_dialect::from_wkb_dialect]; this module writes blobs
+//! with an envelope, and reads envelopes back for bounding box filtering, using the header
+//! functions of [`wkb_dialect`][geoarrow_array::wkb_dialect].
+
+use geoarrow_array::bounds::BoundingRect;
+use geoarrow_array::wkb_dialect::{read_geopackage_header, write_geopackage_header};
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+use wkb::reader::Wkb;
+
+/// Append a little-endian blob to `out`.
+///
+/// The blob gets an XY envelope from `bounds`, or the empty flag and no envelope if `bounds` is
+/// empty.
+pub(crate) fn push_blob(out: &mut Vec<u8>, srs_id: i32, bounds: &BoundingRect, wkb: &[u8]) {
+    let envelope =
+        (!bounds.is_empty()).then(|| [bounds.minx(), bounds.miny(), bounds.maxx(), bounds.maxy()]);
+    write_geopackage_header(out, srs_id, envelope.is_none(), envelope);
+    out.extend_from_slice(wkb);
+}
+
+/// The `[minx, miny, maxx, maxy]` bounds of a blob, or `None` for an empty geometry.
+///
+/// The bounds come from the envelope in the header if there is one, and otherwise from the
+/// geometry itself.
+pub(crate) fn blob_bounds(blob: &[u8]) -> GeoArrowResult<Option<[f64; 4]>> {
+    let header = read_geopackage_header(blob)?;
+    if header.empty {
+        return Ok(None);
+    }
+
+    let bounds = match header.envelope {
+        Some(envelope) => envelope,
+        None => {
+            let geometry = Wkb::try_new(&blob[header.wkb_offset..])
+                .map_err(|err| GeoArrowError::External(Box::new(err)))?;
+            let mut bounds = BoundingRect::new();
+            bounds.add_geometry(&geometry);
+            if bounds.is_empty() {
+                return Ok(None);
+            }
+            [bounds.minx(), bounds.miny(), bounds.maxx(), bounds.maxy()]
+        }
+    };
+
+    // Empty geometries may also be written with a NaN envelope.
+    if bounds.iter().any(|value| value.is_nan()) {
+        Ok(None)
+    } else {
+        Ok(Some(bounds))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// `POINT (1 2)` as little-endian WKB.
+    const WKB_POINT: &[u8] = &[
+        1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 240, 63, 0, 0, 0, 0, 0, 0, 0, 64,
+    ];
+
+    #[test]
+    fn envelope_roundtrip() {
+        let mut bounds = BoundingRect::new();
+        bounds.add_geometry(&Wkb::try_new(WKB_POINT).unwrap());
+
+        let mut blob = Vec::new();
+        push_blob(&mut blob, 4326, &bounds, WKB_POINT);
+        assert_eq!(&blob[..4], b"GP\x00\x03");
+        assert_eq!(&blob[4..8], &4326_i32.to_le_bytes());
+        assert_eq!(&blob[40..], WKB_POINT);
+        assert_eq!(blob_bounds(&blob).unwrap(), Some([1., 2., 1., 2.]));
+    }
+
+    #[test]
+    fn bounds_without_envelope() {
+        // A big-endian header with no envelope, so the bounds come from the WKB.
+        let mut blob = b"GP\x00\x00".to_vec();
+        blob.extend_from_slice(&4326_i32.to_be_bytes());
+        blob.extend_from_slice(WKB_POINT);
+        assert_eq!(blob_bounds(&blob).unwrap(), Some([1., 2., 1., 2.]));
+    }
+
+    #[test]
+    fn empty() {
+        let mut blob = Vec::new();
+        push_blob(&mut blob, 0, &BoundingRect::new(), WKB_POINT);
+        assert_eq!(blob[3], 0b0001_0001);
+        assert_eq!(blob_bounds(&blob).unwrap(), None);
+    }
+
+    #[test]
+    fn invalid_magic() {
+        assert!(blob_bounds(b"XX\x00\x01\x00\x00\x00\x00").is_err());
+    }
+}
@@ -0,0 +1,730 @@
+//! Read feature tables from [GeoPackage](https://www.geopackage.org/) files.
+//!
+//! Rows are read in pages of [`GeoPackageReaderOptions::batch_size`], ordered by the table's
+//! integer primary key, so that the reader doesn't hold a statement open between batches.
+
+use std::sync::Arc;
+
+use arrow_array::builder::{
+    BinaryBuilder, BooleanBuilder, Float32Builder, Float64Builder, Int8Builder, Int16Builder,
+    Int32Builder, Int64Builder, StringBuilder,
+};
+use arrow_array::{ArrayRef, RecordBatch, RecordBatchReader};
+use arrow_cast::CastOptions;
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef, TimeUnit};
+use geoarrow_array::GeoArrowArray;
+use geoarrow_array::array::WkbArray;
+use geoarrow_array::wkb_dialect::{WkbDialect, WkbDialectOptions, from_wkb_dialect};
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+use geoarrow_schema::{
+    CoordType, Crs, Dimension, GeoArrowType, GeometryCollectionType, GeometryType, LineStringType,
+    Metadata, MultiLineStringType, MultiPointType, MultiPolygonType, PointType, PolygonType,
+};
+use rusqlite::types::{Value, ValueRef};
+use rusqlite::{Connection, OptionalExtension, params_from_iter};
+
+use crate::blob::blob_bounds;
+use crate::sql::{column_exists, quote_identifier, rtree_table_name, sqlite_error, table_exists};
+
+/// Options for the GeoPackage reader
+#[derive(Debug, Clone)]
+pub struct GeoPackageReaderOptions {
+    /// The GeoArrow coordinate type to use in the geometry arrays.
+    ///
+    /// This only applies when [`Self::geometry_type`] is `None`.
+    pub coord_type: CoordType,
+
+    /// The number of rows in each batch.
+    pub batch_size: usize,
+
+    /// The GeoArrow type to decode geometries into.
+    ///
+    /// If `None`, the type is chosen from the geometry type and dimensions declared in
+    /// `gpkg_geometry_columns`. The metadata of this type is replaced by the CRS of the column.
+    pub geometry_type: Option<GeoArrowType>,
+
+    /// Only read rows whose geometry envelope intersects this `[minx, miny, maxx, maxy]` box.
+    ///
+    /// If the geometry column has an R-tree spatial index it is used to select rows; otherwise
+    /// each row's envelope is read from its geometry blob. Rows with null or empty geometries
+    /// are skipped. This is an envelope test, so rows whose geometries don't intersect the box
+    /// may still be returned.
+    pub bbox: Option<[f64; 4]>,
+}
+
+impl Default for GeoPackageReaderOptions {
+    fn default() -> Self {
+        Self {
+            coord_type: Default::default(),
+            batch_size: 65_536,
+            geometry_type: None,
+            bbox: None,
+        }
+    }
+}
+
+/// List the feature tables in a GeoPackage, as registered in `gpkg_contents`.
+pub fn feature_tables(conn: &Connection) -> GeoArrowResult<Vec<String>> {
+    let mut statement = conn
+        .prepare("SELECT table_name FROM gpkg_contents WHERE data_type = 'features' ORDER BY 1")
+        .map_err(sqlite_error)?;
+    statement
+        .query_map([], |row| row.get(0))
+        .map_err(sqlite_error)?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(sqlite_error)
+}
+
+/// A column of the feature table.
+#[derive(Debug, Clone)]
+struct SqliteColumn {
+    name: String,
+    data_type: DataType,
+}
+
+/// An iterator over record batches from a GeoPackage feature table.
+///
+/// Columns are read in table order, including the primary key, with these types:
+///
+/// | GeoPackage                         | Arrow                            |
+/// | ---------------------------------- | -------------------------------- |
+/// | `BOOLEAN`                          | `Boolean`                        |
+/// | `TINYINT`, `SMALLINT`, `MEDIUMINT` | `Int8`, `Int16`, `Int32`         |
+/// | `INT`, `INTEGER`                   | `Int64`                          |
+/// | `FLOAT`                            | `Float32`                        |
+/// | `DOUBLE`, `REAL`                   | `Float64`                        |
+/// | `TEXT`                             | `Utf8`                           |
+/// | `BLOB`                             | `Binary`                         |
+/// | `DATE`                             | `Date32`                         |
+/// | `DATETIME`                         | `Timestamp(Millisecond, +00:00)` |
+///
+/// Other declared types fall back to SQLite's type affinity rules. The geometry column is
+/// decoded into the type chosen by [`GeoPackageReaderOptions::geometry_type`], with the CRS of
+/// its `srs_id` in `gpkg_spatial_ref_sys`: an authority code if the organization is known, and
+/// otherwise the WKT definition.
+///
+/// This implements [RecordBatchReader], which you can use to access data.
+pub struct GeoPackageReader<'c> {
+    conn: &'c Connection,
+    sql: String,
+    columns: Vec<SqliteColumn>,
+    fid_index: usize,
+    geometry_index: usize,
+    geometry_type: GeoArrowType,
+    schema: SchemaRef,
+    batch_size: usize,
+    bbox: Option<[f64; 4]>,
+    /// Whether the bounding box is tested against the geometry blobs, for lack of an R-tree.
+    filter_blobs: bool,
+    last_fid: i64,
+    finished: bool,
+}
+
+impl<'c> GeoPackageReader<'c> {
+    /// Create a new reader of the given feature table.
+    pub fn try_new(
+        conn: &'c Connection,
+        table_name: &str,
+        options: GeoPackageReaderOptions,
+    ) -> GeoArrowResult<Self> {
+        let (geometry_column, geometry_type_name, srs_id, z, m) = conn
+            .query_row(
+                "SELECT column_name, geometry_type_name, srs_id, z, m FROM gpkg_geometry_columns \
+                 WHERE table_name = ?1",
+                [table_name],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, i32>(2)?,
+                        row.get::<_, i64>(3)?,
+                        row.get::<_, i64>(4)?,
+                    ))
+                },
+            )
+            .optional()
+            .map_err(sqlite_error)?
+            .ok_or_else(|| {
+                GeoArrowError::GeoPackage(format!(
+                    "Table {table_name:?} is not a feature table in gpkg_geometry_columns"
+                ))
+            })?;
+
+        let metadata = Arc::new(Metadata::new(read_crs(conn, srs_id)?, None));
+        let geometry_type = match options.geometry_type {
+            Some(geometry_type) => geometry_type.with_metadata(metadata),
+            None => infer_geometry_type(&geometry_type_name, z, m, options.coord_type, metadata),
+        };
+
+        let mut columns = Vec::new();
+        let mut fid_index = None;
+        let mut geometry_index = None;
+        let mut statement = conn
+            .prepare("SELECT name, type, pk FROM pragma_table_info(?1) ORDER BY cid")
+            .map_err(sqlite_error)?;
+        let mut rows = statement.query([table_name]).map_err(sqlite_error)?;
+        while let Some(row) = rows.next().map_err(sqlite_error)? {
+            let name = row.get::<_, String>(0).map_err(sqlite_error)?;
+            let declared_type = row.get::<_, String>(1).map_err(sqlite_error)?;
+            let pk = row.get::<_, i64>(2).map_err(sqlite_error)?;
+            if name == geometry_column {
+                geometry_index = Some(columns.len());
+            } else if pk > 0 && declared_type.eq_ignore_ascii_case("INTEGER") {
+                fid_index = Some(columns.len());
+            }
+            columns.push(SqliteColumn {
+                name,
+                data_type: arrow_type(&declared_type),
+            });
+        }
+
+        let geometry_index = geometry_index.ok_or_else(|| {
+            GeoArrowError::GeoPackage(format!(
+                "Table {table_name:?} has no geometry column {geometry_column:?}"
+            ))
+        })?;
+        let fid_index = fid_index.ok_or_else(|| {
+            GeoArrowError::GeoPackage(format!(
+                "Table {table_name:?} has no INTEGER PRIMARY KEY column"
+            ))
+        })?;
+
+        let rtree = rtree_table_name(table_name, &geometry_column);
+        let use_rtree = options.bbox.is_some() && table_exists(conn, &rtree)?;
+        let fid = quote_identifier(&columns[fid_index].name);
+        let mut sql = format!(
+            "SELECT {} FROM {} WHERE {fid} > ?1",
+            columns
+                .iter()
+                .map(|column| quote_identifier(&column.name))
+                .collect::<Vec<_>>()
+                .join(", "),
+            quote_identifier(table_name),
+        );
+        if use_rtree {
+            sql.push_str(&format!(
+                " AND {fid} IN (SELECT id FROM {} \
+                 WHERE minx <= ?5 AND maxx >= ?3 AND miny <= ?6 AND maxy >= ?4)",
+                quote_identifier(&rtree)
+            ));
+        }
+        sql.push_str(&format!(" ORDER BY {fid} LIMIT ?2"));
+
+        let fields = columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                if index == geometry_index {
+                    geometry_type.to_field(&column.name, true)
+                } else {
+                    Field::new(&column.name, column.data_type.clone(), true)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            conn,
+            sql,
+            columns,
+            fid_index,
+            geometry_index,
+            geometry_type,
+            schema: Arc::new(Schema::new(fields)),
+            batch_size: options.batch_size,
+            bbox: options.bbox,
+            filter_blobs: options.bbox.is_some() && !use_rtree,
+            last_fid: i64::MIN,
+            finished: false,
+        })
+    }
+
+    fn process_batch(&mut self) -> GeoArrowResult<Option<RecordBatch>> {
+        let mut builders = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                if index == self.geometry_index {
+                    ColumnBuilder::Geometry(BinaryBuilder::new())
+                } else {
+                    ColumnBuilder::new(&column.data_type, self.batch_size)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let conn = self.conn;
+        let mut row_count = 0;
+        // Rows skipped by the bounding box filter don't count towards the batch, so keep reading
+        // pages until the batch is full or the table is exhausted.
+        while row_count < self.batch_size && !self.finished {
+            let limit = self.batch_size - row_count;
+            let mut params = vec![Value::Integer(self.last_fid), Value::Integer(limit as i64)];
+            if !self.filter_blobs
+                && let Some(bbox) = self.bbox
+            {
+                params.extend(bbox.map(Value::Real));
+            }
+
+            let mut statement = conn.prepare_cached(&self.sql).map_err(sqlite_error)?;
+            let mut rows = statement
+                .query(params_from_iter(params))
+                .map_err(sqlite_error)?;
+            let mut num_scanned = 0;
+            while let Some(row) = rows.next().map_err(sqlite_error)? {
+                num_scanned += 1;
+                self.last_fid = row.get(self.fid_index).map_err(sqlite_error)?;
+
+                if self.filter_blobs
+                    && let Some(bbox) = self.bbox
+                {
+                    let geometry = row.get_ref(self.geometry_index).map_err(sqlite_error)?;
+                    let intersects = match geometry {
+                        ValueRef::Blob(blob) => {
+                            blob_bounds(blob)?.is_some_and(|bounds| intersects(&bounds, &bbox))
+                        }
+                        _ => false,
+                    };
+                    if !intersects {
+                        continue;
+                    }
+                }
+
+                for (index, (builder, column)) in builders.iter_mut().zip(&self.columns).enumerate()
+                {
+                    builder.append(column, row.get_ref(index).map_err(sqlite_error)?)?;
+                }
+                row_count += 1;
+            }
+            if num_scanned < limit {
+                self.finished = true;
+            }
+        }
+
+        if row_count == 0 {
+            return Ok(None);
+        }
+
+        let columns = builders
+            .into_iter()
+            .map(|builder| builder.finish(&self.geometry_type))
+            .collect::<GeoArrowResult<Vec<_>>>()?;
+        Ok(Some(RecordBatch::try_new(self.schema.clone(), columns)?))
+    }
+}
+
+impl Iterator for GeoPackageReader<'_> {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.process_batch().map_err(|err| err.into()).transpose()
+    }
+}
+
+impl RecordBatchReader for GeoPackageReader<'_> {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+/// Read the CRS of an `srs_id` from `gpkg_spatial_ref_sys`.
+fn read_crs(conn: &Connection, srs_id: i32) -> GeoArrowResult<Crs> {
+    // The WKT2 definition is only present with the CRS WKT extension.
+    let wkt2_column = if column_exists(conn, "gpkg_spatial_ref_sys", "definition_12_063")? {
+        "definition_12_063"
+    } else {
+        "NULL"
+    };
+    let sql = format!(
+        "SELECT organization, organization_coordsys_id, definition, {wkt2_column} \
+         FROM gpkg_spatial_ref_sys WHERE srs_id = ?1"
+    );
+    let (organization, code, definition, wkt2) = conn
+        .query_row(&sql, [srs_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
+        })
+        .optional()
+        .map_err(sqlite_error)?
+        .ok_or_else(|| {
+            GeoArrowError::GeoPackage(format!(
+                "srs_id {srs_id} is not defined in gpkg_spatial_ref_sys"
+            ))
+        })?;
+
+    let is_defined = |definition: &str| {
+        let definition = definition.trim();
+        !definition.is_empty() && !definition.eq_ignore_ascii_case("undefined")
+    };
+    let crs = if srs_id == 0 || srs_id == -1 {
+        // The undefined geographic and Cartesian systems that every GeoPackage has.
+        Crs::default()
+    } else if !organization.eq_ignore_ascii_case("NONE") && code > 0 {
+        Crs::from_authority_code(format!("{}:{code}", organization.to_ascii_uppercase()))
+    } else if let Some(wkt2) = wkt2.filter(|wkt2| is_defined(wkt2)) {
+        Crs::from_wkt2_2019(wkt2)
+    } else if is_defined(&definition) {
+        Crs::from_unknown_crs_type(definition)
+    } else {
+        Crs::from_srid(srs_id.to_string())
+    };
+    Ok(crs)
+}
+
+/// The GeoArrow type for a geometry column, from its declared type and dimensions.
+///
+/// Columns whose Z or M values are optional are read as a [`GeometryType`], which can hold
+/// geometries of mixed dimensions.
+fn infer_geometry_type(
+    geometry_type_name: &str,
+    z: i64,
+    m: i64,
+    coord_type: CoordType,
+    metadata: Arc<Metadata>,
+) -> GeoArrowType {
+    let dim = match (z, m) {
+        (0, 0) => Some(Dimension::XY),
+        (1, 0) => Some(Dimension::XYZ),
+        (0, 1) => Some(Dimension::XYM),
+        (1, 1) => Some(Dimension::XYZM),
+        _ => None,
+    };
+    match (geometry_type_name.to_ascii_uppercase().as_str(), dim) {
+        ("POINT", Some(dim)) => PointType::new(dim, metadata)
+            .with_coord_type(coord_type)
+            .into(),
+        ("LINESTRING", Some(dim)) => LineStringType::new(dim, metadata)
+            .with_coord_type(coord_type)
+            .into(),
+        ("POLYGON", Some(dim)) => PolygonType::new(dim, metadata)
+            .with_coord_type(coord_type)
+            .into(),
+        ("MULTIPOINT", Some(dim)) => MultiPointType::new(dim, metadata)
+            .with_coord_type(coord_type)
+            .into(),
+        ("MULTILINESTRING", Some(dim)) => MultiLineStringType::new(dim, metadata)
+            .with_coord_type(coord_type)
+            .into(),
+        ("MULTIPOLYGON", Some(dim)) => MultiPolygonType::new(dim, metadata)
+            .with_coord_type(coord_type)
+            .into(),
+        ("GEOMETRYCOLLECTION", Some(dim)) => GeometryCollectionType::new(dim, metadata)
+            .with_coord_type(coord_type)
+            .into(),
+        _ => GeometryType::new(metadata)
+            .with_coord_type(coord_type)
+            .into(),
+    }
+}
+
+/// The Arrow type that a column with the given declared type is read as.
+fn arrow_type(declared_type: &str) -> DataType {
+    let declared_type = declared_type.to_ascii_uppercase();
+    // Strip a maximum length, as in `TEXT(20)`.
+    let base_type = declared_type.split('(').next().unwrap_or_default().trim();
+    match base_type {
+        "BOOLEAN" => DataType::Boolean,
+        "TINYINT" => DataType::Int8,
+        "SMALLINT" => DataType::Int16,
+        "MEDIUMINT" => DataType::Int32,
+        "INT" | "INTEGER" => DataType::Int64,
+        "FLOAT" => DataType::Float32,
+        "DOUBLE" | "REAL" => DataType::Float64,
+        "TEXT" => DataType::Utf8,
+        "BLOB" => DataType::Binary,
+        "DATE" => DataType::Date32,
+        "DATETIME" => DataType::Timestamp(TimeUnit::Millisecond, Some("+00:00".into())),
+        // https://www.sqlite.org/datatype3.html#determination_of_column_affinity
+        other if other.contains("INT") => DataType::Int64,
+        other if other.contains("CHAR") || other.contains("CLOB") || other.contains("TEXT") => {
+            DataType::Utf8
+        }
+        other if other.is_empty() || other.contains("BLOB") => DataType::Binary,
+        _ => DataType::Float64,
+    }
+}
+
+fn intersects(bounds: &[f64; 4], bbox: &[f64; 4]) -> bool {
+    bounds[0] <= bbox[2] && bounds[2] >= bbox[0] && bounds[1] <= bbox[3] && bounds[3] >= bbox[1]
+}
+
+/// Builds the Arrow array of one column from SQLite values.
+enum ColumnBuilder {
+    Boolean(BooleanBuilder),
+    Int8(Int8Builder),
+    Int16(Int16Builder),
+    Int32(Int32Builder),
+    Int64(Int64Builder),
+    Float32(Float32Builder),
+    Float64(Float64Builder),
+    String(StringBuilder),
+    Binary(BinaryBuilder),
+    /// Dates and datetimes are stored as ISO 8601 text, and parsed when the batch is finished.
+    Temporal(StringBuilder, DataType),
+    Geometry(BinaryBuilder),
+}
+
+impl ColumnBuilder {
+    fn new(data_type: &DataType, capacity: usize) -> Self {
+        match data_type {
+            DataType::Boolean => Self::Boolean(BooleanBuilder::with_capacity(capacity)),
+            DataType::Int8 => Self::Int8(Int8Builder::with_capacity(capacity)),
+            DataType::Int16 => Self::Int16(Int16Builder::with_capacity(capacity)),
+            DataType::Int32 => Self::Int32(Int32Builder::with_capacity(capacity)),
+            DataType::Int64 => Self::Int64(Int64Builder::with_capacity(capacity)),
+            DataType::Float32 => Self::Float32(Float32Builder::with_capacity(capacity)),
+            DataType::Float64 => Self::Float64(Float64Builder::with_capacity(capacity)),
+            DataType::Utf8 => Self::String(StringBuilder::new()),
+            DataType::Binary => Self::Binary(BinaryBuilder::new()),
+            data_type => Self::Temporal(StringBuilder::new(), data_type.clone()),
+        }
+    }
+
+    fn append(&mut self, column: &SqliteColumn, value: ValueRef<'_>) -> GeoArrowResult<()> {
+        if matches!(value, ValueRef::Null) {
+            self.append_null();
+            return Ok(());
+        }
+        // SQLite is dynamically typed, so accept any value that converts without loss.
+        match (self, value) {
+            (Self::Boolean(builder), ValueRef::Integer(value)) => builder.append_value(value != 0),
+            (Self::Int8(builder), ValueRef::Integer(value)) => {
+                builder.append_value(integer(value, column)?)
+            }
+            (Self::Int16(builder), ValueRef::Integer(value)) => {
+                builder.append_value(integer(value, column)?)
+            }
+            (Self::Int32(builder), ValueRef::Integer(value)) => {
+                builder.append_value(integer(value, column)?)
+            }
+            (Self::Int64(builder), ValueRef::Integer(value)) => builder.append_value(value),
+            (Self::Float32(builder), ValueRef::Real(value)) => builder.append_value(value as f32),
+            (Self::Float32(builder), ValueRef::Integer(value)) => {
+                builder.append_value(value as f32)
+            }
+            (Self::Float64(builder), ValueRef::Real(value)) => builder.append_value(value),
+            (Self::Float64(builder), ValueRef::Integer(value)) => {
+                builder.append_value(value as f64)
+            }
+            (Self::String(builder) | Self::Temporal(builder, _), ValueRef::Text(value)) => {
+                builder.append_value(utf8(value, column)?)
+            }
+            (Self::String(builder), ValueRef::Integer(value)) => {
+                builder.append_value(value.to_string())
+            }
+            (Self::String(builder), ValueRef::Real(value)) => {
+                builder.append_value(value.to_string())
+            }
+            (Self::Binary(builder), ValueRef::Blob(value) | ValueRef::Text(value)) => {
+                builder.append_value(value)
+            }
+            (Self::Geometry(builder), ValueRef::Blob(value)) => builder.append_value(value),
+            (_, value) => {
+                return Err(GeoArrowError::GeoPackage(format!(
+                    "Unexpected {} value in column {:?}",
+                    value.data_type(),
+                    column.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn append_null(&mut self) {
+        match self {
+            Self::Boolean(builder) => builder.append_null(),
+            Self::Int8(builder) => builder.append_null(),
+            Self::Int16(builder) => builder.append_null(),
+            Self::Int32(builder) => builder.append_null(),
+            Self::Int64(builder) => builder.append_null(),
+            Self::Float32(builder) => builder.append_null(),
+            Self::Float64(builder) => builder.append_null(),
+            Self::String(builder) | Self::Temporal(builder, _) => builder.append_null(),
+            Self::Binary(builder) | Self::Geometry(builder) => builder.append_null(),
+        }
+    }
+
+    fn finish(self, geometry_type: &GeoArrowType) -> GeoArrowResult<ArrayRef> {
+        let array: ArrayRef = match self {
+            Self::Boolean(mut builder) => Arc::new(builder.finish()),
+            Self::Int8(mut builder) => Arc::new(builder.finish()),
+            Self::Int16(mut builder) => Arc::new(builder.finish()),
+            Self::Int32(mut builder) => Arc::new(builder.finish()),
+            Self::Int64(mut builder) => Arc::new(builder.finish()),
+            Self::Float32(mut builder) => Arc::new(builder.finish()),
+            Self::Float64(mut builder) => Arc::new(builder.finish()),
+            Self::String(mut builder) => Arc::new(builder.finish()),
+            Self::Binary(mut builder) => Arc::new(builder.finish()),
+            Self::Temporal(mut builder, data_type) => {
+                let options = CastOptions {
+                    safe: false,
+                    ..Default::default()
+                };
+                arrow_cast::cast_with_options(&builder.finish(), &data_type, &options)?
+            }
+            Self::Geometry(mut builder) => {
+                let blobs = WkbArray::new(builder.finish(), geometry_type.metadata().clone());
+                let options = WkbDialectOptions::new(WkbDialect::GeoPackage);
+                from_wkb_dialect(&blobs, geometry_type.clone(), &options)?.into_array_ref()
+            }
+        };
+        Ok(array)
+    }
+}
+
+fn integer<T: TryFrom<i64>>(value: i64, column: &SqliteColumn) -> GeoArrowResult<T> {
+    T::try_from(value).map_err(|_| {
+        GeoArrowError::GeoPackage(format!(
+            "Value {value} in column {:?} is out of range for {}",
+            column.name, column.data_type
+        ))
+    })
+}
+
+fn utf8<'a>(value: &'a [u8], column: &SqliteColumn) -> GeoArrowResult<&'a str> {
+    std::str::from_utf8(value).map_err(|err| {
+        GeoArrowError::GeoPackage(format!("Invalid UTF-8 in column {:?}: {err}", column.name))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Float64Type, Int64Type, TimestampMillisecondType};
+    use geo_traits::{GeometryTrait, LineStringTrait};
+    use geoarrow_array::GeoArrowArrayAccessor;
+    use geoarrow_array::array::LineStringArray;
+    use geoarrow_schema::WkbType;
+    use wkb::reader::Wkb;
+
+    use super::*;
+
+    /// A GeoPackage as another tool might write it, with the CRS WKT extension and a big-endian
+    /// geometry blob without an envelope.
+    fn roads() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE gpkg_spatial_ref_sys (
+                srs_name TEXT NOT NULL, srs_id INTEGER PRIMARY KEY, organization TEXT NOT NULL,
+                organization_coordsys_id INTEGER NOT NULL, definition TEXT NOT NULL,
+                description TEXT, definition_12_063 TEXT NOT NULL
+            );
+            INSERT INTO gpkg_spatial_ref_sys VALUES
+                ('Local', 100, 'NONE', 100, 'undefined', NULL, 'ENGCRS["Local"]');
+            CREATE TABLE gpkg_contents (
+                table_name TEXT NOT NULL PRIMARY KEY, data_type TEXT NOT NULL, srs_id INTEGER
+            );
+            INSERT INTO gpkg_contents VALUES ('roads', 'features', 100);
+            CREATE TABLE gpkg_geometry_columns (
+                table_name TEXT NOT NULL, column_name TEXT NOT NULL,
+                geometry_type_name TEXT NOT NULL, srs_id INTEGER NOT NULL, z TINYINT NOT NULL,
+                m TINYINT NOT NULL
+            );
+            INSERT INTO gpkg_geometry_columns VALUES ('roads', 'geom', 'LINESTRING', 100, 0, 0);
+            CREATE TABLE roads (
+                id INTEGER PRIMARY KEY, name TEXT(20), length REAL, opened DATETIME, geom LINESTRING
+            );
+            "#,
+        )
+        .unwrap();
+
+        // `LINESTRING (0 0, 3 4)`
+        let mut blob = b"GP\x00\x00".to_vec();
+        blob.extend_from_slice(&100_i32.to_be_bytes());
+        blob.push(0);
+        blob.extend_from_slice(&2_u32.to_be_bytes());
+        blob.extend_from_slice(&2_u32.to_be_bytes());
+        for value in [0., 0., 3., 4.] {
+            blob.extend_from_slice(&f64::to_be_bytes(value));
+        }
+        conn.execute(
+            "INSERT INTO roads VALUES (7, 'Main', 5, '2024-01-01T00:00:00.000Z', ?1), \
+             (8, NULL, NULL, NULL, NULL)",
+            [blob],
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn read_table() {
+        let conn = roads();
+        assert_eq!(feature_tables(&conn).unwrap(), ["roads"]);
+        let reader =
+            GeoPackageReader::try_new(&conn, "roads", GeoPackageReaderOptions::default()).unwrap();
+        let schema = reader.schema();
+        let data_types = schema
+            .fields()
+            .iter()
+            .take(4)
+            .map(|field| field.data_type().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            data_types,
+            [
+                DataType::Int64,
+                DataType::Utf8,
+                DataType::Float64,
+                DataType::Timestamp(TimeUnit::Millisecond, Some("+00:00".into()))
+            ]
+        );
+        let geometry_type = GeoArrowType::from_extension_field(schema.field(4))
+            .unwrap()
+            .unwrap();
+        assert!(matches!(geometry_type, GeoArrowType::LineString(_)));
+        assert_eq!(
+            geometry_type.metadata().crs(),
+            &Crs::from_wkt2_2019("ENGCRS[\"Local\"]".to_string())
+        );
+
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.column(0).as_primitive::<Int64Type>().value(0), 7);
+        assert_eq!(batch.column(1).as_string::<i32>().value(0), "Main");
+        // An integer stored in a REAL column.
+        assert_eq!(batch.column(2).as_primitive::<Float64Type>().value(0), 5.);
+        assert_eq!(
+            batch
+                .column(3)
+                .as_primitive::<TimestampMillisecondType>()
+                .value(0),
+            1_704_067_200_000
+        );
+        assert!(batch.column(3).is_null(1));
+        let lines = LineStringArray::try_from((batch.column(4).as_ref(), schema.field(4))).unwrap();
+        assert_eq!(lines.value(0).unwrap().num_coords(), 2);
+        assert!(lines.is_null(1));
+    }
+
+    #[test]
+    fn read_as_wkb() {
+        let conn = roads();
+        let options = GeoPackageReaderOptions {
+            geometry_type: Some(GeoArrowType::Wkb(WkbType::new(Default::default()))),
+            ..Default::default()
+        };
+        let mut reader = GeoPackageReader::try_new(&conn, "roads", options).unwrap();
+        let batch = reader.next().unwrap().unwrap();
+        // The GeoPackage header is stripped, leaving plain WKB.
+        let wkb = Wkb::try_new(batch.column(4).as_binary::<i32>().value(0)).unwrap();
+        assert!(matches!(
+            wkb.as_type(),
+            geo_traits::GeometryType::LineString(_)
+        ));
+    }
+
+    #[test]
+    fn not_a_feature_table() {
+        let conn = roads();
+        let err = GeoPackageReader::try_new(&conn, "gpkg_contents", Default::default())
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("not a feature table"), "{err}");
+    }
+}
@@ -0,0 +1,47 @@
+//! Helpers shared by the reader and the writer for building SQL and querying table metadata.
+
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+use rusqlite::{Connection, OptionalExtension};
+
+/// Quote an identifier, such as a table or column name, for use in SQL.
+pub(crate) fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// The name of the R-tree that indexes a geometry column, as defined by the
+/// [R-tree spatial index extension](https://www.geopackage.org/spec/#extension_rtree).
+pub(crate) fn rtree_table_name(table_name: &str, column_name: &str) -> String {
+    format!("rtree_{table_name}_{column_name}")
+}
+
+/// Whether a table (or virtual table) with the given name exists.
+pub(crate) fn table_exists(conn: &Connection, table_name: &str) -> GeoArrowResult<bool> {
+    conn.query_row(
+        "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        [table_name],
+        |_| Ok(()),
+    )
+    .optional()
+    .map(|row| row.is_some())
+    .map_err(sqlite_error)
+}
+
+/// Whether a table has a column with the given name.
+pub(crate) fn column_exists(
+    conn: &Connection,
+    table_name: &str,
+    column_name: &str,
+) -> GeoArrowResult<bool> {
+    conn.query_row(
+        "SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2",
+        [table_name, column_name],
+        |_| Ok(()),
+    )
+    .optional()
+    .map(|row| row.is_some())
+    .map_err(sqlite_error)
+}
+
+pub(crate) fn sqlite_error(err: rusqlite::Error) -> GeoArrowError {
+    GeoArrowError::External(Box::new(err))
+}
@@ -0,0 +1,1085 @@
+//! Write feature tables to [GeoPackage](https://www.geopackage.org/) files.
+//!
+//! The writer creates the GeoPackage metadata tables if they don't exist yet, creates the feature
+//! table, and registers it in `gpkg_contents`, `gpkg_geometry_columns` and, for the CRS of the
+//! geometry column, `gpkg_spatial_ref_sys`. By default it also maintains an
+//! [R-tree spatial index](https://www.geopackage.org/spec/#extension_rtree).
+
+use std::path::Path;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Date32Type, Float64Type, Int64Type, TimestampMillisecondType};
+use arrow_array::{ArrayRef, RecordBatch, RecordBatchReader};
+use arrow_cast::CastOptions;
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use geoarrow_array::GeoArrowArrayAccessor;
+use geoarrow_array::array::from_arrow_array;
+use geoarrow_array::bounds::BoundingRect;
+use geoarrow_array::cast::to_wkb;
+use geoarrow_schema::crs::{CrsTransform, DefaultCrsTransform};
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+use geoarrow_schema::{Crs, CrsType, Dimension, GeoArrowType, Metadata};
+use rusqlite::types::Value;
+use rusqlite::{Connection, OptionalExtension, params, params_from_iter};
+
+use crate::blob::push_blob;
+use crate::sql::{quote_identifier, rtree_table_name, sqlite_error};
+
+/// The `application_id` of a GeoPackage, the ASCII string `GPKG`.
+const APPLICATION_ID: i32 = 0x4750_4B47;
+
+/// The `user_version` of a GeoPackage 1.4.0 file.
+const USER_VERSION: i32 = 10_400;
+
+/// The name of the primary key column created when the schema has no `fid` column.
+const FID_COLUMN: &str = "fid";
+
+/// The first `srs_id` used for CRSs that aren't identified by an EPSG code.
+const FIRST_CUSTOM_SRS_ID: i32 = 100_000;
+
+/// The tables that every GeoPackage has, with the three spatial reference systems it must
+/// define.
+const CORE_TABLES: &str = r#"
+CREATE TABLE IF NOT EXISTS gpkg_spatial_ref_sys (
+    srs_name TEXT NOT NULL,
+    srs_id INTEGER PRIMARY KEY,
+    organization TEXT NOT NULL,
+    organization_coordsys_id INTEGER NOT NULL,
+    definition TEXT NOT NULL,
+    description TEXT
+);
+INSERT OR IGNORE INTO gpkg_spatial_ref_sys VALUES
+    ('WGS 84 geodetic', 4326, 'EPSG', 4326, 'GEOGCS["WGS 84",DATUM["WGS_1984",SPHEROID["WGS 84",6378137,298.257223563,AUTHORITY["EPSG","7030"]],AUTHORITY["EPSG","6326"]],PRIMEM["Greenwich",0,AUTHORITY["EPSG","8901"]],UNIT["degree",0.0174532925199433,AUTHORITY["EPSG","9122"]],AXIS["Latitude",NORTH],AXIS["Longitude",EAST],AUTHORITY["EPSG","4326"]]', 'longitude/latitude coordinates in decimal degrees on the WGS 84 spheroid'),
+    ('Undefined cartesian SRS', -1, 'NONE', -1, 'undefined', 'undefined cartesian coordinate reference system'),
+    ('Undefined geographic SRS', 0, 'NONE', 0, 'undefined', 'undefined geographic coordinate reference system');
+CREATE TABLE IF NOT EXISTS gpkg_contents (
+    table_name TEXT NOT NULL PRIMARY KEY,
+    data_type TEXT NOT NULL,
+    identifier TEXT UNIQUE,
+    description TEXT DEFAULT '',
+    last_change DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
+    min_x DOUBLE,
+    min_y DOUBLE,
+    max_x DOUBLE,
+    max_y DOUBLE,
+    srs_id INTEGER,
+    CONSTRAINT fk_gc_r_srs_id FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys(srs_id)
+);
+CREATE TABLE IF NOT EXISTS gpkg_geometry_columns (
+    table_name TEXT NOT NULL,
+    column_name TEXT NOT NULL,
+    geometry_type_name TEXT NOT NULL,
+    srs_id INTEGER NOT NULL,
+    z TINYINT NOT NULL,
+    m TINYINT NOT NULL,
+    CONSTRAINT pk_geom_cols PRIMARY KEY (table_name, column_name),
+    CONSTRAINT uk_gc_table_name UNIQUE (table_name),
+    CONSTRAINT fk_gc_tn FOREIGN KEY (table_name) REFERENCES gpkg_contents(table_name),
+    CONSTRAINT fk_gc_srs FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys (srs_id)
+);
+CREATE TABLE IF NOT EXISTS gpkg_extensions (
+    table_name TEXT,
+    column_name TEXT,
+    extension_name TEXT NOT NULL,
+    definition TEXT NOT NULL,
+    scope TEXT NOT NULL,
+    CONSTRAINT ge_tce UNIQUE (table_name, column_name, extension_name)
+);
+"#;
+
+/// Options for the GeoPackage writer
+#[derive(Debug)]
+pub struct GeoPackageWriterOptions {
+    spatial_index: bool,
+    crs_transform: Option<Box<dyn CrsTransform>>,
+}
+
+impl Default for GeoPackageWriterOptions {
+    fn default() -> Self {
+        Self {
+            spatial_index: true,
+            crs_transform: None,
+        }
+    }
+}
+
+impl GeoPackageWriterOptions {
+    /// Set whether to create an R-tree spatial index on the geometry column. Defaults to `true`.
+    pub fn with_spatial_index(self, spatial_index: bool) -> Self {
+        Self {
+            spatial_index,
+            ..self
+        }
+    }
+
+    /// Set the method for transforming CRS to WKT
+    ///
+    /// This is implemented as an external trait so that external libraries can inject the method
+    /// for CRS conversions. Without one, CRSs identified by an authority code are written with
+    /// an `undefined` definition, and only CRSs stored as WKT2:2019 or as a WKT string of unknown
+    /// type get a definition.
+    pub fn with_crs_transform(self, crs_transform: Box<dyn CrsTransform>) -> Self {
+        Self {
+            crs_transform: Some(crs_transform),
+            ..self
+        }
+    }
+
+    /// The WKT definition of a CRS, if it has one or can be converted to one.
+    fn definition(&self, crs: &Crs) -> GeoArrowResult<Option<String>> {
+        if crs.crs_type().is_none()
+            && let Some(wkt) = crs.crs_value().and_then(|value| value.as_str())
+            && wkt.contains('[')
+        {
+            return Ok(Some(wkt.to_string()));
+        }
+
+        if let Some(crs_transform) = &self.crs_transform {
+            crs_transform.extract_wkt(crs)
+        } else {
+            DefaultCrsTransform::default().extract_wkt(crs)
+        }
+    }
+}
+
+/// A GeoPackage feature table writer.
+///
+/// The schema must have exactly one geometry column. Every other column becomes a column of the
+/// feature table:
+///
+/// | Arrow                       | GeoPackage  |
+/// | --------------------------- | ----------- |
+/// | `Boolean`                   | `BOOLEAN`   |
+/// | `Int8`                      | `TINYINT`   |
+/// | `Int16`, `UInt8`            | `SMALLINT`  |
+/// | `Int32`, `UInt16`           | `MEDIUMINT` |
+/// | `Int64`, `UInt32`, `UInt64` | `INTEGER`   |
+/// | `Float16`, `Float32`        | `FLOAT`     |
+/// | `Float64` and decimals      | `DOUBLE`    |
+/// | Strings                     | `TEXT`      |
+/// | Binary                      | `BLOB`      |
+/// | `Date32`, `Date64`          | `DATE`      |
+/// | Timestamps                  | `DATETIME`  |
+///
+/// Other column types are an error. A column named `fid` with an integer type becomes the
+/// primary key; otherwise an `fid` primary key is added. Timestamps without a timezone are
+/// written as UTC.
+///
+/// Geometries are written as GeoPackage blobs with an XY envelope. The CRS of the geometry column
+/// is matched to an existing entry of `gpkg_spatial_ref_sys` by its authority code or WKT
+/// definition, or added to it. A geometry column without a CRS is written with the undefined
+/// Cartesian `srs_id` -1, or the undefined geographic `srs_id` 0 if it has spherical edges.
+///
+/// The writer doesn't manage transactions. Wrap it in one for a much faster write, as
+/// [`write_geopackage`] does.
+pub struct GeoPackageWriter<'c> {
+    conn: &'c Connection,
+    table_name: String,
+    geometry_column: String,
+    fid_column: String,
+    schema: SchemaRef,
+    geometry_index: usize,
+    columns: Vec<GpkgColumn>,
+    srs_id: i32,
+    insert_sql: String,
+    rtree_sql: Option<String>,
+    extent: BoundingRect,
+}
+
+impl<'c> GeoPackageWriter<'c> {
+    /// Create a new feature table with the given schema, registering it in the GeoPackage
+    /// metadata tables.
+    ///
+    /// This creates the metadata tables if `conn` is an empty database. It's an error if the
+    /// table already exists.
+    pub fn try_new(
+        conn: &'c Connection,
+        table_name: &str,
+        schema: SchemaRef,
+        options: GeoPackageWriterOptions,
+    ) -> GeoArrowResult<Self> {
+        let geometry_index = geometry_column(&schema)?;
+        let geometry_field = schema.field(geometry_index);
+        let geometry_type = GeoArrowType::try_from(geometry_field)?;
+        let columns = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != geometry_index)
+            .map(|(index, field)| GpkgColumn::try_new(index, field))
+            .collect::<GeoArrowResult<Vec<_>>>()?;
+
+        let fid_column = columns
+            .iter()
+            .find(|column| column.name.eq_ignore_ascii_case(FID_COLUMN));
+        if let Some(column) = fid_column
+            && column.kind != ColumnKind::Integer
+        {
+            return Err(GeoArrowError::GeoPackage(format!(
+                "Column {:?} must have an integer type to be used as the primary key",
+                column.name
+            )));
+        }
+        let fid_column = fid_column
+            .map_or(FID_COLUMN, |column| column.name.as_str())
+            .to_string();
+
+        init_geopackage(conn)?;
+        let srs_id = srs_id(conn, geometry_type.metadata(), &options)?;
+
+        // The feature table, with the columns in schema order.
+        let mut definitions = Vec::new();
+        if !columns.iter().any(|column| column.name == fid_column) {
+            definitions.push(fid_definition(&fid_column));
+        }
+        for (index, field) in schema.fields().iter().enumerate() {
+            if index == geometry_index {
+                let (geometry_type_name, _, _) = geometry_type_name(&geometry_type);
+                definitions.push(format!(
+                    "{} {geometry_type_name}",
+                    quote_identifier(field.name())
+                ));
+            } else if field.name() == &fid_column {
+                definitions.push(fid_definition(&fid_column));
+            } else {
+                let column = columns
+                    .iter()
+                    .find(|column| column.column_index == index)
+                    .unwrap();
+                definitions.push(format!(
+                    "{} {}",
+                    quote_identifier(&column.name),
+                    column.sql_type
+                ));
+            }
+        }
+        conn.execute_batch(&format!(
+            "CREATE TABLE {} ({})",
+            quote_identifier(table_name),
+            definitions.join(", ")
+        ))
+        .map_err(sqlite_error)?;
+
+        let (geometry_type_name, z, m) = geometry_type_name(&geometry_type);
+        conn.execute(
+            "INSERT INTO gpkg_contents (table_name, data_type, identifier, srs_id) \
+             VALUES (?1, 'features', ?1, ?2)",
+            params![table_name, srs_id],
+        )
+        .map_err(sqlite_error)?;
+        conn.execute(
+            "INSERT INTO gpkg_geometry_columns VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                table_name,
+                geometry_field.name(),
+                geometry_type_name,
+                srs_id,
+                z,
+                m
+            ],
+        )
+        .map_err(sqlite_error)?;
+
+        let rtree_sql = if options.spatial_index {
+            let rtree = quote_identifier(&rtree_table_name(table_name, geometry_field.name()));
+            conn.execute_batch(&format!(
+                "CREATE VIRTUAL TABLE {rtree} USING rtree(id, minx, maxx, miny, maxy)"
+            ))
+            .map_err(sqlite_error)?;
+            conn.execute(
+                "INSERT INTO gpkg_extensions VALUES (?1, ?2, 'gpkg_rtree_index', \
+                 'http://www.geopackage.org/spec120/#extension_rtree', 'write-only')",
+                params![table_name, geometry_field.name()],
+            )
+            .map_err(sqlite_error)?;
+            Some(format!("INSERT INTO {rtree} VALUES (?1, ?2, ?3, ?4, ?5)"))
+        } else {
+            None
+        };
+
+        let insert_columns = columns
+            .iter()
+            .map(|column| quote_identifier(&column.name))
+            .chain([quote_identifier(geometry_field.name())])
+            .collect::<Vec<_>>();
+        let insert_sql = format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            quote_identifier(table_name),
+            insert_columns.join(", "),
+            (1..=insert_columns.len())
+                .map(|index| format!("?{index}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        Ok(Self {
+            conn,
+            table_name: table_name.to_string(),
+            geometry_column: geometry_field.name().clone(),
+            fid_column,
+            schema,
+            geometry_index,
+            columns,
+            srs_id,
+            insert_sql,
+            rtree_sql,
+            extent: BoundingRect::new(),
+        })
+    }
+
+    /// Write a batch of data to the feature table.
+    ///
+    /// This will error if the schema of the `RecordBatch` does not match the schema originally
+    /// passed to [`GeoPackageWriter::try_new`].
+    pub fn write(&mut self, batch: &RecordBatch) -> GeoArrowResult<()> {
+        if batch.schema_ref().fields() != self.schema.fields() {
+            return Err(GeoArrowError::GeoPackage(
+                "Record batch schema does not match the writer schema".to_string(),
+            ));
+        }
+
+        let geometry = from_arrow_array(
+            batch.column(self.geometry_index),
+            self.schema.field(self.geometry_index),
+        )?;
+        let geometry = to_wkb::<i32>(geometry.as_ref())?;
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| column.kind.cast(batch.column(column.column_index)))
+            .collect::<GeoArrowResult<Vec<_>>>()?;
+
+        let conn = self.conn;
+        let mut insert = conn
+            .prepare_cached(&self.insert_sql)
+            .map_err(sqlite_error)?;
+        let mut rtree = self
+            .rtree_sql
+            .as_ref()
+            .map(|sql| conn.prepare_cached(sql))
+            .transpose()
+            .map_err(sqlite_error)?;
+
+        let mut values = Vec::with_capacity(self.columns.len() + 1);
+        for (row, value) in geometry.iter().enumerate() {
+            values.clear();
+            for (column, array) in self.columns.iter().zip(&columns) {
+                values.push(column.kind.value(array, row));
+            }
+
+            let mut bounds = BoundingRect::new();
+            match value {
+                Some(value) => {
+                    bounds.add_geometry(&value?);
+                    let mut blob = Vec::new();
+                    push_blob(&mut blob, self.srs_id, &bounds, geometry.inner().value(row));
+                    values.push(Value::Blob(blob));
+                }
+                None => values.push(Value::Null),
+            }
+            insert
+                .execute(params_from_iter(&values))
+                .map_err(sqlite_error)?;
+
+            if bounds.is_empty() {
+                continue;
+            }
+            self.extent.update(&bounds);
+            if let Some(rtree) = &mut rtree {
+                rtree
+                    .execute(params![
+                        conn.last_insert_rowid(),
+                        bounds.minx(),
+                        bounds.maxx(),
+                        bounds.miny(),
+                        bounds.maxy()
+                    ])
+                    .map_err(sqlite_error)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Finish writing the feature table.
+    ///
+    /// This records the extent of the data in `gpkg_contents` and, with a spatial index, creates
+    /// the triggers that keep the R-tree up to date when the table is edited later.
+    pub fn finish(self) -> GeoArrowResult<()> {
+        if !self.extent.is_empty() {
+            self.conn
+                .execute(
+                    "UPDATE gpkg_contents SET min_x = ?2, min_y = ?3, max_x = ?4, max_y = ?5, \
+                     last_change = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE table_name = ?1",
+                    params![
+                        self.table_name,
+                        self.extent.minx(),
+                        self.extent.miny(),
+                        self.extent.maxx(),
+                        self.extent.maxy()
+                    ],
+                )
+                .map_err(sqlite_error)?;
+        }
+
+        // The triggers call functions that GeoPackage-aware SQLite builds provide, so they are
+        // only created once this writer has filled the R-tree itself.
+        if self.rtree_sql.is_some() {
+            self.conn
+                .execute_batch(&rtree_triggers(
+                    &self.table_name,
+                    &self.geometry_column,
+                    &self.fid_column,
+                ))
+                .map_err(sqlite_error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write a stream of GeoArrow RecordBatches to a feature table of a GeoPackage file.
+///
+/// The file is created if it doesn't exist, and the table is written in a single transaction.
+pub fn write_geopackage<S: RecordBatchReader>(
+    stream: S,
+    path: impl AsRef<Path>,
+    table_name: &str,
+    options: GeoPackageWriterOptions,
+) -> GeoArrowResult<()> {
+    let mut conn = Connection::open(path).map_err(sqlite_error)?;
+    let transaction = conn.transaction().map_err(sqlite_error)?;
+    let mut writer = GeoPackageWriter::try_new(&transaction, table_name, stream.schema(), options)?;
+    for batch in stream {
+        writer.write(&batch?)?;
+    }
+    writer.finish()?;
+    transaction.commit().map_err(sqlite_error)
+}
+
+/// Mark an empty database as a GeoPackage and create the metadata tables that don't exist yet.
+fn init_geopackage(conn: &Connection) -> GeoArrowResult<()> {
+    let application_id = conn
+        .query_row("PRAGMA application_id", [], |row| row.get::<_, i32>(0))
+        .map_err(sqlite_error)?;
+    match application_id {
+        0 => conn
+            .execute_batch(&format!(
+                "PRAGMA application_id = {APPLICATION_ID}; PRAGMA user_version = {USER_VERSION};"
+            ))
+            .map_err(sqlite_error)?,
+        APPLICATION_ID => {}
+        other => {
+            return Err(GeoArrowError::GeoPackage(format!(
+                "Database has application_id {other:#x}, so it is not a GeoPackage"
+            )));
+        }
+    }
+    conn.execute_batch(CORE_TABLES).map_err(sqlite_error)
+}
+
+/// Find or add the `gpkg_spatial_ref_sys` entry for the CRS of a geometry column.
+fn srs_id(
+    conn: &Connection,
+    metadata: &Metadata,
+    options: &GeoPackageWriterOptions,
+) -> GeoArrowResult<i32> {
+    let crs = metadata.crs();
+    let undefined_srs_id = if metadata.edges().is_some() { 0 } else { -1 };
+    if crs.crs_value().is_none() {
+        return Ok(undefined_srs_id);
+    }
+
+    if crs.crs_type() == Some(CrsType::Srid) {
+        let srid = crs
+            .crs_value()
+            .and_then(|value| value.as_str())
+            .and_then(|value| value.parse::<i32>().ok())
+            .ok_or_else(|| GeoArrowError::Crs(format!("Invalid SRID {:?}", crs.crs_value())))?;
+        if !srs_exists(conn, srid)? {
+            insert_srs(conn, &format!("SRID {srid}"), srid, "NONE", srid, None)?;
+        }
+        return Ok(srid);
+    }
+
+    let definition = options.definition(crs)?;
+    let authority_code = crs.authority_code().and_then(|(authority, code)| {
+        match (authority.as_str(), code.as_str()) {
+            ("OGC", "CRS84") => Some(("EPSG".to_string(), 4326)),
+            (_, code) => Some((authority, code.parse::<i32>().ok()?)),
+        }
+    });
+    if let Some((organization, code)) = authority_code {
+        let existing = conn
+            .query_row(
+                "SELECT srs_id FROM gpkg_spatial_ref_sys \
+                 WHERE upper(organization) = ?1 AND organization_coordsys_id = ?2",
+                params![organization, code],
+                |row| row.get::<_, i32>(0),
+            )
+            .optional()
+            .map_err(sqlite_error)?;
+        if let Some(srs_id) = existing {
+            return Ok(srs_id);
+        }
+        let srs_id = if organization == "EPSG" && !srs_exists(conn, code)? {
+            code
+        } else {
+            next_srs_id(conn)?
+        };
+        let name = format!("{organization}:{code}");
+        insert_srs(
+            conn,
+            &name,
+            srs_id,
+            &organization,
+            code,
+            definition.as_deref(),
+        )?;
+        return Ok(srs_id);
+    }
+
+    if let Some(definition) = definition {
+        let existing = conn
+            .query_row(
+                "SELECT srs_id FROM gpkg_spatial_ref_sys WHERE definition = ?1",
+                [&definition],
+                |row| row.get::<_, i32>(0),
+            )
+            .optional()
+            .map_err(sqlite_error)?;
+        if let Some(srs_id) = existing {
+            return Ok(srs_id);
+        }
+        let srs_id = next_srs_id(conn)?;
+        insert_srs(conn, "Unknown", srs_id, "NONE", srs_id, Some(&definition))?;
+        return Ok(srs_id);
+    }
+
+    // A CRS that can't be identified or converted to WKT is dropped, as in other writers.
+    Ok(undefined_srs_id)
+}
+
+fn srs_exists(conn: &Connection, srs_id: i32) -> GeoArrowResult<bool> {
+    conn.query_row(
+        "SELECT 1 FROM gpkg_spatial_ref_sys WHERE srs_id = ?1",
+        [srs_id],
+        |_| Ok(()),
+    )
+    .optional()
+    .map(|row| row.is_some())
+    .map_err(sqlite_error)
+}
+
+fn next_srs_id(conn: &Connection) -> GeoArrowResult<i32> {
+    conn.query_row(
+        "SELECT max(?1, coalesce(max(srs_id), 0) + 1) FROM gpkg_spatial_ref_sys",
+        [FIRST_CUSTOM_SRS_ID],
+        |row| row.get(0),
+    )
+    .map_err(sqlite_error)
+}
+
+fn insert_srs(
+    conn: &Connection,
+    name: &str,
+    srs_id: i32,
+    organization: &str,
+    organization_coordsys_id: i32,
+    definition: Option<&str>,
+) -> GeoArrowResult<()> {
+    conn.execute(
+        "INSERT INTO gpkg_spatial_ref_sys VALUES (?1, ?2, ?3, ?4, ?5, NULL)",
+        params![
+            name,
+            srs_id,
+            organization,
+            organization_coordsys_id,
+            definition.unwrap_or("undefined")
+        ],
+    )
+    .map_err(sqlite_error)?;
+    Ok(())
+}
+
+fn fid_definition(fid_column: &str) -> String {
+    format!(
+        "{} INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL",
+        quote_identifier(fid_column)
+    )
+}
+
+/// The GeoPackage geometry type name of a GeoArrow type, with its `z` and `m` flags.
+///
+/// The flags are 0 if the values are prohibited, 1 if they are mandatory and 2 if they are
+/// optional, as for a column whose dimension isn't fixed.
+fn geometry_type_name(geometry_type: &GeoArrowType) -> (&'static str, u8, u8) {
+    let name = match geometry_type {
+        GeoArrowType::Point(_) => "POINT",
+        GeoArrowType::LineString(_) => "LINESTRING",
+        GeoArrowType::Polygon(_) | GeoArrowType::Rect(_) => "POLYGON",
+        GeoArrowType::MultiPoint(_) => "MULTIPOINT",
+        GeoArrowType::MultiLineString(_) => "MULTILINESTRING",
+        GeoArrowType::MultiPolygon(_) => "MULTIPOLYGON",
+        GeoArrowType::GeometryCollection(_) => "GEOMETRYCOLLECTION",
+        _ => "GEOMETRY",
+    };
+    let (z, m) = match geometry_type.dimension() {
+        Some(Dimension::XY) => (0, 0),
+        Some(Dimension::XYZ) => (1, 0),
+        Some(Dimension::XYM) => (0, 1),
+        Some(Dimension::XYZM) => (1, 1),
+        None => (2, 2),
+    };
+    (name, z, m)
+}
+
+/// The triggers that keep an R-tree in sync with its feature table, as defined by the
+/// [R-tree spatial index extension](https://www.geopackage.org/spec/#extension_rtree).
+fn rtree_triggers(table_name: &str, geometry_column: &str, fid_column: &str) -> String {
+    let rtree_name = rtree_table_name(table_name, geometry_column);
+    let trigger = |suffix: &str| quote_identifier(&format!("{rtree_name}_{suffix}"));
+    let t = quote_identifier(table_name);
+    let c = quote_identifier(geometry_column);
+    let i = quote_identifier(fid_column);
+    let rtree = quote_identifier(&rtree_name);
+    let insert = format!(
+        "INSERT OR REPLACE INTO {rtree} VALUES (NEW.{i}, ST_MinX(NEW.{c}), ST_MaxX(NEW.{c}), \
+         ST_MinY(NEW.{c}), ST_MaxY(NEW.{c}))"
+    );
+    format!(
+        "CREATE TRIGGER {insert_trigger} AFTER INSERT ON {t} \
+         WHEN (NEW.{c} NOT NULL AND NOT ST_IsEmpty(NEW.{c})) BEGIN {insert}; END;
+         CREATE TRIGGER {update1} AFTER UPDATE OF {c} ON {t} \
+         WHEN OLD.{i} = NEW.{i} AND (NEW.{c} NOTNULL AND NOT ST_IsEmpty(NEW.{c})) \
+         BEGIN {insert}; END;
+         CREATE TRIGGER {update2} AFTER UPDATE OF {c} ON {t} \
+         WHEN OLD.{i} = NEW.{i} AND (NEW.{c} ISNULL OR ST_IsEmpty(NEW.{c})) \
+         BEGIN DELETE FROM {rtree} WHERE id = OLD.{i}; END;
+         CREATE TRIGGER {update3} AFTER UPDATE ON {t} \
+         WHEN OLD.{i} != NEW.{i} AND (NEW.{c} NOTNULL AND NOT ST_IsEmpty(NEW.{c})) \
+         BEGIN DELETE FROM {rtree} WHERE id = OLD.{i}; {insert}; END;
+         CREATE TRIGGER {update4} AFTER UPDATE ON {t} \
+         WHEN OLD.{i} != NEW.{i} AND (NEW.{c} ISNULL OR ST_IsEmpty(NEW.{c})) \
+         BEGIN DELETE FROM {rtree} WHERE id IN (OLD.{i}, NEW.{i}); END;
+         CREATE TRIGGER {delete_trigger} AFTER DELETE ON {t} WHEN OLD.{c} NOT NULL \
+         BEGIN DELETE FROM {rtree} WHERE id = OLD.{i}; END;",
+        insert_trigger = trigger("insert"),
+        update1 = trigger("update1"),
+        update2 = trigger("update2"),
+        update3 = trigger("update3"),
+        update4 = trigger("update4"),
+        delete_trigger = trigger("delete"),
+    )
+}
+
+/// An attribute column and the column of the feature table it is written to.
+#[derive(Debug)]
+struct GpkgColumn {
+    column_index: usize,
+    name: String,
+    sql_type: &'static str,
+    kind: ColumnKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ColumnKind {
+    Boolean,
+    Integer,
+    Real,
+    Text,
+    Blob,
+    Date,
+    DateTime(DataType),
+}
+
+impl GpkgColumn {
+    fn try_new(column_index: usize, field: &Field) -> GeoArrowResult<Self> {
+        let (sql_type, kind) = match field.data_type() {
+            DataType::Boolean => ("BOOLEAN", ColumnKind::Boolean),
+            DataType::Int8 => ("TINYINT", ColumnKind::Integer),
+            DataType::Int16 | DataType::UInt8 => ("SMALLINT", ColumnKind::Integer),
+            DataType::Int32 | DataType::UInt16 => ("MEDIUMINT", ColumnKind::Integer),
+            DataType::Int64 | DataType::UInt32 | DataType::UInt64 => {
+                ("INTEGER", ColumnKind::Integer)
+            }
+            DataType::Float16 | DataType::Float32 => ("FLOAT", ColumnKind::Real),
+            DataType::Float64 | DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => {
+                ("DOUBLE", ColumnKind::Real)
+            }
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => ("TEXT", ColumnKind::Text),
+            DataType::Dictionary(_, value_type)
+                if matches!(
+                    value_type.as_ref(),
+                    DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+                ) =>
+            {
+                ("TEXT", ColumnKind::Text)
+            }
+            DataType::Binary
+            | DataType::LargeBinary
+            | DataType::BinaryView
+            | DataType::FixedSizeBinary(_) => ("BLOB", ColumnKind::Blob),
+            DataType::Date32 | DataType::Date64 => ("DATE", ColumnKind::Date),
+            // Keep the timezone, so that casting to milliseconds doesn't shift the values.
+            DataType::Timestamp(_, timezone) => (
+                "DATETIME",
+                ColumnKind::DateTime(DataType::Timestamp(TimeUnit::Millisecond, timezone.clone())),
+            ),
+            data_type => {
+                return Err(GeoArrowError::GeoPackage(format!(
+                    "Column {:?} has type {data_type}, which can't be written to a GeoPackage. \
+                     Cast it to a string, number, boolean, binary, date or timestamp column \
+                     first.",
+                    field.name()
+                )));
+            }
+        };
+        Ok(Self {
+            column_index,
+            name: field.name().clone(),
+            sql_type,
+            kind,
+        })
+    }
+}
+
+impl ColumnKind {
+    /// Cast a column to the Arrow type that [`Self::value`] reads.
+    fn cast(&self, column: &ArrayRef) -> GeoArrowResult<ArrayRef> {
+        let to_type = match self {
+            Self::Boolean => DataType::Boolean,
+            Self::Integer => DataType::Int64,
+            Self::Real => DataType::Float64,
+            Self::Text => DataType::Utf8,
+            Self::Blob => DataType::Binary,
+            Self::Date => DataType::Date32,
+            Self::DateTime(to_type) => to_type.clone(),
+        };
+        // Fail on values that don't fit the column type instead of writing them as nulls.
+        let options = CastOptions {
+            safe: false,
+            ..Default::default()
+        };
+        Ok(arrow_cast::cast_with_options(column, &to_type, &options)?)
+    }
+
+    fn value(&self, column: &ArrayRef, row: usize) -> Value {
+        if column.is_null(row) {
+            return Value::Null;
+        }
+        match self {
+            Self::Boolean => Value::Integer(column.as_boolean().value(row).into()),
+            Self::Integer => Value::Integer(column.as_primitive::<Int64Type>().value(row)),
+            Self::Real => Value::Real(column.as_primitive::<Float64Type>().value(row)),
+            Self::Text => Value::Text(column.as_string::<i32>().value(row).to_string()),
+            Self::Blob => Value::Blob(column.as_binary::<i32>().value(row).to_vec()),
+            Self::Date => Value::Text(format_date(
+                column.as_primitive::<Date32Type>().value(row).into(),
+            )),
+            Self::DateTime(_) => Value::Text(format_datetime(
+                column.as_primitive::<TimestampMillisecondType>().value(row),
+            )),
+        }
+    }
+}
+
+fn geometry_column(schema: &Schema) -> GeoArrowResult<usize> {
+    let mut geom_indices = vec![];
+    for (field_idx, field) in schema.fields().iter().enumerate() {
+        if let Ok(Some(_)) = GeoArrowType::from_extension_field(field.as_ref()) {
+            geom_indices.push(field_idx);
+        }
+    }
+    match geom_indices.as_slice() {
+        [geometry_index] => Ok(*geometry_index),
+        _ => Err(GeoArrowError::GeoPackage(format!(
+            "Expected exactly one geometry column, found {}",
+            geom_indices.len()
+        ))),
+    }
+}
+
+/// Format a number of days since the Unix epoch as a GeoPackage `DATE`, `YYYY-MM-DD`.
+fn format_date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Format milliseconds since the Unix epoch as a GeoPackage `DATETIME`,
+/// `YYYY-MM-DDTHH:MM:SS.SSSZ`.
+fn format_datetime(millis: i64) -> String {
+    let millis_of_day = millis.rem_euclid(86_400_000);
+    format!(
+        "{}T{:02}:{:02}:{:02}.{:03}Z",
+        format_date(millis.div_euclid(86_400_000)),
+        millis_of_day / 3_600_000,
+        millis_of_day / 60_000 % 60,
+        millis_of_day / 1000 % 60,
+        millis_of_day % 1000
+    )
+}
+
+/// Convert days since the Unix epoch to a proleptic Gregorian `(year, month, day)`.
+///
+/// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use arrow_array::{Date32Array, RecordBatchIterator, create_array};
+    use geo_traits::{CoordTrait, PointTrait};
+    use geoarrow_array::GeoArrowArray;
+    use geoarrow_array::array::PointArray;
+    use geoarrow_array::builder::PointBuilder;
+    use geoarrow_schema::PointType;
+
+    use super::*;
+    use crate::reader::{GeoPackageReader, GeoPackageReaderOptions, feature_tables};
+
+    fn points_batch(crs: Crs) -> RecordBatch {
+        let typ = PointType::new(Dimension::XY, Arc::new(Metadata::new(crs, None)));
+        let mut builder = PointBuilder::with_capacity(typ, 3);
+        builder.push_point(Some(&wkt::wkt!(POINT (1. 2.))));
+        builder.push_point(Some(&wkt::wkt!(POINT (30. 40.))));
+        builder.push_null();
+        let points = builder.finish();
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("name", DataType::Utf8, true),
+            Field::new("count", DataType::Int32, true),
+            Field::new("visited", DataType::Date32, true),
+            points.data_type().to_field("geometry", true),
+        ]));
+        RecordBatch::try_new(
+            schema,
+            vec![
+                create_array!(Utf8, [Some("a"), None, Some("c")]),
+                create_array!(Int32, [Some(1), Some(2), None]),
+                Arc::new(Date32Array::from(vec![Some(19_723), None, None])),
+                points.into_array_ref(),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn write_batch(
+        conn: &Connection,
+        batch: &RecordBatch,
+        options: GeoPackageWriterOptions,
+    ) -> GeoArrowResult<()> {
+        let mut writer = GeoPackageWriter::try_new(conn, "points", batch.schema(), options)?;
+        writer.write(batch)?;
+        writer.finish()
+    }
+
+    #[test]
+    fn roundtrip() {
+        let conn = Connection::open_in_memory().unwrap();
+        let batch = points_batch(Crs::from_authority_code("EPSG:4326".to_string()));
+        write_batch(&conn, &batch, Default::default()).unwrap();
+        assert_eq!(feature_tables(&conn).unwrap(), ["points"]);
+
+        let reader =
+            GeoPackageReader::try_new(&conn, "points", GeoPackageReaderOptions::default()).unwrap();
+        let schema = reader.schema();
+        let names = schema
+            .fields()
+            .iter()
+            .map(|field| field.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["fid", "name", "count", "visited", "geometry"]);
+        assert_eq!(schema.field(2).data_type(), &DataType::Int32);
+        let geometry_type = GeoArrowType::from_extension_field(schema.field(4))
+            .unwrap()
+            .unwrap();
+        assert!(matches!(geometry_type, GeoArrowType::Point(_)));
+        assert_eq!(
+            geometry_type.metadata().crs(),
+            &Crs::from_authority_code("EPSG:4326".to_string())
+        );
+
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(batch.column(1).as_string::<i32>().value(0), "a");
+        assert!(batch.column(1).is_null(1));
+        assert!(batch.column(2).is_null(2));
+        assert_eq!(
+            batch.column(3).as_primitive::<Date32Type>().value(0),
+            19_723
+        );
+        let points = PointArray::try_from((batch.column(4).as_ref(), schema.field(4))).unwrap();
+        let coord = points.value(1).unwrap().coord().unwrap();
+        assert_eq!((coord.x(), coord.y()), (30., 40.));
+        assert!(points.is_null(2));
+
+        let extent = conn
+            .query_row(
+                "SELECT min_x, min_y, max_x, max_y, srs_id FROM gpkg_contents",
+                [],
+                |row| {
+                    Ok((
+                        row.get::<_, f64>(0)?,
+                        row.get::<_, f64>(1)?,
+                        row.get::<_, f64>(2)?,
+                        row.get::<_, f64>(3)?,
+                        row.get::<_, i32>(4)?,
+                    ))
+                },
+            )
+            .unwrap();
+        assert_eq!(extent, (1., 2., 30., 40., 4326));
+        let num_indexed = conn
+            .query_row("SELECT count(*) FROM rtree_points_geometry", [], |row| {
+                row.get::<_, i64>(0)
+            })
+            .unwrap();
+        assert_eq!(num_indexed, 2);
+    }
+
+    #[test]
+    fn bbox() {
+        for spatial_index in [true, false] {
+            let conn = Connection::open_in_memory().unwrap();
+            let batch = points_batch(Crs::default());
+            let options = GeoPackageWriterOptions::default().with_spatial_index(spatial_index);
+            write_batch(&conn, &batch, options).unwrap();
+
+            let options = GeoPackageReaderOptions {
+                bbox: Some([20., 30., 50., 50.]),
+                batch_size: 1,
+                ..Default::default()
+            };
+            let batches = GeoPackageReader::try_new(&conn, "points", options)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(batches.len(), 1, "spatial_index: {spatial_index}");
+            assert_eq!(batches[0].column(0).as_primitive::<Int64Type>().value(0), 2);
+        }
+    }
+
+    #[test]
+    fn custom_crs() {
+        let wkt = r#"PROJCS["Custom",GEOGCS["GCS_WGS_1984",DATUM["D_WGS_1984"]]]"#;
+        let conn = Connection::open_in_memory().unwrap();
+        let batch = points_batch(Crs::from_unknown_crs_type(wkt.to_string()));
+        write_batch(&conn, &batch, Default::default()).unwrap();
+
+        let reader =
+            GeoPackageReader::try_new(&conn, "points", GeoPackageReaderOptions::default()).unwrap();
+        let geometry_type = GeoArrowType::from_extension_field(reader.schema().field(4))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            geometry_type.metadata().crs(),
+            &Crs::from_unknown_crs_type(wkt.to_string())
+        );
+        let srs_id = conn
+            .query_row("SELECT srs_id FROM gpkg_geometry_columns", [], |row| {
+                row.get::<_, i32>(0)
+            })
+            .unwrap();
+        assert_eq!(srs_id, FIRST_CUSTOM_SRS_ID);
+    }
+
+    #[test]
+    fn fid_column() {
+        let typ = PointType::new(Dimension::XY, Default::default());
+        let mut builder = PointBuilder::with_capacity(typ, 2);
+        builder.push_point(Some(&wkt::wkt!(POINT (1. 2.))));
+        builder.push_point(Some(&wkt::wkt!(POINT (3. 4.))));
+        let points = builder.finish();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("fid", DataType::Int64, true),
+            points.data_type().to_field("geometry", true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![create_array!(Int64, [10, 20]), points.into_array_ref()],
+        )
+        .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("points.gpkg");
+        let stream = RecordBatchIterator::new(vec![Ok(batch)], schema);
+        write_geopackage(stream, &path, "points", Default::default()).unwrap();
+
+        let conn = Connection::open(&path).unwrap();
+        let batches =
+            GeoPackageReader::try_new(&conn, "points", GeoPackageReaderOptions::default())
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+        assert_eq!(batches[0].num_columns(), 2);
+        assert_eq!(
+            batches[0].column(0).as_primitive::<Int64Type>().values(),
+            &[10, 20]
+        );
+        let application_id = conn
+            .query_row("PRAGMA application_id", [], |row| row.get::<_, i32>(0))
+            .unwrap();
+        assert_eq!(application_id, APPLICATION_ID);
+    }
+
+    #[test]
+    fn unsupported_column_type() {
+        let typ = PointType::new(Dimension::XY, Default::default());
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("duration", DataType::Duration(TimeUnit::Second), true),
+            typ.to_field("geometry", true),
+        ]));
+        let conn = Connection::open_in_memory().unwrap();
+        let err = GeoPackageWriter::try_new(&conn, "points", schema, Default::default())
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("\"duration\""), "{err}");
+    }
+
+    #[test]
+    fn out_of_range_integer() {
+        let typ = PointType::new(Dimension::XY, Default::default());
+        let mut builder = PointBuilder::with_capacity(typ, 1);
+        builder.push_point(Some(&wkt::wkt!(POINT (1. 2.))));
+        let points = builder.finish();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("count", DataType::UInt64, true),
+            points.data_type().to_field("geometry", true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![create_array!(UInt64, [u64::MAX]), points.into_array_ref()],
+        )
+        .unwrap();
+
+        // A value that doesn't fit in an INTEGER is an error rather than a null.
+        let conn = Connection::open_in_memory().unwrap();
+        assert!(write_batch(&conn, &batch, Default::default()).is_err());
+    }
+
+    #[test]
+    fn datetimes() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(19_723), "2024-01-01");
+        assert_eq!(format_date(-1), "1969-12-31");
+        assert_eq!(
+            format_datetime(1_704_067_200_123 + 3_723_000),
+            "2024-01-01T01:02:03.123Z"
+        );
+        assert_eq!(format_datetime(-1), "1969-12-31T23:59:59.999Z");
+    }
+}
@@ -0,0 +1,162 @@
+use geo_traits::{CoordTrait, RectTrait};
+
+use crate::hilbert::hilbert_box_center;
+use crate::rtree::{RTree, compute_level_bounds};
+
+/// The default number of children of each node in the tree.
+pub const DEFAULT_NODE_SIZE: usize = 16;
+
+/// A builder for a packed Hilbert [RTree].
+///
+/// Items are pushed as a row index together with a 2D bounding box. Once all items have been
+/// added, [`finish`][Self::finish] sorts them along a Hilbert curve and packs the tree.
+#[derive(Debug, Clone)]
+pub struct RTreeBuilder {
+    boxes: Vec<f64>,
+    indices: Vec<u32>,
+    node_size: usize,
+}
+
+impl RTreeBuilder {
+    /// Creates a new, empty builder.
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates a new builder with space for `capacity` items.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            boxes: Vec::with_capacity(capacity * 4),
+            indices: Vec::with_capacity(capacity),
+            node_size: DEFAULT_NODE_SIZE,
+        }
+    }
+
+    /// Set the number of children of each node in the tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `node_size` is not in the range `2..=65535`.
+    pub fn with_node_size(self, node_size: usize) -> Self {
+        assert!(
+            (2..=u16::MAX as usize).contains(&node_size),
+            "node_size must be between 2 and 65535"
+        );
+        Self { node_size, ..self }
+    }
+
+    /// The number of items added to this builder.
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns `true` if no items have been added to this builder.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Add an item with the given row index and bounding box.
+    pub fn push(&mut self, index: u32, min_x: f64, min_y: f64, max_x: f64, max_y: f64) {
+        self.boxes.extend_from_slice(&[min_x, min_y, max_x, max_y]);
+        self.indices.push(index);
+    }
+
+    /// Add an item with the given row index and bounding rectangle.
+    ///
+    /// Only the x and y dimensions of the rectangle are used.
+    pub fn push_rect(&mut self, index: u32, rect: &impl RectTrait<T = f64>) {
+        let min = rect.min();
+        let max = rect.max();
+        self.push(index, min.x(), min.y(), max.x(), max.y());
+    }
+
+    /// Sort the items along a Hilbert curve and pack them into an [RTree].
+    pub fn finish(self) -> RTree {
+        let Self {
+            boxes: item_boxes,
+            indices: item_indices,
+            node_size,
+        } = self;
+        let num_items = item_indices.len();
+        let level_bounds = compute_level_bounds(num_items, node_size);
+        let num_nodes = *level_bounds.last().unwrap();
+
+        let mut boxes = Vec::with_capacity(num_nodes * 4);
+        let mut indices = Vec::with_capacity(num_nodes);
+
+        // Leaves only need sorting when there is more than one node above them
+        if num_items <= node_size {
+            boxes.extend_from_slice(&item_boxes);
+            indices.extend_from_slice(&item_indices);
+        } else {
+            let extent = item_boxes.chunks_exact(4).fold(
+                [
+                    f64::INFINITY,
+                    f64::INFINITY,
+                    f64::NEG_INFINITY,
+                    f64::NEG_INFINITY,
+                ],
+                |acc, b| {
+                    [
+                        acc[0].min(b[0]),
+                        acc[1].min(b[1]),
+                        acc[2].max(b[2]),
+                        acc[3].max(b[3]),
+                    ]
+                },
+            );
+            let hilbert_values = item_boxes
+                .chunks_exact(4)
+                .map(|b| hilbert_box_center([b[0], b[1], b[2], b[3]], extent))
+                .collect::<Vec<_>>();
+            let mut order = (0..num_items).collect::<Vec<_>>();
+            order.sort_by_key(|&i| hilbert_values[i]);
+
+            for i in order {
+                boxes.extend_from_slice(&item_boxes[i * 4..i * 4 + 4]);
+                indices.push(item_indices[i]);
+            }
+        }
+
+        // Generate each level of parent nodes from the level below it
+        let mut pos = 0;
+        for &end in &level_bounds[..level_bounds.len() - 1] {
+            while pos < end {
+                let node_index = pos;
+                let node_end = (pos + node_size).min(end);
+                let mut node = [
+                    f64::INFINITY,
+                    f64::INFINITY,
+                    f64::NEG_INFINITY,
+                    f64::NEG_INFINITY,
+                ];
+                while pos < node_end {
+                    let b = &boxes[pos * 4..pos * 4 + 4];
+                    node = [
+                        node[0].min(b[0]),
+                        node[1].min(b[1]),
+                        node[2].max(b[2]),
+                        node[3].max(b[3]),
+                    ];
+                    pos += 1;
+                }
+                boxes.extend_from_slice(&node);
+                indices.push(node_index as u32);
+            }
+        }
+
+        RTree::new(
+            boxes.into(),
+            indices.into(),
+            level_bounds,
+            num_items,
+            node_size,
+        )
+    }
+}
+
+impl Default for RTreeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
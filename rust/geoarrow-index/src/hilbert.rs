@@ -0,0 +1,109 @@
+//! Hilbert curve utilities used to order items when bulk-loading an index.
+
+/// The maximum value of each Hilbert coordinate.
+pub const HILBERT_MAX: u32 = (1 << 16) - 1;
+
+/// Compute the Hilbert curve index of a point on a 2^16 x 2^16 grid.
+///
+/// Both `x` and `y` must be in the range `0..=65535`.
+///
+/// This is a port of the algorithm used by
+/// [flatbush](https://github.com/mourner/flatbush), based on
+/// <https://github.com/rawrunprotected/hilbert_curves> (public domain).
+pub fn hilbert(x: u32, y: u32) -> u32 {
+    let mut a = x ^ y;
+    let mut b = 0xFFFF ^ a;
+    let mut c = 0xFFFF ^ (x | y);
+    let mut d = x & (y ^ 0xFFFF);
+
+    let mut aa = a | (b >> 1);
+    let mut bb = (a >> 1) ^ a;
+    let mut cc = ((c >> 1) ^ (b & (d >> 1))) ^ c;
+    let mut dd = ((a & (c >> 1)) ^ (d >> 1)) ^ d;
+
+    a = aa;
+    b = bb;
+    c = cc;
+    d = dd;
+    aa = (a & (a >> 2)) ^ (b & (b >> 2));
+    bb = (a & (b >> 2)) ^ (b & ((a ^ b) >> 2));
+    cc ^= (a & (c >> 2)) ^ (b & (d >> 2));
+    dd ^= (b & (c >> 2)) ^ ((a ^ b) & (d >> 2));
+
+    a = aa;
+    b = bb;
+    c = cc;
+    d = dd;
+    aa = (a & (a >> 4)) ^ (b & (b >> 4));
+    bb = (a & (b >> 4)) ^ (b & ((a ^ b) >> 4));
+    cc ^= (a & (c >> 4)) ^ (b & (d >> 4));
+    dd ^= (b & (c >> 4)) ^ ((a ^ b) & (d >> 4));
+
+    a = aa;
+    b = bb;
+    c = cc;
+    d = dd;
+    cc ^= (a & (c >> 8)) ^ (b & (d >> 8));
+    dd ^= (b & (c >> 8)) ^ ((a ^ b) & (d >> 8));
+
+    a = cc ^ (cc >> 1);
+    b = dd ^ (dd >> 1);
+
+    let mut i0 = x ^ y;
+    let mut i1 = b | (0xFFFF ^ (i0 | a));
+
+    i0 = (i0 | (i0 << 8)) & 0x00FF00FF;
+    i0 = (i0 | (i0 << 4)) & 0x0F0F0F0F;
+    i0 = (i0 | (i0 << 2)) & 0x33333333;
+    i0 = (i0 | (i0 << 1)) & 0x55555555;
+
+    i1 = (i1 | (i1 << 8)) & 0x00FF00FF;
+    i1 = (i1 | (i1 << 4)) & 0x0F0F0F0F;
+    i1 = (i1 | (i1 << 2)) & 0x33333333;
+    i1 = (i1 | (i1 << 1)) & 0x55555555;
+
+    (i1 << 1) | i0
+}
+
+/// Compute the Hilbert curve index of the center of a bounding box, relative to the given
+/// extent.
+///
+/// The center is scaled onto the 2^16 x 2^16 grid spanned by `extent`, given as
+/// `[min_x, min_y, max_x, max_y]`. A zero-width or zero-height extent maps every center to 0
+/// along that axis.
+pub fn hilbert_box_center(bbox: [f64; 4], extent: [f64; 4]) -> u32 {
+    let [min_x, min_y, max_x, max_y] = extent;
+    let x = scale(0.5 * (bbox[0] + bbox[2]), min_x, max_x - min_x);
+    let y = scale(0.5 * (bbox[1] + bbox[3]), min_y, max_y - min_y);
+    hilbert(x, y)
+}
+
+fn scale(value: f64, min: f64, width: f64) -> u32 {
+    if width > 0.0 {
+        // Float to int casts saturate, so values slightly outside the extent are clamped.
+        ((HILBERT_MAX as f64) * (value - min) / width).floor() as u32
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hilbert_order() {
+        // The first order curve visits (0, 0), (1, 0), (1, 1), (0, 1)
+        assert_eq!(hilbert(0, 0), 0);
+        assert_eq!(hilbert(1, 0), 1);
+        assert_eq!(hilbert(1, 1), 2);
+        assert_eq!(hilbert(0, 1), 3);
+        assert_eq!(hilbert(HILBERT_MAX, 0), u32::MAX);
+    }
+
+    #[test]
+    fn box_center_degenerate_extent() {
+        let extent = [1.0, 1.0, 1.0, 1.0];
+        assert_eq!(hilbert_box_center([1.0, 1.0, 1.0, 1.0], extent), 0);
+    }
+}
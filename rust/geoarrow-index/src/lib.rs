@@ -0,0 +1,15 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![doc(
+    html_logo_url = "https://github.com/geoarrow.png",
+    html_favicon_url = "https://github.com/geoarrow.png?size=32"
+)]
+
+mod builder;
+pub mod hilbert;
+mod rtree;
+
+pub use builder::{DEFAULT_NODE_SIZE, RTreeBuilder};
+pub use rtree::RTree;
@@ -0,0 +1,533 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use arrow_buffer::ScalarBuffer;
+use geoarrow_array::array::RectArray;
+use geoarrow_array::bounds::bounding_rect;
+use geoarrow_array::cast::AsGeoArrowArray;
+use geoarrow_array::{GeoArrowArray, GeoArrowArrayAccessor};
+use geoarrow_schema::Dimension;
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+
+use crate::builder::{DEFAULT_NODE_SIZE, RTreeBuilder};
+
+/// Magic bytes at the start of a serialized [RTree].
+const MAGIC: [u8; 4] = *b"GAIX";
+
+/// The current version of the serialization format.
+const VERSION: u8 = 1;
+
+/// Length in bytes of the serialized header.
+const HEADER_LEN: usize = 16;
+
+/// A static, packed Hilbert R-tree over 2D bounding boxes.
+///
+/// The tree is stored as two flat Arrow buffers: one of bounding boxes
+/// (`[min_x, min_y, max_x, max_y]` per node) and one of indices. The first
+/// [`num_items`][Self::num_items] nodes are the leaves, whose indices are row indices into the
+/// indexed array. The remaining nodes are the internal nodes, from the bottom level up to the
+/// root, whose indices point to the position of their first child.
+///
+/// Null and empty geometries are not inserted into the tree, so they are never returned from a
+/// query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RTree {
+    boxes: ScalarBuffer<f64>,
+    indices: ScalarBuffer<u32>,
+    level_bounds: Vec<usize>,
+    num_items: usize,
+    node_size: usize,
+}
+
+impl RTree {
+    pub(crate) fn new(
+        boxes: ScalarBuffer<f64>,
+        indices: ScalarBuffer<u32>,
+        level_bounds: Vec<usize>,
+        num_items: usize,
+        node_size: usize,
+    ) -> Self {
+        Self {
+            boxes,
+            indices,
+            level_bounds,
+            num_items,
+            node_size,
+        }
+    }
+
+    /// Build a tree from the 2D bounding boxes of each geometry in a GeoArrow array, using the
+    /// default node size.
+    pub fn try_new(array: &dyn GeoArrowArray) -> GeoArrowResult<Self> {
+        Self::try_new_with_node_size(array, DEFAULT_NODE_SIZE)
+    }
+
+    /// Build a tree from the 2D bounding boxes of each geometry in a GeoArrow array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `node_size` is not in the range `2..=65535`.
+    pub fn try_new_with_node_size(
+        array: &dyn GeoArrowArray,
+        node_size: usize,
+    ) -> GeoArrowResult<Self> {
+        if let Some(rect_array) = array.as_rect_opt() {
+            return Self::try_from_rect_array(rect_array, node_size);
+        }
+        let rect_array = bounding_rect(array, Dimension::XY)?;
+        Self::try_from_rect_array(&rect_array, node_size)
+    }
+
+    fn try_from_rect_array(array: &RectArray, node_size: usize) -> GeoArrowResult<Self> {
+        if array.len() > u32::MAX as usize {
+            return Err(GeoArrowError::SpatialIndex(format!(
+                "Cannot index more than {} rows, got {}",
+                u32::MAX,
+                array.len()
+            )));
+        }
+
+        let mut builder = RTreeBuilder::with_capacity(array.len()).with_node_size(node_size);
+        for (i, rect) in array.iter().enumerate() {
+            if let Some(rect) = rect {
+                let rect = rect?;
+                builder.push_rect(i as u32, &rect);
+            }
+        }
+        Ok(builder.finish())
+    }
+
+    /// The number of items in the tree.
+    pub fn num_items(&self) -> usize {
+        self.num_items
+    }
+
+    /// The total number of nodes in the tree, including leaves.
+    pub fn num_nodes(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// The maximum number of children of each node.
+    pub fn node_size(&self) -> usize {
+        self.node_size
+    }
+
+    /// The bounding boxes of all nodes, as `[min_x, min_y, max_x, max_y]` per node.
+    pub fn boxes(&self) -> &ScalarBuffer<f64> {
+        &self.boxes
+    }
+
+    /// The indices of all nodes.
+    ///
+    /// For leaves these are row indices into the indexed array; for internal nodes they are the
+    /// position of the node's first child.
+    pub fn indices(&self) -> &ScalarBuffer<u32> {
+        &self.indices
+    }
+
+    /// The 2D extent of all items in the tree, as `[min_x, min_y, max_x, max_y]`.
+    ///
+    /// Returns `None` if the tree is empty.
+    pub fn extent(&self) -> Option<[f64; 4]> {
+        if self.num_items == 0 {
+            return None;
+        }
+        Some(self.node_box(self.num_nodes() - 1))
+    }
+
+    fn node_box(&self, pos: usize) -> [f64; 4] {
+        let b = &self.boxes[pos * 4..pos * 4 + 4];
+        [b[0], b[1], b[2], b[3]]
+    }
+
+    /// The end (exclusive) of the children of the node starting at `node_index`.
+    fn node_end(&self, node_index: usize) -> usize {
+        let level = self.level_bounds.partition_point(|&b| b <= node_index);
+        (node_index + self.node_size).min(self.level_bounds[level])
+    }
+
+    /// Search the tree for items whose bounding box intersects the given query box.
+    ///
+    /// Returns the row indices of all matching items, in no particular order.
+    pub fn search(&self, min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Vec<u32> {
+        let mut results = vec![];
+        if self.num_items == 0 {
+            return results;
+        }
+
+        let mut queue = vec![];
+        let mut node_index = self.num_nodes() - 1;
+        loop {
+            let is_leaf = node_index < self.num_items;
+            for pos in node_index..self.node_end(node_index) {
+                let b = self.node_box(pos);
+                if max_x < b[0] || max_y < b[1] || min_x > b[2] || min_y > b[3] {
+                    continue;
+                }
+
+                let index = self.indices[pos];
+                if is_leaf {
+                    results.push(index);
+                } else {
+                    queue.push(index as usize);
+                }
+            }
+
+            match queue.pop() {
+                Some(next) => node_index = next,
+                None => break,
+            }
+        }
+
+        results
+    }
+
+    /// Search the tree for items whose bounding box intersects the given rectangle.
+    ///
+    /// Only the x and y dimensions of the rectangle are used.
+    pub fn search_rect(&self, rect: &impl geo_traits::RectTrait<T = f64>) -> Vec<u32> {
+        use geo_traits::CoordTrait;
+
+        let min = rect.min();
+        let max = rect.max();
+        self.search(min.x(), min.y(), max.x(), max.y())
+    }
+
+    /// Find the items closest to the given point, ordered by increasing distance.
+    ///
+    /// Distance is measured to each item's bounding box, so callers who need exact distances
+    /// to the underlying geometries should refine the results.
+    ///
+    /// If `max_results` is provided, at most that many items are returned. If `max_distance` is
+    /// provided, only items whose bounding box is within that distance are returned.
+    pub fn neighbors(
+        &self,
+        x: f64,
+        y: f64,
+        max_results: Option<usize>,
+        max_distance: Option<f64>,
+    ) -> Vec<u32> {
+        let mut results = vec![];
+        let max_results = max_results.unwrap_or(usize::MAX);
+        if self.num_items == 0 || max_results == 0 {
+            return results;
+        }
+        let max_dist_squared = max_distance.map_or(f64::INFINITY, |d| d * d);
+
+        let mut queue = BinaryHeap::new();
+        let mut node_index = Some(self.num_nodes() - 1);
+        while let Some(current) = node_index {
+            let is_leaf = current < self.num_items;
+            for pos in current..self.node_end(current) {
+                let b = self.node_box(pos);
+                let dx = axis_dist(x, b[0], b[2]);
+                let dy = axis_dist(y, b[1], b[3]);
+                let dist = dx * dx + dy * dy;
+                if dist > max_dist_squared {
+                    continue;
+                }
+
+                queue.push(NeighborNode {
+                    dist,
+                    index: self.indices[pos] as usize,
+                    is_leaf,
+                });
+            }
+
+            // Any leaves at the top of the queue are closer than every remaining node
+            while let Some(top) = queue.peek()
+                && top.is_leaf
+            {
+                results.push(top.index as u32);
+                queue.pop();
+                if results.len() >= max_results {
+                    return results;
+                }
+            }
+
+            node_index = queue.pop().map(|node| node.index);
+        }
+
+        results
+    }
+
+    /// Serialize this tree to bytes.
+    ///
+    /// The format is a 16-byte header (magic bytes, format version, node size and number of
+    /// items) followed by the little-endian boxes and indices buffers.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEADER_LEN + self.num_nodes() * (4 * 8 + 4));
+        out.extend_from_slice(&MAGIC);
+        out.push(VERSION);
+        out.push(0);
+        out.extend_from_slice(&(self.node_size as u16).to_le_bytes());
+        out.extend_from_slice(&(self.num_items as u64).to_le_bytes());
+        for value in self.boxes.iter() {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in self.indices.iter() {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        out
+    }
+
+    /// Deserialize a tree from bytes written by [`to_bytes`][Self::to_bytes].
+    pub fn try_from_bytes(data: &[u8]) -> GeoArrowResult<Self> {
+        if data.len() < HEADER_LEN || data[0..4] != MAGIC {
+            return Err(GeoArrowError::SpatialIndex(
+                "Data is not a serialized spatial index".to_string(),
+            ));
+        }
+        if data[4] != VERSION {
+            return Err(GeoArrowError::SpatialIndex(format!(
+                "Unsupported spatial index version {}",
+                data[4]
+            )));
+        }
+
+        let node_size = u16::from_le_bytes([data[6], data[7]]) as usize;
+        if node_size < 2 {
+            return Err(GeoArrowError::SpatialIndex(format!(
+                "Invalid node size {node_size}"
+            )));
+        }
+        let num_items = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let num_items = usize::try_from(num_items)
+            .ok()
+            .filter(|&n| n <= u32::MAX as usize)
+            .ok_or_else(|| {
+                GeoArrowError::SpatialIndex(format!("Invalid number of items {num_items}"))
+            })?;
+
+        let level_bounds = compute_level_bounds(num_items, node_size);
+        let num_nodes = *level_bounds.last().unwrap();
+        let boxes_len = num_nodes * 4 * 8;
+        let expected_len = HEADER_LEN + boxes_len + num_nodes * 4;
+        if data.len() != expected_len {
+            return Err(GeoArrowError::SpatialIndex(format!(
+                "Expected {expected_len} bytes for spatial index with {num_items} items, got {}",
+                data.len()
+            )));
+        }
+
+        let (boxes_data, indices_data) = data[HEADER_LEN..].split_at(boxes_len);
+        let boxes = boxes_data
+            .chunks_exact(8)
+            .map(|b| f64::from_le_bytes(b.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        let indices = indices_data
+            .chunks_exact(4)
+            .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+            .collect::<Vec<_>>();
+
+        // Every internal node must point to a node in the level below it, or traversal would read
+        // out of bounds
+        let mut children = 0..level_bounds[0];
+        for level in level_bounds.windows(2) {
+            let nodes = level[0]..level[1];
+            for (pos, &index) in nodes.clone().zip(&indices[nodes.clone()]) {
+                if !children.contains(&(index as usize)) {
+                    return Err(GeoArrowError::SpatialIndex(format!(
+                        "Invalid spatial index: node {pos} points to node {index}, outside of the level below it"
+                    )));
+                }
+            }
+            children = nodes;
+        }
+
+        Ok(Self::new(
+            boxes.into(),
+            indices.into(),
+            level_bounds,
+            num_items,
+            node_size,
+        ))
+    }
+}
+
+/// Compute the cumulative number of nodes at the end of each level of the tree, from the leaves
+/// up to the root.
+pub(crate) fn compute_level_bounds(num_items: usize, node_size: usize) -> Vec<usize> {
+    let mut level_bounds = vec![num_items];
+    if num_items == 0 {
+        return level_bounds;
+    }
+
+    let mut n = num_items;
+    let mut num_nodes = num_items;
+    loop {
+        n = n.div_ceil(node_size);
+        num_nodes += n;
+        level_bounds.push(num_nodes);
+        if n == 1 {
+            break;
+        }
+    }
+    level_bounds
+}
+
+fn axis_dist(k: f64, min: f64, max: f64) -> f64 {
+    if k < min {
+        min - k
+    } else if k <= max {
+        0.0
+    } else {
+        k - max
+    }
+}
+
+/// An entry in the nearest neighbor priority queue.
+#[derive(Debug)]
+struct NeighborNode {
+    dist: f64,
+    index: usize,
+    is_leaf: bool,
+}
+
+impl PartialEq for NeighborNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for NeighborNode {}
+
+impl PartialOrd for NeighborNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NeighborNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so that the BinaryHeap pops the closest node first
+        other.dist.total_cmp(&self.dist)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use geoarrow_array::builder::PointBuilder;
+    use geoarrow_array::test::{point, polygon};
+    use geoarrow_schema::{CoordType, PointType};
+
+    use super::*;
+
+    fn grid_points(n: usize) -> Vec<geo_types::Point> {
+        (0..n)
+            .flat_map(|i| (0..n).map(move |j| geo_types::Point::new(i as f64, j as f64)))
+            .collect()
+    }
+
+    fn grid_tree(n: usize, node_size: usize) -> RTree {
+        let points = grid_points(n);
+        let typ = PointType::new(Dimension::XY, Default::default());
+        let array = PointBuilder::from_points(points.iter(), typ).finish();
+        RTree::try_new_with_node_size(&array, node_size).unwrap()
+    }
+
+    #[test]
+    fn search_matches_brute_force() {
+        let points = grid_points(20);
+        let tree = grid_tree(20, 4);
+        assert_eq!(tree.num_items(), 400);
+
+        let mut result = tree.search(2.5, 3.5, 6.0, 5.0);
+        result.sort();
+        let expected = points
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| (2.5..=6.0).contains(&p.x()) && (3.5..=5.0).contains(&p.y()))
+            .map(|(i, _)| i as u32)
+            .collect::<Vec<_>>();
+        assert_eq!(result, expected);
+
+        assert!(tree.search(100.0, 100.0, 101.0, 101.0).is_empty());
+    }
+
+    #[test]
+    fn neighbors_ordered_by_distance() {
+        let points = grid_points(20);
+        let tree = grid_tree(20, 16);
+
+        let result = tree.neighbors(10.2, 10.1, Some(5), None);
+        assert_eq!(result.len(), 5);
+        let dists = result
+            .iter()
+            .map(|&i| {
+                let p = points[i as usize];
+                (p.x() - 10.2).powi(2) + (p.y() - 10.1).powi(2)
+            })
+            .collect::<Vec<_>>();
+        assert!(dists.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(
+            points[result[0] as usize],
+            geo_types::Point::new(10.0, 10.0)
+        );
+
+        let within = tree.neighbors(10.0, 10.0, None, Some(1.0));
+        assert_eq!(within.len(), 5);
+        assert!(tree.neighbors(10.0, 10.0, Some(0), None).is_empty());
+    }
+
+    #[test]
+    fn skips_null_and_empty() {
+        // Rows 2 and 3 are null and empty respectively
+        let array = point::array(CoordType::Separated, Dimension::XY);
+        let tree = RTree::try_new(&array).unwrap();
+        assert_eq!(tree.num_items(), 2);
+
+        let mut result = tree.search(f64::MIN, f64::MIN, f64::MAX, f64::MAX);
+        result.sort();
+        assert_eq!(result, vec![0, 1]);
+    }
+
+    #[test]
+    fn empty_tree() {
+        let tree = RTreeBuilder::new().finish();
+        assert_eq!(tree.num_items(), 0);
+        assert_eq!(tree.extent(), None);
+        assert!(tree.search(0.0, 0.0, 1.0, 1.0).is_empty());
+        assert!(tree.neighbors(0.0, 0.0, None, None).is_empty());
+
+        let round_trip = RTree::try_from_bytes(&tree.to_bytes()).unwrap();
+        assert_eq!(round_trip, tree);
+    }
+
+    #[test]
+    fn serialize_round_trip() {
+        let array = polygon::array(CoordType::Interleaved, Dimension::XYZ);
+        let tree = RTree::try_new(&array).unwrap();
+        let round_trip = RTree::try_from_bytes(&tree.to_bytes()).unwrap();
+        assert_eq!(round_trip, tree);
+
+        let tree = grid_tree(30, 8);
+        let bytes = tree.to_bytes();
+        let round_trip = RTree::try_from_bytes(&bytes).unwrap();
+        assert_eq!(round_trip, tree);
+        assert_eq!(
+            round_trip.search(1.0, 1.0, 2.0, 2.0).len(),
+            tree.search(1.0, 1.0, 2.0, 2.0).len()
+        );
+
+        assert!(RTree::try_from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(RTree::try_from_bytes(b"not an index").is_err());
+    }
+
+    #[test]
+    fn deserialize_corrupt_indices() {
+        let tree = grid_tree(30, 8);
+        let bytes = tree.to_bytes();
+        let root = bytes.len() - 4;
+
+        // The root points past the end of the tree
+        let mut corrupt = bytes.clone();
+        corrupt[root..].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(RTree::try_from_bytes(&corrupt).is_err());
+
+        // The root points to a leaf instead of the level below it
+        let mut corrupt = bytes.clone();
+        corrupt[root..].copy_from_slice(&0u32.to_le_bytes());
+        assert!(RTree::try_from_bytes(&corrupt).is_err());
+    }
+}
@@ -0,0 +1,311 @@
+//! Encoding and decoding of PostgreSQL's [binary `COPY`
+//! format](https://www.postgresql.org/docs/current/sql-copy.html#id-1.9.3.55.9.4).
+//!
+//! A binary `COPY` stream is a header, followed by one tuple per row and a trailer. Each tuple is
+//! a 16-bit field count followed by the fields, each a 32-bit length (`-1` for null) and that
+//! many bytes in the binary send format of the column type. All integers are big-endian.
+
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+
+/// The signature at the start of every binary `COPY` stream.
+const SIGNATURE: &[u8; 11] = b"PGCOPY\n\xff\r\n\0";
+
+/// The length of the fixed part of the header: the signature, the flags field and the header
+/// extension length.
+const HEADER_LENGTH: usize = SIGNATURE.len() + 8;
+
+/// The field count that marks the end of the stream.
+const TRAILER: i16 = -1;
+
+/// Encodes tuples in the binary `COPY` format.
+///
+/// Encoded bytes accumulate in a buffer until they are taken with [`CopyEncoder::take`], so that
+/// they can be sent to the server in chunks.
+#[derive(Debug)]
+pub(crate) struct CopyEncoder {
+    buffer: Vec<u8>,
+}
+
+impl CopyEncoder {
+    /// Create a new encoder, starting with the stream header.
+    pub(crate) fn new() -> Self {
+        let mut buffer = Vec::with_capacity(HEADER_LENGTH);
+        buffer.extend_from_slice(SIGNATURE);
+        // Flags: no OIDs are included.
+        buffer.extend_from_slice(&0_i32.to_be_bytes());
+        // No header extension.
+        buffer.extend_from_slice(&0_i32.to_be_bytes());
+        Self { buffer }
+    }
+
+    /// Start a tuple with the given number of fields.
+    pub(crate) fn start_tuple(&mut self, num_fields: i16) {
+        self.buffer.extend_from_slice(&num_fields.to_be_bytes());
+    }
+
+    /// Append a null field.
+    pub(crate) fn push_null(&mut self) {
+        self.buffer.extend_from_slice(&(-1_i32).to_be_bytes());
+    }
+
+    /// Append a field with the given bytes.
+    pub(crate) fn push_value(&mut self, value: &[u8]) -> GeoArrowResult<()> {
+        self.push_value_with(|buffer| {
+            buffer.extend_from_slice(value);
+            Ok(())
+        })
+    }
+
+    /// Append a field whose bytes are written by `write`.
+    ///
+    /// This avoids an intermediate allocation for values that are converted while encoding.
+    pub(crate) fn push_value_with(
+        &mut self,
+        write: impl FnOnce(&mut Vec<u8>) -> GeoArrowResult<()>,
+    ) -> GeoArrowResult<()> {
+        let length_position = self.buffer.len();
+        self.buffer.extend_from_slice(&0_i32.to_be_bytes());
+        write(&mut self.buffer)?;
+        let length = self.buffer.len() - length_position - 4;
+        let length = i32::try_from(length).map_err(|_| {
+            GeoArrowError::Postgis(format!(
+                "Value of {length} bytes is too large for a COPY field"
+            ))
+        })?;
+        self.buffer[length_position..length_position + 4].copy_from_slice(&length.to_be_bytes());
+        Ok(())
+    }
+
+    /// The number of bytes encoded since the last call to [`CopyEncoder::take`].
+    pub(crate) fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Take the bytes encoded so far.
+    pub(crate) fn take(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.buffer)
+    }
+
+    /// Finish the stream, returning the remaining bytes including the trailer.
+    pub(crate) fn finish(mut self) -> Vec<u8> {
+        self.buffer.extend_from_slice(&TRAILER.to_be_bytes());
+        self.buffer
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DecoderState {
+    Header,
+    Tuples,
+    Finished,
+}
+
+/// Decodes tuples from a binary `COPY` stream that arrives in arbitrary chunks.
+#[derive(Debug)]
+pub(crate) struct CopyDecoder {
+    buffer: Vec<u8>,
+    position: usize,
+    state: DecoderState,
+}
+
+impl CopyDecoder {
+    pub(crate) fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            position: 0,
+            state: DecoderState::Header,
+        }
+    }
+
+    /// Append a chunk of the stream.
+    pub(crate) fn extend(&mut self, data: &[u8]) {
+        // Drop the bytes of tuples that were already decoded.
+        self.buffer.drain(..self.position);
+        self.position = 0;
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Whether the trailer has been decoded.
+    pub(crate) fn is_finished(&self) -> bool {
+        self.state == DecoderState::Finished
+    }
+
+    /// Decode the next tuple.
+    ///
+    /// Returns `None` if the buffer doesn't hold a complete tuple, in which case more data should
+    /// be passed to [`CopyDecoder::extend`], or if the trailer has been reached.
+    pub(crate) fn next_tuple(&mut self) -> GeoArrowResult<Option<Vec<Option<&[u8]>>>> {
+        if self.state == DecoderState::Header && !self.decode_header()? {
+            return Ok(None);
+        }
+        if self.state == DecoderState::Finished {
+            return Ok(None);
+        }
+
+        let mut position = self.position;
+        let Some(num_fields) = read_i16(&self.buffer, &mut position) else {
+            return Ok(None);
+        };
+        if num_fields == TRAILER {
+            self.position = position;
+            self.state = DecoderState::Finished;
+            return Ok(None);
+        }
+        let num_fields = usize::try_from(num_fields).map_err(|_| {
+            GeoArrowError::Postgis(format!("Invalid COPY field count {num_fields}"))
+        })?;
+
+        let mut ranges = Vec::with_capacity(num_fields);
+        for _ in 0..num_fields {
+            let Some(length) = read_i32(&self.buffer, &mut position) else {
+                return Ok(None);
+            };
+            if length == -1 {
+                ranges.push(None);
+                continue;
+            }
+            let length = usize::try_from(length).map_err(|_| {
+                GeoArrowError::Postgis(format!("Invalid COPY field length {length}"))
+            })?;
+            if self.buffer.len() - position < length {
+                return Ok(None);
+            }
+            ranges.push(Some(position..position + length));
+            position += length;
+        }
+
+        self.position = position;
+        Ok(Some(
+            ranges
+                .into_iter()
+                .map(|range| range.map(|range| &self.buffer[range]))
+                .collect(),
+        ))
+    }
+
+    /// Decode the header, returning `false` if the buffer doesn't hold all of it yet.
+    fn decode_header(&mut self) -> GeoArrowResult<bool> {
+        let header = &self.buffer[self.position..];
+        if header.len() < HEADER_LENGTH {
+            return Ok(false);
+        }
+        if &header[..SIGNATURE.len()] != SIGNATURE {
+            return Err(GeoArrowError::Postgis(
+                "Invalid binary COPY signature".to_string(),
+            ));
+        }
+
+        let mut position = self.position + SIGNATURE.len();
+        let flags = read_i32(&self.buffer, &mut position).unwrap();
+        // Bit 16 means that each tuple has an OID field, which we never request.
+        if flags & (1 << 16) != 0 {
+            return Err(GeoArrowError::Postgis(
+                "Binary COPY data with OIDs is not supported".to_string(),
+            ));
+        }
+        let extension_length = read_i32(&self.buffer, &mut position).unwrap();
+        let extension_length = usize::try_from(extension_length).map_err(|_| {
+            GeoArrowError::Postgis(format!(
+                "Invalid COPY header extension length {extension_length}"
+            ))
+        })?;
+        if self.buffer.len() - position < extension_length {
+            return Ok(false);
+        }
+
+        self.position = position + extension_length;
+        self.state = DecoderState::Tuples;
+        Ok(true)
+    }
+}
+
+fn read_i16(buffer: &[u8], position: &mut usize) -> Option<i16> {
+    let bytes = buffer.get(*position..*position + 2)?;
+    *position += 2;
+    Some(i16::from_be_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_i32(buffer: &[u8], position: &mut usize) -> Option<i32> {
+    let bytes = buffer.get(*position..*position + 4)?;
+    *position += 4;
+    Some(i32::from_be_bytes(bytes.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn encode_rows() -> Vec<u8> {
+        let mut encoder = CopyEncoder::new();
+        encoder.start_tuple(2);
+        encoder.push_value(&1_i32.to_be_bytes()).unwrap();
+        encoder.push_value(b"one").unwrap();
+        encoder.start_tuple(2);
+        encoder.push_null();
+        encoder
+            .push_value_with(|buffer| {
+                buffer.extend_from_slice(b"tw");
+                buffer.push(b'o');
+                Ok(())
+            })
+            .unwrap();
+        encoder.finish()
+    }
+
+    #[test]
+    fn encode() {
+        let bytes = encode_rows();
+        let mut expected = b"PGCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\0".to_vec();
+        expected.extend_from_slice(b"\0\x02\0\0\0\x04\0\0\0\x01\0\0\0\x03one");
+        expected.extend_from_slice(b"\0\x02\xff\xff\xff\xff\0\0\0\x03two");
+        expected.extend_from_slice(b"\xff\xff");
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn decode() {
+        let bytes = encode_rows();
+        let mut decoder = CopyDecoder::new();
+        decoder.extend(&bytes);
+
+        let tuple = decoder.next_tuple().unwrap().unwrap();
+        assert_eq!(tuple, [Some(&[0, 0, 0, 1][..]), Some(&b"one"[..])]);
+        let tuple = decoder.next_tuple().unwrap().unwrap();
+        assert_eq!(tuple, [None, Some(&b"two"[..])]);
+        assert!(decoder.next_tuple().unwrap().is_none());
+        assert!(decoder.is_finished());
+    }
+
+    #[test]
+    fn decode_chunks() {
+        let bytes = encode_rows();
+        let mut decoder = CopyDecoder::new();
+        let mut values = Vec::new();
+        // Feed the stream one byte at a time, so that every boundary is split.
+        for byte in bytes.chunks(1) {
+            decoder.extend(byte);
+            while let Some(tuple) = decoder.next_tuple().unwrap() {
+                values.push(tuple[1].unwrap().to_vec());
+            }
+        }
+        assert_eq!(values, [b"one".to_vec(), b"two".to_vec()]);
+        assert!(decoder.is_finished());
+    }
+
+    #[test]
+    fn decode_header_extension() {
+        let mut bytes = b"PGCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\x02ab".to_vec();
+        bytes.extend_from_slice(b"\0\x01\0\0\0\x01x\xff\xff");
+        let mut decoder = CopyDecoder::new();
+        decoder.extend(&bytes);
+        let tuple = decoder.next_tuple().unwrap().unwrap();
+        assert_eq!(tuple, [Some(&b"x"[..])]);
+    }
+
+    #[test]
+    fn decode_invalid_signature() {
+        let mut decoder = CopyDecoder::new();
+        decoder.extend(b"PGCOPY\n\xff\r\n\x01\0\0\0\0\0\0\0\0");
+        assert!(decoder.next_tuple().is_err());
+    }
+}
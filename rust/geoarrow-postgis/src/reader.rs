@@ -0,0 +1,615 @@
+//! Read query results from PostgreSQL and PostGIS.
+//!
+//! The query is run with `COPY (...) TO STDOUT (FORMAT binary)`, and the binary rows are decoded
+//! straight into Arrow builders.
+
+use std::pin::Pin;
+use std::sync::Arc;
+
+use arrow_array::builder::{
+    BinaryBuilder, BooleanBuilder, Date32Builder, Float32Builder, Float64Builder, Int16Builder,
+    Int32Builder, Int64Builder, StringBuilder, TimestampMicrosecondBuilder,
+};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef, TimeUnit};
+use async_stream::try_stream;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use futures::task::{Context, Poll};
+use futures::{Stream, StreamExt};
+use geoarrow_array::GeoArrowArray;
+use geoarrow_array::array::WkbArray;
+use geoarrow_array::wkb_dialect::{WkbDialect, WkbDialectOptions, from_wkb_dialect};
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+use geoarrow_schema::{Crs, Edges, GeoArrowType, Metadata, WkbType};
+use sqlx::postgres::PgConnection;
+use sqlx::{Column, Executor, TypeInfo};
+
+use crate::copy::CopyDecoder;
+
+/// The number of days from the Unix epoch to the PostgreSQL epoch, 2000-01-01.
+pub(crate) const POSTGRES_EPOCH_DAYS: i32 = 10_957;
+
+/// The number of microseconds from the Unix epoch to the PostgreSQL epoch, 2000-01-01.
+pub(crate) const POSTGRES_EPOCH_MICROS: i64 = 946_684_800_000_000;
+
+/// Options for the PostGIS reader
+#[derive(Debug, Clone)]
+pub struct PostgisReaderOptions {
+    /// The number of rows in each batch.
+    pub batch_size: usize,
+
+    /// The CRS to assign to the geometry columns.
+    ///
+    /// If `None`, each geometry column gets the SRID of its first non-null geometry, stored as a
+    /// CRS of type `srid`. A column with no non-null geometries in the first batch has no CRS.
+    pub crs: Option<Crs>,
+}
+
+impl Default for PostgisReaderOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: 65_536,
+            crs: None,
+        }
+    }
+}
+
+/// The PostgreSQL column types that can be read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PgType {
+    Bool,
+    Int2,
+    Int4,
+    Int8,
+    Float4,
+    Float8,
+    Text,
+    Jsonb,
+    Bytea,
+    Date,
+    Timestamp,
+    Timestamptz,
+    Geometry,
+    Geography,
+}
+
+impl PgType {
+    /// Look up a type by the name that sqlx reports for it.
+    fn from_name(name: &str) -> Option<Self> {
+        let pg_type = match name.to_ascii_lowercase().as_str() {
+            "bool" | "boolean" => Self::Bool,
+            "int2" | "smallint" => Self::Int2,
+            "int4" | "int" | "integer" => Self::Int4,
+            "int8" | "bigint" => Self::Int8,
+            "float4" | "real" => Self::Float4,
+            "float8" | "double precision" => Self::Float8,
+            "text" | "varchar" | "char" | "bpchar" | "name" | "json" => Self::Text,
+            "jsonb" => Self::Jsonb,
+            "bytea" => Self::Bytea,
+            "date" => Self::Date,
+            "timestamp" => Self::Timestamp,
+            "timestamptz" => Self::Timestamptz,
+            "geometry" => Self::Geometry,
+            "geography" => Self::Geography,
+            _ => return None,
+        };
+        Some(pg_type)
+    }
+
+    /// The Arrow type of attribute columns of this type.
+    fn data_type(&self) -> DataType {
+        match self {
+            Self::Bool => DataType::Boolean,
+            Self::Int2 => DataType::Int16,
+            Self::Int4 => DataType::Int32,
+            Self::Int8 => DataType::Int64,
+            Self::Float4 => DataType::Float32,
+            Self::Float8 => DataType::Float64,
+            Self::Text | Self::Jsonb => DataType::Utf8,
+            Self::Bytea | Self::Geometry | Self::Geography => DataType::Binary,
+            Self::Date => DataType::Date32,
+            Self::Timestamp => DataType::Timestamp(TimeUnit::Microsecond, None),
+            Self::Timestamptz => DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+        }
+    }
+
+    fn is_geometry(&self) -> bool {
+        matches!(self, Self::Geometry | Self::Geography)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PgColumn {
+    name: String,
+    pg_type: PgType,
+    /// The SRID of a geometry column, once it is known.
+    srid: Option<i32>,
+}
+
+impl PgColumn {
+    fn merge_srid(&mut self, srid: i32) -> GeoArrowResult<()> {
+        match self.srid {
+            None => self.srid = Some(srid),
+            Some(column_srid) if column_srid == srid => {}
+            Some(column_srid) => {
+                return Err(GeoArrowError::Postgis(format!(
+                    "Column {:?} mixes SRIDs {column_srid} and {srid}",
+                    self.name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Inner structure for the PostGIS record batch stream.
+///
+/// This is separate from the top-level `PostgisRecordBatchStream` because we use
+/// `async_stream::try_stream` to create the stream. This means we need a method to convert into
+/// the opaque stream type, see `[Self::into_stream]`.
+struct PostgisRecordBatchStreamInner<'c> {
+    copy: BoxStream<'c, sqlx::Result<Bytes>>,
+    decoder: CopyDecoder,
+    columns: Vec<PgColumn>,
+    batch_size: usize,
+    crs: Option<Crs>,
+    schema: Option<SchemaRef>,
+}
+
+impl<'c> PostgisRecordBatchStreamInner<'c> {
+    async fn try_new(
+        conn: &'c mut PgConnection,
+        sql: &str,
+        options: PostgisReaderOptions,
+    ) -> GeoArrowResult<Self> {
+        let describe = (&mut *conn).describe(sql).await.map_err(sqlx_error)?;
+        let columns = describe
+            .columns()
+            .iter()
+            .map(|column| {
+                let type_name = column.type_info().name();
+                let pg_type = PgType::from_name(type_name).ok_or_else(|| {
+                    GeoArrowError::Postgis(format!(
+                        "Column {:?} has type {type_name}, which can't be read. Cast it in the \
+                         query to a supported type such as text or float8.",
+                        column.name()
+                    ))
+                })?;
+                Ok(PgColumn {
+                    name: column.name().to_string(),
+                    pg_type,
+                    srid: None,
+                })
+            })
+            .collect::<GeoArrowResult<Vec<_>>>()?;
+
+        let statement = format!(
+            "COPY ({}) TO STDOUT (FORMAT binary)",
+            sql.trim().trim_end_matches(';')
+        );
+        let copy = conn.copy_out_raw(&statement).await.map_err(sqlx_error)?;
+
+        Ok(Self {
+            copy,
+            decoder: CopyDecoder::new(),
+            columns,
+            batch_size: options.batch_size,
+            crs: options.crs,
+            schema: None,
+        })
+    }
+
+    /// The output schema, which is fixed once the SRIDs of the first batch are known.
+    fn output_schema(&mut self) -> SchemaRef {
+        if let Some(schema) = &self.schema {
+            return schema.clone();
+        }
+
+        let fields = self
+            .columns
+            .iter()
+            .map(|column| {
+                if column.pg_type.is_geometry() {
+                    GeoArrowType::Wkb(WkbType::new(self.geometry_metadata(column)))
+                        .to_field(&column.name, true)
+                } else {
+                    Field::new(&column.name, column.pg_type.data_type(), true)
+                }
+            })
+            .collect::<Vec<_>>();
+        let schema = Arc::new(Schema::new(fields));
+        self.schema = Some(schema.clone());
+        schema
+    }
+
+    fn geometry_metadata(&self, column: &PgColumn) -> Arc<Metadata> {
+        let crs = match (&self.crs, column.srid) {
+            (Some(crs), _) => crs.clone(),
+            (None, Some(srid)) if srid != 0 => Crs::from_srid(srid.to_string()),
+            (None, _) => Crs::default(),
+        };
+        let edges = (column.pg_type == PgType::Geography).then_some(Edges::Spherical);
+        Arc::new(Metadata::new(crs, edges))
+    }
+
+    async fn process_batch(&mut self) -> GeoArrowResult<Option<RecordBatch>> {
+        let mut builders = self
+            .columns
+            .iter()
+            .map(|column| ColumnBuilder::new(column.pg_type, self.batch_size))
+            .collect::<Vec<_>>();
+
+        let mut row_count = 0;
+        while row_count < self.batch_size {
+            let Some(tuple) = self.decoder.next_tuple()? else {
+                if self.decoder.is_finished() {
+                    break;
+                }
+                match self.copy.next().await {
+                    Some(chunk) => {
+                        self.decoder.extend(&chunk.map_err(sqlx_error)?);
+                        continue;
+                    }
+                    None => {
+                        return Err(GeoArrowError::Postgis(
+                            "COPY stream ended before its trailer".to_string(),
+                        ));
+                    }
+                }
+            };
+
+            if tuple.len() != self.columns.len() {
+                return Err(GeoArrowError::Postgis(format!(
+                    "Expected {} fields in COPY row, found {}",
+                    self.columns.len(),
+                    tuple.len()
+                )));
+            }
+            for ((builder, column), value) in
+                builders.iter_mut().zip(self.columns.iter_mut()).zip(tuple)
+            {
+                builder.append(column, value)?;
+            }
+            row_count += 1;
+        }
+
+        if row_count == 0 && self.schema.is_some() {
+            return Ok(None);
+        }
+
+        // Finishing the geometry columns reads their SRIDs, which the schema depends on.
+        let columns = builders
+            .into_iter()
+            .zip(self.columns.iter_mut())
+            .map(|(builder, column)| builder.finish(column))
+            .collect::<GeoArrowResult<Vec<_>>>()?;
+        let schema = self.output_schema();
+        Ok(Some(RecordBatch::try_new(schema, columns)?))
+    }
+
+    async fn into_stream(mut self) -> GeoArrowResult<PostgisRecordBatchStream<'c>> {
+        // The first batch is read up front, so that the schema can carry the SRIDs it contains.
+        let first_batch = self.process_batch().await?;
+        let schema = self.output_schema();
+        let stream = Box::pin(try_stream! {
+            if let Some(batch) = first_batch
+                && batch.num_rows() > 0
+            {
+                yield batch;
+            }
+            loop {
+                let maybe_batch = self.process_batch().await.map_err(|e| {
+                    ArrowError::ExternalError(Box::new(e))
+                })?;
+
+                if let Some(batch) = maybe_batch {
+                    yield batch;
+                } else {
+                    break;
+                }
+            }
+        });
+        Ok(PostgisRecordBatchStream { stream, schema })
+    }
+}
+
+/// An async stream of record batches from a PostgreSQL query.
+///
+/// Columns are read with these types:
+///
+/// | PostgreSQL                                       | Arrow                        |
+/// | ------------------------------------------------ | ---------------------------- |
+/// | `bool`                                           | `Boolean`                    |
+/// | `int2`, `int4`, `int8`                           | `Int16`, `Int32`, `Int64`    |
+/// | `float4`, `float8`                               | `Float32`, `Float64`         |
+/// | `text`, `varchar`, `char`, `name`, `json`, `jsonb` | `Utf8`                     |
+/// | `bytea`                                          | `Binary`                     |
+/// | `date`                                           | `Date32`                     |
+/// | `timestamp`, `timestamptz`                       | `Timestamp(Microsecond)`     |
+/// | `geometry`, `geography`                          | GeoArrow WKB                 |
+///
+/// Other types are an error; cast them in the query. Geometries are converted from EWKB to ISO
+/// WKB, and the SRID of the first non-null geometry of each column is stored in the column
+/// metadata. A later geometry with a different SRID is an error. `geography` columns are marked
+/// as having spherical edges.
+///
+/// This implements the `Stream` trait, emitting [`RecordBatch`]es as they are read from the
+/// database. This implementation is modeled to be used with the DataFusion
+/// [`RecordBatchStream`] trait.
+///
+/// [`RecordBatchStream`]: https://docs.rs/datafusion/latest/datafusion/execution/trait.RecordBatchStream.html
+pub struct PostgisRecordBatchStream<'c> {
+    stream: BoxStream<'c, Result<RecordBatch, ArrowError>>,
+    schema: SchemaRef,
+}
+
+impl<'c> PostgisRecordBatchStream<'c> {
+    /// Run a query, streaming its results.
+    ///
+    /// The query can be any statement that `COPY (...) TO STDOUT` accepts, typically a `SELECT`.
+    /// The first batch is read before this returns, so that its SRIDs can be added to the
+    /// schema.
+    pub async fn try_new(
+        conn: &'c mut PgConnection,
+        sql: &str,
+        options: PostgisReaderOptions,
+    ) -> GeoArrowResult<Self> {
+        PostgisRecordBatchStreamInner::try_new(conn, sql, options)
+            .await?
+            .into_stream()
+            .await
+    }
+}
+
+impl Stream for PostgisRecordBatchStream<'_> {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.stream).poll_next(cx)
+    }
+}
+
+impl PostgisRecordBatchStream<'_> {
+    /// Returns the schema of the record batches produced by this stream.
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+/// Builds the Arrow array of one column from binary `COPY` values.
+enum ColumnBuilder {
+    Boolean(BooleanBuilder),
+    Int16(Int16Builder),
+    Int32(Int32Builder),
+    Int64(Int64Builder),
+    Float32(Float32Builder),
+    Float64(Float64Builder),
+    String(StringBuilder),
+    Jsonb(StringBuilder),
+    Binary(BinaryBuilder),
+    Date32(Date32Builder),
+    Timestamp(TimestampMicrosecondBuilder),
+    Geometry(BinaryBuilder),
+}
+
+impl ColumnBuilder {
+    fn new(pg_type: PgType, capacity: usize) -> Self {
+        match pg_type {
+            PgType::Bool => Self::Boolean(BooleanBuilder::with_capacity(capacity)),
+            PgType::Int2 => Self::Int16(Int16Builder::with_capacity(capacity)),
+            PgType::Int4 => Self::Int32(Int32Builder::with_capacity(capacity)),
+            PgType::Int8 => Self::Int64(Int64Builder::with_capacity(capacity)),
+            PgType::Float4 => Self::Float32(Float32Builder::with_capacity(capacity)),
+            PgType::Float8 => Self::Float64(Float64Builder::with_capacity(capacity)),
+            PgType::Text => Self::String(StringBuilder::new()),
+            PgType::Jsonb => Self::Jsonb(StringBuilder::new()),
+            PgType::Bytea => Self::Binary(BinaryBuilder::new()),
+            PgType::Date => Self::Date32(Date32Builder::with_capacity(capacity)),
+            PgType::Timestamp | PgType::Timestamptz => {
+                Self::Timestamp(TimestampMicrosecondBuilder::with_capacity(capacity))
+            }
+            PgType::Geometry | PgType::Geography => Self::Geometry(BinaryBuilder::new()),
+        }
+    }
+
+    fn append(&mut self, column: &mut PgColumn, value: Option<&[u8]>) -> GeoArrowResult<()> {
+        let Some(value) = value else {
+            self.append_null();
+            return Ok(());
+        };
+        match self {
+            Self::Boolean(builder) => builder.append_value(fixed::<1>(value, column)?[0] != 0),
+            Self::Int16(builder) => {
+                builder.append_value(i16::from_be_bytes(fixed(value, column)?));
+            }
+            Self::Int32(builder) => {
+                builder.append_value(i32::from_be_bytes(fixed(value, column)?));
+            }
+            Self::Int64(builder) => {
+                builder.append_value(i64::from_be_bytes(fixed(value, column)?));
+            }
+            Self::Float32(builder) => {
+                builder.append_value(f32::from_be_bytes(fixed(value, column)?));
+            }
+            Self::Float64(builder) => {
+                builder.append_value(f64::from_be_bytes(fixed(value, column)?));
+            }
+            Self::String(builder) => builder.append_value(utf8(value, column)?),
+            Self::Jsonb(builder) => {
+                // Binary jsonb is a version byte followed by the JSON text.
+                match value.split_first() {
+                    Some((1, json)) => builder.append_value(utf8(json, column)?),
+                    _ => {
+                        return Err(GeoArrowError::Postgis(format!(
+                            "Unsupported jsonb format version in column {:?}",
+                            column.name
+                        )));
+                    }
+                }
+            }
+            Self::Date32(builder) => {
+                let days = i32::from_be_bytes(fixed(value, column)?);
+                builder.append_value(days.saturating_add(POSTGRES_EPOCH_DAYS));
+            }
+            Self::Timestamp(builder) => {
+                let micros = i64::from_be_bytes(fixed(value, column)?);
+                builder.append_value(micros.saturating_add(POSTGRES_EPOCH_MICROS));
+            }
+            Self::Binary(builder) | Self::Geometry(builder) => builder.append_value(value),
+        }
+        Ok(())
+    }
+
+    fn append_null(&mut self) {
+        match self {
+            Self::Boolean(builder) => builder.append_null(),
+            Self::Int16(builder) => builder.append_null(),
+            Self::Int32(builder) => builder.append_null(),
+            Self::Int64(builder) => builder.append_null(),
+            Self::Float32(builder) => builder.append_null(),
+            Self::Float64(builder) => builder.append_null(),
+            Self::String(builder) | Self::Jsonb(builder) => builder.append_null(),
+            Self::Binary(builder) | Self::Geometry(builder) => builder.append_null(),
+            Self::Date32(builder) => builder.append_null(),
+            Self::Timestamp(builder) => builder.append_null(),
+        }
+    }
+
+    /// Finish the column, recording the SRID of a geometry column's values in `column`.
+    fn finish(self, column: &mut PgColumn) -> GeoArrowResult<ArrayRef> {
+        let array: ArrayRef = match self {
+            Self::Boolean(mut builder) => Arc::new(builder.finish()),
+            Self::Int16(mut builder) => Arc::new(builder.finish()),
+            Self::Int32(mut builder) => Arc::new(builder.finish()),
+            Self::Int64(mut builder) => Arc::new(builder.finish()),
+            Self::Float32(mut builder) => Arc::new(builder.finish()),
+            Self::Float64(mut builder) => Arc::new(builder.finish()),
+            Self::String(mut builder) | Self::Jsonb(mut builder) => Arc::new(builder.finish()),
+            Self::Binary(mut builder) => Arc::new(builder.finish()),
+            Self::Date32(mut builder) => Arc::new(builder.finish()),
+            Self::Timestamp(builder) => {
+                let DataType::Timestamp(_, timezone) = column.pg_type.data_type() else {
+                    unreachable!("timestamp columns have a timestamp type")
+                };
+                Arc::new(builder.with_timezone_opt(timezone).finish())
+            }
+            Self::Geometry(mut builder) => {
+                let ewkb = WkbArray::new(builder.finish(), Default::default());
+                let options = WkbDialectOptions::new(WkbDialect::Ewkb);
+                let wkb = from_wkb_dialect(&ewkb, GeoArrowType::Wkb(Default::default()), &options)?;
+                if ewkb.logical_null_count() < ewkb.len() {
+                    // Values without an SRID are recorded as SRID 0, as PostGIS does.
+                    let srid = wkb
+                        .data_type()
+                        .metadata()
+                        .crs()
+                        .crs_value()
+                        .and_then(|value| value.as_str()?.parse().ok())
+                        .unwrap_or(0);
+                    column.merge_srid(srid)?;
+                }
+                wkb.to_array_ref()
+            }
+        };
+        Ok(array)
+    }
+}
+
+fn fixed<const N: usize>(value: &[u8], column: &PgColumn) -> GeoArrowResult<[u8; N]> {
+    value.try_into().map_err(|_| {
+        GeoArrowError::Postgis(format!(
+            "Expected a {N}-byte value in column {:?}, found {} bytes",
+            column.name,
+            value.len()
+        ))
+    })
+}
+
+fn utf8<'a>(value: &'a [u8], column: &PgColumn) -> GeoArrowResult<&'a str> {
+    std::str::from_utf8(value).map_err(|err| {
+        GeoArrowError::Postgis(format!("Invalid UTF-8 in column {:?}: {err}", column.name))
+    })
+}
+
+pub(crate) fn sqlx_error(err: sqlx::Error) -> GeoArrowError {
+    GeoArrowError::External(Box::new(err))
+}
+
+#[cfg(test)]
+mod test {
+    use arrow_array::Array;
+    use arrow_array::cast::AsArray;
+
+    use super::*;
+
+    fn column(pg_type: PgType) -> PgColumn {
+        PgColumn {
+            name: "value".to_string(),
+            pg_type,
+            srid: None,
+        }
+    }
+
+    #[test]
+    fn type_names() {
+        assert_eq!(PgType::from_name("INT4"), Some(PgType::Int4));
+        assert_eq!(PgType::from_name("TIMESTAMPTZ"), Some(PgType::Timestamptz));
+        assert_eq!(PgType::from_name("geography"), Some(PgType::Geography));
+        assert_eq!(PgType::from_name("NUMERIC"), None);
+    }
+
+    #[test]
+    fn decode_values() {
+        let mut date = column(PgType::Date);
+        let mut builder = ColumnBuilder::new(PgType::Date, 2);
+        // 2000-01-02 is day 1 in PostgreSQL.
+        builder
+            .append(&mut date, Some(&1_i32.to_be_bytes()))
+            .unwrap();
+        builder.append(&mut date, None).unwrap();
+        let array = builder.finish(&mut date).unwrap();
+        let array = array
+            .as_any()
+            .downcast_ref::<arrow_array::Date32Array>()
+            .unwrap();
+        assert_eq!(array.value(0), 10_958);
+        assert!(array.is_null(1));
+
+        let mut jsonb = column(PgType::Jsonb);
+        let mut builder = ColumnBuilder::new(PgType::Jsonb, 1);
+        builder.append(&mut jsonb, Some(b"\x01{}")).unwrap();
+        assert!(builder.append(&mut jsonb, Some(b"\x02{}")).is_err());
+
+        let mut int = column(PgType::Int4);
+        let mut builder = ColumnBuilder::new(PgType::Int4, 1);
+        assert!(builder.append(&mut int, Some(&[0, 1])).is_err());
+    }
+
+    #[test]
+    fn decode_geometry_srid() {
+        // `SRID=4326;POINT (1 2)` and `SRID=3857;POINT (1 2)` as little-endian EWKB.
+        let mut ewkb = vec![1];
+        ewkb.extend_from_slice(&0x2000_0001_u32.to_le_bytes());
+        ewkb.extend_from_slice(&4326_i32.to_le_bytes());
+        ewkb.extend_from_slice(&1_f64.to_le_bytes());
+        ewkb.extend_from_slice(&2_f64.to_le_bytes());
+        let mut other = ewkb.clone();
+        other[5..9].copy_from_slice(&3857_i32.to_le_bytes());
+
+        let mut geometry = column(PgType::Geometry);
+        let mut builder = ColumnBuilder::new(PgType::Geometry, 2);
+        builder.append(&mut geometry, Some(&ewkb)).unwrap();
+        builder.append(&mut geometry, None).unwrap();
+        let array = builder.finish(&mut geometry).unwrap();
+        assert_eq!(geometry.srid, Some(4326));
+        // The SRID is dropped from the stored ISO WKB.
+        let wkb = array.as_binary::<i32>();
+        assert_eq!(wkb.value(0).len(), ewkb.len() - 4);
+        assert!(wkb.is_null(1));
+
+        let mut builder = ColumnBuilder::new(PgType::Geometry, 1);
+        builder.append(&mut geometry, Some(&other)).unwrap();
+        assert!(builder.finish(&mut geometry).is_err());
+    }
+}
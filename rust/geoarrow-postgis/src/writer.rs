@@ -0,0 +1,441 @@
+//! Write to PostgreSQL and PostGIS tables.
+//!
+//! Batches are bulk-loaded with `COPY ... FROM STDIN (FORMAT binary)`, encoding each Arrow column
+//! in the binary format of the matching PostgreSQL type.
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Date32Type, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, TimestampMicrosecondType,
+};
+use arrow_array::{Array, ArrayRef, RecordBatch, RecordBatchReader};
+use arrow_schema::{DataType, Field, SchemaRef, TimeUnit};
+use geoarrow_array::array::from_arrow_array;
+use geoarrow_array::wkb_dialect::{WkbDialect, to_wkb_dialect};
+use geoarrow_schema::GeoArrowType;
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+use sqlx::postgres::{PgConnection, PgCopyIn};
+
+use crate::copy::CopyEncoder;
+use crate::reader::{POSTGRES_EPOCH_DAYS, POSTGRES_EPOCH_MICROS, sqlx_error};
+
+/// The number of encoded bytes to buffer before sending them to the server.
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// A PostGIS writer.
+///
+/// This loads record batches into an existing table with binary `COPY`. The server parses each
+/// value with the receive function of the column it is loaded into, so the table's column types
+/// must match the Arrow types:
+///
+/// | Arrow                                  | PostgreSQL             |
+/// | -------------------------------------- | ---------------------- |
+/// | `Boolean`                              | `bool`                 |
+/// | `Int8`, `UInt8`, `Int16`               | `int2`                 |
+/// | `UInt16`, `Int32`                      | `int4`                 |
+/// | `UInt32`, `Int64`                      | `int8`                 |
+/// | `Float16`, `Float32`                   | `float4`               |
+/// | `Float64`                              | `float8`               |
+/// | string types                           | `text` or `varchar`    |
+/// | binary types                           | `bytea`                |
+/// | `Date32`, `Date64`                     | `date`                 |
+/// | `Timestamp` without a time zone        | `timestamp`            |
+/// | `Timestamp` with a time zone           | `timestamptz`          |
+/// | GeoArrow geometry                      | `geometry` or `geography` |
+///
+/// Other column types are an error. Geometries are written as EWKB with the SRID of the column's
+/// CRS, if it is stored as an SRID or has an `EPSG` authority code (`OGC:CRS84` is written as
+/// `4326`).
+///
+/// This differs from `write_postgis` in that it allows for **push-based** instead of
+/// **pull-based** iteration.
+pub struct PostgisWriter<'c> {
+    copy: PgCopyIn<&'c mut PgConnection>,
+    encoder: CopyEncoder,
+    schema: SchemaRef,
+    columns: Vec<ColumnEncoder>,
+}
+
+impl<'c> PostgisWriter<'c> {
+    /// Start loading batches with the given schema into `table`.
+    ///
+    /// Columns are matched to table columns by name. `table` is used verbatim, so it can be
+    /// schema-qualified and must be quoted if needed.
+    pub async fn try_new(
+        conn: &'c mut PgConnection,
+        table: &str,
+        schema: SchemaRef,
+    ) -> GeoArrowResult<Self> {
+        if schema.fields().len() > i16::MAX as usize {
+            return Err(GeoArrowError::Postgis(format!(
+                "Cannot write {} columns with COPY",
+                schema.fields().len()
+            )));
+        }
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|field| ColumnEncoder::try_new(field))
+            .collect::<GeoArrowResult<Vec<_>>>()?;
+
+        let column_names = schema
+            .fields()
+            .iter()
+            .map(|field| quote_identifier(field.name()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let statement = format!("COPY {table} ({column_names}) FROM STDIN (FORMAT binary)");
+        let copy = conn.copy_in_raw(&statement).await.map_err(sqlx_error)?;
+
+        Ok(Self {
+            copy,
+            encoder: CopyEncoder::new(),
+            schema,
+            columns,
+        })
+    }
+
+    /// Write a [`RecordBatch`] to the table.
+    ///
+    /// This will error if the schema of the `RecordBatch` does not match the schema originally
+    /// passed to [`PostgisWriter::try_new`].
+    pub async fn write(&mut self, batch: &RecordBatch) -> GeoArrowResult<()> {
+        if batch.schema_ref().fields() != self.schema.fields() {
+            return Err(GeoArrowError::Postgis(
+                "Record batch schema does not match the writer schema".to_string(),
+            ));
+        }
+
+        let arrays = self
+            .columns
+            .iter()
+            .zip(batch.columns())
+            .zip(self.schema.fields())
+            .map(|((column, array), field)| column.cast(array, field))
+            .collect::<GeoArrowResult<Vec<_>>>()?;
+
+        for row in 0..batch.num_rows() {
+            self.encoder.start_tuple(self.columns.len() as i16);
+            for (column, array) in self.columns.iter().zip(&arrays) {
+                column.encode(&mut self.encoder, array, row)?;
+            }
+            if self.encoder.len() >= CHUNK_SIZE {
+                self.send().await?;
+            }
+        }
+        self.send().await
+    }
+
+    /// Finish loading, returning the number of rows written.
+    pub async fn finish(mut self) -> GeoArrowResult<u64> {
+        self.copy
+            .send(self.encoder.finish())
+            .await
+            .map_err(sqlx_error)?;
+        self.copy.finish().await.map_err(sqlx_error)
+    }
+
+    /// Abort loading, discarding every row written so far.
+    pub async fn abort(self, message: &str) -> GeoArrowResult<()> {
+        self.copy.abort(message).await.map_err(sqlx_error)
+    }
+
+    async fn send(&mut self) -> GeoArrowResult<()> {
+        if self.encoder.len() > 0 {
+            self.copy
+                .send(self.encoder.take())
+                .await
+                .map_err(sqlx_error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Load a stream of GeoArrow RecordBatches into an existing table, returning the number of rows
+/// written.
+///
+/// See [`PostgisWriter`] for how Arrow types map to PostgreSQL types. If a batch fails to encode,
+/// the `COPY` is aborted and no rows are written.
+pub async fn write_postgis<S: RecordBatchReader>(
+    conn: &mut PgConnection,
+    table: &str,
+    stream: S,
+) -> GeoArrowResult<u64> {
+    let mut writer = PostgisWriter::try_new(conn, table, stream.schema()).await?;
+    for batch in stream {
+        let result = match batch {
+            Ok(batch) => writer.write(&batch).await,
+            Err(err) => Err(err.into()),
+        };
+        if let Err(err) = result {
+            writer.abort(&err.to_string()).await?;
+            return Err(err);
+        }
+    }
+    writer.finish().await
+}
+
+/// Encodes one column in the binary format of a PostgreSQL type.
+#[derive(Debug, Clone, Copy)]
+enum ColumnEncoder {
+    Bool,
+    Int2,
+    Int4,
+    Int8,
+    Float4,
+    Float8,
+    Text,
+    Bytea,
+    Date,
+    Timestamp,
+    Geometry,
+}
+
+impl ColumnEncoder {
+    fn try_new(field: &Field) -> GeoArrowResult<Self> {
+        if GeoArrowType::from_extension_field(field)?.is_some() {
+            return Ok(Self::Geometry);
+        }
+
+        let encoder = match field.data_type() {
+            DataType::Boolean => Self::Bool,
+            DataType::Int8 | DataType::UInt8 | DataType::Int16 => Self::Int2,
+            DataType::UInt16 | DataType::Int32 => Self::Int4,
+            DataType::UInt32 | DataType::Int64 => Self::Int8,
+            DataType::Float16 | DataType::Float32 => Self::Float4,
+            DataType::Float64 => Self::Float8,
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => Self::Text,
+            DataType::Dictionary(_, value_type)
+                if matches!(
+                    value_type.as_ref(),
+                    DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+                ) =>
+            {
+                Self::Text
+            }
+            DataType::Binary | DataType::LargeBinary | DataType::BinaryView => Self::Bytea,
+            DataType::Date32 | DataType::Date64 => Self::Date,
+            DataType::Timestamp(_, _) => Self::Timestamp,
+            data_type => {
+                return Err(GeoArrowError::Postgis(format!(
+                    "Column {:?} has type {data_type}, which can't be written with COPY. Cast it \
+                     to a boolean, integer, float, string, binary, date or timestamp column \
+                     first.",
+                    field.name()
+                )));
+            }
+        };
+        Ok(encoder)
+    }
+
+    /// Cast a column to the Arrow type that [`Self::encode`] reads.
+    fn cast(&self, array: &ArrayRef, field: &Field) -> GeoArrowResult<ArrayRef> {
+        let to_type = match self {
+            Self::Bool => DataType::Boolean,
+            Self::Int2 => DataType::Int16,
+            Self::Int4 => DataType::Int32,
+            Self::Int8 => DataType::Int64,
+            Self::Float4 => DataType::Float32,
+            Self::Float8 => DataType::Float64,
+            Self::Text => DataType::Utf8,
+            Self::Bytea => DataType::Binary,
+            Self::Date => DataType::Date32,
+            Self::Timestamp => {
+                let DataType::Timestamp(_, timezone) = field.data_type() else {
+                    unreachable!("timestamp encoders have a timestamp field")
+                };
+                DataType::Timestamp(TimeUnit::Microsecond, timezone.clone())
+            }
+            Self::Geometry => {
+                let geometry = from_arrow_array(array, field)?;
+                let ewkb = to_wkb_dialect::<i32>(geometry.as_ref(), WkbDialect::Ewkb)?;
+                return Ok(std::sync::Arc::new(ewkb.inner().clone()));
+            }
+        };
+        Ok(arrow_cast::cast(array, &to_type)?)
+    }
+
+    fn encode(
+        &self,
+        encoder: &mut CopyEncoder,
+        array: &ArrayRef,
+        row: usize,
+    ) -> GeoArrowResult<()> {
+        if array.is_null(row) {
+            encoder.push_null();
+            return Ok(());
+        }
+        match self {
+            Self::Bool => encoder.push_value(&[u8::from(array.as_boolean().value(row))]),
+            Self::Int2 => {
+                encoder.push_value(&array.as_primitive::<Int16Type>().value(row).to_be_bytes())
+            }
+            Self::Int4 => {
+                encoder.push_value(&array.as_primitive::<Int32Type>().value(row).to_be_bytes())
+            }
+            Self::Int8 => {
+                encoder.push_value(&array.as_primitive::<Int64Type>().value(row).to_be_bytes())
+            }
+            Self::Float4 => {
+                encoder.push_value(&array.as_primitive::<Float32Type>().value(row).to_be_bytes())
+            }
+            Self::Float8 => {
+                encoder.push_value(&array.as_primitive::<Float64Type>().value(row).to_be_bytes())
+            }
+            Self::Text => encoder.push_value(array.as_string::<i32>().value(row).as_bytes()),
+            Self::Bytea | Self::Geometry => encoder.push_value(array.as_binary::<i32>().value(row)),
+            Self::Date => {
+                let days = array.as_primitive::<Date32Type>().value(row);
+                encoder.push_value(&days.saturating_sub(POSTGRES_EPOCH_DAYS).to_be_bytes())
+            }
+            Self::Timestamp => {
+                let micros = array.as_primitive::<TimestampMicrosecondType>().value(row);
+                encoder.push_value(&micros.saturating_sub(POSTGRES_EPOCH_MICROS).to_be_bytes())
+            }
+        }
+    }
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use arrow_array::{RecordBatchIterator, create_array};
+    use arrow_schema::Schema;
+    use futures::TryStreamExt;
+    use geo_traits::{CoordTrait, PointTrait};
+    use geoarrow_array::builder::PointBuilder;
+    use geoarrow_array::cast::AsGeoArrowArray;
+    use geoarrow_array::{GeoArrowArray, GeoArrowArrayAccessor};
+    use geoarrow_schema::{Crs, Dimension, Metadata, PointType};
+    use sqlx::{Connection, Executor};
+
+    use super::*;
+    use crate::reader::{PostgisReaderOptions, PostgisRecordBatchStream};
+
+    #[test]
+    fn encode_values() {
+        let timestamps: ArrayRef = Arc::new(
+            arrow_array::TimestampMillisecondArray::from(vec![Some(946_684_800_001), None])
+                .with_timezone("UTC"),
+        );
+        let field = Field::new("observed", timestamps.data_type().clone(), true);
+        let column = ColumnEncoder::try_new(&field).unwrap();
+        let array = column.cast(&timestamps, &field).unwrap();
+
+        let mut encoder = CopyEncoder::new();
+        let header_length = encoder.len();
+        column.encode(&mut encoder, &array, 0).unwrap();
+        column.encode(&mut encoder, &array, 1).unwrap();
+        let bytes = encoder.take();
+        let mut expected = 8_i32.to_be_bytes().to_vec();
+        expected.extend_from_slice(&1000_i64.to_be_bytes());
+        expected.extend_from_slice(&(-1_i32).to_be_bytes());
+        assert_eq!(&bytes[header_length..], expected);
+    }
+
+    #[test]
+    fn unsupported_column_type() {
+        let field = Field::new("amount", DataType::Decimal128(10, 2), true);
+        let err = ColumnEncoder::try_new(&field).unwrap_err().to_string();
+        assert!(err.contains("\"amount\""), "{err}");
+    }
+
+    #[test]
+    fn encode_geometry_srid() {
+        let metadata = Arc::new(Metadata::new(
+            Crs::from_authority_code("EPSG:3857".to_string()),
+            None,
+        ));
+        let mut builder = PointBuilder::with_capacity(PointType::new(Dimension::XY, metadata), 1);
+        builder.push_point(Some(&wkt::wkt!(POINT (1. 2.))));
+        let points = builder.finish();
+        let field = points.data_type().to_field("geom", true);
+        let column = ColumnEncoder::try_new(&field).unwrap();
+        let array = column.cast(&points.into_array_ref(), &field).unwrap();
+
+        // Little-endian EWKB of `SRID=3857;POINT (1 2)`.
+        let mut expected = vec![1];
+        expected.extend_from_slice(&0x2000_0001_u32.to_le_bytes());
+        expected.extend_from_slice(&3857_i32.to_le_bytes());
+        expected.extend_from_slice(&1_f64.to_le_bytes());
+        expected.extend_from_slice(&2_f64.to_le_bytes());
+        assert_eq!(array.as_binary::<i32>().value(0), expected);
+    }
+
+    #[ignore = "requires a PostGIS database at GEOARROW_POSTGIS_URL"]
+    #[tokio::test]
+    async fn roundtrip() {
+        let url = std::env::var("GEOARROW_POSTGIS_URL").unwrap();
+        let mut conn = PgConnection::connect(&url).await.unwrap();
+        conn.execute(
+            "CREATE TEMPORARY TABLE places (name text, visits int8, geom geometry(Point, 4326))",
+        )
+        .await
+        .unwrap();
+
+        let metadata = Arc::new(Metadata::new(
+            Crs::from_authority_code("EPSG:4326".to_string()),
+            None,
+        ));
+        let typ = PointType::new(Dimension::XY, metadata);
+        let mut builder = PointBuilder::with_capacity(typ, 2);
+        builder.push_point(Some(&wkt::wkt!(POINT (1. 2.))));
+        builder.push_point(None::<&wkt::types::Point<f64>>);
+        let points = builder.finish();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("name", DataType::Utf8, true),
+            Field::new("visits", DataType::Int64, true),
+            points.data_type().to_field("geom", true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                create_array!(Utf8, [Some("a"), Some("b")]),
+                create_array!(Int64, [Some(1), None]),
+                points.into_array_ref(),
+            ],
+        )
+        .unwrap();
+        let stream = RecordBatchIterator::new(vec![Ok(batch)], schema);
+        let num_rows = write_postgis(&mut conn, "places", stream).await.unwrap();
+        assert_eq!(num_rows, 2);
+
+        let stream = PostgisRecordBatchStream::try_new(
+            &mut conn,
+            "SELECT * FROM places ORDER BY name",
+            PostgisReaderOptions::default(),
+        )
+        .await
+        .unwrap();
+        let schema = stream.schema();
+        let geometry_type = GeoArrowType::from_extension_field(schema.field(2))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            geometry_type.metadata().crs(),
+            &Crs::from_srid("4326".to_string())
+        );
+
+        let batches = stream.try_collect::<Vec<_>>().await.unwrap();
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.column(0).as_string::<i32>().value(1), "b");
+        assert_eq!(batch.column(1).as_primitive::<Int64Type>().value(0), 1);
+        assert!(batch.column(1).is_null(1));
+
+        let geometries = from_arrow_array(batch.column(2), schema.field(2)).unwrap();
+        let wkb = geometries.as_ref().as_wkb::<i32>();
+        let point = wkb.value(0).unwrap();
+        let geo_traits::GeometryType::Point(point) = geo_traits::GeometryTrait::as_type(&point)
+        else {
+            panic!("expected a point");
+        };
+        let coord = point.coord().unwrap();
+        assert_eq!((coord.x(), coord.y()), (1., 2.));
+        assert!(wkb.is_null(1));
+    }
+}
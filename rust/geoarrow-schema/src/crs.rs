@@ -29,9 +29,10 @@ use crate::error::{GeoArrowError, GeoArrowResult};
 /// (longitude, latitude) and (easting, northing) regardless of the the axis order encoded in the
 /// CRS specification.
 ///
-/// Note that [`PartialEq`] and [`Eq`] currently use their default, derived implementations, so
-/// only `Crs` that are structurally exactly equal will compare as equal. Two different
-/// representations of the same logical CRS will not compare as equal.
+/// Note that [`PartialEq`] and [`Eq`] use their default, derived implementations, so only `Crs`
+/// that are structurally exactly equal will compare as equal. Use
+/// [`is_equivalent`][Self::is_equivalent] to check whether two different representations
+/// identify the same logical CRS.
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Crs {
     /// One of:
@@ -129,12 +130,169 @@ impl Crs {
         self.crs.as_ref()
     }
 
+    /// The authority identifier of this CRS, as `(authority, code)`, if one can be found.
+    ///
+    /// Identifiers are read from:
+    ///
+    /// - The top-level `id` (or first entry of `ids`) of a PROJJSON object.
+    /// - The top-level `ID[...]` (WKT2) or `AUTHORITY[...]` (WKT1) node of a WKT string.
+    /// - An `AUTHORITY:CODE` string, or an OGC URN such as `urn:ogc:def:crs:EPSG::4326`.
+    ///
+    /// The authority is upper-cased. SRIDs are opaque and never produce an identifier.
+    pub fn authority_code(&self) -> Option<(String, String)> {
+        let value = self.crs.as_ref()?;
+        let (authority, code) = match (self.crs_type, value) {
+            (Some(CrsType::Projjson) | None, Value::Object(obj)) => {
+                let id = obj
+                    .get("id")
+                    .or_else(|| obj.get("ids").and_then(|ids| ids.get(0)))?;
+                let authority = id.get("authority")?.as_str()?.to_string();
+                let code = match id.get("code")? {
+                    Value::String(code) => code.clone(),
+                    Value::Number(code) => code.to_string(),
+                    _ => return None,
+                };
+                (authority, code)
+            }
+            (Some(CrsType::Wkt2_2019), Value::String(s)) => wkt_authority_code(s)?,
+            (Some(CrsType::AuthorityCode), Value::String(s)) => parse_authority_code(s)?,
+            (None, Value::String(s)) => {
+                parse_authority_code(s).or_else(|| wkt_authority_code(s))?
+            }
+            _ => return None,
+        };
+
+        let authority = authority.trim().to_ascii_uppercase();
+        let code = code.trim().to_string();
+        if authority.is_empty() || code.is_empty() {
+            return None;
+        }
+        Some((authority, code))
+    }
+
+    /// Returns `true` if this CRS and `other` identify the same logical CRS.
+    ///
+    /// Two CRS are equivalent if they are structurally equal, or if both carry the same authority
+    /// identifier (see [`authority_code`][Self::authority_code]) regardless of how they are
+    /// represented. For example, the string `"EPSG:4326"`, a PROJJSON object with
+    /// `"id": {"authority": "EPSG", "code": 4326}` and a WKT2 string ending in
+    /// `ID["EPSG",4326]` are all equivalent.
+    ///
+    /// Since GeoArrow always interprets coordinates as (longitude, latitude) regardless of the
+    /// axis order of the CRS, `OGC:CRS84` is considered equivalent to `EPSG:4326`.
+    pub fn is_equivalent(&self, other: &Crs) -> bool {
+        if self == other {
+            return true;
+        }
+        match (self.authority_code(), other.authority_code()) {
+            (Some(left), Some(right)) => normalize_crs84(left) == normalize_crs84(right),
+            _ => false,
+        }
+    }
+
     /// Return `true` if we should include a CRS key in the GeoArrow metadata
     pub(crate) fn should_serialize(&self) -> bool {
         self.crs.is_some()
     }
 }
 
+/// Parse an `AUTHORITY:CODE` string or an OGC URN like `urn:ogc:def:crs:EPSG::4326`.
+fn parse_authority_code(s: &str) -> Option<(String, String)> {
+    let s = s.trim();
+    if s.contains(|c: char| c.is_whitespace() || c == '[' || c == '(') {
+        return None;
+    }
+
+    let lower = s.to_ascii_lowercase();
+    if lower.starts_with("urn:ogc:def:crs:") {
+        // urn:ogc:def:crs:{authority}:{version}:{code}, where the version may be empty
+        let mut parts = s["urn:ogc:def:crs:".len()..].split(':');
+        let authority = parts.next()?;
+        let _version = parts.next()?;
+        let code = parts.next()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        return Some((authority.to_string(), code.to_string()));
+    }
+
+    let (authority, code) = s.split_once(':')?;
+    if code.contains(':') {
+        return None;
+    }
+    Some((authority.to_string(), code.to_string()))
+}
+
+/// Find the top-level `ID[...]` or `AUTHORITY[...]` node of a WKT string.
+///
+/// If there are several, the last one wins, as that is where WKT places the identifier of the
+/// CRS itself.
+fn wkt_authority_code(wkt: &str) -> Option<(String, String)> {
+    let bytes = wkt.as_bytes();
+    let mut depth = 0usize;
+    let mut in_quotes = false;
+    let mut keyword_start = None;
+    let mut node_start = None;
+    let mut found = None;
+
+    for (i, &b) in bytes.iter().enumerate() {
+        if in_quotes {
+            // Quotes inside WKT strings are escaped by doubling them, which is handled here by
+            // toggling out of and straight back into a quoted string.
+            if b == b'"' {
+                in_quotes = false;
+            }
+            continue;
+        }
+        match b {
+            b'"' => in_quotes = true,
+            b'[' | b'(' => {
+                if depth == 1
+                    && let Some(start) = keyword_start
+                {
+                    let keyword = wkt[start..i].trim();
+                    if keyword.eq_ignore_ascii_case("ID")
+                        || keyword.eq_ignore_ascii_case("AUTHORITY")
+                    {
+                        node_start = Some(i + 1);
+                    }
+                }
+                depth += 1;
+                keyword_start = None;
+            }
+            b']' | b')' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 1
+                    && let Some(start) = node_start.take()
+                {
+                    found = Some(&wkt[start..i]);
+                }
+                keyword_start = None;
+            }
+            b',' => keyword_start = None,
+            _ if (b.is_ascii_alphanumeric() || b == b'_') && keyword_start.is_none() => {
+                keyword_start = Some(i);
+            }
+            _ => {}
+        }
+    }
+
+    // The node contents are `"AUTHORITY",CODE,...` where CODE may be quoted
+    let mut args = found?.splitn(3, ',');
+    let authority = args.next()?.trim().trim_matches('"');
+    let code = args.next()?.trim().trim_matches('"');
+    Some((authority.to_string(), code.to_string()))
+}
+
+/// `OGC:CRS84` differs from `EPSG:4326` only in axis order, which GeoArrow ignores.
+fn normalize_crs84((authority, code): (String, String)) -> (String, String) {
+    if authority == "OGC" && code.eq_ignore_ascii_case("CRS84") {
+        ("EPSG".to_string(), "4326".to_string())
+    } else {
+        (authority, code)
+    }
+}
+
 /// An optional string disambiguating the value of the `crs` field.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum CrsType {
@@ -305,4 +463,67 @@ mod test {
         assert!(crs.should_serialize());
         assert_eq!(serde_json::to_string(&crs).unwrap(), r#"{"crs":"1234"}"#);
     }
+
+    #[test]
+    fn crs_authority_code_extraction() {
+        let projjson = Crs::from_projjson(json!({"id": {"authority": "EPSG", "code": 4326}}));
+        assert_eq!(
+            projjson.authority_code(),
+            Some(("EPSG".to_string(), "4326".to_string()))
+        );
+
+        let wkt = Crs::from_wkt2_2019(
+            r#"PROJCRS["WGS 84 / UTM zone 33N",BASEGEOGCRS["WGS 84",ID["EPSG",4326]],CONVERSION["UTM zone 33N",ID["EPSG",16033]],ID["EPSG",32633]]"#.to_string(),
+        );
+        assert_eq!(
+            wkt.authority_code(),
+            Some(("EPSG".to_string(), "32633".to_string()))
+        );
+
+        let wkt1 = Crs::from_unknown_crs_type(
+            r#"GEOGCS["WGS 84",DATUM["WGS_1984",AUTHORITY["EPSG","6326"]],AUTHORITY["EPSG","4326"]]"#.to_string(),
+        );
+        assert_eq!(
+            wkt1.authority_code(),
+            Some(("EPSG".to_string(), "4326".to_string()))
+        );
+
+        let urn = Crs::from_unknown_crs_type("urn:ogc:def:crs:EPSG::3857".to_string());
+        assert_eq!(
+            urn.authority_code(),
+            Some(("EPSG".to_string(), "3857".to_string()))
+        );
+
+        assert!(
+            Crs::from_srid("4326".to_string())
+                .authority_code()
+                .is_none()
+        );
+        assert!(Crs::from_projjson(json!({})).authority_code().is_none());
+        assert!(Crs::default().authority_code().is_none());
+    }
+
+    #[test]
+    fn crs_equivalence() {
+        let code = Crs::from_authority_code("EPSG:4326".to_string());
+        let lower = Crs::from_authority_code("epsg:4326".to_string());
+        let projjson = Crs::from_projjson(json!({"id": {"authority": "EPSG", "code": 4326}}));
+        let wkt = Crs::from_wkt2_2019(r#"GEOGCRS["WGS 84",ID["EPSG",4326]]"#.to_string());
+        let crs84 = Crs::from_authority_code("OGC:CRS84".to_string());
+
+        for crs in [&lower, &projjson, &wkt, &crs84] {
+            assert!(code.is_equivalent(crs));
+            assert!(crs.is_equivalent(&code));
+        }
+
+        let other = Crs::from_authority_code("EPSG:3857".to_string());
+        assert!(!code.is_equivalent(&other));
+        assert!(!code.is_equivalent(&Crs::default()));
+        assert!(Crs::default().is_equivalent(&Crs::default()));
+
+        // Without identifiers, only structurally equal CRS are equivalent
+        let srid = Crs::from_srid("4326".to_string());
+        assert!(srid.is_equivalent(&srid.clone()));
+        assert!(!srid.is_equivalent(&code));
+    }
 }
@@ -0,0 +1,721 @@
+//! A [CrsTransform] backed by an embedded registry of common CRS definitions.
+//!
+//! The registry contains PROJJSON and WKT2:2019 definitions for:
+//!
+//! - Geographic CRS: `EPSG:4326` (WGS 84), `EPSG:4269` (NAD83), `EPSG:4258` (ETRS89),
+//!   `EPSG:4283` (GDA94) and `OGC:CRS84`.
+//! - `EPSG:3857` (WGS 84 / Pseudo-Mercator).
+//! - UTM zones: `EPSG:32601`-`EPSG:32660` and `EPSG:32701`-`EPSG:32760` (WGS 84),
+//!   `EPSG:26901`-`EPSG:26923` (NAD83) and `EPSG:25828`-`EPSG:25838` (ETRS89).
+//!
+//! This is not a replacement for a full CRS database such as PROJ, but it covers the CRS that
+//! account for the vast majority of real-world data.
+
+use serde_json::{Map, Value, json};
+
+use crate::crs::{Crs, CrsTransform, CrsType};
+use crate::error::{GeoArrowError, GeoArrowResult};
+
+const PROJJSON_SCHEMA: &str = "https://proj.org/schemas/v0.7/projjson.schema.json";
+
+/// A [CrsTransform] that resolves `AUTHORITY:CODE` and SRID CRS values to PROJJSON and WKT2:2019
+/// using an embedded registry of common CRS definitions.
+///
+/// SRIDs are interpreted as EPSG codes. PROJJSON input is converted to WKT when it has an `id`
+/// that is found in the registry.
+///
+/// By default, a CRS that is not found in the registry is dropped, matching
+/// [`DefaultCrsTransform`][crate::crs::DefaultCrsTransform]. Use
+/// [`with_strict`][Self::with_strict] to error instead.
+#[derive(Debug, Clone, Default)]
+pub struct EpsgCrsTransform {
+    strict: bool,
+}
+
+impl EpsgCrsTransform {
+    /// Create a new, non-strict transform.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether CRS values that cannot be resolved should error instead of being dropped.
+    pub fn with_strict(self, strict: bool) -> Self {
+        Self { strict }
+    }
+
+    fn lookup_crs(&self, crs: &Crs) -> GeoArrowResult<Option<Definition>> {
+        let Some(value) = crs.crs_value() else {
+            return Ok(None);
+        };
+
+        let definition = match (crs.crs_type(), value) {
+            (Some(CrsType::AuthorityCode) | None, Value::String(s)) => s
+                .split_once(':')
+                .and_then(|(auth, code)| lookup(auth, code)),
+            (Some(CrsType::Srid), Value::String(s)) => lookup("EPSG", s),
+            (Some(CrsType::Projjson), Value::Object(obj)) => obj
+                .get("id")
+                .and_then(|id| Some((id.get("authority")?.as_str()?, id.get("code")?)))
+                .and_then(|(auth, code)| match code {
+                    Value::String(code) => lookup(auth, code),
+                    Value::Number(code) => lookup(auth, &code.to_string()),
+                    _ => None,
+                }),
+            _ => None,
+        };
+
+        if definition.is_none() && self.strict {
+            return Err(GeoArrowError::Crs(format!(
+                "CRS {value} is not in the embedded CRS registry"
+            )));
+        }
+        Ok(definition)
+    }
+}
+
+impl CrsTransform for EpsgCrsTransform {
+    fn _convert_to_projjson(&self, crs: &Crs) -> GeoArrowResult<Option<Value>> {
+        Ok(self.lookup_crs(crs)?.map(|def| def.to_projjson()))
+    }
+
+    fn _convert_to_wkt(&self, crs: &Crs) -> GeoArrowResult<Option<String>> {
+        Ok(self.lookup_crs(crs)?.map(|def| def.to_wkt()))
+    }
+}
+
+/// Look up the PROJJSON definition of a CRS by authority and code, e.g. `("EPSG", "4326")`.
+///
+/// The authority is case-insensitive.
+pub fn projjson(authority: &str, code: &str) -> Option<Value> {
+    lookup(authority, code).map(|def| def.to_projjson())
+}
+
+/// Look up the WKT2:2019 definition of a CRS by authority and code, e.g. `("EPSG", "4326")`.
+///
+/// The authority is case-insensitive.
+pub fn wkt(authority: &str, code: &str) -> Option<String> {
+    lookup(authority, code).map(|def| def.to_wkt())
+}
+
+fn lookup(authority: &str, code: &str) -> Option<Definition> {
+    let authority = authority.trim();
+    let code = code.trim();
+
+    if authority.eq_ignore_ascii_case("OGC") {
+        return code
+            .eq_ignore_ascii_case("CRS84")
+            .then_some(Definition::Geographic(&CRS84));
+    }
+    if !authority.eq_ignore_ascii_case("EPSG") {
+        return None;
+    }
+
+    let code = code.parse::<u32>().ok()?;
+    if let Some(geographic) = GEOGRAPHIC.into_iter().find(|def| def.id == Id::Epsg(code)) {
+        return Some(Definition::Geographic(geographic));
+    }
+
+    let projected = match code {
+        3857 => ProjectedDef {
+            code,
+            name: "WGS 84 / Pseudo-Mercator".to_string(),
+            base: &WGS84,
+            conversion: Conversion::PseudoMercator,
+        },
+        32601..=32660 => ProjectedDef::utm(code, &WGS84, code - 32600, true),
+        32701..=32760 => ProjectedDef::utm(code, &WGS84, code - 32700, false),
+        26901..=26923 => ProjectedDef::utm(code, &NAD83, code - 26900, true),
+        25828..=25838 => ProjectedDef::utm(code, &ETRS89, code - 25800, true),
+        _ => return None,
+    };
+    Some(Definition::Projected(projected))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Id {
+    Epsg(u32),
+    Ogc(&'static str),
+}
+
+impl Id {
+    fn to_projjson(self) -> Value {
+        match self {
+            Self::Epsg(code) => json!({ "authority": "EPSG", "code": code }),
+            Self::Ogc(code) => json!({ "authority": "OGC", "code": code }),
+        }
+    }
+
+    fn to_wkt(self) -> String {
+        match self {
+            Self::Epsg(code) => format!("ID[\"EPSG\",{code}]"),
+            Self::Ogc(code) => format!("ID[\"OGC\",\"{code}\"]"),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Ellipsoid {
+    name: &'static str,
+    semi_major_axis: f64,
+    inverse_flattening: f64,
+}
+
+const WGS84_ELLIPSOID: Ellipsoid = Ellipsoid {
+    name: "WGS 84",
+    semi_major_axis: 6378137.0,
+    inverse_flattening: 298.257223563,
+};
+
+const GRS80_ELLIPSOID: Ellipsoid = Ellipsoid {
+    name: "GRS 1980",
+    semi_major_axis: 6378137.0,
+    inverse_flattening: 298.257222101,
+};
+
+/// A geodetic datum, or a datum ensemble if `ensemble_members` is non-empty.
+#[derive(Debug)]
+struct Datum {
+    name: &'static str,
+    ellipsoid: Ellipsoid,
+    ensemble_members: &'static [&'static str],
+    ensemble_accuracy: f64,
+}
+
+const WGS84_ENSEMBLE: Datum = Datum {
+    name: "World Geodetic System 1984 ensemble",
+    ellipsoid: WGS84_ELLIPSOID,
+    ensemble_members: &[
+        "World Geodetic System 1984 (Transit)",
+        "World Geodetic System 1984 (G730)",
+        "World Geodetic System 1984 (G873)",
+        "World Geodetic System 1984 (G1150)",
+        "World Geodetic System 1984 (G1674)",
+        "World Geodetic System 1984 (G1762)",
+        "World Geodetic System 1984 (G2139)",
+        "World Geodetic System 1984 (G2296)",
+    ],
+    ensemble_accuracy: 2.0,
+};
+
+#[derive(Debug)]
+struct GeographicDef {
+    id: Id,
+    name: &'static str,
+    datum: Datum,
+    /// Whether the axis order is longitude, latitude rather than latitude, longitude.
+    lon_lat: bool,
+}
+
+const WGS84: GeographicDef = GeographicDef {
+    id: Id::Epsg(4326),
+    name: "WGS 84",
+    datum: WGS84_ENSEMBLE,
+    lon_lat: false,
+};
+
+const CRS84: GeographicDef = GeographicDef {
+    id: Id::Ogc("CRS84"),
+    name: "WGS 84 (CRS84)",
+    datum: WGS84_ENSEMBLE,
+    lon_lat: true,
+};
+
+const NAD83: GeographicDef = GeographicDef {
+    id: Id::Epsg(4269),
+    name: "NAD83",
+    datum: Datum {
+        name: "North American Datum 1983",
+        ellipsoid: GRS80_ELLIPSOID,
+        ensemble_members: &[],
+        ensemble_accuracy: 0.0,
+    },
+    lon_lat: false,
+};
+
+const ETRS89: GeographicDef = GeographicDef {
+    id: Id::Epsg(4258),
+    name: "ETRS89",
+    datum: Datum {
+        name: "European Terrestrial Reference System 1989",
+        ellipsoid: GRS80_ELLIPSOID,
+        ensemble_members: &[],
+        ensemble_accuracy: 0.0,
+    },
+    lon_lat: false,
+};
+
+const GDA94: GeographicDef = GeographicDef {
+    id: Id::Epsg(4283),
+    name: "GDA94",
+    datum: Datum {
+        name: "Geocentric Datum of Australia 1994",
+        ellipsoid: GRS80_ELLIPSOID,
+        ensemble_members: &[],
+        ensemble_accuracy: 0.0,
+    },
+    lon_lat: false,
+};
+
+const GEOGRAPHIC: [&GeographicDef; 4] = [&WGS84, &NAD83, &ETRS89, &GDA94];
+
+#[derive(Debug)]
+enum Conversion {
+    PseudoMercator,
+    TransverseMercator {
+        name: String,
+        code: u32,
+        longitude_of_origin: f64,
+        false_northing: f64,
+    },
+}
+
+#[derive(Debug)]
+struct ProjectedDef {
+    code: u32,
+    name: String,
+    base: &'static GeographicDef,
+    conversion: Conversion,
+}
+
+impl ProjectedDef {
+    fn utm(code: u32, base: &'static GeographicDef, zone: u32, north: bool) -> Self {
+        let hemisphere = if north { 'N' } else { 'S' };
+        let conversion_name = format!("UTM zone {zone}{hemisphere}");
+        Self {
+            code,
+            name: format!("{} / {conversion_name}", base.name),
+            base,
+            conversion: Conversion::TransverseMercator {
+                name: conversion_name,
+                code: if north { 16000 } else { 16100 } + zone,
+                longitude_of_origin: zone as f64 * 6.0 - 183.0,
+                false_northing: if north { 0.0 } else { 10000000.0 },
+            },
+        }
+    }
+
+    /// The conversion's name, EPSG code, method name, method EPSG code and parameters.
+    #[allow(clippy::type_complexity)]
+    fn conversion(&self) -> (&str, u32, &str, u32, Vec<Parameter>) {
+        match &self.conversion {
+            Conversion::PseudoMercator => (
+                "Popular Visualisation Pseudo-Mercator",
+                3856,
+                "Popular Visualisation Pseudo Mercator",
+                1024,
+                vec![
+                    Parameter::angle("Latitude of natural origin", 0.0, 8801),
+                    Parameter::angle("Longitude of natural origin", 0.0, 8802),
+                    Parameter::length("False easting", 0.0, 8806),
+                    Parameter::length("False northing", 0.0, 8807),
+                ],
+            ),
+            Conversion::TransverseMercator {
+                name,
+                code,
+                longitude_of_origin,
+                false_northing,
+            } => (
+                name,
+                *code,
+                "Transverse Mercator",
+                9807,
+                vec![
+                    Parameter::angle("Latitude of natural origin", 0.0, 8801),
+                    Parameter::angle("Longitude of natural origin", *longitude_of_origin, 8802),
+                    Parameter::scale("Scale factor at natural origin", 0.9996, 8805),
+                    Parameter::length("False easting", 500000.0, 8806),
+                    Parameter::length("False northing", *false_northing, 8807),
+                ],
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Unit {
+    Degree,
+    Metre,
+    Unity,
+}
+
+impl Unit {
+    fn to_projjson(self) -> Value {
+        match self {
+            Self::Degree => json!("degree"),
+            Self::Metre => json!("metre"),
+            Self::Unity => json!("unity"),
+        }
+    }
+
+    fn to_wkt(self) -> &'static str {
+        match self {
+            Self::Degree => "ANGLEUNIT[\"degree\",0.0174532925199433]",
+            Self::Metre => "LENGTHUNIT[\"metre\",1]",
+            Self::Unity => "SCALEUNIT[\"unity\",1]",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Parameter {
+    name: &'static str,
+    value: f64,
+    unit: Unit,
+    code: u32,
+}
+
+impl Parameter {
+    fn angle(name: &'static str, value: f64, code: u32) -> Self {
+        Self {
+            name,
+            value,
+            unit: Unit::Degree,
+            code,
+        }
+    }
+
+    fn length(name: &'static str, value: f64, code: u32) -> Self {
+        Self {
+            name,
+            value,
+            unit: Unit::Metre,
+            code,
+        }
+    }
+
+    fn scale(name: &'static str, value: f64, code: u32) -> Self {
+        Self {
+            name,
+            value,
+            unit: Unit::Unity,
+            code,
+        }
+    }
+}
+
+/// Write integral values as JSON integers, matching the output of PROJ.
+fn number(value: f64) -> Value {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        json!(value as i64)
+    } else {
+        json!(value)
+    }
+}
+
+#[derive(Debug)]
+enum Definition {
+    Geographic(&'static GeographicDef),
+    Projected(ProjectedDef),
+}
+
+impl Definition {
+    fn to_projjson(&self) -> Value {
+        let mut value = match self {
+            Self::Geographic(def) => geographic_projjson(def),
+            Self::Projected(def) => projected_projjson(def),
+        };
+        let obj = value.as_object_mut().unwrap();
+        let mut out = Map::with_capacity(obj.len() + 1);
+        out.insert("$schema".to_string(), json!(PROJJSON_SCHEMA));
+        out.append(obj);
+        Value::Object(out)
+    }
+
+    fn to_wkt(&self) -> String {
+        match self {
+            Self::Geographic(def) => format!(
+                "GEOGCRS[{},{},{}]",
+                geographic_wkt_body(def),
+                geographic_cs_wkt(def),
+                def.id.to_wkt()
+            ),
+            Self::Projected(def) => projected_wkt(def),
+        }
+    }
+}
+
+fn datum_projjson(datum: &Datum) -> (&'static str, Value) {
+    let ellipsoid = json!({
+        "name": datum.ellipsoid.name,
+        "semi_major_axis": number(datum.ellipsoid.semi_major_axis),
+        "inverse_flattening": datum.ellipsoid.inverse_flattening,
+    });
+    if datum.ensemble_members.is_empty() {
+        (
+            "datum",
+            json!({
+                "type": "GeodeticReferenceFrame",
+                "name": datum.name,
+                "ellipsoid": ellipsoid,
+            }),
+        )
+    } else {
+        let members = datum
+            .ensemble_members
+            .iter()
+            .map(|name| json!({ "name": name }))
+            .collect::<Vec<_>>();
+        (
+            "datum_ensemble",
+            json!({
+                "name": datum.name,
+                "members": members,
+                "ellipsoid": ellipsoid,
+                "accuracy": datum.ensemble_accuracy.to_string(),
+            }),
+        )
+    }
+}
+
+fn geographic_projjson(def: &GeographicDef) -> Value {
+    let lat = json!({
+        "name": "Geodetic latitude",
+        "abbreviation": "Lat",
+        "direction": "north",
+        "unit": "degree",
+    });
+    let lon = json!({
+        "name": "Geodetic longitude",
+        "abbreviation": "Lon",
+        "direction": "east",
+        "unit": "degree",
+    });
+    let axis = if def.lon_lat {
+        vec![lon, lat]
+    } else {
+        vec![lat, lon]
+    };
+
+    let (datum_key, datum) = datum_projjson(&def.datum);
+    let mut value = json!({
+        "type": "GeographicCRS",
+        "name": def.name,
+        "coordinate_system": {
+            "subtype": "ellipsoidal",
+            "axis": axis,
+        },
+        "id": def.id.to_projjson(),
+    });
+    value
+        .as_object_mut()
+        .unwrap()
+        .insert(datum_key.to_string(), datum);
+    value
+}
+
+fn projected_projjson(def: &ProjectedDef) -> Value {
+    let (datum_key, datum) = datum_projjson(&def.base.datum);
+    let mut base_crs = json!({
+        "name": def.base.name,
+        "coordinate_system": {
+            "subtype": "ellipsoidal",
+            "axis": [
+                {"name": "Geodetic latitude", "abbreviation": "Lat", "direction": "north", "unit": "degree"},
+                {"name": "Geodetic longitude", "abbreviation": "Lon", "direction": "east", "unit": "degree"},
+            ],
+        },
+        "id": def.base.id.to_projjson(),
+    });
+    base_crs
+        .as_object_mut()
+        .unwrap()
+        .insert(datum_key.to_string(), datum);
+
+    let (conversion_name, conversion_code, method_name, method_code, parameters) = def.conversion();
+    let parameters = parameters
+        .iter()
+        .map(|p| {
+            json!({
+                "name": p.name,
+                "value": number(p.value),
+                "unit": p.unit.to_projjson(),
+                "id": Id::Epsg(p.code).to_projjson(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let (x_abbreviation, y_abbreviation) = match def.conversion {
+        Conversion::PseudoMercator => ("X", "Y"),
+        Conversion::TransverseMercator { .. } => ("E", "N"),
+    };
+
+    json!({
+        "type": "ProjectedCRS",
+        "name": def.name,
+        "base_crs": base_crs,
+        "conversion": {
+            "name": conversion_name,
+            "method": {
+                "name": method_name,
+                "id": Id::Epsg(method_code).to_projjson(),
+            },
+            "parameters": parameters,
+            "id": Id::Epsg(conversion_code).to_projjson(),
+        },
+        "coordinate_system": {
+            "subtype": "Cartesian",
+            "axis": [
+                {"name": "Easting", "abbreviation": x_abbreviation, "direction": "east", "unit": "metre"},
+                {"name": "Northing", "abbreviation": y_abbreviation, "direction": "north", "unit": "metre"},
+            ],
+        },
+        "id": Id::Epsg(def.code).to_projjson(),
+    })
+}
+
+/// The name, datum and prime meridian of a geographic CRS, shared by `GEOGCRS` and
+/// `BASEGEOGCRS`.
+fn geographic_wkt_body(def: &GeographicDef) -> String {
+    let datum = &def.datum;
+    let ellipsoid = format!(
+        "ELLIPSOID[\"{}\",{},{},LENGTHUNIT[\"metre\",1]]",
+        datum.ellipsoid.name, datum.ellipsoid.semi_major_axis, datum.ellipsoid.inverse_flattening
+    );
+    let datum = if datum.ensemble_members.is_empty() {
+        format!("DATUM[\"{}\",{ellipsoid}]", datum.name)
+    } else {
+        let members = datum
+            .ensemble_members
+            .iter()
+            .map(|name| format!("MEMBER[\"{name}\"]"))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            "ENSEMBLE[\"{}\",{members},{ellipsoid},ENSEMBLEACCURACY[{:.1}]]",
+            datum.name, datum.ensemble_accuracy
+        )
+    };
+    format!(
+        "\"{}\",{datum},PRIMEM[\"Greenwich\",0,{}]",
+        def.name,
+        Unit::Degree.to_wkt()
+    )
+}
+
+fn geographic_cs_wkt(def: &GeographicDef) -> String {
+    let degree = Unit::Degree.to_wkt();
+    let lat = "AXIS[\"geodetic latitude (Lat)\",north";
+    let lon = "AXIS[\"geodetic longitude (Lon)\",east";
+    let (first, second) = if def.lon_lat { (lon, lat) } else { (lat, lon) };
+    format!("CS[ellipsoidal,2],{first},ORDER[1],{degree}],{second},ORDER[2],{degree}]")
+}
+
+fn projected_wkt(def: &ProjectedDef) -> String {
+    let (conversion_name, conversion_code, method_name, method_code, parameters) = def.conversion();
+    let parameters = parameters
+        .iter()
+        .map(|p| {
+            format!(
+                "PARAMETER[\"{}\",{},{},{}]",
+                p.name,
+                p.value,
+                p.unit.to_wkt(),
+                Id::Epsg(p.code).to_wkt()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    let (x_axis, y_axis) = match def.conversion {
+        Conversion::PseudoMercator => ("easting (X)", "northing (Y)"),
+        Conversion::TransverseMercator { .. } => ("(E)", "(N)"),
+    };
+    let metre = Unit::Metre.to_wkt();
+
+    format!(
+        "PROJCRS[\"{}\",BASEGEOGCRS[{},{}],CONVERSION[\"{conversion_name}\",METHOD[\"{method_name}\",{}],{parameters},{}],CS[Cartesian,2],AXIS[\"{x_axis}\",east,ORDER[1],{metre}],AXIS[\"{y_axis}\",north,ORDER[2],{metre}],{}]",
+        def.name,
+        geographic_wkt_body(def.base),
+        def.base.id.to_wkt(),
+        Id::Epsg(method_code).to_wkt(),
+        Id::Epsg(conversion_code).to_wkt(),
+        Id::Epsg(def.code).to_wkt(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn authority_code_to_projjson() {
+        let transform = EpsgCrsTransform::new();
+        let crs = Crs::from_authority_code("EPSG:4326".to_string());
+        let projjson = transform.extract_projjson(&crs).unwrap().unwrap();
+        assert_eq!(projjson["type"], "GeographicCRS");
+        assert_eq!(projjson["name"], "WGS 84");
+        assert_eq!(projjson["id"], json!({"authority": "EPSG", "code": 4326}));
+        assert_eq!(projjson["$schema"], PROJJSON_SCHEMA);
+
+        let crs = Crs::from_authority_code("ogc:crs84".to_string());
+        let projjson = transform.extract_projjson(&crs).unwrap().unwrap();
+        assert_eq!(
+            projjson["coordinate_system"]["axis"][0]["direction"],
+            "east"
+        );
+    }
+
+    #[test]
+    fn srid_to_wkt() {
+        let transform = EpsgCrsTransform::new();
+        let wkt = transform
+            .extract_wkt(&Crs::from_srid("3857".to_string()))
+            .unwrap()
+            .unwrap();
+        assert!(wkt.starts_with("PROJCRS[\"WGS 84 / Pseudo-Mercator\",BASEGEOGCRS[\"WGS 84\","));
+        assert!(
+            wkt.contains("METHOD[\"Popular Visualisation Pseudo Mercator\",ID[\"EPSG\",1024]]")
+        );
+        assert!(wkt.ends_with("ID[\"EPSG\",3857]]"));
+    }
+
+    #[test]
+    fn utm_zones() {
+        let value = projjson("EPSG", "32633").unwrap();
+        assert_eq!(value["name"], "WGS 84 / UTM zone 33N");
+        let parameters = &value["conversion"]["parameters"];
+        assert_eq!(parameters[1]["value"], json!(15));
+        assert_eq!(parameters[4]["value"], json!(0));
+        assert_eq!(value["conversion"]["id"]["code"], json!(16033));
+
+        let value = projjson("EPSG", "32719").unwrap();
+        assert_eq!(value["name"], "WGS 84 / UTM zone 19S");
+        let parameters = &value["conversion"]["parameters"];
+        assert_eq!(parameters[1]["value"], json!(-69));
+        assert_eq!(parameters[4]["value"], json!(10000000));
+
+        let value = wkt("EPSG", "26910").unwrap();
+        assert!(value.starts_with("PROJCRS[\"NAD83 / UTM zone 10N\""));
+        assert!(value.contains("PARAMETER[\"Longitude of natural origin\",-123,"));
+        assert!(value.contains("BASEGEOGCRS[\"NAD83\",DATUM[\"North American Datum 1983\","));
+    }
+
+    #[test]
+    fn projjson_to_wkt() {
+        let transform = EpsgCrsTransform::new();
+        let crs = Crs::from_projjson(projjson("EPSG", "4258").unwrap());
+        let wkt = transform.extract_wkt(&crs).unwrap().unwrap();
+        assert!(wkt.starts_with("GEOGCRS[\"ETRS89\""));
+        assert!(wkt.ends_with("ID[\"EPSG\",4258]]"));
+    }
+
+    #[test]
+    fn unknown_crs() {
+        let crs = Crs::from_authority_code("EPSG:999999".to_string());
+        let transform = EpsgCrsTransform::new();
+        assert!(transform.extract_projjson(&crs).unwrap().is_none());
+        assert!(transform.extract_wkt(&crs).unwrap().is_none());
+
+        let strict = EpsgCrsTransform::new().with_strict(true);
+        assert!(strict.extract_projjson(&crs).is_err());
+        assert!(strict.extract_wkt(&crs).is_err());
+
+        // A missing CRS is not an error, even in strict mode
+        assert!(strict.extract_projjson(&Crs::default()).unwrap().is_none());
+
+        // Existing PROJJSON is passed through unchanged
+        let projjson = json!({"type": "GeographicCRS"});
+        let crs = Crs::from_projjson(projjson.clone());
+        assert_eq!(strict.extract_projjson(&crs).unwrap(), Some(projjson));
+    }
+}
@@ -26,6 +26,10 @@ pub enum GeoArrowError {
     #[error("FlatGeobuf error: {0}")]
     FlatGeobuf(String),
 
+    /// GeoPackage error
+    #[error("GeoPackage error: {0}")]
+    GeoPackage(String),
+
     /// GeoParquet error
     #[error("GeoParquet error: {0}")]
     GeoParquet(String),
@@ -48,6 +52,18 @@ pub enum GeoArrowError {
     #[error("Overflow: data does not fit in i32 offsets.")]
     Overflow,
 
+    /// PostGIS error
+    #[error("PostGIS error: {0}")]
+    Postgis(String),
+
+    /// Shapefile error
+    #[error("Shapefile error: {0}")]
+    Shapefile(String),
+
+    /// Spatial index error
+    #[error("Spatial index error: {0}")]
+    SpatialIndex(String),
+
     /// WKB Error
     #[error("WKB error: {0}")]
     Wkb(String),
@@ -12,6 +12,8 @@ pub mod crs;
 mod datatype;
 mod dimension;
 mod edges;
+#[cfg(feature = "epsg")]
+pub mod epsg;
 pub mod error;
 mod metadata;
 mod r#type;
@@ -41,6 +41,12 @@ impl Metadata {
         self.edges
     }
 
+    /// Returns `true` if this metadata and `other` have [equivalent][Crs::is_equivalent] CRS and
+    /// the same edge interpolation.
+    pub fn is_equivalent(&self, other: &Metadata) -> bool {
+        self.edges == other.edges && self.crs.is_equivalent(&other.crs)
+    }
+
     /// Serialize this metadata to a string.
     ///
     /// If `None`, no extension metadata should be written.
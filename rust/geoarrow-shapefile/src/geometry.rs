@@ -0,0 +1,512 @@
+//! Conversion between shapefile shapes and GeoArrow geometries.
+//!
+//! Shapes are read as WKT geometries, which the GeoArrow builders accept through [`geo_traits`],
+//! and written from any [`GeometryTrait`] implementation.
+
+use geo_traits::{
+    CoordTrait, Dimensions, GeometryTrait, GeometryType, LineStringTrait, MultiLineStringTrait,
+    MultiPointTrait, MultiPolygonTrait, PointTrait, PolygonTrait,
+};
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+use shapefile::{
+    Multipoint, MultipointM, MultipointZ, NO_DATA, Polygon as ShapePolygon, PolygonM, PolygonRing,
+    PolygonZ, Polyline, PolylineM, PolylineZ, Shape,
+};
+use wkt::Wkt;
+use wkt::types::{
+    Coord, Dimension, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
+};
+
+/// A shapefile point type that can be converted to a WKT coordinate.
+pub(crate) trait ShapeCoord {
+    /// The dimension of every coordinate of this type.
+    const DIM: Dimension;
+
+    fn to_coord(&self) -> Coord<f64>;
+}
+
+impl ShapeCoord for shapefile::Point {
+    const DIM: Dimension = Dimension::XY;
+
+    fn to_coord(&self) -> Coord<f64> {
+        Coord {
+            x: self.x,
+            y: self.y,
+            z: None,
+            m: None,
+        }
+    }
+}
+
+impl ShapeCoord for shapefile::PointM {
+    const DIM: Dimension = Dimension::XYM;
+
+    fn to_coord(&self) -> Coord<f64> {
+        Coord {
+            x: self.x,
+            y: self.y,
+            z: None,
+            m: Some(measure(self.m)),
+        }
+    }
+}
+
+// PointZ always stores an M value, but it is usually "no data", so only Z is kept.
+impl ShapeCoord for shapefile::PointZ {
+    const DIM: Dimension = Dimension::XYZ;
+
+    fn to_coord(&self) -> Coord<f64> {
+        Coord {
+            x: self.x,
+            y: self.y,
+            z: Some(self.z),
+            m: None,
+        }
+    }
+}
+
+/// Shapefiles store missing measures as any value below [`NO_DATA`].
+fn measure(m: f64) -> f64 {
+    if m <= NO_DATA { f64::NAN } else { m }
+}
+
+/// Convert a shape to a WKT geometry, or `None` for a null shape.
+pub(crate) fn shape_to_wkt(shape: &Shape) -> GeoArrowResult<Option<Wkt<f64>>> {
+    let geometry = match shape {
+        Shape::NullShape => return Ok(None),
+        Shape::Point(point) => Wkt::Point(Point::from_coord(point.to_coord())),
+        Shape::PointM(point) => Wkt::Point(Point::from_coord(point.to_coord())),
+        Shape::PointZ(point) => Wkt::Point(Point::from_coord(point.to_coord())),
+        Shape::Multipoint(multi_point) => Wkt::MultiPoint(to_multi_point(multi_point.points())),
+        Shape::MultipointM(multi_point) => Wkt::MultiPoint(to_multi_point(multi_point.points())),
+        Shape::MultipointZ(multi_point) => Wkt::MultiPoint(to_multi_point(multi_point.points())),
+        Shape::Polyline(polyline) => Wkt::MultiLineString(to_multi_line_string(polyline.parts())),
+        Shape::PolylineM(polyline) => Wkt::MultiLineString(to_multi_line_string(polyline.parts())),
+        Shape::PolylineZ(polyline) => Wkt::MultiLineString(to_multi_line_string(polyline.parts())),
+        Shape::Polygon(polygon) => Wkt::MultiPolygon(to_multi_polygon(polygon.rings())),
+        Shape::PolygonM(polygon) => Wkt::MultiPolygon(to_multi_polygon(polygon.rings())),
+        Shape::PolygonZ(polygon) => Wkt::MultiPolygon(to_multi_polygon(polygon.rings())),
+        Shape::Multipatch(_) => {
+            return Err(GeoArrowError::IncorrectGeometryType(
+                "Multipatch shapes are not supported".to_string(),
+            ));
+        }
+    };
+    Ok(Some(geometry))
+}
+
+fn to_coords<P: ShapeCoord>(points: &[P]) -> Vec<Coord<f64>> {
+    points.iter().map(ShapeCoord::to_coord).collect()
+}
+
+fn to_multi_point<P: ShapeCoord>(points: &[P]) -> MultiPoint<f64> {
+    let points = points
+        .iter()
+        .map(|point| Point::from_coord(point.to_coord()))
+        .collect();
+    MultiPoint::new(points, P::DIM)
+}
+
+fn to_multi_line_string<P: ShapeCoord>(parts: &[Vec<P>]) -> MultiLineString<f64> {
+    let line_strings = parts
+        .iter()
+        .map(|part| LineString::new(to_coords(part), P::DIM))
+        .collect();
+    MultiLineString::new(line_strings, P::DIM)
+}
+
+/// Group the rings of a shapefile polygon into polygons.
+///
+/// Shapefiles store outer rings clockwise and holes counterclockwise, but don't require a hole to
+/// follow its outer ring. Each hole is assigned to the smallest outer ring that contains it. A
+/// hole outside of every outer ring becomes a polygon of its own.
+fn to_multi_polygon<P: ShapeCoord>(rings: &[PolygonRing<P>]) -> MultiPolygon<f64> {
+    let mut shells = Vec::new();
+    let mut holes = Vec::new();
+    for ring in rings {
+        match ring {
+            PolygonRing::Outer(points) => shells.push((to_coords(points), Vec::new())),
+            PolygonRing::Inner(points) => holes.push(to_coords(points)),
+        }
+    }
+
+    let areas = shells
+        .iter()
+        .map(|(shell, _)| ring_area(shell))
+        .collect::<Vec<_>>();
+    let mut orphans = Vec::new();
+    for hole in holes {
+        let parent = hole.first().and_then(|first| {
+            shells
+                .iter()
+                .enumerate()
+                .filter(|(_, (shell, _))| ring_contains(shell, first))
+                .min_by(|(a, _), (b, _)| areas[*a].total_cmp(&areas[*b]))
+                .map(|(i, _)| i)
+        });
+        match parent {
+            Some(i) => shells[i].1.push(hole),
+            None => orphans.push((hole, Vec::new())),
+        }
+    }
+    shells.extend(orphans);
+
+    let polygons = shells
+        .into_iter()
+        .map(|(shell, holes)| {
+            let rings = std::iter::once(shell)
+                .chain(holes)
+                .map(|ring| LineString::new(ring, P::DIM))
+                .collect();
+            Polygon::new(rings, P::DIM)
+        })
+        .collect();
+    MultiPolygon::new(polygons, P::DIM)
+}
+
+/// The unsigned area of a ring in the XY plane.
+fn ring_area(ring: &[Coord<f64>]) -> f64 {
+    let twice_area = ring
+        .iter()
+        .zip(ring.iter().cycle().skip(1))
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum::<f64>();
+    (twice_area / 2.0).abs()
+}
+
+/// Whether `point` lies inside `ring` in the XY plane, by ray casting.
+fn ring_contains(ring: &[Coord<f64>], point: &Coord<f64>) -> bool {
+    let mut inside = false;
+    for (a, b) in ring.iter().zip(ring.iter().cycle().skip(1)) {
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// A shapefile point type that can be built from a GeoArrow coordinate.
+pub(crate) trait ShapePoint: Sized {
+    fn from_coord(coord: &impl CoordTrait<T = f64>) -> Self;
+
+    fn xy(&self) -> (f64, f64);
+
+    fn point_shape(self) -> Shape;
+
+    fn multipoint_shape(points: Vec<Self>) -> Shape;
+
+    fn polyline_shape(parts: Vec<Vec<Self>>) -> Shape;
+
+    fn polygon_shape(rings: Vec<PolygonRing<Self>>) -> Shape;
+}
+
+impl ShapePoint for shapefile::Point {
+    fn from_coord(coord: &impl CoordTrait<T = f64>) -> Self {
+        Self::new(coord.x(), coord.y())
+    }
+
+    fn xy(&self) -> (f64, f64) {
+        (self.x, self.y)
+    }
+
+    fn point_shape(self) -> Shape {
+        Shape::Point(self)
+    }
+
+    fn multipoint_shape(points: Vec<Self>) -> Shape {
+        Shape::Multipoint(Multipoint::new(points))
+    }
+
+    fn polyline_shape(parts: Vec<Vec<Self>>) -> Shape {
+        Shape::Polyline(Polyline::with_parts(parts))
+    }
+
+    fn polygon_shape(rings: Vec<PolygonRing<Self>>) -> Shape {
+        Shape::Polygon(ShapePolygon::with_rings(rings))
+    }
+}
+
+impl ShapePoint for shapefile::PointM {
+    fn from_coord(coord: &impl CoordTrait<T = f64>) -> Self {
+        Self::new(coord.x(), coord.y(), coord.nth_or_panic(2))
+    }
+
+    fn xy(&self) -> (f64, f64) {
+        (self.x, self.y)
+    }
+
+    fn point_shape(self) -> Shape {
+        Shape::PointM(self)
+    }
+
+    fn multipoint_shape(points: Vec<Self>) -> Shape {
+        Shape::MultipointM(MultipointM::new(points))
+    }
+
+    fn polyline_shape(parts: Vec<Vec<Self>>) -> Shape {
+        Shape::PolylineM(PolylineM::with_parts(parts))
+    }
+
+    fn polygon_shape(rings: Vec<PolygonRing<Self>>) -> Shape {
+        Shape::PolygonM(PolygonM::with_rings(rings))
+    }
+}
+
+impl ShapePoint for shapefile::PointZ {
+    fn from_coord(coord: &impl CoordTrait<T = f64>) -> Self {
+        let m = match coord.dim() {
+            Dimensions::Xyzm | Dimensions::Unknown(4) => coord.nth_or_panic(3),
+            _ => NO_DATA,
+        };
+        Self::new(coord.x(), coord.y(), coord.nth_or_panic(2), m)
+    }
+
+    fn xy(&self) -> (f64, f64) {
+        (self.x, self.y)
+    }
+
+    fn point_shape(self) -> Shape {
+        Shape::PointZ(self)
+    }
+
+    fn multipoint_shape(points: Vec<Self>) -> Shape {
+        Shape::MultipointZ(MultipointZ::new(points))
+    }
+
+    fn polyline_shape(parts: Vec<Vec<Self>>) -> Shape {
+        Shape::PolylineZ(PolylineZ::with_parts(parts))
+    }
+
+    fn polygon_shape(rings: Vec<PolygonRing<Self>>) -> Shape {
+        Shape::PolygonZ(PolygonZ::with_rings(rings))
+    }
+}
+
+/// Convert a geometry to a shape. Empty geometries become null shapes.
+///
+/// XY geometries become plain shapes, XYM geometries become M shapes and XYZ and XYZM geometries
+/// become Z shapes. Polygon exterior rings are written clockwise and interior rings
+/// counterclockwise, as the shapefile specification requires.
+pub(crate) fn geometry_to_shape(geometry: &impl GeometryTrait<T = f64>) -> GeoArrowResult<Shape> {
+    match geometry.dim() {
+        Dimensions::Xy | Dimensions::Unknown(2) => to_shape::<shapefile::Point>(geometry),
+        Dimensions::Xym => to_shape::<shapefile::PointM>(geometry),
+        Dimensions::Xyz | Dimensions::Xyzm | Dimensions::Unknown(3) | Dimensions::Unknown(4) => {
+            to_shape::<shapefile::PointZ>(geometry)
+        }
+        Dimensions::Unknown(n) => Err(GeoArrowError::IncorrectGeometryType(format!(
+            "Cannot write geometries with {n} dimensions to a shapefile"
+        ))),
+    }
+}
+
+fn to_shape<P: ShapePoint>(geometry: &impl GeometryTrait<T = f64>) -> GeoArrowResult<Shape> {
+    match geometry.as_type() {
+        GeometryType::Point(point) => Ok(point
+            .coord()
+            .map(|coord| P::from_coord(&coord).point_shape())
+            .unwrap_or(Shape::NullShape)),
+        GeometryType::MultiPoint(multi_point) => {
+            let points = multi_point
+                .points()
+                .filter_map(|point| point.coord().map(|coord| P::from_coord(&coord)))
+                .collect::<Vec<_>>();
+            if points.is_empty() {
+                Ok(Shape::NullShape)
+            } else {
+                Ok(P::multipoint_shape(points))
+            }
+        }
+        GeometryType::LineString(line_string) => {
+            let mut parts = Vec::new();
+            push_polyline_part::<P>(line_string, &mut parts)?;
+            Ok(to_polyline(parts))
+        }
+        GeometryType::MultiLineString(multi_line_string) => {
+            let mut parts = Vec::new();
+            for line_string in multi_line_string.line_strings() {
+                push_polyline_part::<P>(&line_string, &mut parts)?;
+            }
+            Ok(to_polyline(parts))
+        }
+        GeometryType::Polygon(polygon) => {
+            let mut rings = Vec::new();
+            push_polygon_rings::<P>(polygon, &mut rings)?;
+            Ok(to_polygon(rings))
+        }
+        GeometryType::MultiPolygon(multi_polygon) => {
+            let mut rings = Vec::new();
+            for polygon in multi_polygon.polygons() {
+                push_polygon_rings::<P>(&polygon, &mut rings)?;
+            }
+            Ok(to_polygon(rings))
+        }
+        GeometryType::GeometryCollection(_) => Err(unsupported_geometry("GeometryCollection")),
+        GeometryType::Rect(_) => Err(unsupported_geometry("Rect")),
+        GeometryType::Triangle(_) => Err(unsupported_geometry("Triangle")),
+        GeometryType::Line(_) => Err(unsupported_geometry("Line")),
+    }
+}
+
+fn unsupported_geometry(name: &str) -> GeoArrowError {
+    GeoArrowError::IncorrectGeometryType(format!(
+        "{name} geometries cannot be written to a shapefile"
+    ))
+}
+
+fn to_polyline<P: ShapePoint>(parts: Vec<Vec<P>>) -> Shape {
+    if parts.is_empty() {
+        Shape::NullShape
+    } else {
+        P::polyline_shape(parts)
+    }
+}
+
+fn push_polyline_part<P: ShapePoint>(
+    line_string: &impl LineStringTrait<T = f64>,
+    parts: &mut Vec<Vec<P>>,
+) -> GeoArrowResult<()> {
+    match line_string.num_coords() {
+        0 => Ok(()),
+        1 => Err(GeoArrowError::IncorrectGeometryType(
+            "Cannot write a line string with a single coordinate to a shapefile".to_string(),
+        )),
+        _ => {
+            parts.push(ring_points::<P>(line_string));
+            Ok(())
+        }
+    }
+}
+
+fn to_polygon<P: ShapePoint>(rings: Vec<PolygonRing<P>>) -> Shape {
+    if rings.is_empty() {
+        Shape::NullShape
+    } else {
+        P::polygon_shape(rings)
+    }
+}
+
+fn push_polygon_rings<P: ShapePoint>(
+    polygon: &impl PolygonTrait<T = f64>,
+    rings: &mut Vec<PolygonRing<P>>,
+) -> GeoArrowResult<()> {
+    let Some(exterior) = polygon.exterior() else {
+        return Ok(());
+    };
+    if exterior.num_coords() == 0 {
+        return Ok(());
+    }
+    rings.push(PolygonRing::Outer(oriented_ring::<P>(&exterior, true)?));
+    for interior in polygon.interiors() {
+        if interior.num_coords() > 0 {
+            rings.push(PolygonRing::Inner(oriented_ring::<P>(&interior, false)?));
+        }
+    }
+    Ok(())
+}
+
+fn ring_points<P: ShapePoint>(line_string: &impl LineStringTrait<T = f64>) -> Vec<P> {
+    line_string
+        .coords()
+        .map(|coord| P::from_coord(&coord))
+        .collect()
+}
+
+/// The points of a polygon ring, reversed if needed to wind clockwise or counterclockwise.
+fn oriented_ring<P: ShapePoint>(
+    ring: &impl LineStringTrait<T = f64>,
+    clockwise: bool,
+) -> GeoArrowResult<Vec<P>> {
+    if ring.num_coords() < 3 {
+        return Err(GeoArrowError::IncorrectGeometryType(
+            "Cannot write a polygon ring with fewer than three coordinates to a shapefile"
+                .to_string(),
+        ));
+    }
+    let mut points = ring_points::<P>(ring);
+    // The shoelace formula gives a positive area for counterclockwise rings.
+    let twice_area = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| {
+            let ((ax, ay), (bx, by)) = (a.xy(), b.xy());
+            ax * by - bx * ay
+        })
+        .sum::<f64>();
+    if (twice_area > 0.0) == clockwise {
+        points.reverse();
+    }
+    Ok(points)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ring(coords: &[(f64, f64)]) -> Vec<shapefile::Point> {
+        coords
+            .iter()
+            .map(|(x, y)| shapefile::Point::new(*x, *y))
+            .collect()
+    }
+
+    #[test]
+    fn nest_rings() {
+        let big = [(0., 0.), (0., 10.), (10., 10.), (10., 0.), (0., 0.)];
+        let small = [(20., 0.), (20., 2.), (22., 2.), (22., 0.), (20., 0.)];
+        let big_hole = [(1., 1.), (2., 1.), (2., 2.), (1., 2.), (1., 1.)];
+        let small_hole = [(20.5, 0.5), (21., 0.5), (21., 1.), (20.5, 1.), (20.5, 0.5)];
+        let orphan = [(50., 50.), (51., 50.), (51., 51.), (50., 51.), (50., 50.)];
+
+        // Holes listed before, after and between unrelated outer rings.
+        let rings = vec![
+            PolygonRing::Inner(ring(&small_hole)),
+            PolygonRing::Outer(ring(&big)),
+            PolygonRing::Inner(ring(&orphan)),
+            PolygonRing::Outer(ring(&small)),
+            PolygonRing::Inner(ring(&big_hole)),
+        ];
+        let multi_polygon = to_multi_polygon(&rings);
+        let polygons = multi_polygon.polygons();
+        assert_eq!(polygons.len(), 3);
+
+        let exterior_x = |polygon: &Polygon<f64>| polygon.rings()[0].coords()[0].x;
+        let num_rings = |polygon: &Polygon<f64>| polygon.rings().len();
+        assert_eq!(exterior_x(&polygons[0]), 0.);
+        assert_eq!(num_rings(&polygons[0]), 2);
+        assert_eq!(polygons[0].rings()[1].coords()[0].x, 1.);
+        assert_eq!(exterior_x(&polygons[1]), 20.);
+        assert_eq!(num_rings(&polygons[1]), 2);
+        assert_eq!(exterior_x(&polygons[2]), 50.);
+        assert_eq!(num_rings(&polygons[2]), 1);
+    }
+
+    #[test]
+    fn orient_rings() {
+        // A counterclockwise exterior and a clockwise hole, both the wrong way round.
+        let polygon = wkt::wkt!(POLYGON ((0. 0., 10. 0., 10. 10., 0. 10., 0. 0.), (1. 1., 1. 2., 2. 2., 2. 1., 1. 1.)));
+        let Shape::Polygon(shape) = geometry_to_shape(&polygon).unwrap() else {
+            panic!("expected a polygon shape");
+        };
+        let rings = shape.rings();
+        assert!(matches!(rings[0], PolygonRing::Outer(_)));
+        assert_eq!(rings[0].points()[1], shapefile::Point::new(0., 10.));
+        assert!(matches!(rings[1], PolygonRing::Inner(_)));
+        assert_eq!(rings[1].points()[1], shapefile::Point::new(2., 1.));
+
+        let empty = wkt::wkt!(POINT EMPTY);
+        assert!(matches!(
+            geometry_to_shape(&empty).unwrap(),
+            Shape::NullShape
+        ));
+    }
+
+    #[test]
+    fn missing_measures() {
+        let point = shapefile::PointM::new(1., 2., NO_DATA);
+        assert!(point.to_coord().m.unwrap().is_nan());
+        assert!(shape_to_wkt(&Shape::NullShape).unwrap().is_none());
+    }
+}
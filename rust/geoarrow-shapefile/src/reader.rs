@@ -0,0 +1,660 @@
+//! Read from [Shapefile](https://www.esri.com/content/dam/esrisites/sitecore-archive/Files/Pdfs/library/whitepapers/pdfs/shapefile.pdf)
+//! datasets.
+//!
+//! This wraps the [shapefile] crate. Geometries are read from the `.shp` file, attributes from
+//! the `.dbf` file and, when opening a dataset with [`ShapefileReader::try_open`], the CRS from
+//! the `.prj` file.
+
+use std::fs::File;
+use std::io::{BufReader, Read, Seek};
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow_array::builder::{
+    BooleanBuilder, Date32Builder, Float32Builder, Float64Builder, Int32Builder, StringBuilder,
+    TimestampMillisecondBuilder,
+};
+use arrow_array::{ArrayRef, RecordBatch, RecordBatchReader};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef, TimeUnit};
+use geoarrow_array::GeoArrowArray;
+use geoarrow_array::builder::{
+    MultiLineStringBuilder, MultiPointBuilder, MultiPolygonBuilder, PointBuilder,
+};
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+use geoarrow_schema::{
+    CoordType, Crs, Dimension, GeoArrowType, Metadata, MultiLineStringType, MultiPointType,
+    MultiPolygonType, PointType,
+};
+use shapefile::dbase::{self, FieldInfo, FieldType, FieldValue, Record};
+use shapefile::{Shape, ShapeReader, ShapeType};
+use wkt::Wkt;
+
+use crate::geometry::shape_to_wkt;
+
+/// Options for the Shapefile reader
+#[derive(Debug, Clone)]
+pub struct ShapefileReaderOptions {
+    /// The GeoArrow coordinate type to use in the geometry arrays.
+    pub coord_type: CoordType,
+
+    /// The number of rows in each batch.
+    pub batch_size: usize,
+
+    /// The CRS to assign to the geometry column.
+    ///
+    /// If `None`, [`ShapefileReader::try_open`] reads the CRS from the `.prj` file next to the
+    /// `.shp` file, if there is one.
+    pub crs: Option<Crs>,
+}
+
+impl Default for ShapefileReaderOptions {
+    fn default() -> Self {
+        Self {
+            coord_type: Default::default(),
+            batch_size: 1024,
+            crs: None,
+        }
+    }
+}
+
+/// An iterator over record batches from a Shapefile dataset.
+///
+/// Attribute columns come first, in the order of the `.dbf` fields, followed by a `geometry`
+/// column. Points are read as a `PointArray`, multipoints as a `MultiPointArray`, polylines as a
+/// `MultiLineStringArray` and polygons as a `MultiPolygonArray`. Shapes with M values keep them,
+/// and shapes with Z values keep Z but drop M.
+///
+/// This implements [RecordBatchReader], which you can use to access data.
+pub struct ShapefileReader<T: Read + Seek, D: Read + Seek> {
+    shape_reader: ShapeReader<T>,
+    dbase_file: dbase::File<D>,
+    /// Whether the shape reader has a `.shx` index, in which case shapes are read by index.
+    indexed: bool,
+    /// The index of the next shape and record to read, counting deleted records.
+    next_row: usize,
+    fields: Vec<FieldInfo>,
+    geometry_type: GeoArrowType,
+    schema: SchemaRef,
+    batch_size: usize,
+}
+
+impl ShapefileReader<BufReader<File>, BufReader<File>> {
+    /// Open the Shapefile dataset at the given `.shp` path.
+    ///
+    /// The `.dbf` file, and the `.shx` and `.prj` files if they exist, are found next to the `.shp`
+    /// file.
+    pub fn try_open(
+        path: impl AsRef<Path>,
+        mut options: ShapefileReaderOptions,
+    ) -> GeoArrowResult<Self> {
+        let path = path.as_ref();
+        let shape_reader = ShapeReader::from_path(path).map_err(shapefile_error)?;
+        let dbf = BufReader::new(File::open(path.with_extension("dbf"))?);
+        let dbase_file = dbase::File::open(dbf).map_err(dbase_error)?;
+
+        let prj_path = path.with_extension("prj");
+        if options.crs.is_none() && prj_path.exists() {
+            options.crs = Some(crs_from_prj(&std::fs::read_to_string(prj_path)?));
+        }
+
+        Self::try_new(shape_reader, dbase_file, options)
+    }
+}
+
+impl<T: Read + Seek, D: Read + Seek> ShapefileReader<T, D> {
+    /// Create a new reader from a reader of the `.shp` file and the opened `.dbf` file.
+    ///
+    /// Use [`ShapeReader::with_shx`] to make use of a `.shx` index file. The CRS is taken from
+    /// [`ShapefileReaderOptions::crs`]; use [`crs_from_prj`] to read it from a `.prj` file.
+    ///
+    /// Records marked as deleted in the `.dbf` file are skipped, along with their shapes. Memo
+    /// fields, which are stored in a separate `.dbt` file, can't be read.
+    pub fn try_new(
+        shape_reader: ShapeReader<T>,
+        dbase_file: dbase::File<D>,
+        options: ShapefileReaderOptions,
+    ) -> GeoArrowResult<Self> {
+        let metadata = Arc::new(Metadata::new(options.crs.unwrap_or_default(), None));
+        let geometry_type = geometry_type(
+            shape_reader.header().shape_type,
+            options.coord_type,
+            metadata,
+        )?;
+
+        let fields = dbase_file.fields().to_vec();
+        let mut schema_fields = fields
+            .iter()
+            .map(|field| Field::new(field.name(), arrow_type(field.field_type()), true))
+            .collect::<Vec<_>>();
+        schema_fields.push(geometry_type.to_field("geometry", true));
+
+        Ok(Self {
+            indexed: shape_reader.shape_count().is_ok(),
+            shape_reader,
+            dbase_file,
+            next_row: 0,
+            fields,
+            geometry_type,
+            schema: Arc::new(Schema::new(schema_fields)),
+            batch_size: options.batch_size,
+        })
+    }
+
+    fn process_batch(&mut self) -> GeoArrowResult<Option<RecordBatch>> {
+        let num_records = self.dbase_file.num_records();
+        if self.next_row >= num_records {
+            return Ok(None);
+        }
+
+        let capacity = self.batch_size.min(num_records - self.next_row);
+        let mut property_builders = self
+            .fields
+            .iter()
+            .map(|field| PropertyBuilder::new(field.field_type(), capacity))
+            .collect::<Vec<_>>();
+        let mut geometry_builder = GeometryBuilder::new(&self.geometry_type, capacity);
+
+        // A new shape iterator over an indexed reader starts again from the first shape, so with
+        // an index each batch reads its shapes by position instead. Without an index, a new
+        // iterator continues from where the last batch stopped, as one shape is read per record.
+        let next_row = self.next_row;
+        let shape_reader = &mut self.shape_reader;
+        let mut shapes: Box<dyn Iterator<Item = Result<Shape, shapefile::Error>>> = if self.indexed
+        {
+            Box::new((next_row..).map_while(|index| shape_reader.read_nth_shape(index)))
+        } else {
+            Box::new(shape_reader.iter_shapes())
+        };
+
+        let mut row_count = 0;
+        while row_count < self.batch_size && self.next_row < num_records {
+            let Some(shape) = shapes.next() else {
+                // The `.shp` file has fewer shapes than the `.dbf` file has records.
+                self.next_row = num_records;
+                break;
+            };
+            let shape = shape.map_err(shapefile_error)?;
+            let Some(mut record) = self.dbase_file.record(self.next_row) else {
+                break;
+            };
+            self.next_row += 1;
+
+            // Deleted records stay in the `.dbf` file, along with their shapes in the `.shp`
+            // file, until the dataset is packed.
+            if record.is_deleted().map_err(dbase_error)? {
+                continue;
+            }
+            let record = record.read().map_err(dbase_error)?;
+            for (field, builder) in self.fields.iter().zip(property_builders.iter_mut()) {
+                builder.append(field, &record)?;
+            }
+            geometry_builder.push_geometry(shape_to_wkt(&shape)?.as_ref())?;
+            row_count += 1;
+        }
+        if row_count == 0 {
+            return Ok(None);
+        }
+
+        let mut columns = property_builders
+            .into_iter()
+            .map(PropertyBuilder::finish)
+            .collect::<Vec<_>>();
+        columns.push(geometry_builder.finish().into_array_ref());
+        Ok(Some(RecordBatch::try_new(self.schema.clone(), columns)?))
+    }
+}
+
+impl<T: Read + Seek, D: Read + Seek> Iterator for ShapefileReader<T, D> {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.process_batch().map_err(|err| err.into()).transpose()
+    }
+}
+
+impl<T: Read + Seek, D: Read + Seek> RecordBatchReader for ShapefileReader<T, D> {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+/// Parse the contents of a `.prj` file into a [`Crs`].
+///
+/// Most `.prj` files hold ESRI-flavored WKT1, which is stored as a CRS of unknown type. A `.prj`
+/// file holding WKT2 is recognized by its leading keyword and stored as WKT2:2019.
+pub fn crs_from_prj(prj: &str) -> Crs {
+    const WKT2_KEYWORDS: [&str; 12] = [
+        "BOUNDCRS",
+        "COMPOUNDCRS",
+        "DERIVEDPROJCRS",
+        "ENGCRS",
+        "ENGINEERINGCRS",
+        "GEODCRS",
+        "GEODETICCRS",
+        "GEOGCRS",
+        "GEOGRAPHICCRS",
+        "PROJCRS",
+        "PROJECTEDCRS",
+        "VERTCRS",
+    ];
+
+    let prj = prj.trim();
+    let keyword = prj
+        .split(['[', '('])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_uppercase();
+    if WKT2_KEYWORDS.contains(&keyword.as_str()) {
+        Crs::from_wkt2_2019(prj.to_string())
+    } else {
+        Crs::from_unknown_crs_type(prj.to_string())
+    }
+}
+
+fn geometry_type(
+    shape_type: ShapeType,
+    coord_type: CoordType,
+    metadata: Arc<Metadata>,
+) -> GeoArrowResult<GeoArrowType> {
+    let dim = match shape_type {
+        ShapeType::Point | ShapeType::Multipoint | ShapeType::Polyline | ShapeType::Polygon => {
+            Dimension::XY
+        }
+        ShapeType::PointM | ShapeType::MultipointM | ShapeType::PolylineM | ShapeType::PolygonM => {
+            Dimension::XYM
+        }
+        ShapeType::PointZ | ShapeType::MultipointZ | ShapeType::PolylineZ | ShapeType::PolygonZ => {
+            Dimension::XYZ
+        }
+        other => {
+            return Err(GeoArrowError::IncorrectGeometryType(format!(
+                "Unsupported shapefile geometry type: {other}"
+            )));
+        }
+    };
+
+    let typ = match shape_type {
+        ShapeType::Point | ShapeType::PointM | ShapeType::PointZ => PointType::new(dim, metadata)
+            .with_coord_type(coord_type)
+            .into(),
+        ShapeType::Multipoint | ShapeType::MultipointM | ShapeType::MultipointZ => {
+            MultiPointType::new(dim, metadata)
+                .with_coord_type(coord_type)
+                .into()
+        }
+        ShapeType::Polyline | ShapeType::PolylineM | ShapeType::PolylineZ => {
+            MultiLineStringType::new(dim, metadata)
+                .with_coord_type(coord_type)
+                .into()
+        }
+        _ => MultiPolygonType::new(dim, metadata)
+            .with_coord_type(coord_type)
+            .into(),
+    };
+    Ok(typ)
+}
+
+/// The Arrow type that a dBase field is read as.
+pub(crate) fn arrow_type(field_type: FieldType) -> DataType {
+    match field_type {
+        FieldType::Character | FieldType::Memo => DataType::Utf8,
+        FieldType::Numeric | FieldType::Double | FieldType::Currency => DataType::Float64,
+        FieldType::Float => DataType::Float32,
+        FieldType::Integer => DataType::Int32,
+        FieldType::Logical => DataType::Boolean,
+        FieldType::Date => DataType::Date32,
+        // dBase stores datetimes with millisecond precision, but the dbase crate only reads
+        // whole seconds.
+        FieldType::DateTime => DataType::Timestamp(TimeUnit::Millisecond, None),
+    }
+}
+
+/// A builder for one attribute column.
+enum PropertyBuilder {
+    Utf8(StringBuilder),
+    Float32(Float32Builder),
+    Float64(Float64Builder),
+    Int32(Int32Builder),
+    Boolean(BooleanBuilder),
+    Date32(Date32Builder),
+    Timestamp(TimestampMillisecondBuilder),
+}
+
+impl PropertyBuilder {
+    fn new(field_type: FieldType, capacity: usize) -> Self {
+        match arrow_type(field_type) {
+            DataType::Utf8 => Self::Utf8(StringBuilder::with_capacity(capacity, 0)),
+            DataType::Float32 => Self::Float32(Float32Builder::with_capacity(capacity)),
+            DataType::Float64 => Self::Float64(Float64Builder::with_capacity(capacity)),
+            DataType::Int32 => Self::Int32(Int32Builder::with_capacity(capacity)),
+            DataType::Boolean => Self::Boolean(BooleanBuilder::with_capacity(capacity)),
+            DataType::Date32 => Self::Date32(Date32Builder::with_capacity(capacity)),
+            _ => Self::Timestamp(TimestampMillisecondBuilder::with_capacity(capacity)),
+        }
+    }
+
+    fn append(&mut self, field: &FieldInfo, record: &Record) -> GeoArrowResult<()> {
+        let value = record.get(field.name());
+        match (self, value) {
+            (Self::Utf8(builder), Some(FieldValue::Character(value))) => {
+                builder.append_option(value.as_deref())
+            }
+            (Self::Utf8(builder), Some(FieldValue::Memo(value))) => builder.append_value(value),
+            (Self::Float32(builder), Some(FieldValue::Float(value))) => {
+                builder.append_option(*value)
+            }
+            (Self::Float64(builder), Some(FieldValue::Numeric(value))) => {
+                builder.append_option(*value)
+            }
+            (
+                Self::Float64(builder),
+                Some(FieldValue::Double(value) | FieldValue::Currency(value)),
+            ) => builder.append_value(*value),
+            (Self::Int32(builder), Some(FieldValue::Integer(value))) => {
+                builder.append_value(*value)
+            }
+            (Self::Boolean(builder), Some(FieldValue::Logical(value))) => {
+                builder.append_option(*value)
+            }
+            (Self::Date32(builder), Some(FieldValue::Date(value))) => {
+                builder.append_option(value.map(|date| date.to_unix_days()))
+            }
+            (Self::Timestamp(builder), Some(FieldValue::DateTime(value))) => {
+                builder.append_value(value.to_unix_timestamp() * 1000)
+            }
+            (_, value) => {
+                return Err(GeoArrowError::External(
+                    format!(
+                        "Unexpected value {value:?} for dBase field '{}' of type {:?}",
+                        field.name(),
+                        field.field_type()
+                    )
+                    .into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> ArrayRef {
+        match self {
+            Self::Utf8(mut builder) => Arc::new(builder.finish()),
+            Self::Float32(mut builder) => Arc::new(builder.finish()),
+            Self::Float64(mut builder) => Arc::new(builder.finish()),
+            Self::Int32(mut builder) => Arc::new(builder.finish()),
+            Self::Boolean(mut builder) => Arc::new(builder.finish()),
+            Self::Date32(mut builder) => Arc::new(builder.finish()),
+            Self::Timestamp(mut builder) => Arc::new(builder.finish()),
+        }
+    }
+}
+
+/// A builder for the geometry column, of one of the types a shapefile can hold.
+enum GeometryBuilder {
+    Point(PointBuilder),
+    MultiPoint(MultiPointBuilder),
+    MultiLineString(MultiLineStringBuilder),
+    MultiPolygon(MultiPolygonBuilder),
+}
+
+impl GeometryBuilder {
+    fn new(geometry_type: &GeoArrowType, capacity: usize) -> Self {
+        match geometry_type.clone() {
+            GeoArrowType::Point(typ) => Self::Point(PointBuilder::with_capacity(typ, capacity)),
+            GeoArrowType::MultiPoint(typ) => Self::MultiPoint(MultiPointBuilder::new(typ)),
+            GeoArrowType::MultiLineString(typ) => {
+                Self::MultiLineString(MultiLineStringBuilder::new(typ))
+            }
+            GeoArrowType::MultiPolygon(typ) => Self::MultiPolygon(MultiPolygonBuilder::new(typ)),
+            _ => unreachable!("shapefile geometry types are validated on construction"),
+        }
+    }
+
+    fn push_geometry(&mut self, geometry: Option<&Wkt<f64>>) -> GeoArrowResult<()> {
+        match self {
+            Self::Point(builder) => builder.push_geometry(geometry),
+            Self::MultiPoint(builder) => builder.push_geometry(geometry),
+            Self::MultiLineString(builder) => builder.push_geometry(geometry),
+            Self::MultiPolygon(builder) => builder.push_geometry(geometry),
+        }
+    }
+
+    fn finish(self) -> Arc<dyn GeoArrowArray> {
+        match self {
+            Self::Point(builder) => Arc::new(builder.finish()),
+            Self::MultiPoint(builder) => Arc::new(builder.finish()),
+            Self::MultiLineString(builder) => Arc::new(builder.finish()),
+            Self::MultiPolygon(builder) => Arc::new(builder.finish()),
+        }
+    }
+}
+
+fn shapefile_error(err: shapefile::Error) -> GeoArrowError {
+    GeoArrowError::External(Box::new(err))
+}
+
+fn dbase_error(err: dbase::Error) -> GeoArrowError {
+    GeoArrowError::External(Box::new(err))
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Float64Type;
+    use geo_traits::{CoordTrait, MultiPolygonTrait, PointTrait, PolygonTrait};
+    use geoarrow_array::GeoArrowArrayAccessor;
+    use geoarrow_array::array::{MultiPolygonArray, PointArray};
+    use shapefile::dbase::{FieldName, TableWriterBuilder};
+    use shapefile::{Point, Polygon, PolygonRing, ShapeWriter};
+
+    use super::*;
+
+    /// The `.shp`, `.shx` and `.dbf` files of a dataset.
+    type Dataset = (Cursor<Vec<u8>>, Cursor<Vec<u8>>, Cursor<Vec<u8>>);
+
+    fn write_dataset<S: shapefile::record::EsriShape>(shapes: &[S], names: &[&str]) -> Dataset {
+        let mut shp = Cursor::new(Vec::new());
+        let mut shx = Cursor::new(Vec::new());
+        ShapeWriter::with_shx(&mut shp, &mut shx)
+            .write_shapes(shapes)
+            .unwrap();
+
+        let records = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let mut record = Record::default();
+                record.insert(
+                    "name".to_string(),
+                    FieldValue::Character(Some(name.to_string())),
+                );
+                record.insert("value".to_string(), FieldValue::Numeric(Some(i as f64)));
+                record
+            })
+            .collect::<Vec<_>>();
+        let mut dbf = Cursor::new(Vec::new());
+        TableWriterBuilder::new()
+            .add_character_field(FieldName::try_from("name").unwrap(), 20)
+            .add_numeric_field(FieldName::try_from("value").unwrap(), 10, 2)
+            .build_with_dest(&mut dbf)
+            .write_records(&records)
+            .unwrap();
+
+        shp.set_position(0);
+        shx.set_position(0);
+        dbf.set_position(0);
+        (shp, shx, dbf)
+    }
+
+    #[test]
+    fn read_points() {
+        let points = [Point::new(1., 2.), Point::new(3., 4.), Point::new(5., 6.)];
+        let (shp, shx, dbf) = write_dataset(&points, &["a", "b", "c"]);
+
+        let options = ShapefileReaderOptions {
+            batch_size: 2,
+            crs: Some(crs_from_prj(r#"GEOGCS["GCS_WGS_1984"]"#)),
+            ..Default::default()
+        };
+        let reader = ShapefileReader::try_new(
+            ShapeReader::with_shx(shp, shx).unwrap(),
+            dbase::File::open(dbf).unwrap(),
+            options,
+        )
+        .unwrap();
+
+        let schema = reader.schema();
+        assert_eq!(schema.field(0).data_type(), &DataType::Utf8);
+        assert_eq!(schema.field(1).data_type(), &DataType::Float64);
+        assert!(matches!(
+            GeoArrowType::from_extension_field(schema.field(2)).unwrap(),
+            Some(GeoArrowType::Point(_))
+        ));
+
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[1].num_rows(), 1);
+        assert_eq!(batches[1].column(0).as_string::<i32>().value(0), "c");
+        assert_eq!(
+            batches[1].column(1).as_primitive::<Float64Type>().value(0),
+            2.
+        );
+        let points =
+            PointArray::try_from((batches[1].column(2).as_ref(), schema.field(2))).unwrap();
+        let coord = points.value(0).unwrap().coord().unwrap();
+        assert_eq!((coord.x(), coord.y()), (5., 6.));
+    }
+
+    #[test]
+    fn read_batches_without_shx() {
+        let points = (0..5).map(|i| Point::new(i as f64, 0.)).collect::<Vec<_>>();
+        let (shp, _, dbf) = write_dataset(&points, &["a", "b", "c", "d", "e"]);
+
+        let options = ShapefileReaderOptions {
+            batch_size: 2,
+            ..Default::default()
+        };
+        let reader = ShapefileReader::try_new(
+            ShapeReader::new(shp).unwrap(),
+            dbase::File::open(dbf).unwrap(),
+            options,
+        )
+        .unwrap();
+        let schema = reader.schema();
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(
+            batches
+                .iter()
+                .map(|batch| batch.num_rows())
+                .collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+        for (i, batch) in batches.iter().enumerate() {
+            let points = PointArray::try_from((batch.column(2).as_ref(), schema.field(2))).unwrap();
+            for j in 0..batch.num_rows() {
+                let coord = points.value(j).unwrap().coord().unwrap();
+                assert_eq!(coord.x(), (i * 2 + j) as f64);
+            }
+        }
+    }
+
+    #[test]
+    fn skip_deleted_records() {
+        let points = (0..4).map(|i| Point::new(i as f64, 0.)).collect::<Vec<_>>();
+        let (shp, shx, mut dbf) = write_dataset(&points, &["a", "b", "c", "d"]);
+
+        // Mark the second record as deleted. Each record starts with its deletion flag.
+        let bytes = dbf.get_mut();
+        let header_size = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        let record_size = u16::from_le_bytes([bytes[10], bytes[11]]) as usize;
+        bytes[header_size + record_size] = b'*';
+
+        for indexed in [true, false] {
+            let shape_reader = if indexed {
+                ShapeReader::with_shx(shp.clone(), shx.clone()).unwrap()
+            } else {
+                ShapeReader::new(shp.clone()).unwrap()
+            };
+            let options = ShapefileReaderOptions {
+                batch_size: 2,
+                ..Default::default()
+            };
+            let reader = ShapefileReader::try_new(
+                shape_reader,
+                dbase::File::open(dbf.clone()).unwrap(),
+                options,
+            )
+            .unwrap();
+            let schema = reader.schema();
+            let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+            assert_eq!(
+                batches
+                    .iter()
+                    .map(|batch| batch.num_rows())
+                    .collect::<Vec<_>>(),
+                vec![2, 1]
+            );
+
+            let mut names = Vec::new();
+            let mut xs = Vec::new();
+            for batch in &batches {
+                let points =
+                    PointArray::try_from((batch.column(2).as_ref(), schema.field(2))).unwrap();
+                for i in 0..batch.num_rows() {
+                    names.push(batch.column(0).as_string::<i32>().value(i).to_string());
+                    xs.push(points.value(i).unwrap().coord().unwrap().x());
+                }
+            }
+            assert_eq!(names, ["a", "c", "d"]);
+            assert_eq!(xs, [0., 2., 3.]);
+        }
+    }
+
+    #[test]
+    fn read_polygon_with_hole() {
+        let outer = vec![
+            Point::new(0., 0.),
+            Point::new(0., 10.),
+            Point::new(10., 10.),
+            Point::new(10., 0.),
+            Point::new(0., 0.),
+        ];
+        let hole = vec![
+            Point::new(1., 1.),
+            Point::new(2., 1.),
+            Point::new(2., 2.),
+            Point::new(1., 2.),
+            Point::new(1., 1.),
+        ];
+        let polygon =
+            Polygon::with_rings(vec![PolygonRing::Inner(hole), PolygonRing::Outer(outer)]);
+        let (shp, _, dbf) = write_dataset(&[polygon], &["a"]);
+
+        let reader = ShapefileReader::try_new(
+            ShapeReader::new(shp).unwrap(),
+            dbase::File::open(dbf).unwrap(),
+            Default::default(),
+        )
+        .unwrap();
+        let schema = reader.schema();
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        let multi_polygons =
+            MultiPolygonArray::try_from((batches[0].column(2).as_ref(), schema.field(2))).unwrap();
+        let multi_polygon = multi_polygons.value(0).unwrap();
+        assert_eq!(multi_polygon.num_polygons(), 1);
+        assert_eq!(multi_polygon.polygon(0).unwrap().num_interiors(), 1);
+    }
+
+    #[test]
+    fn prj_crs() {
+        let crs = crs_from_prj(r#"GEOGCRS["WGS 84", DATUM["World Geodetic System 1984"]]"#);
+        assert_eq!(crs.crs_type(), Some(geoarrow_schema::CrsType::Wkt2_2019));
+
+        let crs = crs_from_prj(r#"GEOGCS["GCS_WGS_1984",DATUM["D_WGS_1984"]]"#);
+        assert_eq!(crs.crs_type(), None);
+        assert!(crs.crs_value().is_some());
+    }
+}
@@ -0,0 +1,823 @@
+//! Write to [Shapefile](https://www.esri.com/content/dam/esrisites/sitecore-archive/Files/Pdfs/library/whitepapers/pdfs/shapefile.pdf)
+//! datasets.
+//!
+//! Geometries are written to the `.shp` and `.shx` files, attributes to the `.dbf` file and, when
+//! writing a dataset with [`write_shapefile`], the CRS to the `.prj` file.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Date32Type, Float64Type};
+use arrow_array::{ArrayRef, RecordBatch, RecordBatchReader};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use geoarrow_array::array::from_arrow_array;
+use geoarrow_array::{GeoArrowArrayAccessor, downcast_geoarrow_array};
+use geoarrow_schema::crs::{CrsTransform, DefaultCrsTransform};
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+use geoarrow_schema::{GeoArrowType, Metadata};
+use shapefile::dbase::{self, FieldName, FieldValue, Record, TableWriterBuilder};
+use shapefile::record::EsriShape;
+use shapefile::{Shape, ShapeType};
+
+use crate::geometry::geometry_to_shape;
+
+/// The maximum length in bytes of a dBase character field.
+const MAX_CHARACTER_LENGTH: usize = 254;
+
+/// The maximum length of a dBase field name.
+const MAX_FIELD_NAME_LENGTH: usize = 10;
+
+/// The length in bytes of the `.shp` and `.shx` file headers.
+const HEADER_LENGTH: usize = 100;
+
+/// Options for the Shapefile writer
+#[derive(Debug, Default)]
+pub struct ShapefileWriterOptions {
+    crs_transform: Option<Box<dyn CrsTransform>>,
+}
+
+impl ShapefileWriterOptions {
+    /// Set the method for transforming CRS to WKT
+    ///
+    /// This is implemented as an external trait so that external libraries can inject the method
+    /// for CRS conversions. Without one, only CRSs stored as WKT2:2019 or as a WKT string of
+    /// unknown type (as read from a `.prj` file) are written.
+    pub fn with_crs_transform(self, crs_transform: Box<dyn CrsTransform>) -> Self {
+        Self {
+            crs_transform: Some(crs_transform),
+        }
+    }
+
+    /// Create the contents of a `.prj` file from whatever CRS exists in the [Metadata].
+    ///
+    /// If no CRS exists in the Metadata, or it can't be converted to WKT, None will be returned
+    /// here.
+    fn create_prj(&self, array_meta: &Metadata) -> GeoArrowResult<Option<String>> {
+        let crs = array_meta.crs();
+        if crs.crs_type().is_none()
+            && let Some(wkt) = crs.crs_value().and_then(|value| value.as_str())
+        {
+            return Ok(Some(wkt.to_string()));
+        }
+
+        if let Some(crs_transform) = &self.crs_transform {
+            crs_transform.extract_wkt(crs)
+        } else {
+            DefaultCrsTransform::default().extract_wkt(crs)
+        }
+    }
+}
+
+/// A Shapefile writer.
+///
+/// The schema must have exactly one geometry column. Every other column becomes a `.dbf` field:
+///
+/// - String columns become character fields, with values truncated to 254 bytes.
+/// - Integer, float and decimal columns become numeric fields.
+/// - Boolean columns become logical fields.
+/// - Date columns become date fields.
+///
+/// Other column types are an error; cast them to one of the types above first. Column names are
+/// truncated to 10 characters, with non-ASCII characters replaced by `_` and a numeric suffix
+/// added to names that would otherwise clash. [`ShapefileWriter::field_names`] returns the names
+/// that were chosen.
+///
+/// Polygon exterior rings are written clockwise and interior rings counterclockwise. All
+/// geometries must map to the same shape type, so a column mixing points and polygons can't be
+/// written. Null and empty geometries are written as null shapes, which are read back as nulls.
+pub struct ShapefileWriter<T: Write + Seek> {
+    shape_writer: ShpWriter<T>,
+    dbase_writer: dbase::TableWriter<T>,
+    schema: SchemaRef,
+    geometry_index: usize,
+    fields: Vec<DbaseField>,
+}
+
+impl<T: Write + Seek> ShapefileWriter<T> {
+    /// Create a new writer to the `.shp`, `.shx` and `.dbf` files of a dataset.
+    ///
+    /// The `.prj` file isn't written; use [`write_shapefile`] to write a complete dataset to disk.
+    pub fn try_new(shp: T, shx: T, dbf: T, schema: SchemaRef) -> GeoArrowResult<Self> {
+        let geometry_index = geometry_column(&schema)?;
+
+        let columns = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|(column_index, _)| *column_index != geometry_index)
+            .collect::<Vec<_>>();
+        let names = dbase_field_names(columns.iter().map(|(_, field)| field.name().as_str()));
+
+        let mut fields = Vec::with_capacity(columns.len());
+        let mut builder = TableWriterBuilder::new();
+        for ((column_index, field), name) in columns.into_iter().zip(names) {
+            let kind = DbaseFieldKind::try_new(field)?;
+            let field_name = FieldName::try_from(name.as_str()).map_err(|err| {
+                GeoArrowError::Shapefile(format!("Invalid dBase field name {name:?}: {err}"))
+            })?;
+            builder = match kind {
+                DbaseFieldKind::Character => {
+                    builder.add_character_field(field_name, MAX_CHARACTER_LENGTH as u8)
+                }
+                DbaseFieldKind::Numeric {
+                    length,
+                    decimal_count,
+                } => builder.add_numeric_field(field_name, length, decimal_count),
+                DbaseFieldKind::Logical => builder.add_logical_field(field_name),
+                DbaseFieldKind::Date => builder.add_date_field(field_name),
+            };
+            fields.push(DbaseField {
+                column_index,
+                name,
+                kind,
+            });
+        }
+
+        Ok(Self {
+            shape_writer: ShpWriter::try_new(shp, shx)?,
+            dbase_writer: builder.build_with_dest(dbf),
+            schema,
+            geometry_index,
+            fields,
+        })
+    }
+
+    /// The `.dbf` field name chosen for each attribute column, as `(column name, field name)`
+    /// pairs.
+    pub fn field_names(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.fields.iter().map(|field| {
+            (
+                self.schema.field(field.column_index).name().as_str(),
+                field.name.as_str(),
+            )
+        })
+    }
+
+    /// Write a [`RecordBatch`] to the Shapefile dataset.
+    ///
+    /// This will error if the schema of the `RecordBatch` does not match the schema originally
+    /// passed to [`ShapefileWriter::try_new`].
+    pub fn write(&mut self, batch: &RecordBatch) -> GeoArrowResult<()> {
+        if batch.schema_ref().fields() != self.schema.fields() {
+            return Err(GeoArrowError::Shapefile(
+                "Record batch schema does not match the writer schema".to_string(),
+            ));
+        }
+
+        let geometry = from_arrow_array(
+            batch.column(self.geometry_index),
+            self.schema.field(self.geometry_index),
+        )?;
+        let geometry = geometry.as_ref();
+        let shapes = downcast_geoarrow_array!(geometry, to_shapes)?;
+
+        let columns = self
+            .fields
+            .iter()
+            .map(|field| field.kind.cast(batch.column(field.column_index)))
+            .collect::<GeoArrowResult<Vec<_>>>()?;
+
+        for (row, shape) in shapes.iter().enumerate() {
+            self.shape_writer.write(shape)?;
+            let mut record = Record::default();
+            for (field, column) in self.fields.iter().zip(&columns) {
+                record.insert(field.name.clone(), field.kind.value(column, row));
+            }
+            self.dbase_writer
+                .write_record(&record)
+                .map_err(dbase_error)?;
+        }
+
+        Ok(())
+    }
+
+    /// Finish writing the Shapefile dataset.
+    ///
+    /// This writes the file headers, which hold the extent and the record count, and flushes the
+    /// `.shp` and `.shx` files. The `.dbf` file is flushed when its writer is dropped; pass a
+    /// mutable reference to a buffered writer to flush it yourself.
+    pub fn finish(mut self) -> GeoArrowResult<()> {
+        self.shape_writer.finish()?;
+        self.dbase_writer.close().map_err(dbase_error)?;
+        Ok(())
+    }
+}
+
+/// Write a stream of GeoArrow RecordBatches to a Shapefile dataset.
+///
+/// `path` is the path of the `.shp` file; the `.shx`, `.dbf` and `.prj` files are written next to
+/// it. The `.prj` file is only written if the geometry column has a CRS that can be converted to
+/// WKT, see [`ShapefileWriterOptions::with_crs_transform`].
+pub fn write_shapefile<S: RecordBatchReader>(
+    stream: S,
+    path: impl AsRef<Path>,
+    options: ShapefileWriterOptions,
+) -> GeoArrowResult<()> {
+    let path = path.as_ref();
+    let schema = stream.schema();
+    let geometry_field = schema.field(geometry_column(&schema)?);
+    let geometry_type = GeoArrowType::try_from(geometry_field)?;
+
+    let create = |extension: &str| -> GeoArrowResult<BufWriter<File>> {
+        Ok(BufWriter::new(File::create(
+            path.with_extension(extension),
+        )?))
+    };
+    let (mut shp, mut shx, mut dbf) = (create("shp")?, create("shx")?, create("dbf")?);
+    let mut writer = ShapefileWriter::try_new(&mut shp, &mut shx, &mut dbf, schema)?;
+    for batch in stream {
+        writer.write(&batch?)?;
+    }
+    writer.finish()?;
+    dbf.flush()?;
+
+    if let Some(prj) = options.create_prj(geometry_type.metadata())? {
+        std::fs::write(path.with_extension("prj"), prj)?;
+    }
+    Ok(())
+}
+
+/// An attribute column and the `.dbf` field it is written to.
+#[derive(Debug)]
+struct DbaseField {
+    column_index: usize,
+    name: String,
+    kind: DbaseFieldKind,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DbaseFieldKind {
+    Character,
+    Numeric { length: u8, decimal_count: u8 },
+    Logical,
+    Date,
+}
+
+impl DbaseFieldKind {
+    fn try_new(field: &Field) -> GeoArrowResult<Self> {
+        let kind = match field.data_type() {
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => Self::Character,
+            DataType::Dictionary(_, value_type)
+                if matches!(
+                    value_type.as_ref(),
+                    DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+                ) =>
+            {
+                Self::Character
+            }
+            DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::UInt8
+            | DataType::UInt16 => Self::Numeric {
+                length: 11,
+                decimal_count: 0,
+            },
+            DataType::Int64 | DataType::UInt32 | DataType::UInt64 => Self::Numeric {
+                length: 20,
+                decimal_count: 0,
+            },
+            DataType::Float16 | DataType::Float32 | DataType::Float64 => Self::Numeric {
+                length: 24,
+                decimal_count: 15,
+            },
+            DataType::Decimal128(precision, scale) | DataType::Decimal256(precision, scale)
+                if (0..=15).contains(scale) =>
+            {
+                Self::Numeric {
+                    // Room for the sign and the decimal point.
+                    length: (*precision).min(30) + 2,
+                    decimal_count: *scale as u8,
+                }
+            }
+            DataType::Boolean => Self::Logical,
+            DataType::Date32 | DataType::Date64 => Self::Date,
+            data_type => {
+                return Err(GeoArrowError::Shapefile(format!(
+                    "Column {:?} has type {data_type}, which can't be written to a dBase file. \
+                     Cast it to a string, number, boolean or date column first.",
+                    field.name()
+                )));
+            }
+        };
+        Ok(kind)
+    }
+
+    /// Cast a column to the Arrow type that [`Self::value`] reads.
+    fn cast(&self, column: &ArrayRef) -> GeoArrowResult<ArrayRef> {
+        let to_type = match self {
+            Self::Character => DataType::Utf8,
+            Self::Numeric { .. } => DataType::Float64,
+            Self::Logical => DataType::Boolean,
+            Self::Date => DataType::Date32,
+        };
+        Ok(arrow_cast::cast(column, &to_type)?)
+    }
+
+    fn value(&self, column: &ArrayRef, row: usize) -> FieldValue {
+        let valid = column.is_valid(row);
+        match self {
+            Self::Character => FieldValue::Character(valid.then(|| {
+                truncate(column.as_string::<i32>().value(row), MAX_CHARACTER_LENGTH).to_string()
+            })),
+            Self::Numeric { .. } => {
+                FieldValue::Numeric(valid.then(|| column.as_primitive::<Float64Type>().value(row)))
+            }
+            Self::Logical => FieldValue::Logical(valid.then(|| column.as_boolean().value(row))),
+            Self::Date => FieldValue::Date(
+                valid.then(|| date_from_days(column.as_primitive::<Date32Type>().value(row))),
+            ),
+        }
+    }
+}
+
+/// Writes shapes to the `.shp` and `.shx` files.
+///
+/// [`shapefile::ShapeWriter`] writes every record with the shape type of the file, so it can't
+/// write the null shapes that null and empty geometries become.
+struct ShpWriter<T: Write + Seek> {
+    shp: T,
+    shx: T,
+    /// The type of the first shape that isn't null, which all other shapes must match.
+    shape_type: Option<ShapeType>,
+    /// The x, y, z and m ranges of the shapes that aren't null.
+    ranges: [[f64; 2]; 4],
+    /// The length of the `.shp` file in 16-bit words, which is how offsets are stored.
+    shp_length: i32,
+    num_records: i32,
+}
+
+impl<T: Write + Seek> ShpWriter<T> {
+    fn try_new(mut shp: T, mut shx: T) -> GeoArrowResult<Self> {
+        // Reserve room for the headers, which are written once the extent is known.
+        shp.write_all(&[0; HEADER_LENGTH])?;
+        shx.write_all(&[0; HEADER_LENGTH])?;
+        Ok(Self {
+            shp,
+            shx,
+            shape_type: None,
+            ranges: [[f64::MAX, f64::MIN]; 4],
+            shp_length: HEADER_LENGTH as i32 / 2,
+            num_records: 0,
+        })
+    }
+
+    /// Write a shape as its concrete type, which is what [`EsriShape`] is implemented for.
+    fn write(&mut self, shape: &Shape) -> GeoArrowResult<()> {
+        match shape {
+            Shape::Point(shape) => self.write_shape(shape),
+            Shape::PointM(shape) => self.write_shape(shape),
+            Shape::PointZ(shape) => self.write_shape(shape),
+            Shape::Polyline(shape) => self.write_shape(shape),
+            Shape::PolylineM(shape) => self.write_shape(shape),
+            Shape::PolylineZ(shape) => self.write_shape(shape),
+            Shape::Polygon(shape) => self.write_shape(shape),
+            Shape::PolygonM(shape) => self.write_shape(shape),
+            Shape::PolygonZ(shape) => self.write_shape(shape),
+            Shape::Multipoint(shape) => self.write_shape(shape),
+            Shape::MultipointM(shape) => self.write_shape(shape),
+            Shape::MultipointZ(shape) => self.write_shape(shape),
+            Shape::Multipatch(shape) => self.write_shape(shape),
+            Shape::NullShape => self.write_record(ShapeType::NullShape, &[]),
+        }
+    }
+
+    fn write_shape<S: EsriShape>(&mut self, shape: &S) -> GeoArrowResult<()> {
+        let shape_type = S::shapetype();
+        match self.shape_type {
+            None => self.shape_type = Some(shape_type),
+            Some(expected) if expected == shape_type => {}
+            Some(expected) => {
+                return Err(GeoArrowError::Shapefile(format!(
+                    "Cannot write a {shape_type} shape to a shapefile of {expected} shapes"
+                )));
+            }
+        }
+
+        let [x_range, y_range, z_range, m_range] = &mut self.ranges;
+        extend_range(x_range, shape.x_range());
+        extend_range(y_range, shape.y_range());
+        if shape_type.has_z() {
+            extend_range(z_range, shape.z_range());
+        }
+        if shape_type.has_m() {
+            extend_range(m_range, shape.m_range());
+        }
+
+        let mut content = Vec::with_capacity(shape.size_in_bytes());
+        shape.write_to(&mut content).map_err(shapefile_error)?;
+        self.write_record(shape_type, &content)
+    }
+
+    fn write_record(&mut self, shape_type: ShapeType, content: &[u8]) -> GeoArrowResult<()> {
+        // The record content starts with the shape type, and its length is in 16-bit words.
+        let content_length = (4 + content.len()) as i32 / 2;
+        self.num_records += 1;
+
+        self.shx.write_all(&self.shp_length.to_be_bytes())?;
+        self.shx.write_all(&content_length.to_be_bytes())?;
+
+        self.shp.write_all(&self.num_records.to_be_bytes())?;
+        self.shp.write_all(&content_length.to_be_bytes())?;
+        self.shp.write_all(&(shape_type as i32).to_le_bytes())?;
+        self.shp.write_all(content)?;
+        // The record header holds the record number and the content length.
+        self.shp_length += 4 + content_length;
+        Ok(())
+    }
+
+    /// Write the headers and flush both files.
+    fn finish(&mut self) -> GeoArrowResult<()> {
+        let shx_length = HEADER_LENGTH as i32 / 2 + 4 * self.num_records;
+        let header = |file_length: i32| {
+            let shape_type = self.shape_type.unwrap_or(ShapeType::NullShape);
+            let [
+                [min_x, max_x],
+                [min_y, max_y],
+                [min_z, max_z],
+                [min_m, max_m],
+            ] = self
+                .ranges
+                .map(|[min, max]| if min <= max { [min, max] } else { [0., 0.] });
+
+            let mut header = Vec::with_capacity(HEADER_LENGTH);
+            // The file code, followed by five unused integers.
+            header.extend_from_slice(&9994_i32.to_be_bytes());
+            header.extend_from_slice(&[0; 20]);
+            header.extend_from_slice(&file_length.to_be_bytes());
+            // The version.
+            header.extend_from_slice(&1000_i32.to_le_bytes());
+            header.extend_from_slice(&(shape_type as i32).to_le_bytes());
+            for value in [min_x, min_y, max_x, max_y, min_z, max_z, min_m, max_m] {
+                header.extend_from_slice(&value.to_le_bytes());
+            }
+            header
+        };
+        let (shp_header, shx_header) = (header(self.shp_length), header(shx_length));
+
+        for (file, header) in [(&mut self.shp, shp_header), (&mut self.shx, shx_header)] {
+            file.seek(SeekFrom::Start(0))?;
+            file.write_all(&header)?;
+            file.seek(SeekFrom::End(0))?;
+            file.flush()?;
+        }
+        Ok(())
+    }
+}
+
+fn extend_range(range: &mut [f64; 2], [min, max]: [f64; 2]) {
+    range[0] = range[0].min(min);
+    range[1] = range[1].max(max);
+}
+
+fn to_shapes<'a>(array: &'a impl GeoArrowArrayAccessor<'a>) -> GeoArrowResult<Vec<Shape>> {
+    array
+        .iter()
+        .map(|geometry| match geometry {
+            Some(geometry) => geometry_to_shape(&geometry?),
+            None => Ok(Shape::NullShape),
+        })
+        .collect()
+}
+
+fn geometry_column(schema: &Schema) -> GeoArrowResult<usize> {
+    let mut geom_indices = vec![];
+    for (field_idx, field) in schema.fields().iter().enumerate() {
+        if let Ok(Some(_)) = GeoArrowType::from_extension_field(field.as_ref()) {
+            geom_indices.push(field_idx);
+        }
+    }
+    match geom_indices.as_slice() {
+        [geometry_index] => Ok(*geometry_index),
+        _ => Err(GeoArrowError::Shapefile(format!(
+            "Expected exactly one geometry column, found {}",
+            geom_indices.len()
+        ))),
+    }
+}
+
+/// Choose a dBase field name for each column name.
+///
+/// Names are truncated to 10 characters, with characters other than ASCII letters, digits and `_`
+/// replaced by `_`. A name that matches an earlier one, ignoring case, gets a `_1`, `_2`, …
+/// suffix, truncating it further to make room.
+fn dbase_field_names<'a>(column_names: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut used = HashSet::new();
+    column_names
+        .map(|column_name| {
+            let mut base = column_name
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || c == '_' {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .take(MAX_FIELD_NAME_LENGTH)
+                .collect::<String>();
+            if base.is_empty() {
+                base = "FIELD".to_string();
+            }
+
+            let mut name = base.clone();
+            let mut suffix = 1;
+            while !used.insert(name.to_ascii_uppercase()) {
+                let suffix_str = format!("_{suffix}");
+                let prefix_len = base.len().min(MAX_FIELD_NAME_LENGTH - suffix_str.len());
+                name = format!("{}{suffix_str}", &base[..prefix_len]);
+                suffix += 1;
+            }
+            name
+        })
+        .collect()
+}
+
+/// Truncate a string to at most `max_len` bytes, on a character boundary.
+fn truncate(value: &str, max_len: usize) -> &str {
+    if value.len() <= max_len {
+        return value;
+    }
+    let mut end = max_len;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    &value[..end]
+}
+
+/// Convert days since the Unix epoch to a calendar date.
+fn date_from_days(days: i32) -> dbase::Date {
+    // Howard Hinnant's `civil_from_days` algorithm.
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    dbase::Date::new(day as u32, month as u32, year as u32)
+}
+
+fn shapefile_error(err: shapefile::Error) -> GeoArrowError {
+    GeoArrowError::External(Box::new(err))
+}
+
+fn dbase_error(err: dbase::Error) -> GeoArrowError {
+    GeoArrowError::External(Box::new(err))
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+    use std::sync::Arc;
+
+    use arrow_array::{Date32Array, RecordBatchIterator, create_array};
+    use geo_traits::{CoordTrait, MultiPolygonTrait, PointTrait, PolygonTrait};
+    use geoarrow_array::GeoArrowArray;
+    use geoarrow_array::array::{MultiPolygonArray, PointArray};
+    use geoarrow_array::builder::{PointBuilder, PolygonBuilder};
+    use geoarrow_schema::{Crs, Dimension, PointType, PolygonType};
+    use shapefile::ShapeReader;
+
+    use super::*;
+    use crate::reader::{ShapefileReader, ShapefileReaderOptions, crs_from_prj};
+
+    /// A shared buffer, so that the written files can be read back after the writer is dropped.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<std::sync::Mutex<Cursor<Vec<u8>>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.0.lock().unwrap().flush()
+        }
+    }
+
+    impl Seek for Buffer {
+        fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+            self.0.lock().unwrap().seek(pos)
+        }
+    }
+
+    impl Buffer {
+        fn into_cursor(self) -> Cursor<Vec<u8>> {
+            Cursor::new(self.0.lock().unwrap().get_ref().clone())
+        }
+    }
+
+    /// The `.shp` and `.dbf` files of a dataset.
+    type Dataset = (Cursor<Vec<u8>>, Cursor<Vec<u8>>);
+
+    fn write_batch(batch: &RecordBatch) -> GeoArrowResult<Dataset> {
+        let (shp, shx, dbf) = (Buffer::default(), Buffer::default(), Buffer::default());
+        let mut writer =
+            ShapefileWriter::try_new(shp.clone(), shx.clone(), dbf.clone(), batch.schema())?;
+        writer.write(batch)?;
+        writer.finish()?;
+        Ok((shp.into_cursor(), dbf.into_cursor()))
+    }
+
+    #[test]
+    fn write_points() {
+        let typ = PointType::new(Dimension::XY, Default::default());
+        let mut builder = PointBuilder::with_capacity(typ, 2);
+        builder.push_point(Some(&wkt::wkt!(POINT (1. 2.))));
+        builder.push_point(Some(&wkt::wkt!(POINT (3. 4.))));
+        let points = builder.finish();
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("name", DataType::Utf8, true),
+            Field::new("count", DataType::Int64, true),
+            Field::new("visited", DataType::Date32, true),
+            points.data_type().to_field("geometry", true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                create_array!(Utf8, [Some("a"), None]),
+                create_array!(Int64, [Some(1), Some(2)]),
+                Arc::new(Date32Array::from(vec![Some(19_723), None])),
+                points.into_array_ref(),
+            ],
+        )
+        .unwrap();
+        let (shp, dbf) = write_batch(&batch).unwrap();
+
+        let reader = ShapefileReader::try_new(
+            ShapeReader::new(shp).unwrap(),
+            dbase::File::open(dbf).unwrap(),
+            ShapefileReaderOptions::default(),
+        )
+        .unwrap();
+        let schema = reader.schema();
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.column(0).as_string::<i32>().value(0), "a");
+        assert!(batch.column(0).is_null(1));
+        assert_eq!(batch.column(1).as_primitive::<Float64Type>().value(1), 2.);
+        // 2024-01-01
+        assert_eq!(
+            batch.column(2).as_primitive::<Date32Type>().value(0),
+            19_723
+        );
+        let points = PointArray::try_from((batch.column(3).as_ref(), schema.field(3))).unwrap();
+        let coord = points.value(1).unwrap().coord().unwrap();
+        assert_eq!((coord.x(), coord.y()), (3., 4.));
+    }
+
+    #[test]
+    fn write_null_and_empty() {
+        let typ = PointType::new(Dimension::XY, Default::default());
+        let mut builder = PointBuilder::with_capacity(typ, 4);
+        builder.push_point(Some(&wkt::wkt!(POINT (1. 2.))));
+        builder.push_point(None::<&wkt::types::Point>);
+        builder.push_point(Some(&wkt::wkt!(POINT EMPTY)));
+        builder.push_point(Some(&wkt::wkt!(POINT (3. 4.))));
+        let points = builder.finish();
+
+        let schema = Arc::new(Schema::new(vec![
+            points.data_type().to_field("geometry", true),
+        ]));
+        let batch = RecordBatch::try_new(schema, vec![points.into_array_ref()]).unwrap();
+        let (shp, shx, dbf) = (Buffer::default(), Buffer::default(), Buffer::default());
+        let mut writer =
+            ShapefileWriter::try_new(shp.clone(), shx.clone(), dbf.clone(), batch.schema())
+                .unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+
+        let shape_reader = ShapeReader::with_shx(shp.into_cursor(), shx.into_cursor()).unwrap();
+        let header = shape_reader.header();
+        assert_eq!(header.shape_type, ShapeType::Point);
+        assert_eq!((header.bbox.min.x, header.bbox.min.y), (1., 2.));
+        assert_eq!((header.bbox.max.x, header.bbox.max.y), (3., 4.));
+        assert_eq!(shape_reader.shape_count().unwrap(), 4);
+
+        let reader = ShapefileReader::try_new(
+            shape_reader,
+            dbase::File::open(dbf.into_cursor()).unwrap(),
+            ShapefileReaderOptions::default(),
+        )
+        .unwrap();
+        let schema = reader.schema();
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        let points =
+            PointArray::try_from((batches[0].column(0).as_ref(), schema.field(0))).unwrap();
+        assert_eq!(points.len(), 4);
+        assert!(points.is_null(1));
+        assert!(points.is_null(2));
+        let coord = points.value(3).unwrap().coord().unwrap();
+        assert_eq!((coord.x(), coord.y()), (3., 4.));
+    }
+
+    #[test]
+    fn write_polygon_with_hole() {
+        let typ = PolygonType::new(Dimension::XY, Default::default());
+        let mut builder = PolygonBuilder::new(typ);
+        builder
+            .push_polygon(Some(&wkt::wkt!(POLYGON ((0. 0., 10. 0., 10. 10., 0. 10., 0. 0.), (1. 1., 1. 2., 2. 2., 2. 1., 1. 1.)))))
+            .unwrap();
+        let polygons = builder.finish();
+
+        let schema = Arc::new(Schema::new(vec![
+            polygons.data_type().to_field("geometry", true),
+        ]));
+        let batch = RecordBatch::try_new(schema, vec![polygons.into_array_ref()]).unwrap();
+        let (shp, dbf) = write_batch(&batch).unwrap();
+
+        let reader = ShapefileReader::try_new(
+            ShapeReader::new(shp).unwrap(),
+            dbase::File::open(dbf).unwrap(),
+            ShapefileReaderOptions::default(),
+        )
+        .unwrap();
+        let schema = reader.schema();
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        let multi_polygons =
+            MultiPolygonArray::try_from((batches[0].column(0).as_ref(), schema.field(0))).unwrap();
+        let multi_polygon = multi_polygons.value(0).unwrap();
+        assert_eq!(multi_polygon.num_polygons(), 1);
+        assert_eq!(multi_polygon.polygon(0).unwrap().num_interiors(), 1);
+    }
+
+    #[test]
+    fn field_names() {
+        let names = dbase_field_names(
+            [
+                "population_2020",
+                "population_2021",
+                "POPULATION_2022",
+                "höhe",
+                "",
+            ]
+            .into_iter(),
+        );
+        assert_eq!(
+            names,
+            ["population", "populati_1", "POPULATI_2", "h_he", "FIELD"]
+        );
+    }
+
+    #[test]
+    fn unsupported_column_type() {
+        let typ = PointType::new(Dimension::XY, Default::default());
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("payload", DataType::Binary, true),
+            typ.to_field("geometry", true),
+        ]));
+        let (shp, shx, dbf) = (Buffer::default(), Buffer::default(), Buffer::default());
+        let err = ShapefileWriter::try_new(shp, shx, dbf, schema)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("\"payload\""), "{err}");
+        assert!(err.contains("Binary"), "{err}");
+    }
+
+    #[test]
+    fn write_prj() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("points.shp");
+
+        let prj = r#"GEOGCS["GCS_WGS_1984",DATUM["D_WGS_1984"]]"#;
+        let metadata = Arc::new(Metadata::new(crs_from_prj(prj), None));
+        let typ = PointType::new(Dimension::XY, metadata);
+        let mut builder = PointBuilder::with_capacity(typ, 1);
+        builder.push_point(Some(&wkt::wkt!(POINT (1. 2.))));
+        let points = builder.finish();
+        let schema = Arc::new(Schema::new(vec![
+            points.data_type().to_field("geometry", true),
+        ]));
+        let batch = RecordBatch::try_new(schema.clone(), vec![points.into_array_ref()]).unwrap();
+
+        let stream = RecordBatchIterator::new(vec![Ok(batch)], schema);
+        write_shapefile(stream, &path, Default::default()).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(path.with_extension("prj")).unwrap(),
+            prj
+        );
+
+        let reader = ShapefileReader::try_open(&path, Default::default()).unwrap();
+        let geometry_type = GeoArrowType::from_extension_field(reader.schema().field(0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            geometry_type.metadata().crs(),
+            &Crs::from_unknown_crs_type(prj.to_string())
+        );
+    }
+}
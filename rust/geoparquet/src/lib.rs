@@ -11,5 +11,4 @@ pub mod metadata;
 pub mod reader;
 #[cfg(test)]
 mod test;
-mod total_bounds;
 pub mod writer;
@@ -619,7 +619,11 @@ impl GeoParquetMetadata {
 
             match (left.crs.as_ref(), right.crs.as_ref()) {
                 (Some(left_crs), Some(right_crs)) => {
-                    if left_crs != right_crs {
+                    // Compare authority identifiers so that e.g. PROJJSON with and without
+                    // optional members such as `scope` or `bbox` are considered compatible
+                    let left_crs = Crs::from_projjson(left_crs.clone());
+                    let right_crs = Crs::from_projjson(right_crs.clone());
+                    if !left_crs.is_equivalent(&right_crs) {
                         return Err(GeoArrowError::GeoParquet(format!(
                             "Different GeoParquet CRS for column {key}",
                         )));
@@ -889,4 +893,31 @@ mod test {
 
         dbg!(&meta);
     }
+
+    #[test]
+    fn compatible_with_equivalent_crs() {
+        let metadata = |crs: &str| -> GeoParquetMetadata {
+            serde_json::from_str(&format!(
+                r#"{{
+                    "version": "1.1.0",
+                    "primary_column": "geometry",
+                    "columns": {{
+                        "geometry": {{"encoding": "WKB", "geometry_types": [], "crs": {crs}}}
+                    }}
+                }}"#
+            ))
+            .unwrap()
+        };
+
+        let left = metadata(r#"{"name": "WGS 84", "id": {"authority": "EPSG", "code": 4326}}"#);
+        let right = metadata(
+            r#"{"name": "WGS 84", "scope": "Horizontal component of 3D system.", "id": {"authority": "EPSG", "code": "4326"}}"#,
+        );
+        assert!(left.is_compatible_with(&right));
+
+        let other = metadata(
+            r#"{"name": "WGS 84 / Pseudo-Mercator", "id": {"authority": "EPSG", "code": 3857}}"#,
+        );
+        assert!(!left.is_compatible_with(&other));
+    }
 }
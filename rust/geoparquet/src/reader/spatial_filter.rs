@@ -11,6 +11,7 @@ use geo_traits::{CoordTrait, RectTrait};
 use geo_types::{CoordNum, Rect, coord};
 use geoarrow_array::GeoArrowArrayAccessor;
 use geoarrow_array::array::{RectArray, from_arrow_array};
+use geoarrow_array::bounds::bounding_rect;
 use geoarrow_array::builder::RectBuilder;
 use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
 use geoarrow_schema::{BoxType, Dimension, Metadata};
@@ -21,7 +22,6 @@ use parquet::file::statistics::Statistics;
 use parquet::schema::types::{ColumnPath, SchemaDescriptor};
 
 use crate::metadata::GeoParquetBboxCovering;
-use crate::total_bounds::bounding_rect;
 
 /// A helper for interpreting bounding box row group statistics from GeoParquet files
 ///
@@ -223,7 +223,7 @@ fn construct_native_predicate(
         let field = batch.schema_ref().field(0);
         let nulls = array.nulls();
         let geo_arr = from_arrow_array(array, field)?;
-        let rect_arr = bounding_rect(geo_arr.as_ref())?;
+        let rect_arr = bounding_rect(geo_arr.as_ref(), Dimension::XY)?;
 
         let xmin_col = Float64Array::new(rect_arr.lower().raw_buffers()[0].clone(), nulls.cloned());
         let ymin_col = Float64Array::new(rect_arr.lower().raw_buffers()[1].clone(), nulls.cloned());
@@ -2,13 +2,14 @@ use arrow_array::{Array, ArrayRef, RecordBatch};
 use arrow_schema::{Field, Schema, SchemaRef};
 use geoarrow_array::GeoArrowArray;
 use geoarrow_array::array::from_arrow_array;
+use geoarrow_array::bounds::{BoundingRect, bounding_rect, total_bounds};
 use geoarrow_array::cast::{AsGeoArrowArray, to_wkb};
+use geoarrow_array::snap::snap_to_grid;
 use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
-use geoarrow_schema::{CoordType, GeoArrowType};
+use geoarrow_schema::{CoordType, Dimension, GeoArrowType};
 use parquet::file::metadata::KeyValue;
 
 use crate::metadata::{GeoParquetColumnEncoding, GeoParquetMetadata};
-use crate::total_bounds::{BoundingRect, bounding_rect, total_bounds};
 use crate::writer::GeoParquetWriterOptions;
 use crate::writer::metadata::{ColumnInfo, GeoParquetMetadataBuilder};
 
@@ -95,15 +96,18 @@ pub(super) fn encode_record_batch(
 
     output_columns.resize(output_schema.fields().len(), None);
     for (column_idx, column_info) in metadata_builder.columns.iter_mut() {
-        let array = batch.column(*column_idx);
         let field = batch.schema_ref().field(*column_idx);
-        column_info.update_geometry_types(array, field)?;
+        let mut array = batch.column(*column_idx).clone();
+        if let Some(snap) = &column_info.snap {
+            array = snap_to_grid(from_arrow_array(&array, field)?.as_ref(), snap)?.into_array_ref();
+        }
+        column_info.update_geometry_types(&array, field)?;
 
-        let (encoded_column, array_bounds) = encode_column(array, field, column_info)?;
+        let (encoded_column, array_bounds) = encode_column(&array, field, column_info)?;
         output_columns[*column_idx] = Some(encoded_column);
 
         if let Some(covering_field_idx) = column_info.covering_field_idx {
-            let covering = bounding_rect(from_arrow_array(array, field)?.as_ref())?;
+            let covering = bounding_rect(from_arrow_array(&array, field)?.as_ref(), Dimension::XY)?;
             output_columns[covering_field_idx] = Some(covering.into_array_ref());
         }
 
@@ -169,3 +173,45 @@ fn encode_native_column(geo_arr: &dyn GeoArrowArray) -> ArrayRef {
         _ => geo_arr.to_array_ref(),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use geo_traits::{CoordTrait, PointTrait};
+    use geoarrow_array::GeoArrowArrayAccessor;
+    use geoarrow_array::builder::PointBuilder;
+    use geoarrow_array::snap::SnapOptions;
+    use geoarrow_schema::PointType;
+    use wkt::wkt;
+
+    use super::*;
+    use crate::writer::{GeoParquetWriterEncoding, GeoParquetWriterOptionsBuilder};
+
+    #[test]
+    fn encode_snap_to_grid() {
+        let typ = PointType::new(Dimension::XY, Default::default());
+        let points = [wkt!(POINT (0.12 0.74)), wkt!(POINT (1.3 2.6))];
+        let array = PointBuilder::from_points(points.iter(), typ.clone()).finish();
+        let schema = Schema::new(vec![typ.to_field("geometry", true)]);
+        let batch =
+            RecordBatch::try_new(Arc::new(schema.clone()), vec![array.into_array_ref()]).unwrap();
+
+        let options = GeoParquetWriterOptionsBuilder::default()
+            .set_encoding(GeoParquetWriterEncoding::GeoArrow)
+            .set_snap_to_grid(SnapOptions::new(0.5))
+            .build();
+        let mut encoder = GeoParquetRecordBatchEncoder::try_new(&schema, &options).unwrap();
+        let encoded = encoder.encode_record_batch(&batch).unwrap();
+
+        let geo_arr = from_arrow_array(encoded.column(0), encoded.schema_ref().field(0)).unwrap();
+        let coord = geo_arr.as_point().value(0).unwrap().coord().unwrap();
+        assert_eq!((coord.x(), coord.y()), (0.0, 0.5));
+
+        let metadata = encoder.into_geoparquet_metadata();
+        assert_eq!(
+            metadata.columns["geometry"].bbox,
+            Some(vec![0.0, 0.5, 1.5, 2.5])
+        );
+    }
+}
@@ -7,7 +7,9 @@ use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
 use geo_traits::GeometryTrait;
 use geoarrow_array::GeoArrowArrayAccessor;
 use geoarrow_array::array::from_arrow_array;
+use geoarrow_array::bounds::BoundingRect;
 use geoarrow_array::cast::AsGeoArrowArray;
+use geoarrow_array::snap::SnapOptions;
 use geoarrow_schema::crs::{CrsTransform, DefaultCrsTransform};
 use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
 use geoarrow_schema::{CoordType, Dimension, Edges, GeoArrowType, Metadata, WkbType};
@@ -17,7 +19,6 @@ use crate::metadata::{
     GeoParquetBboxCovering, GeoParquetColumnEncoding, GeoParquetColumnMetadata, GeoParquetCovering,
     GeoParquetGeometryType, GeoParquetGeometryTypeAndDimension, GeoParquetMetadata,
 };
-use crate::total_bounds::BoundingRect;
 use crate::writer::options::{GeoParquetWriterEncoding, GeoParquetWriterOptions};
 
 // https://github.com/geoarrow/geoarrow-rs/pull/1159#issuecomment-2904610370
@@ -54,6 +55,9 @@ pub(crate) struct ColumnInfo {
     /// Whether or not to use large, i64 offsets when
     /// writing the column as WKB
     pub(crate) large_offsets: bool,
+
+    /// If set, coordinates are snapped to a grid before being written.
+    pub(crate) snap: Option<SnapOptions>,
 }
 
 impl ColumnInfo {
@@ -86,6 +90,7 @@ impl ColumnInfo {
             covering_name,
             covering_field_idx: None,
             large_offsets,
+            snap: None,
         })
     }
 
@@ -231,7 +236,7 @@ impl ColumnInfo {
             Edges::Spherical => Some("spherical".to_string()),
             _ => None,
         });
-        let bbox = if let Some(bbox) = self.bbox {
+        let bbox = if let Some(bbox) = self.bbox.filter(|bbox| !bbox.is_empty()) {
             if let (Some(minz), Some(maxz)) = (bbox.minz(), bbox.maxz()) {
                 Some(vec![
                     bbox.minx(),
@@ -362,7 +367,13 @@ impl GeoParquetMetadataBuilder {
                     .map(|props| props.large_offsets)
                     .unwrap_or_default();
 
-                let column_info = ColumnInfo::try_new(
+                let snap = options
+                    .column_properties
+                    .get(&column_name)
+                    .and_then(|props| props.snap)
+                    .or(options.default_column_properties.snap);
+
+                let mut column_info = ColumnInfo::try_new(
                     column_name,
                     column_encoding,
                     &geo_data_type,
@@ -371,6 +382,7 @@ impl GeoParquetMetadataBuilder {
                     covering_name,
                     large_offsets,
                 )?;
+                column_info.snap = snap;
 
                 columns.insert(col_idx, column_info);
             }
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use geoarrow_array::snap::SnapOptions;
 use geoarrow_schema::crs::CrsTransform;
 
 /// Allowed encodings when writing to GeoParquet
@@ -29,6 +30,8 @@ pub(crate) struct ColumnOptions {
     /// If true, use i64 offsets when writing the column
     /// as WKB
     pub(crate) large_offsets: bool,
+    /// If set, snap coordinates to a grid before writing
+    pub(crate) snap: Option<SnapOptions>,
 }
 
 impl ColumnOptions {
@@ -43,6 +46,10 @@ impl ColumnOptions {
     fn set_covering_name(&mut self, value: String) {
         self.covering_name = Some(value);
     }
+
+    fn set_snap(&mut self, value: SnapOptions) {
+        self.snap = Some(value);
+    }
 }
 
 /// Builder for [`GeoParquetWriterOptions`]
@@ -121,6 +128,21 @@ impl GeoParquetWriterOptionsBuilder {
         self
     }
 
+    /// Snap the coordinates of all geometry columns to a grid before writing.
+    ///
+    /// Reducing precision this way makes the output compress far better. The bounding boxes and
+    /// coverings written to the file describe the snapped geometries.
+    pub fn set_snap_to_grid(mut self, value: SnapOptions) -> Self {
+        self.default_column_properties.set_snap(value);
+        self
+    }
+
+    /// Snap the coordinates of a specific geometry column to a grid before writing.
+    pub fn set_column_snap_to_grid(mut self, col: String, value: SnapOptions) -> Self {
+        self.get_mut_props(col).set_snap(value);
+        self
+    }
+
     /// Finalizes the configuration and returns immutable writer options struct.
     pub fn build(self) -> GeoParquetWriterOptions {
         GeoParquetWriterOptions {